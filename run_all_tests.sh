@@ -62,8 +62,8 @@ if [ ! -d "$TESTS_DIR" ]; then
     exit 1
 fi
 
-# Find all .sh files in tests directory
-TEST_FILES=$(find "$TESTS_DIR" -name "*.sh" -type f | sort)
+# Find all .sh files in tests directory, except the shared helpers
+TEST_FILES=$(find "$TESTS_DIR" -name "*.sh" ! -name "lib.sh" -type f | sort)
 
 if [ -z "$TEST_FILES" ]; then
     echo -e "${YELLOW}⚠️  No test files found in $TESTS_DIR${NC}"
@@ -0,0 +1,49 @@
+// Local stand-in for the mail worker: prints pending outbox emails and marks them sent
+
+use survey::database::create_connection_pool;
+use survey::repository::EmailOutboxRepository;
+
+/// Emails claimed per round trip
+const BATCH_SIZE: i64 = 50;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Load environment variables
+    dotenvy::dotenv().ok();
+
+    // Initialize logging
+    tracing_subscriber::fmt()
+        .with_target(false)
+        .with_max_level(tracing::Level::INFO)
+        .compact()
+        .init();
+
+    // Create database connection
+    let pool = create_connection_pool()
+        .await
+        .expect("Failed to create database connection pool");
+
+    let mut delivered = 0;
+    loop {
+        let mut emails = EmailOutboxRepository::take_pending(&pool, BATCH_SIZE)
+            .await
+            .expect("Failed to read the email outbox");
+        if emails.is_empty() {
+            break;
+        }
+
+        emails.sort_by_key(|email| email.created_at);
+        for email in emails {
+            println!("📧 To: {}", email.recipient);
+            println!("   Subject: {}", email.subject);
+            println!("   Template: {}", email.template);
+            println!("   {}", serde_json::to_string_pretty(&email.payload)?.replace('\n', "\n   "));
+            println!();
+            delivered += 1;
+        }
+    }
+
+    println!("✅ Delivered {} email(s)", delivered);
+
+    Ok(())
+}
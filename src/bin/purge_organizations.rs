@@ -0,0 +1,30 @@
+// Removes organizations whose deletion grace period has run out; run it daily from cron or a scheduler
+
+use survey::database::create_connection_pool;
+use survey::services::OrganizationService;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Load environment variables
+    dotenvy::dotenv().ok();
+
+    // Initialize logging
+    tracing_subscriber::fmt()
+        .with_target(false)
+        .with_max_level(tracing::Level::INFO)
+        .compact()
+        .init();
+
+    // Create database connection
+    let pool = create_connection_pool()
+        .await
+        .expect("Failed to create database connection pool");
+
+    let purged = OrganizationService::purge_deleted(&pool)
+        .await
+        .expect("Failed to purge deleted organizations");
+
+    println!("✅ Purged {} organization(s)", purged);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+// Runs queued contact imports; run it every minute from cron or a scheduler
+
+use survey::database::create_connection_pool;
+use survey::repository::ContactImportJobRepository;
+use survey::services::ContactImportService;
+use survey::AppState;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Load environment variables
+    dotenvy::dotenv().ok();
+
+    // Initialize logging
+    tracing_subscriber::fmt()
+        .with_target(false)
+        .with_max_level(tracing::Level::INFO)
+        .compact()
+        .init();
+
+    // Create database connection
+    let state = AppState {
+        db: create_connection_pool()
+            .await
+            .expect("Failed to create database connection pool"),
+    };
+
+    let mut processed = 0;
+    while let Some(job) = ContactImportJobRepository::claim_next(&state.db)
+        .await
+        .expect("Failed to claim a contact import job")
+    {
+        let job = ContactImportService::run_job(&state, &job)
+            .await
+            .expect("Failed to record the contact import job outcome");
+
+        match &job.error {
+            Some(error) => println!("❌ Job {}: {}", job.id, error),
+            None => println!("✅ Job {}: {} created, {} updated, {} skipped, {} failed", job.id, job.created_rows, job.updated_rows, job.skipped_rows, job.failed_rows),
+        }
+        processed += 1;
+    }
+
+    println!("✅ Processed {} job(s)", processed);
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+// Auth Controller - Session refresh, logout, account recovery, MFA and public signing keys
+
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{
+        header::{CACHE_CONTROL, USER_AGENT},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Json},
+    Extension,
+};
+use sqlx::PgPool;
+use std::{env, net::SocketAddr};
+
+use crate::dto::{
+    AccountMessageResponse, AuthTokensResponse, ForgotPasswordRequest, LogoutAllResponse, MfaCodeRequest,
+    MfaEnrollmentResponse, MfaLoginResponse, MfaRecoveryCodesResponse, MfaSetupRequest, MfaStatusResponse,
+    MfaVerifyRequest, RefreshTokenRequest, ResetPasswordRequest, VerifyEmailRequest,
+};
+use crate::errors::AppError;
+use crate::services::{AccountService, MfaService, SessionService, UserService};
+use crate::utils::{jwt_key_set, JwtUser};
+use crate::AppState;
+
+/// POST /auth/refresh - Exchange a refresh token for a new token pair
+pub async fn refresh_token(
+    State(pool): State<PgPool>,
+    Json(payload): Json<RefreshTokenRequest>,
+) -> Result<Json<AuthTokensResponse>, AppError> {
+    let response = SessionService::refresh(&pool, payload).await?;
+    Ok(Json(response))
+}
+
+/// GET /.well-known/jwks.json - Public keys access tokens can be verified with
+pub async fn jwks() -> impl IntoResponse {
+    ([(CACHE_CONTROL, "public, max-age=300")], Json(jwt_key_set().jwks()))
+}
+
+/// POST /auth/logout - Revoke the current session
+pub async fn logout(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+) -> Result<StatusCode, AppError> {
+    SessionService::logout(&pool, &jwt_user).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /auth/logout-all - Revoke every session of the current user
+pub async fn logout_all(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+) -> Result<Json<LogoutAllResponse>, AppError> {
+    let response = SessionService::logout_all(&pool, jwt_user.id).await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/forgot-password - Email a password reset link
+pub async fn forgot_password(
+    State(pool): State<PgPool>,
+    Json(payload): Json<ForgotPasswordRequest>,
+) -> Result<(StatusCode, Json<AccountMessageResponse>), AppError> {
+    let response = AccountService::forgot_password(&pool, payload).await?;
+    Ok((StatusCode::ACCEPTED, Json(response)))
+}
+
+/// POST /auth/reset-password - Set a new password with a reset token
+pub async fn reset_password(
+    State(state): State<AppState>,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> Result<Json<AccountMessageResponse>, AppError> {
+    let response = AccountService::reset_password(&state, payload).await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/verify-email - Verify an email address with a verification token
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Json(payload): Json<VerifyEmailRequest>,
+) -> Result<Json<AccountMessageResponse>, AppError> {
+    let response = AccountService::verify_email(&state, payload).await?;
+    Ok(Json(response))
+}
+
+/// GET /auth/mfa - Whether the current user has MFA, and whether it is required
+pub async fn mfa_status(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+) -> Result<Json<MfaStatusResponse>, AppError> {
+    let response = MfaService::status(&pool, jwt_user.id).await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/mfa/enroll - Issue a TOTP secret for the current user
+pub async fn enroll_mfa(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+) -> Result<Json<MfaEnrollmentResponse>, AppError> {
+    let response = MfaService::begin_enrollment(&pool, jwt_user.id, &jwt_user.email).await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/mfa/activate - Confirm enrollment with a first code
+pub async fn activate_mfa(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+    Json(payload): Json<MfaCodeRequest>,
+) -> Result<Json<MfaRecoveryCodesResponse>, AppError> {
+    let response = MfaService::activate(&pool, &jwt_user, payload).await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/mfa/disable - Turn MFA off
+pub async fn disable_mfa(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+    Json(payload): Json<MfaCodeRequest>,
+) -> Result<StatusCode, AppError> {
+    MfaService::disable(&pool, &jwt_user, payload).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /auth/mfa/recovery-codes - Replace the recovery codes
+pub async fn regenerate_recovery_codes(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+    Json(payload): Json<MfaCodeRequest>,
+) -> Result<Json<MfaRecoveryCodesResponse>, AppError> {
+    let response = MfaService::regenerate_recovery_codes(&pool, &jwt_user, payload).await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/mfa/setup - Issue a TOTP secret during a login that requires MFA
+pub async fn setup_mfa(
+    State(pool): State<PgPool>,
+    Json(payload): Json<MfaSetupRequest>,
+) -> Result<Json<MfaEnrollmentResponse>, AppError> {
+    let response = MfaService::setup_for_login(&pool, payload).await?;
+    Ok(Json(response))
+}
+
+/// POST /auth/mfa/verify - Exchange an mfa_token and a code for session tokens
+pub async fn verify_mfa(
+    State(pool): State<PgPool>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(payload): Json<MfaVerifyRequest>,
+) -> Result<Json<MfaLoginResponse>, AppError> {
+    let client_ip = client_ip(&headers, connect_info);
+    let response = UserService::login_with_mfa(&pool, payload, user_agent(&headers), client_ip).await?;
+    Ok(Json(response))
+}
+
+/// Client description recorded on new sessions
+pub(crate) fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(512).collect())
+}
+
+/// Address of the client, used to throttle failed logins per IP
+///
+/// `X-Forwarded-For` can be forged by anyone, so it is only believed with `TRUST_PROXY_HEADERS=true`,
+/// i.e. when a reverse proxy in front of the server sets it.
+pub(crate) fn client_ip(headers: &HeaderMap, connect_info: Option<ConnectInfo<SocketAddr>>) -> Option<String> {
+    let trust_proxy = env::var("TRUST_PROXY_HEADERS")
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(|ip| ip.trim().chars().take(64).collect::<String>())
+        .filter(|ip| !ip.is_empty());
+
+    match forwarded {
+        Some(ip) if trust_proxy => Some(ip),
+        _ => connect_info.map(|ConnectInfo(addr)| addr.ip().to_string()),
+    }
+}
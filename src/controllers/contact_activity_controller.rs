@@ -0,0 +1,152 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::dto::contact_activity_dto::{
+    ActivityQueueQueryParams, CompleteActivityRequest, CreateActivityRequest, TimelineQueryParams,
+    UpdateActivityRequest,
+};
+use crate::errors::AppError;
+use crate::middleware::ActiveOrganization;
+use crate::services::contact_activity_service::ContactActivityService;
+use crate::AppState;
+
+/// Log or schedule an activity for a contact
+/// POST /contacts/:id/activities
+pub async fn create_activity(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(contact_id): Path<Uuid>,
+    Json(request): Json<CreateActivityRequest>,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    tracing::info!(
+        "Creating {} activity for contact: {} by user: {}",
+        request.activity_type,
+        contact_id,
+        auth.user.id
+    );
+
+    let activity = ContactActivityService::create_activity(&state.db, auth.org_id, contact_id, request, auth.user.id).await?;
+
+    Ok((StatusCode::CREATED, Json(json!({
+        "success": true,
+        "message": "Activity created successfully",
+        "data": activity
+    }))))
+}
+
+/// Paginated activity timeline of a contact
+/// GET /contacts/:id/timeline
+pub async fn get_contact_timeline(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(contact_id): Path<Uuid>,
+    Query(params): Query<TimelineQueryParams>,
+) -> Result<Json<Value>, AppError> {
+    let timeline = ContactActivityService::get_timeline(&state.db, auth.org_id, contact_id, params).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": timeline.data,
+        "pagination": timeline.pagination,
+        "total_count": timeline.total_count
+    })))
+}
+
+/// View a single activity
+/// GET /activities/:id
+pub async fn get_activity(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(activity_id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let activity = ContactActivityService::get_activity(&state.db, auth.org_id, activity_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": activity
+    })))
+}
+
+/// Update an activity
+/// PUT /activities/:id
+pub async fn update_activity(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(activity_id): Path<Uuid>,
+    Json(request): Json<UpdateActivityRequest>,
+) -> Result<Json<Value>, AppError> {
+    let activity = ContactActivityService::update_activity(&state.db, auth.org_id, activity_id, request).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Activity updated successfully",
+        "data": activity
+    })))
+}
+
+/// Mark an activity as completed (body is optional)
+/// POST /activities/:id/complete
+pub async fn complete_activity(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(activity_id): Path<Uuid>,
+    request: Option<Json<CompleteActivityRequest>>,
+) -> Result<Json<Value>, AppError> {
+    let request = request.map(|Json(request)| request).unwrap_or_default();
+    let activity = ContactActivityService::complete_activity(&state.db, auth.org_id, activity_id, request).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Activity completed successfully",
+        "data": activity
+    })))
+}
+
+/// Delete an activity
+/// DELETE /activities/:id
+pub async fn delete_activity(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(activity_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    ContactActivityService::delete_activity(&state.db, auth.org_id, activity_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Scheduled activities still ahead for an owner (defaults to the caller)
+/// GET /activities/upcoming
+pub async fn get_upcoming_activities(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Query(params): Query<ActivityQueueQueryParams>,
+) -> Result<Json<Value>, AppError> {
+    let owner_id = params.owner_id.unwrap_or(auth.user.id);
+    let activities = ContactActivityService::get_upcoming(&state.db, auth.org_id, owner_id, params.limit).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": activities
+    })))
+}
+
+/// Scheduled activities past their date for an owner (defaults to the caller)
+/// GET /activities/overdue
+pub async fn get_overdue_activities(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Query(params): Query<ActivityQueueQueryParams>,
+) -> Result<Json<Value>, AppError> {
+    let owner_id = params.owner_id.unwrap_or(auth.user.id);
+    let activities = ContactActivityService::get_overdue(&state.db, auth.org_id, owner_id, params.limit).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": activities
+    })))
+}
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::dto::contact_bulk_dto::BulkContactRequest;
+use crate::errors::AppError;
+use crate::middleware::ActiveOrganization;
+use crate::services::contact_bulk_service::{BulkContactOutcome, ContactBulkService};
+use crate::AppState;
+
+/// Apply one action to many contacts
+/// POST /contacts/bulk
+///
+/// Responds 200 with per-contact results, or 202 with the queued job when the selection
+/// exceeds the synchronous limit.
+pub async fn bulk_update_contacts(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(request): Json<BulkContactRequest>,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    tracing::info!(
+        "Bulk contact action {:?} by user: {} in organization: {} (dry run: {})",
+        request.action,
+        auth.user.id,
+        auth.org_id,
+        request.dry_run
+    );
+
+    let outcome = ContactBulkService::submit(&state, auth.org_id, auth.user.id, &auth.permissions, request).await?;
+
+    let (status, response) = match outcome {
+        BulkContactOutcome::DryRun(dry_run) => (
+            StatusCode::OK,
+            json!({
+                "success": true,
+                "dry_run": true,
+                "data": dry_run
+            }),
+        ),
+        BulkContactOutcome::Completed(result) => (
+            StatusCode::OK,
+            json!({
+                "success": result.failed == 0,
+                "data": result
+            }),
+        ),
+        BulkContactOutcome::Queued(job) => (
+            StatusCode::ACCEPTED,
+            json!({
+                "success": true,
+                "message": "Bulk operation queued",
+                "data": job
+            }),
+        ),
+    };
+
+    Ok((status, Json(response)))
+}
+
+/// Progress of a queued bulk operation
+/// GET /contacts/bulk/:job_id
+pub async fn get_contact_bulk_job(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let job = ContactBulkService::get_job(&state.db, auth.org_id, job_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": job
+    })))
+}
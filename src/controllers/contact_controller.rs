@@ -1,5 +1,6 @@
 use axum::{
     extract::{Path, State},
+    Extension,
     http::StatusCode,
     response::Json,
 };
@@ -7,7 +8,8 @@ use serde_json::{json, Value};
 
 use crate::dto::contact_dto::{CreateContactRequest, UpdateContactRequest, PatchContactRequest};
 use crate::errors::AppError;
-use crate::middleware::check_user_permission;
+use crate::middleware::ActiveOrganization;
+use crate::models::PermissionScope;
 use crate::services::contact_service::ContactService;
 use crate::AppState;
 use uuid::Uuid;
@@ -16,20 +18,18 @@ use uuid::Uuid;
 /// POST /api/contacts
 pub async fn create_contact(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
+    auth: ActiveOrganization,
     Json(request): Json<CreateContactRequest>,
 ) -> Result<(StatusCode, Json<Value>), AppError> {
-    // Use permission middleware to check permission and get user (handles token extraction internally)
-    let user = check_user_permission(&state, &headers, "contacts:create").await?;
-
     tracing::info!(
-        "Creating contact: {} {} by user: {} (permission verified via middleware)",
+        "Creating contact: {} {} by user: {} in organization: {} (permission verified via middleware)",
         request.first_name,
         request.last_name,
-        user.id
+        auth.user.id,
+        auth.org_id
     );
 
-    let contact = ContactService::create_contact(&state.db, request, user.id).await?;
+    let contact = ContactService::create_contact(&state, auth.org_id, request, auth.user.id).await?;
 
     let response = json!({
         "success": true,
@@ -44,13 +44,11 @@ pub async fn create_contact(
 /// GET /api/contacts/:id
 pub async fn get_contact(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
+    auth: ActiveOrganization,
+    Extension(scope): Extension<PermissionScope>,
     Path(contact_id): Path<Uuid>,
 ) -> Result<Json<Value>, AppError> {
-    // Permission: read contacts
-    let user = check_user_permission(&state, &headers, "contacts:read").await?;
-
-    let contact = ContactService::get_contact_by_id(&state.db, contact_id, user.id).await?;
+    let contact = ContactService::get_contact_by_id(&state.db, auth.org_id, contact_id, auth.user.id, scope).await?;
 
     let response = json!({
         "success": true,
@@ -64,20 +62,18 @@ pub async fn get_contact(
 /// PUT /api/contacts/:id
 pub async fn update_contact(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
+    auth: ActiveOrganization,
+    Extension(scope): Extension<PermissionScope>,
     Path(contact_id): Path<Uuid>,
     Json(request): Json<UpdateContactRequest>,
 ) -> Result<Json<Value>, AppError> {
-    // Permission: update contacts
-    let user = check_user_permission(&state, &headers, "contacts:update").await?;
-
     tracing::info!(
         "Updating contact: {} by user: {} (permission verified via middleware)",
         contact_id,
-        user.id
+        auth.user.id
     );
 
-    let contact = ContactService::update_contact(&state.db, contact_id, request, user.id).await?;
+    let contact = ContactService::update_contact(&state, auth.org_id, contact_id, request, auth.user.id, scope).await?;
 
     let response = json!({
         "success": true,
@@ -92,20 +88,18 @@ pub async fn update_contact(
 /// PATCH /api/contacts/:id
 pub async fn patch_contact(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
+    auth: ActiveOrganization,
+    Extension(scope): Extension<PermissionScope>,
     Path(contact_id): Path<Uuid>,
     Json(request): Json<PatchContactRequest>,
 ) -> Result<Json<Value>, AppError> {
-    // Permission: update contacts (same permission as PUT)
-    let user = check_user_permission(&state, &headers, "contacts:update").await?;
-
     tracing::info!(
         "Patching contact: {} by user: {} (permission verified via middleware)",
         contact_id,
-        user.id
+        auth.user.id
     );
 
-    let contact = ContactService::patch_contact(&state.db, contact_id, request, user.id).await?;
+    let contact = ContactService::patch_contact(&state, auth.org_id, contact_id, request, auth.user.id, scope).await?;
 
     let response = json!({
         "success": true,
@@ -120,15 +114,27 @@ pub async fn patch_contact(
 /// DELETE /api/contacts/:id
 pub async fn delete_contact(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
+    auth: ActiveOrganization,
+    Extension(scope): Extension<PermissionScope>,
     Path(contact_id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
-    // Permission: delete contacts
-    let user = check_user_permission(&state, &headers, "contacts:delete").await?;
-
-    ContactService::delete_contact(&state.db, contact_id, user.id).await?;
+    ContactService::delete_contact(&state.db, auth.org_id, contact_id, auth.user.id, scope).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
 
+
+/// List the lead statuses contacts can be in
+/// GET /lead-statuses
+pub async fn list_lead_statuses(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+) -> Result<Json<Value>, AppError> {
+    let lead_statuses = ContactService::list_lead_statuses(&state.db, auth.org_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": lead_statuses
+    })))
+}
@@ -1,15 +1,16 @@
 use axum::{
     extract::State,
-    http::HeaderMap,
     response::Json,
+    Extension,
 };
 use validator::Validate;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
 use crate::dto::contact_filter_dto::*;
-use crate::errors::AppError;
-use crate::middleware::permission_middleware::check_user_permission;
+use crate::errors::{field_errors, AppError};
+use crate::middleware::ActiveOrganization;
+use crate::models::PermissionScope;
 use crate::services::contact_filter_service::ContactFilterService;
 use crate::AppState;
 
@@ -17,19 +18,23 @@ use crate::AppState;
 /// POST /api/contacts/filter
 pub async fn filter_contacts(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    auth: ActiveOrganization,
+    Extension(scope): Extension<PermissionScope>,
     Json(filter_request): Json<ContactFilterRequest>,
 ) -> Result<Json<Value>, AppError> {
-    // Permission: read contacts
-    let user = check_user_permission(&state, &headers, "contacts:read").await?;
-
     tracing::info!(
         "Filtering contacts with {} conditions by user: {} (permission verified via middleware)",
         filter_request.conditions.len(),
-        user.id
+        auth.user.id
     );
 
-    let response = ContactFilterService::filter_contacts(&state.db, filter_request).await?;
+    // Callers with only `contacts:read_own` see just their own contacts
+    let owner_id = match scope {
+        PermissionScope::All => None,
+        PermissionScope::Own => Some(auth.user.id),
+    };
+
+    let response = ContactFilterService::filter_contacts(&state.db, auth.org_id, owner_id, filter_request).await?;
 
     Ok(Json(json!(response)))
 }
@@ -38,16 +43,13 @@ pub async fn filter_contacts(
 /// GET /api/contacts/filter/fields
 pub async fn get_filter_fields(
     State(state): State<AppState>,
-    headers: HeaderMap,
+    auth: ActiveOrganization,
 ) -> Result<Json<Value>, AppError> {
-    // Permission: read contacts
-    let _user = check_user_permission(&state, &headers, "contacts:read").await?;
-
     // Get standard fields
     let standard_fields = get_standard_field_definitions();
     
-    // Get custom fields from database
-    let custom_fields = get_custom_field_definitions(&state.db).await?;
+    // Get the organization's custom fields from database
+    let custom_fields = get_custom_field_definitions(&state.db, auth.org_id).await?;
 
     let response = json!({
         "success": true,
@@ -64,11 +66,7 @@ pub async fn get_filter_fields(
 /// GET /api/contacts/filter/presets
 pub async fn get_filter_presets(
     State(_state): State<AppState>,
-    headers: HeaderMap,
 ) -> Result<Json<Value>, AppError> {
-    // Permission: read contacts
-    let _user = check_user_permission(&_state, &headers, "contacts:read").await?;
-
     let presets = get_common_filter_presets();
 
     let response = json!({
@@ -83,12 +81,8 @@ pub async fn get_filter_presets(
 /// POST /api/contacts/filter/validate
 pub async fn validate_filter(
     State(_state): State<AppState>,
-    headers: HeaderMap,
     Json(filter_request): Json<ContactFilterRequest>,
 ) -> Result<Json<Value>, AppError> {
-    // Permission: read contacts
-    let _user = check_user_permission(&_state, &headers, "contacts:read").await?;
-
     // Validate the filter structure
     match filter_request.validate() {
         Ok(_) => {
@@ -105,7 +99,7 @@ pub async fn validate_filter(
             Ok(Json(json!({
                 "success": false,
                 "message": "Filter validation failed",
-                "errors": e.to_string()
+                "errors": field_errors(&e)
             })))
         }
     }
@@ -199,15 +193,16 @@ fn get_standard_field_definitions() -> HashMap<String, FieldDefinition> {
     fields
 }
 
-async fn get_custom_field_definitions(pool: &sqlx::PgPool) -> Result<HashMap<String, FieldDefinition>, AppError> {
+async fn get_custom_field_definitions(pool: &sqlx::PgPool, org_id: uuid::Uuid) -> Result<HashMap<String, FieldDefinition>, AppError> {
     let query = r#"
         SELECT field_name, field_type, is_required
         FROM custom_fields
-        WHERE is_active = true
+        WHERE org_id = $1 AND is_active = true
         ORDER BY field_name
     "#;
     
     let rows = sqlx::query_as::<_, CustomFieldRow>(query)
+        .bind(org_id)
         .fetch_all(pool)
         .await
         .map_err(|e| {
@@ -367,14 +362,14 @@ fn validate_filter_logic(filter: &ContactFilterRequest) -> Result<FilterValidati
 // Helper functions for validation
 fn count_total_conditions(conditions: &[FilterNode]) -> usize {
     conditions.iter().map(|node| match node {
-        FilterNode::Condition { .. } => 1,
+        FilterNode::Condition { .. } | FilterNode::Tags { .. } => 1,
         FilterNode::Group { conditions, .. } => count_total_conditions(conditions),
     }).sum()
 }
 
 fn has_empty_groups(conditions: &[FilterNode]) -> bool {
     conditions.iter().any(|node| match node {
-        FilterNode::Condition { .. } => false,
+        FilterNode::Condition { .. } | FilterNode::Tags { .. } => false,
         FilterNode::Group { conditions, .. } => {
             conditions.is_empty() || has_empty_groups(conditions)
         }
@@ -0,0 +1,139 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json},
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::dto::contact_import_dto::{ContactImportOptions, DuplicateStrategy};
+use crate::errors::AppError;
+use crate::middleware::ActiveOrganization;
+use crate::services::contact_import_service::{ContactImportOutcome, ContactImportService};
+use crate::AppState;
+
+/// Import contacts from a CSV file
+/// POST /contacts/import
+///
+/// Multipart form with a `file` part and optional `mapping` (JSON object of header -> field),
+/// `duplicates` (skip, update or create) and `dry_run` parts. Responds 200 with the import
+/// report, or 202 with the queued job when the file exceeds the synchronous limit.
+pub async fn import_contacts(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    let mut file: Option<(String, String)> = None;
+    let mut options = ContactImportOptions::default();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::ValidationError(format!("Invalid multipart body: {}", e)))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        let file_name = field.file_name().unwrap_or("contacts.csv").to_string();
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| AppError::ValidationError(format!("Invalid multipart body: {}", e)))?;
+
+        match name.as_str() {
+            "file" => {
+                let content = String::from_utf8(bytes.to_vec())
+                    .map_err(|_| AppError::field("file", "invalid_encoding", "The file must be UTF-8 encoded"))?;
+                let content = content.strip_prefix('\u{feff}').map(str::to_string).unwrap_or(content);
+                file = Some((file_name, content));
+            }
+            "mapping" => {
+                options.mapping = serde_json::from_slice(&bytes).map_err(|_| {
+                    AppError::field("mapping", "invalid", "Mapping must be a JSON object of column -> field")
+                })?;
+            }
+            "duplicates" => {
+                let value = String::from_utf8_lossy(&bytes);
+                options.duplicates = DuplicateStrategy::parse(value.trim()).ok_or_else(|| {
+                    AppError::field("duplicates", "invalid", "Duplicates must be one of: skip, update, create")
+                })?;
+            }
+            "dry_run" => {
+                options.dry_run = match String::from_utf8_lossy(&bytes).trim() {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(AppError::field("dry_run", "invalid", "dry_run must be true or false")),
+                };
+            }
+            _ => tracing::warn!("Ignoring unknown import form field '{}'", name),
+        }
+    }
+
+    let (file_name, content) = file.ok_or_else(|| AppError::field("file", "required", "A CSV file is required"))?;
+
+    tracing::info!(
+        "Contact import of '{}' by user: {} in organization: {} (dry run: {})",
+        file_name,
+        auth.user.id,
+        auth.org_id,
+        options.dry_run
+    );
+
+    let outcome =
+        ContactImportService::import(&state, auth.org_id, auth.user.id, &auth.permissions, file_name, content, options)
+            .await?;
+
+    let (status, response) = match outcome {
+        ContactImportOutcome::Completed(report) => (
+            StatusCode::OK,
+            json!({
+                "success": report.failed == 0,
+                "data": report
+            }),
+        ),
+        ContactImportOutcome::Queued(job) => (
+            StatusCode::ACCEPTED,
+            json!({
+                "success": true,
+                "message": "Import queued",
+                "data": job
+            }),
+        ),
+    };
+
+    Ok((status, Json(response)))
+}
+
+/// Progress of a queued import
+/// GET /contacts/import/:job_id
+pub async fn get_contact_import_job(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let job = ContactImportService::get_job(&state.db, auth.org_id, job_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": job
+    })))
+}
+
+/// Rows of an import job that failed, as a CSV download
+/// GET /contacts/import/:job_id/errors
+pub async fn download_contact_import_errors(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(job_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let csv = ContactImportService::error_csv(&state.db, auth.org_id, job_id).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"contact-import-{}-errors.csv\"", job_id),
+            ),
+        ],
+        csv,
+    ))
+}
@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::dto::contact_tag_dto::{AssignTagsRequest, BulkTagRequest, CreateTagRequest, UpdateTagRequest};
+use crate::errors::AppError;
+use crate::middleware::ActiveOrganization;
+use crate::services::contact_tag_service::ContactTagService;
+use crate::AppState;
+
+/// List tags with contact counts
+/// GET /tags
+pub async fn list_tags(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+) -> Result<Json<Value>, AppError> {
+    let tags = ContactTagService::list_tags(&state.db, auth.org_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": tags
+    })))
+}
+
+/// View a single tag
+/// GET /tags/:id
+pub async fn get_tag(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(tag_id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let tag = ContactTagService::get_tag(&state.db, auth.org_id, tag_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": tag
+    })))
+}
+
+/// Create a new tag
+/// POST /tags
+pub async fn create_tag(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(request): Json<CreateTagRequest>,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    tracing::info!("Creating tag '{}' by user: {}", request.name, auth.user.id);
+
+    let tag = ContactTagService::create_tag(&state.db, auth.org_id, request).await?;
+
+    Ok((StatusCode::CREATED, Json(json!({
+        "success": true,
+        "message": "Tag created successfully",
+        "data": tag
+    }))))
+}
+
+/// Update a tag
+/// PUT /tags/:id
+pub async fn update_tag(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(tag_id): Path<Uuid>,
+    Json(request): Json<UpdateTagRequest>,
+) -> Result<Json<Value>, AppError> {
+    let tag = ContactTagService::update_tag(&state.db, auth.org_id, tag_id, request).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Tag updated successfully",
+        "data": tag
+    })))
+}
+
+/// Delete a tag and its assignments
+/// DELETE /tags/:id
+pub async fn delete_tag(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(tag_id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    ContactTagService::delete_tag(&state.db, auth.org_id, tag_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List the tags of a contact
+/// GET /contacts/:id/tags
+pub async fn get_contact_tags(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(contact_id): Path<Uuid>,
+) -> Result<Json<Value>, AppError> {
+    let tags = ContactTagService::get_contact_tags(&state.db, auth.org_id, contact_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "data": tags
+    })))
+}
+
+/// Assign tags to a contact
+/// POST /contacts/:id/tags
+pub async fn assign_contact_tags(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(contact_id): Path<Uuid>,
+    Json(request): Json<AssignTagsRequest>,
+) -> Result<Json<Value>, AppError> {
+    let tags = ContactTagService::assign_tags(&state.db, auth.org_id, contact_id, request, auth.user.id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Tags assigned successfully",
+        "data": tags
+    })))
+}
+
+/// Remove a tag from a contact
+/// DELETE /contacts/:id/tags/:tag_id
+pub async fn unassign_contact_tag(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((contact_id, tag_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    ContactTagService::unassign_tag(&state.db, auth.org_id, contact_id, tag_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Assign tags to many contacts
+/// POST /tags/bulk-assign
+pub async fn bulk_assign_tags(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(request): Json<BulkTagRequest>,
+) -> Result<Json<Value>, AppError> {
+    tracing::info!(
+        "Bulk tagging {} contact(s) with {} tag(s) by user: {}",
+        request.contact_ids.len(),
+        request.tag_ids.len(),
+        auth.user.id
+    );
+
+    let result = ContactTagService::bulk_assign(&state.db, auth.org_id, request, auth.user.id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Tags assigned successfully",
+        "data": result
+    })))
+}
+
+/// Remove tags from many contacts
+/// POST /tags/bulk-unassign
+pub async fn bulk_unassign_tags(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(request): Json<BulkTagRequest>,
+) -> Result<Json<Value>, AppError> {
+    let result = ContactTagService::bulk_unassign(&state.db, auth.org_id, request).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Tags removed successfully",
+        "data": result
+    })))
+}
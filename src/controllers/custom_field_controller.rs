@@ -1,34 +1,35 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
 use serde_json::{json, Value};
+use uuid::Uuid;
 
-use crate::dto::custom_field_dto::CreateCustomFieldRequest;
+use crate::dto::custom_field_dto::{
+    CreateCustomFieldRequest, CustomFieldQueryParams, DeleteCustomFieldParams, ReorderCustomFieldsRequest,
+    UpdateCustomFieldOptionsRequest, UpdateCustomFieldRequest,
+};
 use crate::errors::AppError;
-use crate::middleware::check_user_permission;
+use crate::middleware::ActiveOrganization;
 use crate::services::CustomFieldService;
 use crate::AppState;
 
 /// Create a new custom field
-/// POST /api/custom-fields
+/// POST /custom-fields
 pub async fn create_custom_field(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
+    auth: ActiveOrganization,
     Json(request): Json<CreateCustomFieldRequest>,
 ) -> Result<(StatusCode, Json<Value>), AppError> {
-    // Check permission
-    let user = check_user_permission(&state, &headers, "custom_fields:create").await?;
-
     tracing::info!(
         "Creating custom field: {} for module: {} by user: {}",
         request.field_name,
         request.module,
-        user.id
+        auth.user.id
     );
 
-    let custom_field = CustomFieldService::create_custom_field(&state.db, request, user.id).await?;
+    let custom_field = CustomFieldService::create_custom_field(&state.db, auth.org_id, request, auth.user.id).await?;
 
     let response = json!({
         "success": true,
@@ -40,16 +41,15 @@ pub async fn create_custom_field(
 }
 
 /// Get custom fields by module
-/// GET /api/custom-fields/:module
+/// GET /custom-fields/:module
 pub async fn get_custom_fields_by_module(
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
+    auth: ActiveOrganization,
     Path(module): Path<String>,
+    Query(params): Query<CustomFieldQueryParams>,
 ) -> Result<Json<Value>, AppError> {
-    // Check permission
-    let _user = check_user_permission(&state, &headers, "custom_fields:read").await?;
-
-    let custom_fields = CustomFieldService::get_custom_fields_by_module(&state.db, &module).await?;
+    let include_inactive = params.include_inactive.unwrap_or(false);
+    let custom_fields = CustomFieldService::get_custom_fields_by_module(&state.db, auth.org_id, &module, include_inactive).await?;
 
     let response = json!({
         "success": true,
@@ -57,4 +57,85 @@ pub async fn get_custom_fields_by_module(
     });
 
     Ok(Json(response))
-}
\ No newline at end of file
+}
+
+/// Reorder the custom fields of a module
+/// PUT /custom-fields/:module/order
+pub async fn reorder_custom_fields(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path(module): Path<String>,
+    Json(request): Json<ReorderCustomFieldsRequest>,
+) -> Result<Json<Value>, AppError> {
+    let custom_fields = CustomFieldService::reorder_custom_fields(&state.db, auth.org_id, &module, request).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Custom fields reordered successfully",
+        "data": custom_fields
+    })))
+}
+
+/// Update a custom field
+/// PUT /custom-fields/:module/:id
+pub async fn update_custom_field(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((module, field_id)): Path<(String, Uuid)>,
+    Json(request): Json<UpdateCustomFieldRequest>,
+) -> Result<Json<Value>, AppError> {
+    let custom_field = CustomFieldService::update_custom_field(&state.db, auth.org_id, &module, field_id, request).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Custom field updated successfully",
+        "data": custom_field
+    })))
+}
+
+/// Deactivate a custom field, keeping its stored values
+/// POST /custom-fields/:module/:id/deactivate
+pub async fn deactivate_custom_field(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((module, field_id)): Path<(String, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let custom_field = CustomFieldService::deactivate_custom_field(&state.db, auth.org_id, &module, field_id).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Custom field deactivated successfully",
+        "data": custom_field
+    })))
+}
+
+/// Replace the options of a select field and migrate stored values
+/// PUT /custom-fields/:module/:id/options
+pub async fn update_custom_field_options(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((module, field_id)): Path<(String, Uuid)>,
+    Json(request): Json<UpdateCustomFieldOptionsRequest>,
+) -> Result<Json<Value>, AppError> {
+    let result = CustomFieldService::update_options(&state, auth.org_id, &module, field_id, request).await?;
+
+    Ok(Json(json!({
+        "success": true,
+        "message": "Custom field options updated successfully",
+        "data": result
+    })))
+}
+
+/// Permanently delete a custom field
+/// DELETE /custom-fields/:module/:id?force=true
+pub async fn delete_custom_field(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((module, field_id)): Path<(String, Uuid)>,
+    Query(params): Query<DeleteCustomFieldParams>,
+) -> Result<StatusCode, AppError> {
+    let force = params.force.unwrap_or(false);
+    CustomFieldService::delete_custom_field(&state.db, auth.org_id, &module, field_id, force).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
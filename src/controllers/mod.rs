@@ -1,13 +1,27 @@
 // Controllers module - Handle HTTP requests and responses
+pub mod auth_controller;
 pub mod contact_controller;
 pub mod organization_controller;
 pub mod user_controller;
 pub mod user_organization_controller;
 
+pub use auth_controller::*;
 pub use contact_controller::*;
 
+pub mod contact_bulk_controller;
+pub use contact_bulk_controller::*;
 pub mod contact_filter_controller;
 pub use contact_filter_controller::*;
+pub mod contact_import_controller;
+pub use contact_import_controller::*;
+pub mod contact_tag_controller;
+pub use contact_tag_controller::*;
+pub mod contact_activity_controller;
+pub use contact_activity_controller::*;
+pub mod custom_field_controller;
+pub use custom_field_controller::*;
+pub mod role_controller;
+pub use role_controller::*;
 pub use organization_controller::*;
 pub use user_controller::*;
 pub use user_organization_controller::*;
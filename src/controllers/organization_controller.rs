@@ -1,21 +1,106 @@
 // Organization Controller - Handles organization-related HTTP requests
 
 use axum::{
-    extract::State,
+    extract::{Path, State},
     http::StatusCode,
     response::Json,
+    Extension,
 };
-use sqlx::PgPool;
+use uuid::Uuid;
 
-use crate::dto::{CreateOrganizationRequest, OrganizationResponse};
+use crate::dto::{
+    CreateOrganizationRequest, MfaPolicyResponse, OrganizationDeletionResponse, OrganizationResponse,
+    OrganizationSettingsResponse, UpdateMfaPolicyRequest, UpdateOrganizationRequest,
+};
 use crate::errors::AppError;
-use crate::services::OrganizationService;
+use crate::middleware::ActiveOrganization;
+use crate::models::OrganizationSettings;
+use crate::services::{MfaService, OrganizationService};
+use crate::utils::JwtUser;
+use crate::AppState;
 
-/// Create a new organization
+/// Create a new organization; the caller becomes its admin
 pub async fn create_organization(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
+    Extension(user): Extension<JwtUser>,
     Json(payload): Json<CreateOrganizationRequest>,
 ) -> Result<(StatusCode, Json<OrganizationResponse>), AppError> {
-    let response = OrganizationService::create_organization(&pool, payload).await?;
+    let response = OrganizationService::create_organization(&state, &user, payload).await?;
     Ok((StatusCode::CREATED, Json(response)))
 }
+
+/// GET /organizations/:org_id - Get the organization
+pub async fn get_organization(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+) -> Result<Json<OrganizationResponse>, AppError> {
+    let response = OrganizationService::get_organization(&state.db, auth.org_id).await?;
+    Ok(Json(response))
+}
+
+/// PUT /organizations/:org_id - Rename the organization and set its country and timezone
+pub async fn update_organization(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(payload): Json<UpdateOrganizationRequest>,
+) -> Result<Json<OrganizationResponse>, AppError> {
+    let response = OrganizationService::update_organization(&state.db, auth.org_id, &auth.user, payload).await?;
+    Ok(Json(response))
+}
+
+/// DELETE /organizations/:org_id - Delete the organization after a grace period
+pub async fn delete_organization(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+) -> Result<Json<OrganizationDeletionResponse>, AppError> {
+    let response = OrganizationService::delete_organization(&state, auth.org_id, &auth.user).await?;
+    Ok(Json(response))
+}
+
+/// POST /organizations/:org_id/restore - Undo a deletion within its grace period
+pub async fn restore_organization(
+    State(state): State<AppState>,
+    Extension(jwt_user): Extension<JwtUser>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationResponse>, AppError> {
+    let response = OrganizationService::restore_organization(&state, org_id, &jwt_user).await?;
+    Ok(Json(response))
+}
+
+/// GET /organizations/:org_id/settings - Get the organization's settings
+pub async fn get_organization_settings(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+) -> Result<Json<OrganizationSettingsResponse>, AppError> {
+    let response = OrganizationService::get_settings(&state.db, auth.org_id).await?;
+    Ok(Json(response))
+}
+
+/// PUT /organizations/:org_id/settings - Replace the organization's settings
+pub async fn update_organization_settings(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(payload): Json<OrganizationSettings>,
+) -> Result<Json<OrganizationSettingsResponse>, AppError> {
+    let response = OrganizationService::update_settings(&state.db, auth.org_id, &auth.user, payload).await?;
+    Ok(Json(response))
+}
+
+/// GET /organizations/:org_id/mfa-policy - Whether privileged roles must use MFA
+pub async fn get_mfa_policy(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+) -> Result<Json<MfaPolicyResponse>, AppError> {
+    let response = MfaService::policy(&state.db, auth.org_id).await?;
+    Ok(Json(response))
+}
+
+/// PUT /organizations/:org_id/mfa-policy - Require MFA for privileged roles, or stop requiring it
+pub async fn update_mfa_policy(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(payload): Json<UpdateMfaPolicyRequest>,
+) -> Result<Json<MfaPolicyResponse>, AppError> {
+    let response = MfaService::update_policy(&state.db, auth.org_id, &auth.user, payload).await?;
+    Ok(Json(response))
+}
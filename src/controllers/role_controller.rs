@@ -0,0 +1,104 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::dto::role_dto::{CreateRoleRequest, UpdateRoleRequest};
+use crate::errors::AppError;
+use crate::middleware::ActiveOrganization;
+use crate::services::RoleService;
+use crate::AppState;
+
+// The `:org_id` path segment selects the active organization (see `require_permission`),
+// so `auth.org_id` is always the organization named in the URL.
+
+/// List built-in and custom roles
+/// GET /organizations/:org_id/roles
+pub async fn list_roles(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+) -> Result<Json<Value>, AppError> {
+    let roles = RoleService::list_roles(&state.db, auth.org_id).await?;
+
+    let response = json!({
+        "success": true,
+        "data": roles
+    });
+
+    Ok(Json(response))
+}
+
+/// Create a custom role
+/// POST /organizations/:org_id/roles
+pub async fn create_role(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(request): Json<CreateRoleRequest>,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    tracing::info!("Creating role: {} in organization {} by user: {}", request.name, auth.org_id, auth.user.id);
+
+    let role = RoleService::create_role(&state.db, auth.org_id, &auth.permissions, request).await?;
+
+    let response = json!({
+        "success": true,
+        "message": "Role created successfully",
+        "data": role
+    });
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Get a role
+/// GET /organizations/:org_id/roles/:role_id
+pub async fn get_role(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((_org_id, role_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let role = RoleService::get_role(&state.db, auth.org_id, role_id).await?;
+
+    let response = json!({
+        "success": true,
+        "data": role
+    });
+
+    Ok(Json(response))
+}
+
+/// Update a custom role
+/// PUT /organizations/:org_id/roles/:role_id
+pub async fn update_role(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((_org_id, role_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateRoleRequest>,
+) -> Result<Json<Value>, AppError> {
+    tracing::info!("Updating role: {} in organization {} by user: {}", role_id, auth.org_id, auth.user.id);
+
+    let role = RoleService::update_role(&state.db, auth.org_id, role_id, &auth.permissions, request).await?;
+
+    let response = json!({
+        "success": true,
+        "message": "Role updated successfully",
+        "data": role
+    });
+
+    Ok(Json(response))
+}
+
+/// Delete a custom role that no member holds
+/// DELETE /organizations/:org_id/roles/:role_id
+pub async fn delete_role(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((_org_id, role_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    tracing::info!("Deleting role: {} in organization {} by user: {}", role_id, auth.org_id, auth.user.id);
+
+    RoleService::delete_role(&state.db, auth.org_id, role_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,18 +1,23 @@
 // User Controller - Handles user-related HTTP requests
 
 use axum::{
-    extract::{Path, Query, Request, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Path, Query, Request, State},
+    http::{HeaderMap, StatusCode},
     response::Json,
+    Extension,
 };
 use serde::Deserialize;
 use sqlx::PgPool;
+use std::net::SocketAddr;
 use uuid::Uuid;
 
-use crate::dto::{CreateUserRequest, LoginRequest, LoginResponse, UpdatePasswordRequest, UpdateUserStatusRequest, UserCreationResponse, UserResponse};
+use crate::dto::{CreateUserRequest, LoginRequest, LoginResponse, MyPermissionsResponse, UpdatePasswordRequest, UpdateUserStatusRequest, UserCreationResponse, UserResponse};
 use crate::errors::AppError;
-use crate::middleware::extract_user_from_request;
-use crate::services::UserService;
+use crate::controllers::auth_controller::{client_ip, user_agent};
+use crate::middleware::{extract_user_from_request, ActiveOrganization};
+use crate::services::{PermissionService, UserService};
+use crate::utils::{Claims, JwtUser};
+use crate::AppState;
 
 #[derive(Debug, Deserialize)]
 pub struct UserQueryParams {
@@ -21,12 +26,25 @@ pub struct UserQueryParams {
     pub status: Option<String>,
 }
 
+/// GET /me/permissions - The caller's effective permissions in the active organization
+pub async fn get_my_permissions(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+    Extension(claims): Extension<Claims>,
+    headers: HeaderMap,
+) -> Result<Json<MyPermissionsResponse>, AppError> {
+    let active = ActiveOrganization::for_user(&pool, jwt_user, &headers, claims.organization_id()?).await?;
+    let response = PermissionService::my_permissions(&pool, active.user.id, active.org_id, &active.permissions).await?;
+    Ok(Json(response))
+}
+
 /// POST /users - Create a new user
 pub async fn create_user(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<CreateUserRequest>,
 ) -> Result<(StatusCode, Json<UserCreationResponse>), AppError> {
-    let response = UserService::create_user(&pool, payload).await?;
+    let response = UserService::create_user(&state, payload, user_agent(&headers)).await?;
     Ok((StatusCode::CREATED, Json(response)))
 }
 
@@ -77,19 +95,26 @@ pub async fn delete_user(
 /// POST /users/login - Login user with email and password
 pub async fn login_user(
     State(pool): State<PgPool>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(payload): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
-    let response = UserService::login_user(&pool, payload).await?;
+    let client_ip = client_ip(&headers, connect_info);
+    let response = UserService::login_user(&pool, payload, user_agent(&headers), client_ip).await?;
     Ok(Json(response))
 }
 
-/// PUT /users/:id/password - Update user password
+/// PUT /users/:id/password - Update the caller's own password
 pub async fn update_user_password(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
+    Extension(jwt_user): Extension<JwtUser>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdatePasswordRequest>,
 ) -> Result<Json<UserResponse>, AppError> {
-    let response = UserService::update_password(&pool, id, payload).await?;
+    let client_ip = client_ip(&headers, connect_info);
+    let response = UserService::update_password(&state, &jwt_user, id, payload, client_ip).await?;
     Ok(Json(response))
 }
 
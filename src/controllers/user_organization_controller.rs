@@ -1,40 +1,37 @@
 // UserOrganization Controller - HTTP handlers for user-organization relationships
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{ConnectInfo, Extension, Path, Query, State},
+    http::{HeaderMap, StatusCode},
     response::Json,
 };
+use serde_json::{json, Value};
 use sqlx::PgPool;
+use std::net::SocketAddr;
 use uuid::Uuid;
 
+use crate::controllers::auth_controller::{client_ip, user_agent};
 use crate::dto::{
-    CreateUserOrganizationRequest, InviteUserToOrganizationRequest,
+    AcceptInvitationRequest, AcceptInvitationResponse, CreateInvitationRequest,
+    CreateUserOrganizationRequest, InvitationInfoResponse, InvitationQueryParams,
+    SwitchOrganizationRequest, SwitchOrganizationResponse,
     UpdateUserOrganizationRequest, UserOrganizationDetailResponse,
     UserOrganizationQueryParams,
 };
 use crate::errors::AppError;
-use crate::services::UserOrganizationService;
+use crate::middleware::ActiveOrganization;
+use crate::services::{InvitationService, LoginProtectionService, UserOrganizationService};
+use crate::utils::JwtUser;
+use crate::AppState;
 
-/// POST /user-organizations - Add user to organization
+/// POST /user-organizations - Add user to the active organization
 pub async fn add_user_to_organization(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
     Json(payload): Json<CreateUserOrganizationRequest>,
 ) -> Result<(StatusCode, Json<UserOrganizationDetailResponse>), AppError> {
-    // TODO: Add authentication check here when needed
-    
-    let response = UserOrganizationService::add_user_to_organization(&pool, payload).await?;
-    Ok((StatusCode::CREATED, Json(response)))
-}
-
-/// POST /user-organizations/invite - Invite user to organization
-pub async fn invite_user_to_organization(
-    State(pool): State<PgPool>,
-    Json(payload): Json<InviteUserToOrganizationRequest>,
-) -> Result<(StatusCode, Json<UserOrganizationDetailResponse>), AppError> {
-    // TODO: Add authentication check here when needed
-    
-    let response = UserOrganizationService::invite_user_to_organization(&pool, payload).await?;
+    let response =
+        UserOrganizationService::add_user_to_organization(&state, auth.org_id, &auth.permissions, payload).await?;
     Ok((StatusCode::CREATED, Json(response)))
 }
 
@@ -44,8 +41,6 @@ pub async fn get_user_organizations(
     Path(user_id): Path<Uuid>,
     Query(params): Query<UserOrganizationQueryParams>,
 ) -> Result<Json<Vec<UserOrganizationDetailResponse>>, AppError> {
-    // TODO: Add authentication and authorization checks here
-    
     let response = UserOrganizationService::get_user_organizations(
         &pool, 
         user_id, 
@@ -61,8 +56,6 @@ pub async fn get_organization_users(
     Path(org_id): Path<Uuid>,
     Query(params): Query<UserOrganizationQueryParams>,
 ) -> Result<Json<Vec<UserOrganizationDetailResponse>>, AppError> {
-    // TODO: Add authentication and authorization checks here
-    
     let response = UserOrganizationService::get_organization_users(
         &pool, 
         org_id, 
@@ -72,26 +65,26 @@ pub async fn get_organization_users(
     Ok(Json(response))
 }
 
-/// PUT /user-organizations/:id - Update user organization relationship
+/// PUT /user-organizations/:id - Update user organization relationship in the active organization
 pub async fn update_user_organization(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateUserOrganizationRequest>,
 ) -> Result<Json<UserOrganizationDetailResponse>, AppError> {
-    // TODO: Add authentication and authorization checks here
-    
-    let response = UserOrganizationService::update_user_organization(&pool, id, payload).await?;
+    let response =
+        UserOrganizationService::update_user_organization(&state, auth.org_id, &auth.permissions, id, payload)
+            .await?;
     Ok(Json(response))
 }
 
-/// DELETE /user-organizations/:id - Remove user from organization
+/// DELETE /user-organizations/:id - Remove user from the active organization
 pub async fn remove_user_from_organization(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
-    // TODO: Add authentication and authorization checks here
-    
-    UserOrganizationService::remove_user_from_organization(&pool, id).await?;
+    UserOrganizationService::remove_user_from_organization(&state, auth.org_id, id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -112,3 +105,128 @@ pub async fn get_current_user_organizations(
     
     Ok(Json(response))
 }
+
+/// POST /users/me/organizations/switch - Select the active organization for subsequent requests
+pub async fn switch_organization(
+    State(pool): State<PgPool>,
+    Extension(jwt_user): Extension<JwtUser>,
+    Json(payload): Json<SwitchOrganizationRequest>,
+) -> Result<Json<SwitchOrganizationResponse>, AppError> {
+    let response = UserOrganizationService::switch_organization(&pool, &jwt_user, payload).await?;
+    Ok(Json(response))
+}
+
+// Invitation management routes are authorized against the `:org_id` path segment (see `require_permission`).
+
+/// POST /organizations/:org_id/invitations - Invite an email address to the organization
+pub async fn create_invitation(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Json(request): Json<CreateInvitationRequest>,
+) -> Result<(StatusCode, Json<Value>), AppError> {
+    let invitation = InvitationService::create_invitation(&state, auth.org_id, &auth.user, &auth.permissions, request).await?;
+
+    let response = json!({
+        "success": true,
+        "message": "Invitation sent successfully",
+        "data": invitation
+    });
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// GET /organizations/:org_id/invitations - List the organization's invitations
+pub async fn list_invitations(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Query(params): Query<InvitationQueryParams>,
+) -> Result<Json<Value>, AppError> {
+    let invitations = InvitationService::list_invitations(&state.db, auth.org_id, params.status).await?;
+
+    let response = json!({
+        "success": true,
+        "data": invitations
+    });
+
+    Ok(Json(response))
+}
+
+/// POST /organizations/:org_id/invitations/:invitation_id/resend - Send a pending invitation again
+pub async fn resend_invitation(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((_org_id, invitation_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let invitation = InvitationService::resend_invitation(&state, auth.org_id, invitation_id, &auth.user).await?;
+
+    let response = json!({
+        "success": true,
+        "message": "Invitation resent successfully",
+        "data": invitation
+    });
+
+    Ok(Json(response))
+}
+
+/// DELETE /organizations/:org_id/invitations/:invitation_id - Revoke a pending invitation
+pub async fn revoke_invitation(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    Path((_org_id, invitation_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, AppError> {
+    InvitationService::revoke_invitation(&state.db, auth.org_id, invitation_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /organizations/:org_id/members/:user_id/unlock - Lift a member's login lockout
+pub async fn unlock_member(
+    State(state): State<AppState>,
+    auth: ActiveOrganization,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Path((_org_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Value>, AppError> {
+    let client_ip = client_ip(&headers, connect_info);
+    let was_locked =
+        LoginProtectionService::unlock_member(&state.db, auth.org_id, &auth.user, user_id, client_ip).await?;
+
+    let response = json!({
+        "success": true,
+        "message": if was_locked { "Account unlocked" } else { "Account was not locked" },
+        "data": {
+            "user_id": user_id,
+            "was_locked": was_locked
+        }
+    });
+
+    Ok(Json(response))
+}
+
+/// GET /invitations/:token - Describe the invitation behind a link
+pub async fn get_invitation_info(
+    State(pool): State<PgPool>,
+    Path(token): Path<String>,
+) -> Result<Json<InvitationInfoResponse>, AppError> {
+    let response = InvitationService::get_invitation_info(&pool, &token).await?;
+    Ok(Json(response))
+}
+
+/// POST /invitations/:token/accept - Join the organization, creating an account if needed
+pub async fn accept_invitation(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+    Json(payload): Json<AcceptInvitationRequest>,
+) -> Result<Json<AcceptInvitationResponse>, AppError> {
+    let response = InvitationService::accept_invitation(&state, &token, payload, user_agent(&headers)).await?;
+    Ok(Json(response))
+}
+
+/// POST /invitations/:token/decline - Decline an invitation
+pub async fn decline_invitation(
+    State(pool): State<PgPool>,
+    Path(token): Path<String>,
+) -> Result<StatusCode, AppError> {
+    InvitationService::decline_invitation(&pool, &token).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
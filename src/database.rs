@@ -1,11 +1,13 @@
 // Database connection module for CockroachDB
 
 pub mod migrations;
+pub mod unit_of_work;
 
 use sqlx::PgPool;
 use std::env;
 
 pub use migrations::*;
+pub use unit_of_work::*;
 
 /// Creates a connection pool to CockroachDB
 ///
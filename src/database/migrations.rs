@@ -1,7 +1,25 @@
 // Database migrations runner
 
-use sqlx::{PgPool, Row};
+use sqlx::{PgExecutor, PgPool, Row};
+use uuid::Uuid;
 use crate::errors::AppError;
+use crate::models::DEFAULT_LEAD_STATUSES;
+use crate::services::permission_registry::*;
+
+/// `$2` appended to a role's permissions, skipping the ones the role already holds
+const MERGE_ROLE_PERMISSIONS: &str = r#"
+    permissions || COALESCE(
+        (
+            SELECT jsonb_agg(granted.value ORDER BY granted.position)
+            FROM jsonb_array_elements($2::jsonb) WITH ORDINALITY AS granted(value, position)
+            WHERE NOT roles.permissions @> jsonb_build_array(granted.value)
+        ),
+        '[]'::jsonb
+    )
+"#;
+
+/// Organization migration 008 creates for CRM rows when the database has no organization to put them in
+const UNASSIGNED_CRM_ORG_NAME: &str = "Unassigned CRM Data";
 
 pub struct MigrationRunner;
 
@@ -21,6 +39,20 @@ impl MigrationRunner {
         Self::run_migration_005_insert_sample_crm_data(pool).await?;
         Self::run_migration_006_add_owner_to_contacts(pool).await?;
         Self::run_migration_007_add_contact_permissions(pool).await?;
+        Self::run_migration_008_scope_crm_tables_to_organizations(pool).await?;
+        Self::run_migration_009_add_activity_permissions(pool).await?;
+        Self::run_migration_010_add_custom_field_permissions(pool).await?;
+        Self::run_migration_011_create_user_sessions_table(pool).await?;
+        Self::run_migration_012_add_organization_roles(pool).await?;
+        Self::run_migration_013_create_invitations_and_email_outbox(pool).await?;
+        Self::run_migration_014_create_user_tokens_table(pool).await?;
+        Self::run_migration_015_create_login_throttles_and_audit_log(pool).await?;
+        Self::run_migration_016_add_totp_mfa(pool).await?;
+        Self::run_migration_017_create_password_history(pool).await?;
+        Self::run_migration_018_add_organization_lifecycle(pool).await?;
+        Self::run_migration_019_create_lead_statuses(pool).await?;
+        Self::run_migration_020_create_contact_bulk_jobs(pool).await?;
+        Self::run_migration_021_create_contact_import_jobs(pool).await?;
 
         tracing::info!("All migrations completed successfully");
         Ok(())
@@ -55,11 +87,40 @@ impl MigrationRunner {
     }
 
     /// Mark migration as applied
-    async fn mark_migration_applied(pool: &PgPool, migration_name: &str) -> Result<(), AppError> {
+    async fn mark_migration_applied(executor: impl PgExecutor<'_>, migration_name: &str) -> Result<(), AppError> {
         sqlx::query("INSERT INTO _migrations (migration_name) VALUES ($1)")
             .bind(migration_name)
+            .execute(executor)
+            .await?;
+        Ok(())
+    }
+
+    /// Seed the built-in roles every organization starts with
+    async fn insert_default_roles(pool: &PgPool) -> Result<(), AppError> {
+        let default_roles = [
+            ("owner", "Organization owner with full access", vec!["*"]),
+            ("admin", "Organization administrator", vec![USERS_READ, USERS_WRITE, ORG_READ, ORG_WRITE]),
+            ("member", "Regular organization member", vec![ORG_READ, USERS_READ]),
+            ("viewer", "Read-only access to organization", vec![ORG_READ]),
+        ];
+
+        // Migration 012 replaces the unique name constraint with partial indexes, so ON CONFLICT (name)
+        // cannot be used on this path, which runs on every startup
+        for (role_name, description, permissions) in default_roles {
+            sqlx::query(
+                r#"
+                INSERT INTO roles (name, description, permissions)
+                SELECT $1, $2, $3
+                WHERE NOT EXISTS (SELECT 1 FROM roles WHERE name = $1)
+                "#,
+            )
+            .bind(role_name)
+            .bind(description)
+            .bind(PermissionRegistry::to_json(&permissions)?)
             .execute(pool)
             .await?;
+        }
+
         Ok(())
     }
 
@@ -176,16 +237,7 @@ impl MigrationRunner {
         tracing::info!("Roles table created successfully");
 
         // Insert default roles
-        let insert_roles = r#"
-            INSERT INTO roles (name, description, permissions) VALUES
-                ('owner', 'Organization owner with full access', '["*"]'::jsonb),
-                ('admin', 'Organization administrator', '["users:read", "users:write", "org:read", "org:write"]'::jsonb),
-                ('member', 'Regular organization member', '["org:read", "users:read"]'::jsonb),
-                ('viewer', 'Read-only access to organization', '["org:read"]'::jsonb)
-            ON CONFLICT (name) DO NOTHING
-        "#;
-
-        sqlx::query(insert_roles).execute(pool).await?;
+        Self::insert_default_roles(pool).await?;
         tracing::info!("Default roles inserted successfully");
 
         // Create user_organizations table
@@ -332,16 +384,7 @@ impl MigrationRunner {
         tracing::info!("Roles table created successfully");
 
         // Insert default roles
-        let insert_roles = r#"
-            INSERT INTO roles (name, description, permissions) VALUES
-                ('owner', 'Organization owner with full access', '["*"]'::jsonb),
-                ('admin', 'Organization administrator', '["users:read", "users:write", "org:read", "org:write"]'::jsonb),
-                ('member', 'Regular organization member', '["org:read", "users:read"]'::jsonb),
-                ('viewer', 'Read-only access to organization', '["org:read"]'::jsonb)
-            ON CONFLICT (name) DO NOTHING
-        "#;
-
-        sqlx::query(insert_roles).execute(pool).await?;
+        Self::insert_default_roles(pool).await?;
         tracing::info!("Default roles inserted successfully");
 
         // Create user_organizations table
@@ -864,72 +907,1017 @@ impl MigrationRunner {
         tracing::info!("Running migration: {}", migration_name);
 
         // Update existing roles with contact permissions
-        let update_admin_role = r#"
-            UPDATE roles SET permissions = '[
-                "users:read", "users:write", "users:create", "users:delete",
-                "org:read", "org:write", "org:create", "org:delete",
-                "contacts:read", "contacts:create", "contacts:update", "contacts:delete",
-                "contacts:export", "contacts:import", "contacts:assign_owner", "contacts:bulk_update",
-                "reports:read", "reports:create", "reports:export"
-            ]'::jsonb WHERE name = 'admin'
+        let built_in_roles = [
+            (
+                "admin",
+                vec![
+                    USERS_READ, USERS_WRITE, USERS_CREATE, USERS_DELETE,
+                    ORG_READ, ORG_WRITE, ORG_CREATE, ORG_DELETE,
+                    CONTACTS_READ, CONTACTS_CREATE, CONTACTS_UPDATE, CONTACTS_DELETE,
+                    CONTACTS_EXPORT, CONTACTS_IMPORT, CONTACTS_ASSIGN_OWNER, CONTACTS_BULK_UPDATE,
+                    REPORTS_READ, REPORTS_CREATE, REPORTS_EXPORT,
+                ],
+            ),
+            (
+                "member",
+                vec![
+                    ORG_READ, USERS_READ,
+                    CONTACTS_READ, CONTACTS_CREATE, "contacts:update_own", "contacts:delete_own",
+                    REPORTS_READ,
+                ],
+            ),
+            (
+                "viewer",
+                vec![ORG_READ, CONTACTS_READ, "contacts:read_own", REPORTS_READ],
+            ),
+        ];
+
+        for (role_name, permissions) in built_in_roles {
+            sqlx::query("UPDATE roles SET permissions = $2 WHERE name = $1")
+                .bind(role_name)
+                .bind(PermissionRegistry::to_json(&permissions)?)
+                .execute(pool)
+                .await?;
+            tracing::info!("Updated {} role with contact permissions", role_name);
+        }
+
+        // Add new contact-specific roles
+        let contact_roles = [
+            (
+                "contact_manager",
+                "Contact management specialist",
+                vec![
+                    ORG_READ, USERS_READ,
+                    CONTACTS_READ, CONTACTS_CREATE, CONTACTS_UPDATE, CONTACTS_DELETE,
+                    CONTACTS_EXPORT, CONTACTS_IMPORT, CONTACTS_ASSIGN_OWNER,
+                    REPORTS_READ, REPORTS_CREATE,
+                ],
+            ),
+            (
+                "sales_rep",
+                "Sales representative",
+                vec![
+                    ORG_READ, USERS_READ,
+                    CONTACTS_READ, CONTACTS_CREATE, "contacts:update_own", "contacts:delete_own",
+                    CONTACTS_EXPORT, REPORTS_READ,
+                ],
+            ),
+            (
+                "marketing_user",
+                "Marketing team member",
+                vec![
+                    ORG_READ, USERS_READ,
+                    CONTACTS_READ, CONTACTS_CREATE, "contacts:update_own",
+                    CONTACTS_EXPORT, CONTACTS_BULK_UPDATE, REPORTS_READ,
+                ],
+            ),
+            (
+                "support_agent",
+                "Customer support agent",
+                vec![ORG_READ, USERS_READ, CONTACTS_READ, CONTACTS_UPDATE, REPORTS_READ],
+            ),
+            (
+                "readonly_user",
+                "Read-only access to all contacts",
+                vec![ORG_READ, USERS_READ, CONTACTS_READ, REPORTS_READ],
+            ),
+        ];
+
+        for (role_name, description, permissions) in contact_roles {
+            sqlx::query(
+                "INSERT INTO roles (name, description, permissions) VALUES ($1, $2, $3) ON CONFLICT (name) DO NOTHING",
+            )
+            .bind(role_name)
+            .bind(description)
+            .bind(PermissionRegistry::to_json(&permissions)?)
+            .execute(pool)
+            .await?;
+        }
+        tracing::info!("Added new contact-specific roles");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 008: Scope CRM tables to organizations
+    ///
+    /// Adds `org_id` to contacts, custom_fields, contact_tags and contact_activities,
+    /// backfills existing rows, makes the column required and replaces the global
+    /// unique constraints with per-organization ones.
+    async fn run_migration_008_scope_crm_tables_to_organizations(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "008_scope_crm_tables_to_organizations";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        // Add org_id columns
+        let add_org_columns = vec![
+            "ALTER TABLE contacts ADD COLUMN IF NOT EXISTS org_id UUID REFERENCES organization(id) ON DELETE CASCADE",
+            "ALTER TABLE custom_fields ADD COLUMN IF NOT EXISTS org_id UUID REFERENCES organization(id) ON DELETE CASCADE",
+            "ALTER TABLE contact_tags ADD COLUMN IF NOT EXISTS org_id UUID REFERENCES organization(id) ON DELETE CASCADE",
+            "ALTER TABLE contact_activities ADD COLUMN IF NOT EXISTS org_id UUID REFERENCES organization(id) ON DELETE CASCADE",
+        ];
+
+        for query in add_org_columns {
+            sqlx::query(query).execute(pool).await?;
+        }
+        tracing::info!("Added org_id columns to CRM tables");
+
+        // Drop the global unique constraints (Postgres names them <table>_<cols>_key,
+        // CockroachDB only allows dropping them as indexes)
+        let global_unique_constraints = vec![
+            ("contacts", "contacts_email_key"),
+            ("custom_fields", "custom_fields_module_field_name_key"),
+            ("contact_tags", "contact_tags_name_key"),
+        ];
+
+        for (table, constraint) in global_unique_constraints {
+            let drop_constraint = format!("ALTER TABLE {} DROP CONSTRAINT IF EXISTS {}", table, constraint);
+            if let Err(e) = sqlx::query(&drop_constraint).execute(pool).await {
+                let drop_index = format!("DROP INDEX IF EXISTS {}@{} CASCADE", table, constraint);
+                if let Err(index_err) = sqlx::query(&drop_index).execute(pool).await {
+                    tracing::warn!(
+                        "Could not drop unique constraint {} ({}; {})",
+                        constraint,
+                        e,
+                        index_err
+                    );
+                }
+            }
+        }
+        tracing::info!("Dropped global unique constraints");
+
+        // Per-organization uniqueness and lookup indexes
+        let org_indexes = vec![
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_contacts_org_email ON contacts(org_id, email)",
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_custom_fields_org_module_field_name ON custom_fields(org_id, module, field_name)",
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_contact_tags_org_name ON contact_tags(org_id, name)",
+            "CREATE INDEX IF NOT EXISTS idx_contacts_org_id ON contacts(org_id)",
+            "CREATE INDEX IF NOT EXISTS idx_custom_fields_org_id ON custom_fields(org_id)",
+            "CREATE INDEX IF NOT EXISTS idx_contact_tags_org_id ON contact_tags(org_id)",
+            "CREATE INDEX IF NOT EXISTS idx_contact_activities_org_id ON contact_activities(org_id)",
+        ];
+
+        for index in org_indexes {
+            sqlx::query(index).execute(pool).await?;
+        }
+        tracing::info!("Created per-organization indexes");
+
+        // Backfill and require org_id in one transaction, so a failure leaves no table half scoped
+        let mut tx = pool.begin().await?;
+
+        // Rows no membership places go to the oldest organization; a database without any (e.g. a fresh
+        // install holding migration 005's sample data) gets a designated organization for them instead
+        let needs_designated_org = r#"
+            SELECT NOT EXISTS (SELECT 1 FROM organization)
+                AND (EXISTS (SELECT 1 FROM contacts WHERE org_id IS NULL)
+                    OR EXISTS (SELECT 1 FROM custom_fields WHERE org_id IS NULL)
+                    OR EXISTS (SELECT 1 FROM contact_tags WHERE org_id IS NULL))
+        "#;
+
+        if sqlx::query_scalar::<_, bool>(needs_designated_org).fetch_one(&mut *tx).await? {
+            sqlx::query("INSERT INTO organization (name) VALUES ($1)")
+                .bind(UNASSIGNED_CRM_ORG_NAME)
+                .execute(&mut *tx)
+                .await?;
+            tracing::warn!("Created organization '{}' for CRM data that belonged to no organization", UNASSIGNED_CRM_ORG_NAME);
+        }
+
+        // Backfill contacts: owner's oldest active membership, then the oldest organization
+        let backfill_contacts_from_owner = r#"
+            UPDATE contacts c
+            SET org_id = (
+                SELECT uo.org_id FROM user_organizations uo
+                WHERE uo.user_id = c.owner_id AND uo.status = 'active'
+                ORDER BY uo.joined_at ASC
+                LIMIT 1
+            )
+            WHERE c.org_id IS NULL AND c.owner_id IS NOT NULL
         "#;
 
-        sqlx::query(update_admin_role).execute(pool).await?;
-        tracing::info!("Updated admin role with contact permissions");
+        sqlx::query(backfill_contacts_from_owner).execute(&mut *tx).await?;
 
-        let update_member_role = r#"
-            UPDATE roles SET permissions = '[
-                "org:read", "users:read",
-                "contacts:read", "contacts:create", "contacts:update_own", "contacts:delete_own",
-                "reports:read"
-            ]'::jsonb WHERE name = 'member'
+        let backfill_contacts_default = r#"
+            UPDATE contacts
+            SET org_id = (SELECT id FROM organization ORDER BY created_at ASC LIMIT 1)
+            WHERE org_id IS NULL
         "#;
 
-        sqlx::query(update_member_role).execute(pool).await?;
-        tracing::info!("Updated member role with contact permissions");
+        sqlx::query(backfill_contacts_default).execute(&mut *tx).await?;
+        tracing::info!("Backfilled contacts.org_id");
 
-        let update_viewer_role = r#"
-            UPDATE roles SET permissions = '[
-                "org:read",
-                "contacts:read", "contacts:read_own",
-                "reports:read"
-            ]'::jsonb WHERE name = 'viewer'
+        // Backfill activities from their contact
+        let backfill_activities = r#"
+            UPDATE contact_activities a
+            SET org_id = c.org_id
+            FROM contacts c
+            WHERE a.contact_id = c.id AND a.org_id IS NULL
         "#;
 
-        sqlx::query(update_viewer_role).execute(pool).await?;
-        tracing::info!("Updated viewer role with contact permissions");
+        sqlx::query(backfill_activities).execute(&mut *tx).await?;
+        tracing::info!("Backfilled contact_activities.org_id");
 
-        // Add new contact-specific roles
-        let insert_contact_roles = r#"
-            INSERT INTO roles (name, description, permissions) VALUES
-                ('contact_manager', 'Contact management specialist', '[
-                    "org:read", "users:read",
-                    "contacts:read", "contacts:create", "contacts:update", "contacts:delete",
-                    "contacts:export", "contacts:import", "contacts:assign_owner",
-                    "reports:read", "reports:create"
-                ]'::jsonb),
-                ('sales_rep', 'Sales representative', '[
-                    "org:read", "users:read",
-                    "contacts:read", "contacts:create", "contacts:update_own", "contacts:delete_own",
-                    "contacts:export", "reports:read"
-                ]'::jsonb),
-                ('marketing_user', 'Marketing team member', '[
-                    "org:read", "users:read",
-                    "contacts:read", "contacts:create", "contacts:update_own",
-                    "contacts:export", "contacts:bulk_update", "reports:read"
-                ]'::jsonb),
-                ('support_agent', 'Customer support agent', '[
-                    "org:read", "users:read",
-                    "contacts:read", "contacts:update", "reports:read"
-                ]'::jsonb),
-                ('readonly_user', 'Read-only access to all contacts', '[
-                    "org:read", "users:read", "contacts:read", "reports:read"
-                ]'::jsonb)
-            ON CONFLICT (name) DO NOTHING
+        // Backfill custom fields: creator's organization, then the oldest organization
+        let backfill_custom_fields_from_creator = r#"
+            UPDATE custom_fields cf
+            SET org_id = (
+                SELECT uo.org_id FROM user_organizations uo
+                WHERE uo.user_id = cf.created_by AND uo.status = 'active'
+                ORDER BY uo.joined_at ASC
+                LIMIT 1
+            )
+            WHERE cf.org_id IS NULL AND cf.created_by IS NOT NULL
         "#;
 
-        sqlx::query(insert_contact_roles).execute(pool).await?;
-        tracing::info!("Added new contact-specific roles");
+        sqlx::query(backfill_custom_fields_from_creator).execute(&mut *tx).await?;
+
+        let backfill_custom_fields_default = r#"
+            UPDATE custom_fields
+            SET org_id = (SELECT id FROM organization ORDER BY created_at ASC LIMIT 1)
+            WHERE org_id IS NULL
+        "#;
+
+        sqlx::query(backfill_custom_fields_default).execute(&mut *tx).await?;
+
+        // Copy field definitions into every other organization that already stores
+        // values for them, then point those values at the organization's own copy
+        let copy_custom_fields = r#"
+            INSERT INTO custom_fields (
+                org_id, module, label, field_name, field_type, is_required, is_active,
+                options, validation_rules, default_value, help_text, display_order, created_by
+            )
+            SELECT DISTINCT
+                c.org_id, cf.module, cf.label, cf.field_name, cf.field_type, cf.is_required, cf.is_active,
+                cf.options, cf.validation_rules, cf.default_value, cf.help_text, cf.display_order, cf.created_by
+            FROM contact_custom_values ccv
+            JOIN contacts c ON ccv.contact_id = c.id
+            JOIN custom_fields cf ON ccv.custom_field_id = cf.id
+            WHERE c.org_id IS NOT NULL AND cf.org_id IS DISTINCT FROM c.org_id
+            ON CONFLICT (org_id, module, field_name) DO NOTHING
+        "#;
+
+        sqlx::query(copy_custom_fields).execute(&mut *tx).await?;
+
+        let repoint_custom_values = r#"
+            UPDATE contact_custom_values ccv
+            SET custom_field_id = org_cf.id
+            FROM contacts c, custom_fields cf, custom_fields org_cf
+            WHERE ccv.contact_id = c.id
+              AND ccv.custom_field_id = cf.id
+              AND cf.org_id IS DISTINCT FROM c.org_id
+              AND org_cf.org_id = c.org_id
+              AND org_cf.module = cf.module
+              AND org_cf.field_name = cf.field_name
+        "#;
+
+        sqlx::query(repoint_custom_values).execute(&mut *tx).await?;
+        tracing::info!("Backfilled custom_fields.org_id");
+
+        // Backfill tags the same way: oldest organization, then per-organization copies
+        let backfill_tags_default = r#"
+            UPDATE contact_tags
+            SET org_id = (SELECT id FROM organization ORDER BY created_at ASC LIMIT 1)
+            WHERE org_id IS NULL
+        "#;
+
+        sqlx::query(backfill_tags_default).execute(&mut *tx).await?;
+
+        let copy_tags = r#"
+            INSERT INTO contact_tags (org_id, name, color, description)
+            SELECT DISTINCT c.org_id, t.name, t.color, t.description
+            FROM contact_tag_assignments cta
+            JOIN contacts c ON cta.contact_id = c.id
+            JOIN contact_tags t ON cta.tag_id = t.id
+            WHERE c.org_id IS NOT NULL AND t.org_id IS DISTINCT FROM c.org_id
+            ON CONFLICT (org_id, name) DO NOTHING
+        "#;
+
+        sqlx::query(copy_tags).execute(&mut *tx).await?;
+
+        let repoint_tag_assignments = r#"
+            UPDATE contact_tag_assignments cta
+            SET tag_id = org_t.id
+            FROM contacts c, contact_tags t, contact_tags org_t
+            WHERE cta.contact_id = c.id
+              AND cta.tag_id = t.id
+              AND t.org_id IS DISTINCT FROM c.org_id
+              AND org_t.org_id = c.org_id
+              AND org_t.name = t.name
+        "#;
+
+        sqlx::query(repoint_tag_assignments).execute(&mut *tx).await?;
+        tracing::info!("Backfilled contact_tags.org_id");
+
+        // Every row has an organization by now; name any that does not rather than dropping it
+        let scoped_tables = vec!["contact_activities", "contacts", "custom_fields", "contact_tags"];
+
+        for table in &scoped_tables {
+            let find_unassigned = format!("SELECT id FROM {} WHERE org_id IS NULL ORDER BY id", table);
+            let unassigned: Vec<Uuid> = sqlx::query_scalar(&find_unassigned).fetch_all(&mut *tx).await?;
+            if !unassigned.is_empty() {
+                let ids: Vec<String> = unassigned.iter().map(|id| id.to_string()).collect();
+                return Err(AppError::InternalServerError(format!(
+                    "Migration {} could not assign {} {} rows to an organization: {}",
+                    migration_name,
+                    ids.len(),
+                    table,
+                    ids.join(", ")
+                )));
+            }
+        }
+
+        for table in &scoped_tables {
+            let require_org = format!("ALTER TABLE {} ALTER COLUMN org_id SET NOT NULL", table);
+            sqlx::query(&require_org).execute(&mut *tx).await?;
+        }
+        tracing::info!("Required org_id on CRM tables");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(&mut *tx, migration_name).await?;
+        tx.commit().await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 009: Grant activity permissions to the built-in roles
+    async fn run_migration_009_add_activity_permissions(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "009_add_activity_permissions";
+
+        // Check if migration already applied
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let full_access = [ACTIVITIES_READ, ACTIVITIES_CREATE, ACTIVITIES_UPDATE, ACTIVITIES_DELETE];
+        let contributor = [ACTIVITIES_READ, ACTIVITIES_CREATE, ACTIVITIES_UPDATE];
+        let read_only = [ACTIVITIES_READ];
+
+        let role_permissions: [(&str, &[&str]); 8] = [
+            ("admin", &full_access),
+            ("contact_manager", &full_access),
+            ("member", &contributor),
+            ("sales_rep", &contributor),
+            ("marketing_user", &contributor),
+            ("support_agent", &contributor),
+            ("viewer", &read_only),
+            ("readonly_user", &read_only),
+        ];
+
+        for (role_name, permissions) in role_permissions {
+            sqlx::query(&format!(
+                "UPDATE roles SET permissions = {}, updated_at = NOW() WHERE name = $1",
+                MERGE_ROLE_PERMISSIONS
+            ))
+            .bind(role_name)
+            .bind(PermissionRegistry::to_json(permissions)?)
+            .execute(pool)
+            .await?;
+            tracing::info!("Granted activity permissions to {} role", role_name);
+        }
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 010: Grant custom field management to the built-in roles
+    async fn run_migration_010_add_custom_field_permissions(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "010_add_custom_field_permissions";
+
+        // Check if migration already applied
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        for role_name in ["admin", "contact_manager"] {
+            sqlx::query(&format!(
+                "UPDATE roles SET permissions = {}, updated_at = NOW() WHERE name = $1",
+                MERGE_ROLE_PERMISSIONS
+            ))
+            .bind(role_name)
+            .bind(PermissionRegistry::to_json(&[CUSTOM_FIELDS_MANAGE])?)
+            .execute(pool)
+            .await?;
+            tracing::info!("Granted custom field permissions to {} role", role_name);
+        }
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 011: Create user_sessions for refresh tokens and revocation
+    async fn run_migration_011_create_user_sessions_table(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "011_create_user_sessions_table";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let create_user_sessions_table = r#"
+            CREATE TABLE IF NOT EXISTS user_sessions (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                user_id UUID NOT NULL,
+                org_id UUID,
+                refresh_token_hash VARCHAR(64) NOT NULL UNIQUE,
+                previous_refresh_token_hash VARCHAR(64),
+                user_agent TEXT,
+                expires_at TIMESTAMPTZ NOT NULL,
+                last_used_at TIMESTAMPTZ,
+                revoked_at TIMESTAMPTZ,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_user_sessions_user
+                    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+        "#;
+
+        sqlx::query(create_user_sessions_table).execute(pool).await?;
+        tracing::info!("User sessions table created successfully");
+
+        let indexes = [
+            "CREATE INDEX IF NOT EXISTS idx_user_sessions_user_id ON user_sessions(user_id)",
+            "CREATE INDEX IF NOT EXISTS idx_user_sessions_previous_hash ON user_sessions(previous_refresh_token_hash)",
+        ];
+
+        for index_query in indexes {
+            sqlx::query(index_query).execute(pool).await?;
+        }
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 012: Allow custom roles per organization and grant role management to admins
+    async fn run_migration_012_add_organization_roles(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "012_add_organization_roles";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        // Built-in roles keep a NULL org_id and are shared by every organization
+        sqlx::query("ALTER TABLE roles ADD COLUMN IF NOT EXISTS org_id UUID REFERENCES organization(id) ON DELETE CASCADE")
+            .execute(pool)
+            .await?;
+        tracing::info!("Added org_id column to roles");
+
+        // Role names are unique among built-in roles and within each organization
+        let drop_constraint = "ALTER TABLE roles DROP CONSTRAINT IF EXISTS roles_name_key";
+        if let Err(e) = sqlx::query(drop_constraint).execute(pool).await {
+            if let Err(index_err) = sqlx::query("DROP INDEX IF EXISTS roles@roles_name_key CASCADE").execute(pool).await {
+                tracing::warn!("Could not drop unique constraint roles_name_key ({}; {})", e, index_err);
+            }
+        }
+
+        let role_indexes = [
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_roles_builtin_name ON roles(name) WHERE org_id IS NULL",
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_roles_org_name ON roles(org_id, name) WHERE org_id IS NOT NULL",
+        ];
+
+        for index_query in role_indexes {
+            sqlx::query(index_query).execute(pool).await?;
+        }
+        tracing::info!("Created per-organization role indexes");
+
+        sqlx::query(&format!(
+            "UPDATE roles SET permissions = {}, updated_at = NOW() WHERE name = $1 AND org_id IS NULL",
+            MERGE_ROLE_PERMISSIONS
+        ))
+        .bind("admin")
+        .bind(PermissionRegistry::to_json(&[ROLES_READ, ROLES_MANAGE])?)
+        .execute(pool)
+        .await?;
+        tracing::info!("Granted role management permissions to admin role");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 013: Email invitations to organizations and the outbox their emails are queued in
+    async fn run_migration_013_create_invitations_and_email_outbox(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "013_create_invitations_and_email_outbox";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let create_invitations_table = r#"
+            CREATE TABLE IF NOT EXISTS organization_invitations (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                org_id UUID NOT NULL,
+                email VARCHAR(255) NOT NULL,
+                role_id UUID NOT NULL,
+                invited_by UUID,
+                token_hash VARCHAR(64) NOT NULL UNIQUE,
+                status VARCHAR(20) NOT NULL DEFAULT 'pending'
+                    CHECK (status IN ('pending', 'accepted', 'declined', 'revoked')),
+                expires_at TIMESTAMPTZ NOT NULL,
+                accepted_by UUID,
+                responded_at TIMESTAMPTZ,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_invitations_org
+                    FOREIGN KEY (org_id) REFERENCES organization(id) ON DELETE CASCADE,
+                CONSTRAINT fk_invitations_role
+                    FOREIGN KEY (role_id) REFERENCES roles(id) ON DELETE CASCADE,
+                CONSTRAINT fk_invitations_invited_by
+                    FOREIGN KEY (invited_by) REFERENCES users(id) ON DELETE SET NULL,
+                CONSTRAINT fk_invitations_accepted_by
+                    FOREIGN KEY (accepted_by) REFERENCES users(id) ON DELETE SET NULL
+            )
+        "#;
+
+        sqlx::query(create_invitations_table).execute(pool).await?;
+        tracing::info!("Organization invitations table created successfully");
+
+        let create_email_outbox_table = r#"
+            CREATE TABLE IF NOT EXISTS email_outbox (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                recipient VARCHAR(255) NOT NULL,
+                template VARCHAR(100) NOT NULL,
+                subject VARCHAR(255) NOT NULL,
+                payload JSONB NOT NULL DEFAULT '{}',
+                status VARCHAR(20) NOT NULL DEFAULT 'pending'
+                    CHECK (status IN ('pending', 'sent', 'failed')),
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                sent_at TIMESTAMPTZ
+            )
+        "#;
+
+        sqlx::query(create_email_outbox_table).execute(pool).await?;
+        tracing::info!("Email outbox table created successfully");
+
+        let indexes = [
+            "CREATE INDEX IF NOT EXISTS idx_invitations_org_id ON organization_invitations(org_id, created_at)",
+            // At most one pending invitation per email and organization
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_invitations_pending_email ON organization_invitations(org_id, email) WHERE status = 'pending'",
+            "CREATE INDEX IF NOT EXISTS idx_email_outbox_pending ON email_outbox(created_at) WHERE status = 'pending'",
+        ];
+
+        for index_query in indexes {
+            sqlx::query(index_query).execute(pool).await?;
+        }
+
+        sqlx::query(&format!(
+            "UPDATE roles SET permissions = {}, updated_at = NOW() WHERE name = $1 AND org_id IS NULL",
+            MERGE_ROLE_PERMISSIONS
+        ))
+        .bind("admin")
+        .bind(PermissionRegistry::to_json(&[MEMBERS_INVITE])?)
+        .execute(pool)
+        .await?;
+        tracing::info!("Granted invitation permission to admin role");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 014: Single-use password reset and email verification tokens
+    async fn run_migration_014_create_user_tokens_table(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "014_create_user_tokens_table";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        // Existing accounts predate verification and stay unverified, but active
+        sqlx::query("ALTER TABLE users ADD COLUMN IF NOT EXISTS email_verified_at TIMESTAMPTZ")
+            .execute(pool)
+            .await?;
+        tracing::info!("Added email_verified_at column to users");
+
+        // Registrations awaiting verification get their own status
+        sqlx::query("ALTER TABLE users DROP CONSTRAINT IF EXISTS check_user_status")
+            .execute(pool)
+            .await?;
+        sqlx::query(
+            "ALTER TABLE users ADD CONSTRAINT check_user_status \
+             CHECK (status IN ('active', 'inactive', 'suspended', 'pending', 'pending_verification'))",
+        )
+        .execute(pool)
+        .await?;
+        tracing::info!("Allowed pending_verification in check_user_status");
+
+        let create_user_tokens_table = r#"
+            CREATE TABLE IF NOT EXISTS user_tokens (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                user_id UUID NOT NULL,
+                purpose VARCHAR(50) NOT NULL
+                    CHECK (purpose IN ('password_reset', 'email_verification')),
+                token_hash VARCHAR(64) NOT NULL UNIQUE,
+                expires_at TIMESTAMPTZ NOT NULL,
+                used_at TIMESTAMPTZ,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_user_tokens_user
+                    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+        "#;
+
+        sqlx::query(create_user_tokens_table).execute(pool).await?;
+        tracing::info!("User tokens table created successfully");
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_user_tokens_user_purpose ON user_tokens(user_id, purpose)")
+            .execute(pool)
+            .await?;
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    async fn run_migration_015_create_login_throttles_and_audit_log(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "015_create_login_throttles_and_audit_log";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        // Keyed by email rather than user, so emails without an account are throttled too
+        let create_login_throttles_table = r#"
+            CREATE TABLE IF NOT EXISTS login_throttles (
+                scope VARCHAR(20) NOT NULL CHECK (scope IN ('account', 'ip')),
+                subject VARCHAR(255) NOT NULL,
+                failed_attempts INTEGER NOT NULL DEFAULT 0,
+                last_failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                locked_until TIMESTAMPTZ,
+                PRIMARY KEY (scope, subject)
+            )
+        "#;
+
+        sqlx::query(create_login_throttles_table).execute(pool).await?;
+        tracing::info!("Login throttles table created successfully");
+
+        let create_audit_log_table = r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                action VARCHAR(100) NOT NULL,
+                actor_id UUID,
+                target_user_id UUID,
+                ip_address VARCHAR(64),
+                details JSONB NOT NULL DEFAULT '{}'::jsonb,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_audit_log_actor
+                    FOREIGN KEY (actor_id) REFERENCES users(id) ON DELETE SET NULL,
+                CONSTRAINT fk_audit_log_target_user
+                    FOREIGN KEY (target_user_id) REFERENCES users(id) ON DELETE SET NULL
+            )
+        "#;
+
+        sqlx::query(create_audit_log_table).execute(pool).await?;
+        tracing::info!("Audit log table created successfully");
+
+        let indexes = [
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_target_user ON audit_log(target_user_id, created_at DESC)",
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_action ON audit_log(action, created_at DESC)",
+        ];
+
+        for index in indexes {
+            sqlx::query(index).execute(pool).await?;
+        }
+        tracing::info!("Audit log indexes created successfully");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    async fn run_migration_016_add_totp_mfa(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "016_add_totp_mfa";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let create_user_mfa_table = r#"
+            CREATE TABLE IF NOT EXISTS user_mfa (
+                user_id UUID PRIMARY KEY,
+                secret VARCHAR(64) NOT NULL,
+                enabled_at TIMESTAMPTZ,
+                last_used_step BIGINT,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_user_mfa_user
+                    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+        "#;
+
+        sqlx::query(create_user_mfa_table).execute(pool).await?;
+        tracing::info!("User MFA table created successfully");
+
+        let create_recovery_codes_table = r#"
+            CREATE TABLE IF NOT EXISTS mfa_recovery_codes (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                user_id UUID NOT NULL,
+                code_hash VARCHAR(64) NOT NULL,
+                used_at TIMESTAMPTZ,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_mfa_recovery_codes_user
+                    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+        "#;
+
+        sqlx::query(create_recovery_codes_table).execute(pool).await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_mfa_recovery_codes_user ON mfa_recovery_codes(user_id)")
+            .execute(pool)
+            .await?;
+        tracing::info!("MFA recovery codes table created successfully");
+
+        // Logins waiting for their second factor hold an mfa_pending token
+        let widen_token_purposes = [
+            "ALTER TABLE user_tokens DROP CONSTRAINT IF EXISTS user_tokens_purpose_check",
+            "ALTER TABLE user_tokens ADD CONSTRAINT user_tokens_purpose_check
+                CHECK (purpose IN ('password_reset', 'email_verification', 'mfa_pending'))",
+        ];
+
+        for statement in widen_token_purposes {
+            sqlx::query(statement).execute(pool).await?;
+        }
+
+        sqlx::query("ALTER TABLE organization ADD COLUMN IF NOT EXISTS require_mfa BOOLEAN NOT NULL DEFAULT false")
+            .execute(pool)
+            .await?;
+        tracing::info!("Added require_mfa column to organization");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    async fn run_migration_017_create_password_history(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "017_create_password_history";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let create_password_history_table = r#"
+            CREATE TABLE IF NOT EXISTS password_history (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                user_id UUID NOT NULL,
+                password_hash VARCHAR(255) NOT NULL,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_password_history_user
+                    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            )
+        "#;
+
+        sqlx::query(create_password_history_table).execute(pool).await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_password_history_user ON password_history(user_id, created_at DESC)",
+        )
+        .execute(pool)
+        .await?;
+        tracing::info!("Password history table created successfully");
+
+        // Current passwords count as history, so the first change cannot keep them
+        sqlx::query(
+            "INSERT INTO password_history (user_id, password_hash, created_at) SELECT id, password, updated_at FROM users",
+        )
+        .execute(pool)
+        .await?;
+        tracing::info!("Recorded current passwords in password history");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    async fn run_migration_018_add_organization_lifecycle(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "018_add_organization_lifecycle";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        // Deleted organizations keep their data until purge_after, when they are removed for good
+        let add_lifecycle_columns = [
+            "ALTER TABLE organization ADD COLUMN IF NOT EXISTS settings JSONB NOT NULL DEFAULT '{}'",
+            "ALTER TABLE organization ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ DEFAULT NOW()",
+            "ALTER TABLE organization ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ",
+            "ALTER TABLE organization ADD COLUMN IF NOT EXISTS purge_after TIMESTAMPTZ",
+            "CREATE INDEX IF NOT EXISTS idx_organization_purge_after ON organization(purge_after) WHERE deleted_at IS NOT NULL",
+        ];
+
+        for statement in add_lifecycle_columns {
+            sqlx::query(statement).execute(pool).await?;
+        }
+        tracing::info!("Added settings and deletion columns to organization");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    async fn run_migration_019_create_lead_statuses(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "019_create_lead_statuses";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let create_lead_statuses_table = r#"
+            CREATE TABLE IF NOT EXISTS lead_statuses (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                org_id UUID NOT NULL,
+                name VARCHAR(50) NOT NULL,
+                label VARCHAR(100) NOT NULL,
+                display_order INTEGER NOT NULL DEFAULT 0,
+                is_closed BOOLEAN NOT NULL DEFAULT false,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                CONSTRAINT fk_lead_statuses_org
+                    FOREIGN KEY (org_id) REFERENCES organization(id) ON DELETE CASCADE,
+                UNIQUE(org_id, name)
+            )
+        "#;
+
+        sqlx::query(create_lead_statuses_table).execute(pool).await?;
+        tracing::info!("Lead statuses table created successfully");
+
+        // Organizations created before now get the pipeline new ones are seeded with
+        for (position, (name, label, is_closed)) in DEFAULT_LEAD_STATUSES.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO lead_statuses (org_id, name, label, display_order, is_closed)
+                SELECT id, $1, $2, $3, $4 FROM organization
+                ON CONFLICT (org_id, name) DO NOTHING
+                "#,
+            )
+            .bind(name)
+            .bind(label)
+            .bind(position as i32)
+            .bind(is_closed)
+            .execute(pool)
+            .await?;
+        }
+        tracing::info!("Seeded default lead statuses for existing organizations");
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 020: Bulk contact operations too large to run within a request
+    async fn run_migration_020_create_contact_bulk_jobs(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "020_create_contact_bulk_jobs";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let create_contact_bulk_jobs_table = r#"
+            CREATE TABLE IF NOT EXISTS contact_bulk_jobs (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                org_id UUID NOT NULL,
+                requested_by UUID,
+                request JSONB NOT NULL,
+                status VARCHAR(20) NOT NULL DEFAULT 'queued'
+                    CHECK (status IN ('queued', 'running', 'completed', 'failed')),
+                total INTEGER NOT NULL DEFAULT 0,
+                succeeded INTEGER NOT NULL DEFAULT 0,
+                failed INTEGER NOT NULL DEFAULT 0,
+                failures JSONB NOT NULL DEFAULT '[]',
+                error TEXT,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                started_at TIMESTAMPTZ,
+                finished_at TIMESTAMPTZ,
+                CONSTRAINT fk_contact_bulk_jobs_org
+                    FOREIGN KEY (org_id) REFERENCES organization(id) ON DELETE CASCADE,
+                CONSTRAINT fk_contact_bulk_jobs_requested_by
+                    FOREIGN KEY (requested_by) REFERENCES users(id) ON DELETE SET NULL
+            )
+        "#;
+
+        sqlx::query(create_contact_bulk_jobs_table).execute(pool).await?;
+        tracing::info!("Contact bulk jobs table created successfully");
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_contact_bulk_jobs_queued ON contact_bulk_jobs(created_at) WHERE status = 'queued'")
+            .execute(pool)
+            .await?;
+
+        // Mark migration as completed
+        Self::mark_migration_applied(pool, migration_name).await?;
+        tracing::info!("Migration {} completed successfully", migration_name);
+
+        Ok(())
+    }
+
+    /// Migration 021: CSV contact imports too large to run within a request
+    async fn run_migration_021_create_contact_import_jobs(pool: &PgPool) -> Result<(), AppError> {
+        let migration_name = "021_create_contact_import_jobs";
+
+        if Self::is_migration_applied(pool, migration_name).await? {
+            tracing::info!("Migration {} already applied, skipping", migration_name);
+            return Ok(());
+        }
+
+        tracing::info!("Running migration: {}", migration_name);
+
+        let create_contact_import_jobs_table = r#"
+            CREATE TABLE IF NOT EXISTS contact_import_jobs (
+                id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+                org_id UUID NOT NULL,
+                requested_by UUID,
+                file_name VARCHAR(255) NOT NULL,
+                content TEXT NOT NULL,
+                options JSONB NOT NULL DEFAULT '{}',
+                status VARCHAR(20) NOT NULL DEFAULT 'queued'
+                    CHECK (status IN ('queued', 'running', 'completed', 'failed')),
+                total_rows INTEGER NOT NULL DEFAULT 0,
+                processed_rows INTEGER NOT NULL DEFAULT 0,
+                created_rows INTEGER NOT NULL DEFAULT 0,
+                updated_rows INTEGER NOT NULL DEFAULT 0,
+                skipped_rows INTEGER NOT NULL DEFAULT 0,
+                failed_rows INTEGER NOT NULL DEFAULT 0,
+                errors JSONB NOT NULL DEFAULT '[]',
+                error TEXT,
+                created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                started_at TIMESTAMPTZ,
+                finished_at TIMESTAMPTZ,
+                CONSTRAINT fk_contact_import_jobs_org
+                    FOREIGN KEY (org_id) REFERENCES organization(id) ON DELETE CASCADE,
+                CONSTRAINT fk_contact_import_jobs_requested_by
+                    FOREIGN KEY (requested_by) REFERENCES users(id) ON DELETE SET NULL
+            )
+        "#;
+
+        sqlx::query(create_contact_import_jobs_table).execute(pool).await?;
+        tracing::info!("Contact import jobs table created successfully");
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_contact_import_jobs_queued ON contact_import_jobs(created_at) WHERE status = 'queued'")
+            .execute(pool)
+            .await?;
 
         // Mark migration as completed
         Self::mark_migration_applied(pool, migration_name).await?;
@@ -0,0 +1,54 @@
+// Unit of work - One transaction shared by the repository calls of a service operation
+
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Called after the commit for every membership whose permissions the transaction changed
+pub type OnMembershipChanged = fn(user_id: Uuid, org_id: Uuid);
+
+/// A transaction that repository methods run in when given `uow.conn()`
+///
+/// Nothing is written unless `commit` is called; dropping the unit of work, e.g. when a `?`
+/// returns early, rolls every statement back.
+pub struct UnitOfWork {
+    tx: Transaction<'static, Postgres>,
+    /// Memberships whose cached permissions go stale once the transaction commits
+    changed_memberships: Vec<(Uuid, Uuid)>,
+    on_membership_changed: OnMembershipChanged,
+}
+
+impl UnitOfWork {
+    /// Start a transaction; use `AppState::unit_of_work`, which knows what to do with changed memberships
+    pub async fn begin(pool: &PgPool, on_membership_changed: OnMembershipChanged) -> Result<Self, AppError> {
+        Ok(Self {
+            tx: pool.begin().await?,
+            changed_memberships: Vec::new(),
+            on_membership_changed,
+        })
+    }
+
+    /// Executor for the next repository call
+    pub fn conn(&mut self) -> &mut PgConnection {
+        &mut self.tx
+    }
+
+    /// Forget the cached permissions of a user in an organization after the commit
+    ///
+    /// Invalidating earlier would let a concurrent request cache the permissions the
+    /// transaction is about to replace.
+    pub fn invalidate_permissions(&mut self, user_id: Uuid, org_id: Uuid) {
+        self.changed_memberships.push((user_id, org_id));
+    }
+
+    pub async fn commit(self) -> Result<(), AppError> {
+        self.tx.commit().await?;
+
+        for (user_id, org_id) in self.changed_memberships {
+            (self.on_membership_changed)(user_id, org_id);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+// Authentication session Data Transfer Objects
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+/// A short-lived access token and the refresh token that renews it
+#[derive(Debug, Serialize)]
+pub struct AuthTokensResponse {
+    pub token: String,
+    pub refresh_token: String,
+    pub token_type: String,
+    pub expires_in: i64, // Access token lifetime in seconds
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogoutAllResponse {
+    pub revoked_sessions: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountMessageResponse {
+    pub message: String,
+}
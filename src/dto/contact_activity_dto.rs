@@ -0,0 +1,130 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::contact_filter_dto::PaginationInfo;
+use crate::models::{ContactActivity, ACTIVITY_STATUSES, ACTIVITY_TYPES};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateActivityRequest {
+    #[validate(custom = "validate_activity_type")]
+    pub activity_type: String,
+
+    #[validate(length(max = 255, message = "Subject must be less than 255 characters"))]
+    pub subject: Option<String>,
+
+    pub description: Option<String>,
+
+    pub activity_date: Option<DateTime<Utc>>,
+
+    #[validate(range(min = 0, max = 10080, message = "Duration must be between 0 and 10080 minutes"))]
+    pub duration_minutes: Option<i32>,
+
+    /// Defaults to `scheduled` for future dates and `completed` otherwise
+    #[validate(custom = "validate_activity_status")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateActivityRequest {
+    #[validate(custom = "validate_activity_type")]
+    pub activity_type: Option<String>,
+
+    #[validate(length(max = 255, message = "Subject must be less than 255 characters"))]
+    pub subject: Option<String>,
+
+    pub description: Option<String>,
+
+    pub activity_date: Option<DateTime<Utc>>,
+
+    #[validate(range(min = 0, max = 10080, message = "Duration must be between 0 and 10080 minutes"))]
+    pub duration_minutes: Option<i32>,
+
+    #[validate(custom = "validate_activity_status")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct CompleteActivityRequest {
+    #[validate(range(min = 0, max = 10080, message = "Duration must be between 0 and 10080 minutes"))]
+    pub duration_minutes: Option<i32>,
+
+    /// Outcome notes, replacing the description when provided
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimelineQueryParams {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    pub activity_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActivityQueueQueryParams {
+    /// Defaults to the authenticated user
+    pub owner_id: Option<Uuid>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActivityResponse {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub contact_id: Uuid,
+    pub activity_type: String,
+    pub subject: Option<String>,
+    pub description: Option<String>,
+    pub activity_date: Option<DateTime<Utc>>,
+    pub duration_minutes: Option<i32>,
+    pub status: String,
+    pub is_overdue: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimelineResponse {
+    pub data: Vec<ActivityResponse>,
+    pub pagination: PaginationInfo,
+    pub total_count: u64,
+}
+
+impl From<ContactActivity> for ActivityResponse {
+    fn from(activity: ContactActivity) -> Self {
+        let is_overdue = activity.is_overdue();
+        Self {
+            id: activity.id,
+            org_id: activity.org_id,
+            contact_id: activity.contact_id,
+            activity_type: activity.activity_type,
+            subject: activity.subject,
+            description: activity.description,
+            activity_date: activity.activity_date,
+            duration_minutes: activity.duration_minutes,
+            status: activity.status,
+            is_overdue,
+            created_by: activity.created_by,
+            created_at: activity.created_at,
+            updated_at: activity.updated_at,
+        }
+    }
+}
+
+fn validate_activity_type(activity_type: &str) -> Result<(), validator::ValidationError> {
+    if ACTIVITY_TYPES.contains(&activity_type) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_activity_type"))
+    }
+}
+
+fn validate_activity_status(status: &str) -> Result<(), validator::ValidationError> {
+    if ACTIVITY_STATUSES.contains(&status) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_activity_status"))
+    }
+}
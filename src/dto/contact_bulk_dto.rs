@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::contact_filter_dto::ContactFilterRequest;
+
+/// Most contacts one request may select, by `ids` or by `filter`
+pub const MAX_BULK_SELECTION: usize = 10000;
+
+/// `POST /contacts/bulk`: one action applied to a selection of contacts
+///
+/// Contacts are selected either by `ids` or by `filter`; the filter's pagination and
+/// sorting are ignored and every matching contact is selected.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct BulkContactRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_selection_size")]
+    pub ids: Option<Vec<Uuid>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub filter: Option<ContactFilterRequest>,
+
+    pub action: BulkContactAction,
+
+    /// Report how many contacts the action would change without changing them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkContactAction {
+    UpdateFields { fields: BulkContactFields },
+    /// Merge custom field values; an empty value removes the field
+    SetCustomFields { custom_fields: HashMap<String, String> },
+    ChangeOwner { owner_id: Uuid },
+    ChangeLeadStatus { lead_status: String },
+    AddTags { tag_ids: Vec<Uuid> },
+    RemoveTags { tag_ids: Vec<Uuid> },
+    Delete,
+    Restore,
+}
+
+/// Standard fields set on every selected contact; an empty value clears the field
+///
+/// Names and emails identify a single contact and cannot be set in bulk.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct BulkContactFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 20, message = "Phone must be less than 20 characters"))]
+    pub phone: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 255, message = "Company must be less than 255 characters"))]
+    pub company: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 100, message = "Job title must be less than 100 characters"))]
+    pub job_title: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 100, message = "City must be less than 100 characters"))]
+    pub city: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 100, message = "State must be less than 100 characters"))]
+    pub state: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 20, message = "Postal code must be less than 20 characters"))]
+    pub postal_code: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 100, message = "Country must be less than 100 characters"))]
+    pub country: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(length(max = 100, message = "Lead source must be less than 100 characters"))]
+    pub lead_source: Option<String>,
+}
+
+impl BulkContactFields {
+    pub fn is_empty(&self) -> bool {
+        [
+            &self.phone, &self.company, &self.job_title, &self.address, &self.city,
+            &self.state, &self.postal_code, &self.country, &self.notes, &self.lead_source,
+        ]
+        .iter()
+        .all(|field| field.is_none())
+    }
+}
+
+/// Outcome for one selected contact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkItemResult {
+    pub contact_id: Uuid,
+    /// `succeeded` or `failed`
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of a bulk operation run within the request
+#[derive(Debug, Serialize)]
+pub struct BulkContactResponse {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+/// Result of a dry run
+#[derive(Debug, Serialize)]
+pub struct BulkDryRunResponse {
+    /// Contacts the selector matched
+    pub matched: usize,
+    /// Matched contacts the action would change for the caller
+    pub affected: usize,
+    /// Whether the operation would be queued as a job instead of running in the request
+    pub queued: bool,
+}
+
+fn validate_selection_size(ids: &[Uuid]) -> Result<(), validator::ValidationError> {
+    if (1..=MAX_BULK_SELECTION).contains(&ids.len()) {
+        Ok(())
+    } else {
+        let mut error = validator::ValidationError::new("length");
+        error.message = Some(format!("Between 1 and {} contact IDs are required", MAX_BULK_SELECTION).into());
+        Err(error)
+    }
+}
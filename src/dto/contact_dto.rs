@@ -46,13 +46,41 @@ pub struct CreateContactRequest {
     #[validate(length(max = 100, message = "Lead source must be less than 100 characters"))]
     pub lead_source: Option<String>,
 
-    #[validate(custom = "validate_lead_status")]
+    /// Checked against the organization's lead statuses by the service
     pub lead_status: Option<String>,
 
     /// Custom field values - key is field_name, value is the field value
     pub custom_fields: Option<HashMap<String, String>>,
 }
 
+impl CreateContactRequest {
+    /// Build the contact this request describes, owned by `owner_id`.
+    /// Custom field values are not part of the contact row and are left on the request.
+    pub fn to_contact(&self, org_id: Uuid, owner_id: Uuid) -> Contact {
+        let mut contact = Contact::new(
+            org_id,
+            self.first_name.clone(),
+            self.last_name.clone(),
+            self.email.clone(),
+            Some(owner_id),
+        );
+        contact.phone = self.phone.clone();
+        contact.company = self.company.clone();
+        contact.job_title = self.job_title.clone();
+        contact.address = self.address.clone();
+        contact.city = self.city.clone();
+        contact.state = self.state.clone();
+        contact.postal_code = self.postal_code.clone();
+        contact.country = self.country.clone();
+        contact.notes = self.notes.clone();
+        contact.lead_source = self.lead_source.clone();
+        if let Some(status) = &self.lead_status {
+            contact.lead_status = status.clone();
+        }
+        contact
+    }
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct UpdateContactRequest {
     #[validate(length(min = 1, max = 100, message = "First name must be between 1 and 100 characters"))]
@@ -150,6 +178,7 @@ pub struct PatchContactRequest {
 #[derive(Debug, Serialize)]
 pub struct ContactResponse {
     pub id: Uuid,
+    pub org_id: Uuid,
     pub first_name: String,
     pub last_name: String,
     pub full_name: String,
@@ -177,6 +206,7 @@ impl From<Contact> for ContactResponse {
     fn from(contact: Contact) -> Self {
         Self {
             id: contact.id,
+            org_id: contact.org_id,
             first_name: contact.first_name.clone(),
             last_name: contact.last_name.clone(),
             full_name: contact.full_name(),
@@ -200,24 +230,3 @@ impl From<Contact> for ContactResponse {
         }
     }
 }
-
-// Custom validator for lead status
-fn validate_lead_status(lead_status: &str) -> Result<(), validator::ValidationError> {
-    let valid_statuses = [
-        "new",
-        "contacted",
-        "qualified",
-        "proposal",
-        "negotiation",
-        "closed_won",
-        "closed_lost"
-    ];
-
-    if valid_statuses.contains(&lead_status) {
-        Ok(())
-    } else {
-        Err(validator::ValidationError::new("invalid_lead_status"))
-    }
-}
-
-
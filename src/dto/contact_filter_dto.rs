@@ -47,6 +47,15 @@ pub enum FieldType {
     Boolean,
 }
 
+/// How a `tags` filter matches the given tag names
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMatch {
+    Any,
+    All,
+    None,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum FilterNode {
@@ -61,6 +70,12 @@ pub enum FilterNode {
         logic: LogicOperator,
         conditions: Vec<FilterNode>,
     },
+    /// Contacts having any/all/none of the named tags
+    Tags {
+        #[serde(rename = "match", default = "default_tag_match")]
+        match_mode: TagMatch,
+        tags: Vec<String>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -137,6 +152,7 @@ pub struct FilterSummary {
 fn default_page() -> u32 { 1 }
 fn default_limit() -> u32 { 50 }
 fn default_sort_order() -> SortOrder { SortOrder::Desc }
+fn default_tag_match() -> TagMatch { TagMatch::Any }
 
 // Standard contact fields that are stored in the main table
 pub const STANDARD_FIELDS: &[&str] = &[
@@ -212,6 +228,10 @@ impl FilterNode {
             conditions 
         }
     }
+    
+    pub fn tags(match_mode: TagMatch, tags: Vec<String>) -> Self {
+        Self::Tags { match_mode, tags }
+    }
 }
 
 impl PaginationInfo {
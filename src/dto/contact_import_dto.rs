@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// What an imported row does when its email belongs to an existing contact
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateStrategy {
+    /// Leave the existing contact alone
+    #[default]
+    Skip,
+    /// Overwrite the existing contact with the row's non-empty values
+    Update,
+    /// Insert the row as a new contact anyway; emails are unique per organization, so it fails as a conflict
+    Create,
+}
+
+impl DuplicateStrategy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "skip" => Some(Self::Skip),
+            "update" => Some(Self::Update),
+            "create" => Some(Self::Create),
+            _ => None,
+        }
+    }
+}
+
+/// Settings sent as form fields next to the uploaded file of `POST /contacts/import`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContactImportOptions {
+    /// CSV header -> standard contact field or custom `field_name`; an empty target ignores the column
+    ///
+    /// Headers left out are matched by name, e.g. `First Name` -> `first_name`.
+    #[serde(default)]
+    pub mapping: HashMap<String, String>,
+
+    #[serde(default)]
+    pub duplicates: DuplicateStrategy,
+
+    /// Validate every row and report what would happen without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A row that could not be imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowError {
+    /// Line of the row in the file; the header is line 1
+    pub row: u64,
+    pub email: Option<String>,
+    pub code: String,
+    pub error: String,
+    /// The row as uploaded, for the error CSV
+    pub values: Vec<String>,
+}
+
+/// Outcome of an import; in a dry run the counts are what the import would do
+#[derive(Debug, Default, Serialize)]
+pub struct ContactImportReport {
+    pub dry_run: bool,
+    /// CSV header -> field its values are imported into
+    pub columns: BTreeMap<String, String>,
+    pub ignored_columns: Vec<String>,
+    pub total_rows: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ContactImportReport {
+    /// (processed, created, updated, skipped, failed) rows, as stored on an import job
+    pub fn counts(&self) -> (i32, i32, i32, i32, i32) {
+        (
+            self.total_rows as i32,
+            self.created as i32,
+            self.updated as i32,
+            self.skipped as i32,
+            self.failed as i32,
+        )
+    }
+}
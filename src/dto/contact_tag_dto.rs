@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{ContactTag, ContactTagWithCount};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTagRequest {
+    #[validate(length(min = 1, max = 100, message = "Tag name must be between 1 and 100 characters"))]
+    pub name: String,
+
+    #[validate(custom = "validate_color")]
+    pub color: Option<String>,
+
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateTagRequest {
+    #[validate(length(min = 1, max = 100, message = "Tag name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+
+    #[validate(custom = "validate_color")]
+    pub color: Option<String>,
+
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AssignTagsRequest {
+    #[validate(length(min = 1, max = 100, message = "Between 1 and 100 tag IDs are required"))]
+    pub tag_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BulkTagRequest {
+    #[validate(length(min = 1, max = 1000, message = "Between 1 and 1000 contact IDs are required"))]
+    pub contact_ids: Vec<Uuid>,
+
+    #[validate(length(min = 1, max = 100, message = "Between 1 and 100 tag IDs are required"))]
+    pub tag_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkTagResponse {
+    pub affected: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagResponse {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_count: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<ContactTag> for TagResponse {
+    fn from(tag: ContactTag) -> Self {
+        Self {
+            id: tag.id,
+            org_id: tag.org_id,
+            name: tag.name,
+            color: tag.color,
+            description: tag.description,
+            contact_count: None,
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+        }
+    }
+}
+
+impl From<ContactTagWithCount> for TagResponse {
+    fn from(tag: ContactTagWithCount) -> Self {
+        Self {
+            id: tag.id,
+            org_id: tag.org_id,
+            name: tag.name,
+            color: tag.color,
+            description: tag.description,
+            contact_count: Some(tag.contact_count),
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+        }
+    }
+}
+
+fn validate_color(color: &str) -> Result<(), validator::ValidationError> {
+    let is_hex = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+
+    if is_hex {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_color"))
+    }
+}
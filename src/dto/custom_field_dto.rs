@@ -1,35 +1,83 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::models::CustomField;
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateCustomFieldRequest {
     #[validate(length(min = 1, max = 50, message = "Module must be between 1 and 50 characters"))]
     pub module: String,
-    
+
     #[validate(length(min = 1, max = 255, message = "Label must be between 1 and 255 characters"))]
     pub label: String,
-    
+
     #[validate(length(min = 1, max = 100, message = "Field name must be between 1 and 100 characters"))]
-    #[validate(regex(path = "crate::utils::validation::FIELD_NAME_REGEX", message = "Field name must be snake_case"))]
+    #[validate(custom = "validate_field_name")]
     pub field_name: String,
-    
+
     #[validate(length(min = 1, max = 50, message = "Field type is required"))]
     pub field_type: String, // 'text', 'number', 'email', 'phone', 'date', 'boolean', 'select', 'multi_select', 'textarea'
-    
+
+    pub is_required: Option<bool>,
+    pub is_active: Option<bool>,
+    pub options: Option<Vec<String>>, // For select/multi_select fields
+    pub validation_rules: Option<JsonValue>,
+    pub default_value: Option<String>,
+    pub help_text: Option<String>,
+    pub display_order: Option<i64>,
+}
+
+/// Field name, type and options are not editable here: the first two would
+/// orphan stored values and options go through the migrating options endpoint
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCustomFieldRequest {
+    #[validate(length(min = 1, max = 255, message = "Label must be between 1 and 255 characters"))]
+    pub label: Option<String>,
+
     pub is_required: Option<bool>,
     pub is_active: Option<bool>,
-    pub options: Option<JsonValue>, // For select/multi_select fields
     pub validation_rules: Option<JsonValue>,
     pub default_value: Option<String>,
     pub help_text: Option<String>,
     pub display_order: Option<i64>,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReorderCustomFieldsRequest {
+    /// Fields in their new display order; unlisted fields keep their position
+    #[validate(length(min = 1, message = "At least one field is required"))]
+    pub field_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCustomFieldOptionsRequest {
+    #[validate(length(min = 1, message = "At least one option is required"))]
+    pub options: Vec<String>,
+
+    /// Maps a previous option to its replacement, or to `null` to clear it from contacts
+    #[serde(default)]
+    pub value_mapping: HashMap<String, Option<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CustomFieldQueryParams {
+    pub include_inactive: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteCustomFieldParams {
+    /// Required to delete a field that still has stored values
+    pub force: Option<bool>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct CustomFieldResponse {
     pub id: Uuid,
+    pub org_id: Uuid,
     pub module: String,
     pub label: String,
     pub field_name: String,
@@ -45,3 +93,46 @@ pub struct CustomFieldResponse {
     pub created_at: String,
     pub updated_at: String,
 }
+
+#[derive(Debug, Serialize)]
+pub struct CustomFieldOptionsResponse {
+    pub field: CustomFieldResponse,
+    /// Stored values rewritten through `value_mapping`
+    pub migrated_values: u64,
+    /// Stored values removed because every option they held was cleared
+    pub cleared_values: u64,
+}
+
+impl From<CustomField> for CustomFieldResponse {
+    fn from(custom_field: CustomField) -> Self {
+        Self {
+            id: custom_field.id,
+            org_id: custom_field.org_id,
+            module: custom_field.module,
+            label: custom_field.label,
+            field_name: custom_field.field_name,
+            field_type: custom_field.field_type,
+            is_required: custom_field.is_required,
+            is_active: custom_field.is_active,
+            options: custom_field.options,
+            validation_rules: custom_field.validation_rules,
+            default_value: custom_field.default_value,
+            help_text: custom_field.help_text,
+            display_order: custom_field.display_order,
+            created_by: custom_field.created_by,
+            created_at: custom_field.created_at.to_rfc3339(),
+            updated_at: custom_field.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+fn validate_field_name(field_name: &str) -> Result<(), validator::ValidationError> {
+    let is_snake_case = field_name.starts_with(|c: char| c.is_ascii_lowercase())
+        && field_name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if is_snake_case {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_field_name"))
+    }
+}
@@ -0,0 +1,67 @@
+// Invitation Data Transfer Objects
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::UserOrganizationDetailResponse;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateInvitationRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    pub role_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InvitationQueryParams {
+    /// `pending`, `accepted`, `declined` or `revoked`
+    pub status: Option<String>,
+}
+
+/// Account details for an invitee without an account; ignored when the email already has one
+#[derive(Debug, Deserialize, Validate)]
+pub struct AcceptInvitationRequest {
+    #[validate(length(min = 1, max = 255, message = "Name must be between 1 and 255 characters"))]
+    pub name: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvitationResponse {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub email: String,
+    pub role_id: Uuid,
+    pub role_name: String,
+    /// `pending`, `expired`, `accepted`, `declined` or `revoked`
+    pub status: String,
+    pub invited_by: Option<Uuid>,
+    pub accepted_by: Option<Uuid>,
+    pub expires_at: String,
+    pub responded_at: Option<String>,
+    pub created_at: String,
+}
+
+/// What the holder of an invitation link is invited to
+#[derive(Debug, Serialize)]
+pub struct InvitationInfoResponse {
+    pub email: String,
+    pub organization_id: Uuid,
+    pub organization_name: String,
+    pub role_name: String,
+    pub expires_at: String,
+    /// Whether accepting joins an existing account; otherwise `name` and `password` are required
+    pub account_exists: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AcceptInvitationResponse {
+    pub membership: UserOrganizationDetailResponse,
+    pub account_created: bool,
+    /// Session tokens for a newly created account; existing users log in as usual
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
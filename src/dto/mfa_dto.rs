@@ -0,0 +1,80 @@
+// Two-factor authentication Data Transfer Objects
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dto::LoginResponse;
+
+#[derive(Debug, Serialize)]
+pub struct MfaStatusResponse {
+    pub enabled: bool,
+    /// A secret was issued but no code has confirmed it yet
+    pub enrollment_pending: bool,
+    /// An organization requires MFA for one of the user's roles
+    pub required: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+/// A new TOTP secret; shown once, for the user to add to an authenticator app
+#[derive(Debug, Serialize)]
+pub struct MfaEnrollmentResponse {
+    pub secret: String,
+    /// `otpauth://` URI to render as a QR code
+    pub provisioning_uri: String,
+}
+
+/// A second factor: a code from the authenticator app, or one of the recovery codes
+#[derive(Debug, Deserialize)]
+pub struct MfaCodeRequest {
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MfaRecoveryCodesResponse {
+    /// Shown once; only their hashes are stored
+    pub recovery_codes: Vec<String>,
+}
+
+/// Returned by the password step of a login instead of tokens when a second factor is needed
+#[derive(Debug, Serialize)]
+pub struct MfaChallengeResponse {
+    pub mfa_token: String,
+    pub expires_in: i64, // Seconds the mfa_token stays valid
+    /// MFA is required but not set up yet: call `/auth/mfa/setup` before verifying
+    pub enrollment_required: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MfaSetupRequest {
+    pub mfa_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MfaVerifyRequest {
+    pub mfa_token: String,
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+/// A completed login; carries recovery codes when the login also finished a required enrollment
+#[derive(Debug, Serialize)]
+pub struct MfaLoginResponse {
+    #[serde(flatten)]
+    pub login: LoginResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_codes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MfaPolicyResponse {
+    pub org_id: Uuid,
+    pub require_mfa: bool,
+    /// Members whose role holds any of these permissions must use MFA when `require_mfa` is set
+    pub applies_to_permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMfaPolicyRequest {
+    pub require_mfa: bool,
+}
@@ -1,13 +1,31 @@
 // DTO module - Data Transfer Objects
+pub mod auth_dto;
+pub mod contact_activity_dto;
+pub mod contact_bulk_dto;
 pub mod contact_dto;
 pub mod contact_filter_dto;
+pub mod contact_import_dto;
+pub mod contact_tag_dto;
+pub mod custom_field_dto;
+pub mod invitation_dto;
+pub mod mfa_dto;
 pub mod organization_dto;
+pub mod permission_dto;
 pub mod role_dto;
 pub mod user_dto;
 pub mod user_organization_dto;
 
+pub use auth_dto::*;
+pub use contact_activity_dto::*;
+pub use contact_bulk_dto::*;
 pub use contact_dto::*;
+pub use contact_import_dto::*;
+pub use contact_tag_dto::*;
+pub use custom_field_dto::*;
+pub use invitation_dto::*;
+pub use mfa_dto::*;
 pub use organization_dto::*;
+pub use permission_dto::*;
 pub use role_dto::*;
 pub use user_dto::*;
 pub use user_organization_dto::*;
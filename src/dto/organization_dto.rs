@@ -1,11 +1,28 @@
 // Organization Data Transfer Objects
 
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
-#[derive(Deserialize)]
+use crate::models::OrganizationSettings;
+
+#[derive(Deserialize, Validate)]
 pub struct CreateOrganizationRequest {
     pub name: String,
+    #[validate(custom = "validate_country")]
+    pub country: Option<String>,
+    #[validate(custom = "validate_timezone")]
+    pub timezone: Option<String>,
+}
+
+/// Replaces the organization's details; `null` clears the country or timezone
+#[derive(Deserialize, Validate)]
+pub struct UpdateOrganizationRequest {
+    #[validate(length(min = 1, max = 255, message = "Organization name must be between 1 and 255 characters"))]
+    pub name: String,
+    #[validate(custom = "validate_country")]
     pub country: Option<String>,
+    #[validate(custom = "validate_timezone")]
     pub timezone: Option<String>,
 }
 
@@ -17,3 +34,33 @@ pub struct OrganizationResponse {
     pub timezone: Option<String>,
     pub created_at: String,
 }
+
+#[derive(Serialize)]
+pub struct OrganizationSettingsResponse {
+    pub org_id: Uuid,
+    pub settings: OrganizationSettings,
+}
+
+/// Returned when an organization is deleted; it can be restored until `purge_after`
+#[derive(Serialize)]
+pub struct OrganizationDeletionResponse {
+    pub org_id: Uuid,
+    pub deleted_at: String,
+    pub purge_after: String,
+}
+
+/// ISO 3166-1 alpha-2 code, as used by the IANA time zone database
+fn validate_country(country: &str) -> Result<(), ValidationError> {
+    match isocountry::CountryCode::for_alpha2_caseless(country.trim()) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ValidationError::new("invalid_country")),
+    }
+}
+
+/// Zone name from the IANA time zone database, e.g. `Europe/Berlin`
+fn validate_timezone(timezone: &str) -> Result<(), ValidationError> {
+    match timezone.trim().parse::<chrono_tz::Tz>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ValidationError::new("invalid_timezone")),
+    }
+}
@@ -0,0 +1,27 @@
+// Permission Data Transfer Objects
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::PermissionScope;
+
+/// A registered permission the caller holds, for every resource or only their own
+#[derive(Debug, Serialize)]
+pub struct EffectivePermission {
+    pub name: &'static str,
+    pub resource: &'static str,
+    pub description: &'static str,
+    pub scope: PermissionScope,
+}
+
+/// Response for `GET /me/permissions`
+#[derive(Debug, Serialize)]
+pub struct MyPermissionsResponse {
+    pub user_id: Uuid,
+    pub org_id: Uuid,
+    pub roles: Vec<String>,
+    /// Permission strings as stored on the caller's roles, wildcards included
+    pub granted: Vec<String>,
+    /// Registered permissions the grants resolve to
+    pub permissions: Vec<EffectivePermission>,
+}
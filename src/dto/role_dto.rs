@@ -1,18 +1,23 @@
 // Role Data Transfer Objects
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value as JsonValue;
 use uuid::Uuid;
+use validator::Validate;
 
-#[derive(Debug, Deserialize)]
+use crate::models::Role;
+use crate::utils::format_timestamp;
+
+#[derive(Debug, Deserialize, Validate)]
 pub struct CreateRoleRequest {
+    #[validate(length(min = 1, max = 50, message = "Name must be between 1 and 50 characters"))]
     pub name: String,
     pub description: Option<String>,
     pub permissions: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdateRoleRequest {
+    #[validate(length(min = 1, max = 50, message = "Name must be between 1 and 50 characters"))]
     pub name: Option<String>,
     pub description: Option<String>,
     pub permissions: Option<Vec<String>>,
@@ -21,9 +26,27 @@ pub struct UpdateRoleRequest {
 #[derive(Debug, Serialize)]
 pub struct RoleResponse {
     pub id: Uuid,
+    /// `None` for built-in roles shared by every organization
+    pub org_id: Option<Uuid>,
     pub name: String,
     pub description: Option<String>,
     pub permissions: Vec<String>,
+    pub is_builtin: bool,
     pub created_at: String,
     pub updated_at: String,
 }
+
+impl From<Role> for RoleResponse {
+    fn from(role: Role) -> Self {
+        Self {
+            id: role.id,
+            org_id: role.org_id,
+            permissions: role.get_permissions(),
+            is_builtin: role.is_builtin(),
+            name: role.name,
+            description: role.description,
+            created_at: format_timestamp(role.created_at),
+            updated_at: format_timestamp(role.updated_at),
+        }
+    }
+}
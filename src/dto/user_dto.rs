@@ -3,6 +3,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::dto::MfaChallengeResponse;
+
 #[derive(Debug, Deserialize)]
 pub struct CreateUserRequest {
     pub name: String,
@@ -34,7 +36,10 @@ pub struct UserResponse {
 #[derive(Debug, Serialize)]
 pub struct UserCreationResponse {
     pub user: UserResponse,
-    pub token: String,
+    /// Absent while the account is `pending_verification`
+    pub token: Option<String>,
+    pub refresh_token: Option<String>,
+    pub expires_in: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,7 +51,12 @@ pub struct LoginRequest {
 #[derive(Debug, Serialize)]
 pub struct LoginResponse {
     pub user: UserResponse,
-    pub token: Option<String>, // For future JWT implementation
+    pub token: Option<String>,
+    pub refresh_token: Option<String>,
+    pub expires_in: Option<i64>, // Access token lifetime in seconds
+    /// Set, with no tokens, when the login still needs a second factor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mfa: Option<MfaChallengeResponse>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -10,13 +10,6 @@ pub struct CreateUserOrganizationRequest {
     pub role_name: String, // We'll resolve this to role_id in the service
 }
 
-#[derive(Debug, Deserialize)]
-pub struct InviteUserToOrganizationRequest {
-    pub email: String, // Email of user to invite
-    pub org_id: Uuid,
-    pub role_name: String,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct UpdateUserOrganizationRequest {
     pub role_name: Option<String>,
@@ -98,3 +91,14 @@ pub struct UserOrganizationListResponse {
     pub limit: u32,
     pub total_pages: u32,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct SwitchOrganizationRequest {
+    pub org_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SwitchOrganizationResponse {
+    pub org_id: Uuid,
+    pub token: String, // JWT carrying the active organization claim
+}
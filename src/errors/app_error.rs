@@ -1,24 +1,111 @@
 // Application Error Types
 
 use axum::{
-    http::StatusCode,
+    http::{header::{RETRY_AFTER, WWW_AUTHENTICATE}, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
+use std::collections::BTreeMap;
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+/// One reason a request field was rejected
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    /// Machine-readable reason, e.g. `required`, `length`, `email` or a custom field rule name
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { code: code.into(), message: message.into() }
+    }
+}
+
+/// Rejected fields keyed by path (`email`, `filters[0].field`, `custom_fields.tier`)
+pub type FieldErrors = BTreeMap<String, Vec<FieldError>>;
 
 #[derive(Debug)]
 pub enum AppError {
     DatabaseError(sqlx::Error),
     ValidationError(String),
+    FieldValidation(FieldErrors),
     NotFound(String),
     InternalServerError(String),
+    /// Missing, malformed or expired credentials (401 with a `WWW-Authenticate` challenge)
     Unauthorized(String),
+    /// Authenticated, but not allowed to perform the action
+    Forbidden(String),
+    /// The request clashes with existing state, e.g. a duplicate email
+    Conflict(String),
+    /// Refused until the client backs off (429 with a `Retry-After` header in seconds)
+    TooManyRequests { message: String, retry_after: u64 },
+}
+
+impl AppError {
+    /// Reject a single field
+    pub fn field(field: impl Into<String>, code: impl Into<String>, message: impl Into<String>) -> Self {
+        let mut errors = FieldErrors::new();
+        errors.insert(field.into(), vec![FieldError::new(code, message)]);
+        AppError::FieldValidation(errors)
+    }
+
+    /// Machine-readable error code included in every error response
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::DatabaseError(e) if is_unique_violation(e) => "conflict",
+            AppError::DatabaseError(_) => "database_error",
+            AppError::ValidationError(_) => "invalid_request",
+            AppError::FieldValidation(_) => "validation_failed",
+            AppError::NotFound(_) => "not_found",
+            AppError::InternalServerError(_) => "internal_error",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::Conflict(_) => "conflict",
+            AppError::TooManyRequests { .. } => "too_many_requests",
+        }
+    }
+
+    /// One-line message as the response would carry it, with field errors spelled out
+    ///
+    /// Used where errors are reported per item instead of as the response.
+    pub fn summary(&self) -> String {
+        match self {
+            AppError::DatabaseError(e) if is_unique_violation(e) => CONFLICTING_WRITE.to_string(),
+            AppError::DatabaseError(_) => "Database error occurred".to_string(),
+            AppError::FieldValidation(fields) => fields
+                .iter()
+                .map(|(field, errors)| {
+                    let messages: Vec<&str> = errors.iter().map(|error| error.message.as_str()).collect();
+                    format!("{}: {}", field, messages.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+            AppError::TooManyRequests { message, .. } => message.clone(),
+            AppError::ValidationError(message)
+            | AppError::NotFound(message)
+            | AppError::InternalServerError(message)
+            | AppError::Unauthorized(message)
+            | AppError::Forbidden(message)
+            | AppError::Conflict(message) => message.clone(),
+        }
+    }
 }
 
 impl IntoResponse for AppError {
+    /// Renders `{"error", "code", "status"}`, plus `fields` for field-level validation failures
     fn into_response(self) -> Response {
+        let code = self.code();
+        let mut field_errors = None;
+        let mut retry_after = None;
+
         let (status, error_message) = match self {
+            AppError::DatabaseError(e) if is_unique_violation(&e) => {
+                tracing::warn!("Unique violation: {}", e);
+                (StatusCode::CONFLICT, CONFLICTING_WRITE.to_string())
+            }
             AppError::DatabaseError(e) => {
                 tracing::error!("Database error: {}", e);
                 (
@@ -27,25 +114,108 @@ impl IntoResponse for AppError {
                 )
             }
             AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::FieldValidation(errors) => {
+                field_errors = Some(errors);
+                (StatusCode::BAD_REQUEST, "Validation failed".to_string())
+            }
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             AppError::InternalServerError(msg) => {
                 tracing::error!("Internal server error: {}", msg);
                 (StatusCode::INTERNAL_SERVER_ERROR, msg)
             }
             AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
+            AppError::TooManyRequests { message, retry_after: seconds } => {
+                retry_after = Some(seconds);
+                (StatusCode::TOO_MANY_REQUESTS, message)
+            }
         };
 
-        let body = Json(json!({
+        let mut body = json!({
             "error": error_message,
+            "code": code,
             "status": status.as_u16()
-        }));
+        });
+        if let Some(field_errors) = field_errors {
+            body["fields"] = json!(field_errors);
+        }
+        let body = Json(body);
+
+        if status == StatusCode::UNAUTHORIZED {
+            return (status, [(WWW_AUTHENTICATE, "Bearer")], body).into_response();
+        }
+
+        if let Some(seconds) = retry_after {
+            return (status, [(RETRY_AFTER, seconds.to_string())], body).into_response();
+        }
 
         (status, body).into_response()
     }
 }
 
+/// Message for unique violations no repository turned into a specific conflict, e.g. a write that lost a race
+const CONFLICTING_WRITE: &str = "The request conflicts with existing data";
+
+fn is_unique_violation(error: &sqlx::Error) -> bool {
+    matches!(error, sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23505"))
+}
+
 impl From<sqlx::Error> for AppError {
     fn from(err: sqlx::Error) -> Self {
         AppError::DatabaseError(err)
     }
 }
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::FieldValidation(field_errors(&errors))
+    }
+}
+
+/// Flatten `validator` errors into field paths, descending into nested structs and lists
+pub fn field_errors(errors: &ValidationErrors) -> FieldErrors {
+    let mut fields = FieldErrors::new();
+    collect_field_errors(&mut fields, "", errors);
+    fields
+}
+
+fn collect_field_errors(fields: &mut FieldErrors, prefix: &str, errors: &ValidationErrors) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                let entry = fields.entry(path).or_default();
+                entry.extend(errors.iter().map(|error| FieldError::new(error.code.to_string(), describe(error))));
+            }
+            ValidationErrorsKind::Struct(errors) => collect_field_errors(fields, &path, errors),
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_field_errors(fields, &format!("{}[{}]", path, index), errors);
+                }
+            }
+        }
+    }
+}
+
+/// The validator's message, or a readable default for the built-in codes
+fn describe(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+
+    let param = |name: &str| error.params.get(name).map(|value| value.to_string());
+    match (error.code.as_ref(), param("min"), param("max")) {
+        ("required", _, _) => "is required".to_string(),
+        ("email", _, _) => "must be a valid email address".to_string(),
+        ("url", _, _) => "must be a valid URL".to_string(),
+        ("length", Some(min), Some(max)) => format!("length must be between {} and {}", min, max),
+        ("length", Some(min), None) => format!("length must be at least {}", min),
+        ("length", None, Some(max)) => format!("length must be at most {}", max),
+        ("range", Some(min), Some(max)) => format!("must be between {} and {}", min, max),
+        ("range", Some(min), None) => format!("must be at least {}", min),
+        ("range", None, Some(max)) => format!("must be at most {}", max),
+        _ => "is invalid".to_string(),
+    }
+}
@@ -11,9 +11,29 @@ pub mod routes;
 pub mod services;
 pub mod utils;
 
+use axum::extract::FromRef;
 use sqlx::PgPool;
 
+use crate::database::UnitOfWork;
+use crate::errors::AppError;
+use crate::services::PermissionCache;
+
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
 }
+
+impl AppState {
+    /// Start a transaction for writes that must succeed or fail together
+    ///
+    /// Cached permissions of the memberships it changes are dropped once it commits.
+    pub async fn unit_of_work(&self) -> Result<UnitOfWork, AppError> {
+        UnitOfWork::begin(&self.db, PermissionCache::invalidate).await
+    }
+}
+
+impl FromRef<AppState> for PgPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.db.clone()
+    }
+}
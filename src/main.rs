@@ -1,37 +1,32 @@
 // Survey Application - Main entry point
 
-mod controllers;
-mod database;
-mod dto;
-mod errors;
-mod middleware;
-mod models;
-mod repository;
-mod services;
-mod utils;
-
 use axum::{
-    middleware::from_fn_with_state,
     response::Json,
-    routing::{delete, get, post, put},
+    routing::get,
     Router,
 };
 use serde_json::{json, Value};
-use sqlx::PgPool;
 use std::net::SocketAddr;
 use tower::ServiceBuilder;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::{info, Level};
 
 use survey::routes::{
-    contact_routes, contact_routes_with_permissions,
+    auth_routes_with_permissions, public_auth_routes,
+    contact_routes_with_permissions,
     contact_filter_routes::contact_filter_routes_with_permissions,
-    user_routes, public_user_routes,
-    organization_routes,
-    user_organization_routes,
+    contact_tag_routes_with_permissions,
+    contact_activity_routes_with_permissions,
+    custom_field_routes_with_permissions,
+    role_routes_with_permissions,
+    invitation_routes_with_permissions,
+    member_routes_with_permissions,
+    user_routes_with_permissions, public_user_routes,
+    organization_routes_with_permissions,
+    user_organization_routes_with_permissions, public_user_organization_routes,
+    PermissionRoutes,
 };
-use crate::middleware::jwt_auth_middleware;
-use crate::database::{create_connection_pool, create_organization_table, MigrationRunner};
+use survey::database::{create_connection_pool, create_organization_table, MigrationRunner};
 use survey::AppState;
 
 
@@ -50,6 +45,11 @@ async fn main() {
 
     info!("Survey Application Starting...");
 
+    // Refuse to boot with a missing or broken JWT key configuration
+    survey::utils::init_jwt_keys().expect("Invalid JWT key configuration");
+    survey::utils::init_password_hashing().expect("Invalid password hashing configuration");
+    survey::utils::init_password_policy().expect("Invalid password policy configuration");
+
     // Initialize database connection pool
     let db_pool = create_connection_pool()
         .await
@@ -84,8 +84,10 @@ async fn main() {
     // Create a TCP listener
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
 
-    // Run the server
-    axum::serve(listener, app).await.unwrap();
+    // Run the server, exposing client addresses for per-IP login throttling
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
 }
 
 fn create_app(app_state: AppState) -> Router {
@@ -96,39 +98,44 @@ fn create_app(app_state: AppState) -> Router {
         // Health check route
         .route("/health", get(health_check))
         // Merge public user routes (registration, login)
-        .merge(public_user_routes());
-
-    // Protected routes (authentication required)
-    let protected_routes = Router::new()
-        // Merge all protected route modules
-        .merge(user_routes())
-        .merge(organization_routes())
-        .merge(user_organization_routes())
-        .merge(contact_routes())
-        // Add JWT authentication middleware to all protected routes
-        .layer(from_fn_with_state(
-            app_state.db.clone(),
-            jwt_auth_middleware,
-        ));
-
-    // Permission-protected routes (authentication + permission required)
-    let permission_protected_routes = Router::new()
+        .merge(public_user_routes())
+        // Token refresh authenticates with the refresh token itself
+        .merge(public_auth_routes())
+        // Invitation links authenticate with the invitation token
+        .merge(public_user_organization_routes());
+
+    // Protected routes (authentication + an explicit policy required)
+    let permission_routes = PermissionRoutes::new()
+        .merge(user_routes_with_permissions())
+        .merge(auth_routes_with_permissions())
+        .merge(organization_routes_with_permissions())
+        .merge(user_organization_routes_with_permissions())
         .merge(contact_routes_with_permissions())
         .merge(contact_filter_routes_with_permissions())
-        .layer(from_fn_with_state(
-            app_state.db.clone(),
-            jwt_auth_middleware,
-        ))
+        .merge(contact_tag_routes_with_permissions())
+        .merge(contact_activity_routes_with_permissions())
+        .merge(custom_field_routes_with_permissions())
+        .merge(role_routes_with_permissions())
+        .merge(invitation_routes_with_permissions())
+        .merge(member_routes_with_permissions());
+
+    // Refuse to start with a route that is declared twice or has an invalid policy
+    permission_routes
+        .check()
+        .expect("Invalid route permission policies");
+    for (method, path, policy) in permission_routes.policies() {
+        tracing::debug!("{} {} requires {}", method, path, policy);
+    }
+
+    let protected_routes = permission_routes
+        .into_router(app_state.clone())
         .with_state(app_state.clone());
 
-
-
     // Combine routes
     Router::new()
         .merge(public_routes)
         .merge(protected_routes)
-        .merge(permission_protected_routes)
-        .with_state(app_state.db)
+        .with_state(app_state)
         // Add global middleware
         .layer(
             ServiceBuilder::new()
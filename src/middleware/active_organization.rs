@@ -0,0 +1,189 @@
+// Active organization resolution for multi-organization users
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderMap},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    errors::AppError,
+    models::PermissionSet,
+    repository::{OrganizationRepository, UserOrganizationRepository},
+    services::{PermissionService, SessionService},
+    utils::jwt_utils::{extract_token_from_header, JwtUser},
+    AppState,
+};
+
+/// Header used to select the organization a request operates on
+pub const ORGANIZATION_HEADER: &str = "x-organization-id";
+
+/// Authenticated user together with the organization selected for the request
+///
+/// The organization comes from the `X-Organization-Id` header, falling back to the
+/// org claim minted by `POST /users/me/organizations/switch`. Users with a single
+/// active membership may omit both.
+#[derive(Debug, Clone)]
+pub struct ActiveOrganization {
+    pub user: JwtUser,
+    pub org_id: Uuid,
+    /// The user's permissions in `org_id`, resolved once for the request
+    pub permissions: PermissionSet,
+}
+
+impl ActiveOrganization {
+    /// Resolve the active organization from request headers
+    pub async fn from_headers(pool: &PgPool, headers: &HeaderMap) -> Result<Self, AppError> {
+        let auth_header = headers
+            .get("authorization")
+            .and_then(|h| h.to_str().ok())
+            .ok_or_else(|| AppError::Unauthorized("Missing authorization header".to_string()))?;
+
+        let token = extract_token_from_header(auth_header)
+            .map_err(|_| AppError::Unauthorized("Invalid authorization header format".to_string()))?;
+
+        let (claims, user) = SessionService::authenticate(pool, token).await?;
+        Self::for_user(pool, user, headers, claims.organization_id()?).await
+    }
+
+    /// Resolve the active organization for an already authenticated user
+    ///
+    /// `token_org_id` is the org claim of the user's token; the header takes precedence.
+    pub async fn for_user(
+        pool: &PgPool,
+        user: JwtUser,
+        headers: &HeaderMap,
+        token_org_id: Option<Uuid>,
+    ) -> Result<Self, AppError> {
+        let requested_org_id = requested_organization(headers)?;
+        Self::resolve(pool, user, requested_org_id.or(token_org_id)).await
+    }
+
+    /// Resolve an explicitly named organization (e.g. from the request path) for an authenticated user
+    pub async fn for_organization(pool: &PgPool, user: JwtUser, org_id: Uuid) -> Result<Self, AppError> {
+        Self::resolve(pool, user, Some(org_id)).await
+    }
+
+    /// Resolve the active organization from a bare token (no request headers available)
+    pub async fn from_token(pool: &PgPool, token: &str) -> Result<Self, AppError> {
+        let (claims, user) = SessionService::authenticate(pool, token).await?;
+        Self::resolve(pool, user, claims.organization_id()?).await
+    }
+
+    async fn resolve(
+        pool: &PgPool,
+        user: JwtUser,
+        requested_org_id: Option<Uuid>,
+    ) -> Result<Self, AppError> {
+        let org_id = match requested_org_id {
+            Some(org_id) => {
+                ensure_active_member(pool, user.id, org_id).await?;
+                org_id
+            }
+            None => default_organization(pool, user.id).await?,
+        };
+        let permissions = PermissionService::permission_set(pool, user.id, org_id).await?;
+
+        Ok(Self { user, org_id, permissions })
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for ActiveOrganization {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(active) = parts.extensions.get::<ActiveOrganization>() {
+            return Ok(active.clone());
+        }
+
+        // Keep the resolution, including the permission set, for later extractors of the same request
+        let active = Self::from_headers(&state.db, &parts.headers).await?;
+        parts.extensions.insert(active.clone());
+        parts.extensions.insert(active.permissions.clone());
+        Ok(active)
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for PermissionSet {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(permissions) = parts.extensions.get::<PermissionSet>() {
+            return Ok(permissions.clone());
+        }
+
+        Ok(ActiveOrganization::from_request_parts(parts, state).await?.permissions)
+    }
+}
+
+/// Verify that the user is an active member of the organization, and that it is not deleted
+pub async fn ensure_active_member(pool: &PgPool, user_id: Uuid, org_id: Uuid) -> Result<(), AppError> {
+    let membership = UserOrganizationRepository::find_by_user_and_org(pool, user_id, org_id).await?;
+
+    let not_found = || AppError::NotFound(format!("Organization with id {} not found", org_id));
+
+    match membership {
+        Some(membership) if membership.is_active() => {}
+        // Members of a deleted organization lose access to it, and to its data, until it is restored
+        Some(membership) if membership.is_org_deleted() => return Err(not_found()),
+        _ => {
+            return Err(AppError::Forbidden(format!(
+                "User is not an active member of organization {}",
+                org_id
+            )))
+        }
+    }
+
+    match OrganizationRepository::find_by_id(pool, org_id).await? {
+        Some(_) => Ok(()),
+        None => Err(not_found()),
+    }
+}
+
+/// Parse the `X-Organization-Id` header, if present
+fn requested_organization(headers: &HeaderMap) -> Result<Option<Uuid>, AppError> {
+    headers
+        .get(ORGANIZATION_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| Uuid::parse_str(value.trim()).ok())
+                .ok_or_else(|| AppError::ValidationError("Invalid X-Organization-Id header".to_string()))
+        })
+        .transpose()
+}
+
+/// Fall back to the user's only active organization when none was selected
+async fn default_organization(pool: &PgPool, user_id: Uuid) -> Result<Uuid, AppError> {
+    let query = r#"
+        SELECT uo.org_id
+        FROM user_organizations uo
+        JOIN organization o ON o.id = uo.org_id
+        WHERE uo.user_id = $1 AND uo.status = 'active' AND o.deleted_at IS NULL
+        LIMIT 2
+    "#;
+
+    let rows = sqlx::query_as::<_, (Uuid,)>(query)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+    match rows.as_slice() {
+        [(org_id,)] => Ok(*org_id),
+        [] => Err(AppError::Forbidden("User not associated with any organization".to_string())),
+        _ => Err(AppError::ValidationError(
+            "User belongs to multiple organizations; select one with the X-Organization-Id header or POST /users/me/organizations/switch".to_string(),
+        )),
+    }
+}
@@ -9,12 +9,13 @@ use axum::{
 use sqlx::PgPool;
 
 use crate::errors::AppError;
-use crate::utils::{extract_token_from_header, validate_token, JwtUser};
+use crate::services::SessionService;
+use crate::utils::{extract_token_from_header, JwtUser};
 
 /// JWT Authentication middleware
-/// Validates JWT token and injects user info into request extensions
+/// Validates the JWT and its session, then injects user info into request extensions
 pub async fn jwt_auth_middleware(
-    State(_pool): State<PgPool>,
+    State(pool): State<PgPool>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
@@ -24,27 +25,18 @@ pub async fn jwt_auth_middleware(
         .get(AUTHORIZATION)
         .and_then(|header| header.to_str().ok())
         .ok_or_else(|| {
-            AppError::ValidationError("Missing Authorization header".to_string())
+            AppError::Unauthorized("Missing Authorization header".to_string())
         })?;
 
     // Extract token from header
     let token = extract_token_from_header(auth_header)?;
 
-    // Validate token and extract claims
-    let claims = validate_token(token)?;
+    // Validate token, its session and the user's current status
+    let (claims, user) = SessionService::authenticate(&pool, token).await?;
 
-    // Convert claims to user info
-    let user = claims.to_user()?;
-
-    // Check if user is active
-    if user.status != "active" {
-        return Err(AppError::ValidationError(
-            format!("User account is {}", user.status),
-        ));
-    }
-
-    // Insert user info into request extensions
+    // Insert user info and claims into request extensions
     request.extensions_mut().insert(user);
+    request.extensions_mut().insert(claims);
 
     // Continue to the next middleware/handler
     Ok(next.run(request).await)
@@ -54,7 +46,7 @@ pub async fn jwt_auth_middleware(
 /// Similar to jwt_auth_middleware but doesn't fail if no token is provided
 /// Useful for routes that can work with or without authentication
 pub async fn optional_jwt_auth_middleware(
-    State(_pool): State<PgPool>,
+    State(pool): State<PgPool>,
     mut request: Request,
     next: Next,
 ) -> Response {
@@ -66,13 +58,9 @@ pub async fn optional_jwt_auth_middleware(
     {
         // Try to extract and validate token
         if let Ok(token) = extract_token_from_header(auth_header) {
-            if let Ok(claims) = validate_token(token) {
-                if let Ok(user) = claims.to_user() {
-                    if user.status == "active" {
-                        // Insert user info into request extensions
-                        request.extensions_mut().insert(user);
-                    }
-                }
+            if let Ok((_claims, user)) = SessionService::authenticate(&pool, token).await {
+                // Insert user info into request extensions
+                request.extensions_mut().insert(user);
             }
         }
     }
@@ -1,8 +1,12 @@
 // Middleware module - Request/response processing middleware
+pub mod active_organization;
 pub mod auth;
 pub mod auth_middleware;
 pub mod permission_middleware;
+pub mod require_permission;
 
+pub use active_organization::*;
 pub use auth::*;
 pub use auth_middleware::*;
 pub use permission_middleware::*;
+pub use require_permission::*;
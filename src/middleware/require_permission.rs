@@ -0,0 +1,182 @@
+// Declarative per-route permission checks
+
+use axum::{
+    extract::{RawPathParams, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::fmt;
+
+use crate::{
+    errors::AppError,
+    middleware::active_organization::ActiveOrganization,
+    models::{own_permission, PermissionScope},
+    repository::UserOrganizationRepository,
+    utils::{Claims, JwtUser},
+    AppState,
+};
+use uuid::Uuid;
+
+/// Permission a route is mounted with
+///
+/// Attached through `PermissionRoutes`, which runs `require_permission` before the handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequirePermission {
+    permission: Option<&'static str>,
+    allow_own: bool,
+    /// The route acts on the user account named by its `:id` or `:user_id` segment
+    account: bool,
+}
+
+impl RequirePermission {
+    pub const fn new(permission: &'static str) -> Self {
+        Self {
+            permission: Some(permission),
+            allow_own: false,
+            account: false,
+        }
+    }
+
+    /// Also admit callers holding only `<permission>_own`; the handler enforces ownership
+    pub const fn owned(permission: &'static str) -> Self {
+        Self {
+            permission: Some(permission),
+            allow_own: true,
+            account: false,
+        }
+    }
+
+    /// Admit any signed-in user; the handler only acts on the caller or checks access itself
+    pub const fn authenticated() -> Self {
+        Self {
+            permission: None,
+            allow_own: false,
+            account: false,
+        }
+    }
+
+    /// Admit the user whose account the route names, or a holder of `permission` in an
+    /// active organization that user belongs to
+    pub const fn account(permission: &'static str) -> Self {
+        Self {
+            permission: Some(permission),
+            allow_own: false,
+            account: true,
+        }
+    }
+
+    /// Admit only the user whose account the route names
+    pub const fn own_account() -> Self {
+        Self {
+            permission: None,
+            allow_own: false,
+            account: true,
+        }
+    }
+
+    pub fn permission(&self) -> Option<&'static str> {
+        self.permission
+    }
+
+    pub fn is_account(&self) -> bool {
+        self.account
+    }
+}
+
+impl fmt::Display for RequirePermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.permission, self.account) {
+            (Some(permission), true) => write!(f, "own account or '{}'", permission),
+            (None, true) => write!(f, "own account"),
+            (Some(permission), false) if self.allow_own => {
+                write!(f, "'{}' or '{}'", permission, own_permission(permission))
+            }
+            (Some(permission), false) => write!(f, "'{}'", permission),
+            (None, false) => write!(f, "authenticated"),
+        }
+    }
+}
+
+/// Authorize a request against the route's `RequirePermission` policy
+///
+/// Reuses the user and claims inserted by `jwt_auth_middleware`, so the token is not parsed
+/// again. Account routes admit the user they name without resolving an organization, and
+/// routes without a permission admit any signed-in user. Routes with an `:org_id` path segment are authorized against that organization
+/// instead of the header or token one. The resolved `ActiveOrganization` and its `PermissionSet` are stored in the request
+/// extensions for handlers to extract, together with the granted `PermissionScope`.
+pub async fn require_permission(
+    State((state, policy)): State<(AppState, RequirePermission)>,
+    path_params: Option<RawPathParams>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let user = request
+        .extensions()
+        .get::<JwtUser>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Authentication required".to_string()))?;
+    let token_org_id = match request.extensions().get::<Claims>() {
+        Some(claims) => claims.organization_id()?,
+        None => None,
+    };
+
+    let path_id = |segments: &[&str], message: &str| {
+        path_params
+            .as_ref()
+            .and_then(|params| params.iter().find(|(name, _)| segments.contains(name)))
+            .map(|(_, value)| Uuid::parse_str(value).map_err(|_| AppError::ValidationError(message.to_string())))
+            .transpose()
+    };
+
+    let account_id = if policy.account {
+        path_id(&["id", "user_id"], "Invalid user id")?
+    } else {
+        None
+    };
+    if account_id == Some(user.id) {
+        return Ok(next.run(request).await);
+    }
+
+    let Some(permission) = policy.permission else {
+        if policy.account {
+            return Err(AppError::Forbidden("You can only manage your own account".to_string()));
+        }
+        return Ok(next.run(request).await);
+    };
+
+    let path_org_id = path_id(&["org_id"], "Invalid organization id")?;
+
+    let active = match path_org_id {
+        Some(org_id) => ActiveOrganization::for_organization(&state.db, user, org_id).await?,
+        None => ActiveOrganization::for_user(&state.db, user, request.headers(), token_org_id).await?,
+    };
+
+    let scope = match active.permissions.scope(permission) {
+        Some(PermissionScope::Own) if !policy.allow_own => None,
+        scope => scope,
+    };
+    let Some(scope) = scope else {
+        let message = if policy.allow_own {
+            format!("Permission '{}' or '{}' required", permission, own_permission(permission))
+        } else {
+            format!("Permission '{}' required", permission)
+        };
+        return Err(AppError::Forbidden(message));
+    };
+
+    // Other users' accounts are only reachable through an organization they belong to
+    if let Some(account_id) = account_id {
+        if UserOrganizationRepository::find_by_user_and_org(&state.db, account_id, active.org_id)
+            .await?
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!("User with id {} not found", account_id)));
+        }
+    }
+
+    request.extensions_mut().insert(scope);
+    request.extensions_mut().insert(active.permissions.clone());
+    request.extensions_mut().insert(active);
+
+    Ok(next.run(request).await)
+}
@@ -0,0 +1,53 @@
+// Audit log model - Security-relevant events kept for review
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const AUDIT_ACCOUNT_LOCKED: &str = "account_locked";
+pub const AUDIT_IP_LOCKED: &str = "ip_locked";
+pub const AUDIT_ACCOUNT_UNLOCKED: &str = "account_unlocked";
+pub const AUDIT_MFA_ENABLED: &str = "mfa_enabled";
+pub const AUDIT_MFA_DISABLED: &str = "mfa_disabled";
+pub const AUDIT_MFA_POLICY_UPDATED: &str = "mfa_policy_updated";
+pub const AUDIT_ORG_UPDATED: &str = "organization_updated";
+pub const AUDIT_ORG_SETTINGS_UPDATED: &str = "organization_settings_updated";
+pub const AUDIT_ORG_DELETED: &str = "organization_deleted";
+pub const AUDIT_ORG_RESTORED: &str = "organization_restored";
+pub const AUDIT_ORG_PURGED: &str = "organization_purged";
+
+/// One audited event; who did it, to whom and from where are each optional
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub action: String,
+    /// User who performed the action, e.g. the admin unlocking an account
+    pub actor_id: Option<Uuid>,
+    /// User the action was about
+    pub target_user_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub details: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    pub fn new(
+        action: &str,
+        actor_id: Option<Uuid>,
+        target_user_id: Option<Uuid>,
+        ip_address: Option<String>,
+        details: Value,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            action: action.to_string(),
+            actor_id,
+            target_user_id,
+            ip_address,
+            details,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -5,6 +5,7 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Contact {
     pub id: Uuid,
+    pub org_id: Uuid,
     pub first_name: String,
     pub last_name: String,
     pub email: String,
@@ -26,24 +27,17 @@ pub struct Contact {
 }
 
 impl Contact {
-    pub fn new(
-        first_name: String,
-        last_name: String,
-        email: String,
-        phone: Option<String>,
-        company: Option<String>,
-        job_title: Option<String>,
-        owner_id: Option<Uuid>,
-    ) -> Self {
+    pub fn new(org_id: Uuid, first_name: String, last_name: String, email: String, owner_id: Option<Uuid>) -> Self {
         let now = Utc::now();
         Self {
             id: Uuid::new_v4(),
+            org_id,
             first_name,
             last_name,
             email,
-            phone,
-            company,
-            job_title,
+            phone: None,
+            company: None,
+            job_title: None,
             address: None,
             city: None,
             state: None,
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const ACTIVITY_TYPES: &[&str] = &[
+    "call",
+    "email",
+    "meeting",
+    "note",
+    "task",
+    "survey_sent",
+    "survey_completed",
+];
+
+pub const ACTIVITY_STATUSES: &[&str] = &["completed", "scheduled", "cancelled"];
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContactActivity {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub contact_id: Uuid,
+    pub activity_type: String,
+    pub subject: Option<String>,
+    pub description: Option<String>,
+    pub activity_date: Option<DateTime<Utc>>,
+    pub duration_minutes: Option<i32>,
+    pub status: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ContactActivity {
+    pub fn new(
+        org_id: Uuid,
+        contact_id: Uuid,
+        activity_type: String,
+        created_by: Option<Uuid>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            org_id,
+            contact_id,
+            activity_type,
+            subject: None,
+            description: None,
+            activity_date: None,
+            duration_minutes: None,
+            status: "completed".to_string(),
+            created_by,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Scheduled activity whose date has passed
+    pub fn is_overdue(&self) -> bool {
+        self.status == "scheduled" && self.activity_date.is_some_and(|date| date < Utc::now())
+    }
+
+    /// Mark the activity as completed
+    pub fn complete(&mut self) {
+        self.status = "completed".to_string();
+        if self.activity_date.is_none() {
+            self.activity_date = Some(Utc::now());
+        }
+        self.updated_at = Utc::now();
+    }
+}
@@ -0,0 +1,57 @@
+// Contact bulk job model - Bulk contact operations queued for a background worker
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Waiting for the worker
+pub const BULK_JOB_QUEUED: &str = "queued";
+/// Claimed by the worker
+pub const BULK_JOB_RUNNING: &str = "running";
+/// Every selected contact was processed; individual contacts may still have failed
+pub const BULK_JOB_COMPLETED: &str = "completed";
+/// The job could not run at all, see `error`
+pub const BULK_JOB_FAILED: &str = "failed";
+
+/// A bulk contact operation whose selection exceeded the synchronous limit
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContactBulkJob {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// The job runs with this user's permissions at the time it is processed
+    pub requested_by: Option<Uuid>,
+    /// The submitted `BulkContactRequest`
+    pub request: JsonValue,
+    pub status: String,
+    pub total: i32,
+    pub succeeded: i32,
+    pub failed: i32,
+    /// Results of the contacts that could not be changed
+    pub failures: JsonValue,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ContactBulkJob {
+    pub fn new(org_id: Uuid, requested_by: Uuid, request: JsonValue) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            org_id,
+            requested_by: Some(requested_by),
+            request,
+            status: BULK_JOB_QUEUED.to_string(),
+            total: 0,
+            succeeded: 0,
+            failed: 0,
+            failures: JsonValue::Array(Vec::new()),
+            error: None,
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// Contact import job model - CSV imports queued for a background worker
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Waiting for the worker; then `running`, and finally `completed` or `failed`
+pub const IMPORT_JOB_QUEUED: &str = "queued";
+
+/// A CSV import whose row count exceeded the synchronous limit
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ContactImportJob {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// The job runs with this user's permissions at the time it is processed
+    pub requested_by: Option<Uuid>,
+    pub file_name: String,
+    /// The uploaded CSV
+    #[serde(skip_serializing)]
+    pub content: String,
+    /// The submitted `ContactImportOptions`
+    pub options: JsonValue,
+    pub status: String,
+    pub total_rows: i32,
+    pub processed_rows: i32,
+    pub created_rows: i32,
+    pub updated_rows: i32,
+    pub skipped_rows: i32,
+    pub failed_rows: i32,
+    /// Rows that could not be imported, available as CSV from the errors endpoint
+    pub errors: JsonValue,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ContactImportJob {
+    pub fn new(
+        org_id: Uuid,
+        requested_by: Uuid,
+        file_name: String,
+        content: String,
+        options: JsonValue,
+        total_rows: i32,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            org_id,
+            requested_by: Some(requested_by),
+            file_name,
+            content,
+            options,
+            status: IMPORT_JOB_QUEUED.to_string(),
+            total_rows,
+            processed_rows: 0,
+            created_rows: 0,
+            updated_rows: 0,
+            skipped_rows: 0,
+            failed_rows: 0,
+            errors: JsonValue::Array(Vec::new()),
+            error: None,
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Tags every new organization starts with, as (name, color)
+pub const DEFAULT_CONTACT_TAGS: &[(&str, &str)] = &[
+    ("Customer", "#28a745"),
+    ("Prospect", "#007bff"),
+    ("Partner", "#6f42c1"),
+    ("VIP", "#ffc107"),
+];
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContactTag {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Tag together with the number of active contacts carrying it
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ContactTagWithCount {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub contact_count: i64,
+}
+
+impl ContactTag {
+    pub fn new(
+        org_id: Uuid,
+        name: String,
+        color: Option<String>,
+        description: Option<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            org_id,
+            name,
+            color,
+            description,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// The default tags for an organization
+    pub fn defaults(org_id: Uuid) -> Vec<Self> {
+        DEFAULT_CONTACT_TAGS
+            .iter()
+            .map(|(name, color)| Self::new(org_id, name.to_string(), Some(color.to_string()), None))
+            .collect()
+    }
+}
@@ -2,11 +2,48 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Supported custom field types
+pub const FIELD_TYPES: &[&str] = &[
+    "text", "textarea", "number", "email", "phone", "date", "boolean", "select", "multi_select",
+];
+
+/// Schema of `custom_fields.validation_rules`; every key is optional
+///
+/// ```text
+/// {
+///   "min": 0, "max": 1000000,                           // number fields
+///   "min_length": 2, "max_length": 80,                  // characters, or selections for multi_select
+///   "pattern": "^https://.*linkedin\\.com/.*",          // regex searched in text values; anchor with ^ and $
+///   "min_date": "2000-01-01", "max_date": "2099-12-31", // date fields, inclusive
+///   "options": ["a", "b"]                               // allowed values for any field type
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidationRules {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<String>,
+    pub min_date: Option<NaiveDate>,
+    pub max_date: Option<NaiveDate>,
+    pub options: Option<Vec<String>>,
+}
+
+/// Contact fields every new organization starts with, as (label, field_name, field_type, options)
+pub const DEFAULT_CUSTOM_FIELDS: &[(&str, &str, &str, &[&str])] = &[
+    ("Birthday", "birthday", "date", &[]),
+    ("Website", "website", "text", &[]),
+    ("Preferred Contact Method", "preferred_contact_method", "select", &["email", "phone", "sms"]),
+];
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct CustomField {
     pub id: Uuid,
+    pub org_id: Uuid,
     pub module: String,
     pub label: String,
     pub field_name: String,
@@ -25,6 +62,7 @@ pub struct CustomField {
 
 impl CustomField {
     pub fn new(
+        org_id: Uuid,
         module: String,
         label: String,
         field_name: String,
@@ -35,6 +73,7 @@ impl CustomField {
         
         Self {
             id: Uuid::new_v4(),
+            org_id,
             module,
             label,
             field_name,
@@ -51,4 +90,71 @@ impl CustomField {
             updated_at: now,
         }
     }
-}
\ No newline at end of file
+
+    /// The default contact fields for an organization, in display order
+    pub fn defaults(org_id: Uuid, created_by: Option<Uuid>) -> Vec<Self> {
+        DEFAULT_CUSTOM_FIELDS
+            .iter()
+            .enumerate()
+            .map(|(position, (label, field_name, field_type, options))| {
+                let mut custom_field = Self::new(
+                    org_id,
+                    "contact".to_string(),
+                    label.to_string(),
+                    field_name.to_string(),
+                    field_type.to_string(),
+                    created_by,
+                );
+                if !options.is_empty() {
+                    custom_field.options = Some(serde_json::json!({ "options": options }));
+                }
+                custom_field.display_order = position as i64;
+                custom_field
+            })
+            .collect()
+    }
+
+    /// Whether values of this field must be one of `options`
+    pub fn is_select(&self) -> bool {
+        self.field_type == "select" || self.field_type == "multi_select"
+    }
+
+    /// Parse and sanity-check `validation_rules`
+    pub fn rules(&self) -> Result<ValidationRules, String> {
+        let rules: ValidationRules = match &self.validation_rules {
+            Some(JsonValue::Null) | None => return Ok(ValidationRules::default()),
+            Some(value) => serde_json::from_value(value.clone())
+                .map_err(|e| format!("Invalid validation rules: {}", e))?,
+        };
+
+        if let Some(pattern) = &rules.pattern {
+            regex::Regex::new(pattern).map_err(|e| format!("Invalid validation pattern: {}", e))?;
+        }
+        if matches!((rules.min, rules.max), (Some(min), Some(max)) if min > max) {
+            return Err("Validation rule min must not exceed max".to_string());
+        }
+        if matches!((rules.min_length, rules.max_length), (Some(min), Some(max)) if min > max) {
+            return Err("Validation rule min_length must not exceed max_length".to_string());
+        }
+        if matches!((rules.min_date, rules.max_date), (Some(min), Some(max)) if min > max) {
+            return Err("Validation rule min_date must not be after max_date".to_string());
+        }
+
+        Ok(rules)
+    }
+
+    /// Allowed values of a select field, stored as `{"options": [...]}`
+    pub fn option_values(&self) -> Vec<String> {
+        self.options
+            .as_ref()
+            .and_then(|options| options.get("options"))
+            .and_then(|values| values.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,68 @@
+// Invitation domain model for joining an organization by email
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const INVITATION_PENDING: &str = "pending";
+pub const INVITATION_ACCEPTED: &str = "accepted";
+pub const INVITATION_DECLINED: &str = "declined";
+pub const INVITATION_REVOKED: &str = "revoked";
+
+/// An invitation to join an organization, addressed to an email that may not have an account yet
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Invitation {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub email: String,
+    /// Role the invitee receives on acceptance
+    pub role_id: Uuid,
+    pub invited_by: Option<Uuid>,
+    /// Hash of the token in the invitation link; the token itself is never stored
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    /// User who accepted the invitation
+    pub accepted_by: Option<Uuid>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Invitation {
+    pub fn new(
+        org_id: Uuid,
+        email: String,
+        role_id: Uuid,
+        invited_by: Option<Uuid>,
+        token_hash: String,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            org_id,
+            email,
+            role_id,
+            invited_by,
+            token_hash,
+            status: INVITATION_PENDING.to_string(),
+            expires_at,
+            accepted_by: None,
+            responded_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Not yet accepted, declined or revoked (it may still have expired)
+    pub fn is_pending(&self) -> bool {
+        self.status == INVITATION_PENDING
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
@@ -0,0 +1,59 @@
+// Lead status domain model
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Pipeline every new organization starts with, as (name, label, is_closed), in display order
+pub const DEFAULT_LEAD_STATUSES: &[(&str, &str, bool)] = &[
+    ("new", "New", false),
+    ("contacted", "Contacted", false),
+    ("qualified", "Qualified", false),
+    ("proposal", "Proposal", false),
+    ("negotiation", "Negotiation", false),
+    ("closed_won", "Closed Won", true),
+    ("closed_lost", "Closed Lost", true),
+];
+
+/// Stage of an organization's sales pipeline a contact can be in
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LeadStatus {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// Value stored in `contacts.lead_status`
+    pub name: String,
+    pub label: String,
+    pub display_order: i32,
+    /// Whether contacts in this status have left the pipeline
+    pub is_closed: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl LeadStatus {
+    pub fn new(org_id: Uuid, name: String, label: String, display_order: i32, is_closed: bool) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            org_id,
+            name,
+            label,
+            display_order,
+            is_closed,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// The default pipeline for an organization
+    pub fn defaults(org_id: Uuid) -> Vec<Self> {
+        DEFAULT_LEAD_STATUSES
+            .iter()
+            .enumerate()
+            .map(|(position, (name, label, is_closed))| {
+                Self::new(org_id, name.to_string(), label.to_string(), position as i32, *is_closed)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,31 @@
+// Login throttle model - Failed login attempts counted per account or per client IP
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Attempts against one email address, whether or not an account exists for it
+pub const THROTTLE_SCOPE_ACCOUNT: &str = "account";
+/// Attempts from one client IP address, across every email it tries
+pub const THROTTLE_SCOPE_IP: &str = "ip";
+
+/// Recent failed logins for one account or IP, and the lockout they earned
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LoginThrottle {
+    pub scope: String,
+    /// Normalized email or IP address
+    pub subject: String,
+    /// Consecutive failures; reset by a successful login or once the failures go stale
+    pub failed_attempts: i32,
+    pub last_failed_at: DateTime<Utc>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+impl LoginThrottle {
+    /// Whole seconds until the lockout ends, at least one while it is in force
+    pub fn retry_after_secs(&self) -> u64 {
+        self.locked_until
+            .map(|until| (until - Utc::now()).num_seconds().max(1) as u64)
+            .unwrap_or(0)
+    }
+}
@@ -1,16 +1,42 @@
 // Models module - Define data structures and entities
+pub mod audit_entry;
 pub mod contact;
+pub mod contact_activity;
+pub mod contact_bulk_job;
 pub mod contact_custom_value;
+pub mod contact_import_job;
+pub mod contact_tag;
 pub mod custom_field;
+pub mod invitation;
+pub mod lead_status;
+pub mod login_throttle;
 pub mod organization;
+pub mod outbox_email;
+pub mod permission_set;
 pub mod role;
 pub mod user;
 pub mod user_organization;
+pub mod user_session;
+pub mod user_mfa;
+pub mod user_token;
 
+pub use audit_entry::*;
 pub use contact::*;
+pub use contact_activity::*;
+pub use contact_bulk_job::*;
 pub use contact_custom_value::*;
+pub use contact_import_job::*;
+pub use contact_tag::*;
 pub use custom_field::*;
+pub use invitation::*;
+pub use lead_status::*;
+pub use login_throttle::*;
 pub use organization::*;
+pub use outbox_email::*;
+pub use permission_set::*;
 pub use role::*;
 pub use user::*;
 pub use user_organization::*;
+pub use user_session::*;
+pub use user_mfa::*;
+pub use user_token::*;
@@ -4,6 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Organization {
@@ -25,3 +26,116 @@ impl Organization {
         }
     }
 }
+
+/// Date formats an organization can choose for exports and emails
+pub const DATE_FORMATS: &[&str] = &["YYYY-MM-DD", "DD/MM/YYYY", "MM/DD/YYYY"];
+/// Days a calendar week can start on
+pub const WEEK_START_DAYS: &[&str] = &["monday", "sunday", "saturday"];
+
+/// Organization-wide preferences, stored as the `settings` JSONB document
+///
+/// Missing keys take their defaults, so documents written before a setting existed stay valid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+#[serde(default, deny_unknown_fields)]
+pub struct OrganizationSettings {
+    /// Language tag such as `en` or `pt-BR`
+    #[validate(custom = "validate_locale")]
+    pub locale: String,
+    /// ISO 4217 code amounts are shown in
+    #[validate(custom = "validate_currency")]
+    pub currency: String,
+    #[validate(custom = "validate_date_format")]
+    pub date_format: String,
+    #[validate(custom = "validate_week_start")]
+    pub week_starts_on: String,
+}
+
+impl Default for OrganizationSettings {
+    fn default() -> Self {
+        Self {
+            locale: "en".to_string(),
+            currency: "USD".to_string(),
+            date_format: "YYYY-MM-DD".to_string(),
+            week_starts_on: "monday".to_string(),
+        }
+    }
+}
+
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    let mut parts = locale.split('-');
+    let language = parts.next().unwrap_or_default();
+    let region = parts.next();
+
+    let valid_language = (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase());
+    let valid_region = match region {
+        Some(region) => region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()),
+        None => true,
+    };
+
+    if valid_language && valid_region && parts.next().is_none() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_locale"))
+    }
+}
+
+fn validate_currency(currency: &str) -> Result<(), ValidationError> {
+    if currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_currency"))
+    }
+}
+
+fn validate_date_format(date_format: &str) -> Result<(), ValidationError> {
+    if DATE_FORMATS.contains(&date_format) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_date_format"))
+    }
+}
+
+fn validate_week_start(day: &str) -> Result<(), ValidationError> {
+    if WEEK_START_DAYS.contains(&day) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_week_start"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_settings_take_defaults() {
+        let settings: OrganizationSettings = serde_json::from_str(r#"{"currency": "EUR"}"#).unwrap();
+
+        assert_eq!(settings.currency, "EUR");
+        assert_eq!(settings.locale, "en");
+        assert_eq!(settings.week_starts_on, "monday");
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_unknown_settings_are_rejected() {
+        assert!(serde_json::from_str::<OrganizationSettings>(r#"{"theme": "dark"}"#).is_err());
+    }
+
+    #[test]
+    fn test_settings_values_are_validated() {
+        let valid = OrganizationSettings { locale: "pt-BR".to_string(), ..Default::default() };
+        assert!(valid.validate().is_ok());
+
+        let invalid = OrganizationSettings {
+            locale: "english".to_string(),
+            currency: "usd".to_string(),
+            date_format: "YY/MM/DD".to_string(),
+            week_starts_on: "friday".to_string(),
+        };
+        let errors = invalid.validate().unwrap_err();
+        let fields = errors.field_errors();
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields["currency"][0].code, "invalid_currency");
+    }
+}
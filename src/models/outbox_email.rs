@@ -0,0 +1,32 @@
+// Outbox email model - Messages queued for delivery by a mail worker
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Template of an organization invitation email
+pub const EMAIL_TEMPLATE_INVITATION: &str = "organization_invitation";
+/// Template of a password reset email
+pub const EMAIL_TEMPLATE_PASSWORD_RESET: &str = "password_reset";
+/// Template of an email address verification email
+pub const EMAIL_TEMPLATE_EMAIL_VERIFICATION: &str = "email_verification";
+
+/// An email recorded in the same request that triggered it and sent later by a worker,
+/// so a mail outage never fails or rolls back the request
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OutboxEmail {
+    pub id: Uuid,
+    pub recipient: String,
+    pub template: String,
+    pub subject: String,
+    /// Template variables, e.g. the invitation link
+    pub payload: JsonValue,
+    /// `pending` until delivered (`sent`) or abandoned (`failed`)
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
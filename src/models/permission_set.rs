@@ -0,0 +1,168 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// How far a permission reaches: every resource, or only the ones the user owns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionScope {
+    All,
+    Own,
+}
+
+/// Ownership-scoped form of a permission (`contacts:update` -> `contacts:update_own`)
+pub fn own_permission(permission: &str) -> String {
+    format!("{}_own", permission)
+}
+
+/// Permissions a user holds in one organization, resolved once and checked in memory
+///
+/// Cloning is cheap; the underlying set is shared.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PermissionSet {
+    permissions: Arc<HashSet<String>>,
+}
+
+impl PermissionSet {
+    pub fn new(permissions: HashSet<String>) -> Self {
+        Self {
+            permissions: Arc::new(permissions),
+        }
+    }
+
+    /// Granted permission strings as stored on the user's roles
+    pub fn permissions(&self) -> &HashSet<String> {
+        &self.permissions
+    }
+
+    /// Check a permission, honouring the `*` and `resource:*` wildcards
+    ///
+    /// A `_own` permission is also satisfied by its unscoped form.
+    pub fn allows(&self, required_permission: &str) -> bool {
+        if self.permissions.contains("*") || self.permissions.contains(required_permission) {
+            return true;
+        }
+
+        if let Some(unscoped) = required_permission.strip_suffix("_own") {
+            if self.permissions.contains(unscoped) {
+                return true;
+            }
+        }
+
+        match required_permission.split_once(':') {
+            Some((resource, _)) => self.permissions.contains(&format!("{}:*", resource)),
+            None => false,
+        }
+    }
+
+    pub fn allows_any(&self, required_permissions: &[&str]) -> bool {
+        required_permissions.iter().any(|permission| self.allows(permission))
+    }
+
+    pub fn allows_all(&self, required_permissions: &[&str]) -> bool {
+        required_permissions.iter().all(|permission| self.allows(permission))
+    }
+
+    /// Whether every permission in `granted` is already held, so granting them gives nothing more
+    ///
+    /// `*` and `<resource>:*` are only covered by the same or a wider wildcard.
+    pub fn covers(&self, granted: &[String]) -> bool {
+        granted.iter().all(|permission| self.allows(permission))
+    }
+
+    /// Widest scope granted for `base_permission`, if any
+    pub fn scope(&self, base_permission: &str) -> Option<PermissionScope> {
+        if self.allows(base_permission) {
+            Some(PermissionScope::All)
+        } else if self.allows(&own_permission(base_permission)) {
+            Some(PermissionScope::Own)
+        } else {
+            None
+        }
+    }
+
+    /// Full `base_permission`, or `base_permission_own` when `user_id` owns the resource
+    pub fn allows_resource(&self, base_permission: &str, user_id: Uuid, resource_owner_id: Option<Uuid>) -> bool {
+        match self.scope(base_permission) {
+            Some(PermissionScope::All) => true,
+            Some(PermissionScope::Own) => resource_owner_id == Some(user_id),
+            None => false,
+        }
+    }
+}
+
+impl FromIterator<String> for PermissionSet {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(permissions: &[&str]) -> PermissionSet {
+        permissions.iter().map(|permission| permission.to_string()).collect()
+    }
+
+    #[test]
+    fn test_exact_and_wildcard_permissions() {
+        let permissions = set(&["contacts:read", "activities:*"]);
+        assert!(permissions.allows("contacts:read"));
+        assert!(!permissions.allows("contacts:update"));
+        assert!(permissions.allows("activities:delete"));
+        assert!(!permissions.allows("activities"));
+
+        assert!(set(&["*"]).allows("custom_fields:manage"));
+        assert!(!PermissionSet::default().allows("contacts:read"));
+    }
+
+    #[test]
+    fn test_any_and_all() {
+        let permissions = set(&["contacts:read", "contacts:create"]);
+        assert!(permissions.allows_any(&["contacts:delete", "contacts:read"]));
+        assert!(!permissions.allows_any(&["contacts:delete"]));
+        assert!(permissions.allows_all(&["contacts:read", "contacts:create"]));
+        assert!(!permissions.allows_all(&["contacts:read", "contacts:delete"]));
+    }
+
+    #[test]
+    fn test_covers() {
+        let permissions = set(&["contacts:*", "roles:read"]);
+        assert!(permissions.covers(&["contacts:delete".to_string(), "contacts:*".to_string()]));
+        assert!(permissions.covers(&["contacts:update_own".to_string()]));
+        assert!(!permissions.covers(&["roles:manage".to_string()]));
+        assert!(!permissions.covers(&["roles:*".to_string()]));
+        assert!(!permissions.covers(&["*".to_string()]));
+
+        assert!(set(&["*"]).covers(&["*".to_string()]));
+        assert!(permissions.covers(&[]));
+    }
+
+    #[test]
+    fn test_resource_ownership() {
+        let user_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+
+        let own = set(&["contacts:update_own"]);
+        assert!(own.allows_resource("contacts:update", user_id, Some(user_id)));
+        assert!(!own.allows_resource("contacts:update", user_id, Some(other_id)));
+        assert!(!own.allows_resource("contacts:update", user_id, None));
+
+        let full = set(&["contacts:update"]);
+        assert!(full.allows_resource("contacts:update", user_id, Some(other_id)));
+    }
+
+    #[test]
+    fn test_scope() {
+        assert_eq!(set(&["contacts:read"]).scope("contacts:read"), Some(PermissionScope::All));
+        assert_eq!(set(&["contacts:read_own"]).scope("contacts:read"), Some(PermissionScope::Own));
+        assert_eq!(set(&["contacts:*"]).scope("contacts:delete"), Some(PermissionScope::All));
+        assert_eq!(set(&["contacts:update_own"]).scope("contacts:delete"), None);
+
+        // The unscoped grant implies the owned one
+        assert!(set(&["contacts:update"]).allows("contacts:update_own"));
+        assert!(!set(&["contacts:update_own"]).allows("contacts:update"));
+    }
+}
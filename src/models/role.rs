@@ -6,9 +6,17 @@ use serde_json::Value as JsonValue;
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Built-in role that can never be left without an active member
+pub const ADMIN_ROLE: &str = "admin";
+
+/// Built-in role granting every permission (`*`)
+pub const OWNER_ROLE: &str = "owner";
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Role {
     pub id: Uuid,
+    /// Owning organization of a custom role; `None` for the built-in roles shared by all organizations
+    pub org_id: Option<Uuid>,
     pub name: String,
     pub description: Option<String>,
     pub permissions: JsonValue,
@@ -18,10 +26,11 @@ pub struct Role {
 
 impl Role {
     /// Create a new role instance
-    pub fn new(name: String, description: Option<String>, permissions: JsonValue) -> Self {
+    pub fn new(org_id: Option<Uuid>, name: String, description: Option<String>, permissions: JsonValue) -> Self {
         let now = Some(Utc::now());
         Self {
             id: Uuid::new_v4(),
+            org_id,
             name,
             description,
             permissions,
@@ -30,6 +39,11 @@ impl Role {
         }
     }
 
+    /// Built-in roles are seeded by migrations and cannot be edited through the API
+    pub fn is_builtin(&self) -> bool {
+        self.org_id.is_none()
+    }
+
     /// Check if role has a specific permission
     pub fn has_permission(&self, permission: &str) -> bool {
         if let Some(perms) = self.permissions.as_array() {
@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Registered but has not yet proven control of their email address; cannot log in
+pub const USER_STATUS_PENDING_VERIFICATION: &str = "pending_verification";
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct User {
     pub id: Uuid,
@@ -59,6 +62,11 @@ impl User {
         self.status == "active"
     }
 
+    /// Check if user still has to verify their email address
+    pub fn is_pending_verification(&self) -> bool {
+        self.status == USER_STATUS_PENDING_VERIFICATION
+    }
+
     /// Update user status
     pub fn set_status(&mut self, status: String) {
         self.status = status;
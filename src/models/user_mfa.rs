@@ -0,0 +1,37 @@
+// User MFA model - TOTP second factor and its recovery codes
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A user's TOTP enrollment; it only protects logins once the first code has confirmed it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserMfa {
+    pub user_id: Uuid,
+    /// Base32 TOTP secret; it must be readable to check codes, so it is never returned after enrollment
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub enabled_at: Option<DateTime<Utc>>,
+    /// Time step of the last accepted code, so a code cannot be used twice
+    pub last_used_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserMfa {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+/// A single-use code that stands in for a TOTP code when the authenticator is lost; only its hash is stored
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MfaRecoveryCode {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    #[serde(skip_serializing)]
+    pub code_hash: String,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
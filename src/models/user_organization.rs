@@ -5,6 +5,10 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Status of memberships that were active when their organization was deleted; restoring
+/// the organization makes them active again
+pub const MEMBERSHIP_ORG_DELETED: &str = "org_deleted";
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct UserOrganization {
     pub id: Uuid,
@@ -85,6 +89,11 @@ impl UserOrganization {
         self.status == "pending"
     }
 
+    /// Check if the relationship was set aside when its organization was deleted
+    pub fn is_org_deleted(&self) -> bool {
+        self.status == MEMBERSHIP_ORG_DELETED
+    }
+
     /// Check if the relationship is an invitation
     pub fn is_invited(&self) -> bool {
         self.status == "invited"
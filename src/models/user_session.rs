@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A login session; access tokens reference it by `sid` and refresh tokens rotate within it
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Active organization selected for the session, carried into refreshed access tokens
+    pub org_id: Option<Uuid>,
+    #[serde(skip_serializing)]
+    pub refresh_token_hash: String,
+    /// Hash of the token replaced by the last rotation; presenting it again revokes the session
+    #[serde(skip_serializing)]
+    pub previous_refresh_token_hash: Option<String>,
+    pub user_agent: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserSession {
+    pub fn new(
+        user_id: Uuid,
+        org_id: Option<Uuid>,
+        refresh_token_hash: String,
+        user_agent: Option<String>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            org_id,
+            refresh_token_hash,
+            previous_refresh_token_hash: None,
+            user_agent,
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Not revoked and not expired
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+}
+
+/// A session joined with the current state of its user
+#[derive(Debug, Clone, FromRow)]
+pub struct SessionUser {
+    pub session_id: Uuid,
+    pub user_id: Uuid,
+    pub org_id: Option<Uuid>,
+    pub name: String,
+    pub email: String,
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl SessionUser {
+    /// Not revoked and not expired
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && self.expires_at > Utc::now()
+    }
+}
@@ -0,0 +1,38 @@
+// User token model - Single-use tokens emailed to a user or handed out mid-login
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const TOKEN_PURPOSE_PASSWORD_RESET: &str = "password_reset";
+pub const TOKEN_PURPOSE_EMAIL_VERIFICATION: &str = "email_verification";
+/// Issued when the password was right but a second factor is still needed
+pub const TOKEN_PURPOSE_MFA_PENDING: &str = "mfa_pending";
+
+/// A password reset, email verification or pending MFA token; only its hash is stored and it can be used once
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub purpose: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserToken {
+    pub fn new(user_id: Uuid, purpose: &str, token_hash: String, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            purpose: purpose.to_string(),
+            token_hash,
+            expires_at,
+            used_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
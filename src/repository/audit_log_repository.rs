@@ -0,0 +1,32 @@
+// Audit Log Repository - Database operations for audit entries
+
+use sqlx::PgExecutor;
+
+use crate::errors::AppError;
+use crate::models::AuditEntry;
+
+pub struct AuditLogRepository;
+
+impl AuditLogRepository {
+    /// Append an entry; audit entries are never updated
+    pub async fn create(executor: impl PgExecutor<'_>, entry: &AuditEntry) -> Result<AuditEntry, AppError> {
+        let result = sqlx::query_as::<_, AuditEntry>(
+            r#"
+            INSERT INTO audit_log (id, action, actor_id, target_user_id, ip_address, details, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(entry.id)
+        .bind(&entry.action)
+        .bind(entry.actor_id)
+        .bind(entry.target_user_id)
+        .bind(&entry.ip_address)
+        .bind(&entry.details)
+        .bind(entry.created_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+}
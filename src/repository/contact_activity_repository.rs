@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::ContactActivity;
+
+const ACTIVITY_COLUMNS: &str = r#"
+    a.id, a.org_id, a.contact_id, a.activity_type, a.subject, a.description,
+    a.activity_date, a.duration_minutes, a.status, a.created_by, a.created_at, a.updated_at
+"#;
+
+pub struct ContactActivityRepository;
+
+impl ContactActivityRepository {
+    /// Create a new activity
+    pub async fn create(executor: impl PgExecutor<'_>, activity: &ContactActivity) -> Result<ContactActivity, AppError> {
+        let query = r#"
+            INSERT INTO contact_activities (
+                id, org_id, contact_id, activity_type, subject, description,
+                activity_date, duration_minutes, status, created_by, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING id, org_id, contact_id, activity_type, subject, description,
+                activity_date, duration_minutes, status, created_by, created_at, updated_at
+        "#;
+
+        let result = sqlx::query_as::<_, ContactActivity>(query)
+            .bind(activity.id)
+            .bind(activity.org_id)
+            .bind(activity.contact_id)
+            .bind(&activity.activity_type)
+            .bind(&activity.subject)
+            .bind(&activity.description)
+            .bind(activity.activity_date)
+            .bind(activity.duration_minutes)
+            .bind(&activity.status)
+            .bind(activity.created_by)
+            .bind(activity.created_at)
+            .bind(activity.updated_at)
+            .fetch_one(executor)
+            .await;
+
+        match result {
+            Ok(activity) => {
+                tracing::info!("Activity created successfully with ID: {}", activity.id);
+                Ok(activity)
+            }
+            Err(e) => {
+                tracing::error!("Error creating activity: {}", e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Find activity by ID within an organization
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<ContactActivity>, AppError> {
+        let query = format!(
+            "SELECT {} FROM contact_activities a WHERE a.id = $1 AND a.org_id = $2",
+            ACTIVITY_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, ContactActivity>(&query)
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(executor)
+            .await;
+
+        match result {
+            Ok(activity) => Ok(activity),
+            Err(e) => {
+                tracing::error!("Error finding activity by ID {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Update an existing activity
+    pub async fn update(executor: impl PgExecutor<'_>, activity: &ContactActivity) -> Result<ContactActivity, AppError> {
+        let query = r#"
+            UPDATE contact_activities SET
+                activity_type = $3,
+                subject = $4,
+                description = $5,
+                activity_date = $6,
+                duration_minutes = $7,
+                status = $8,
+                updated_at = NOW()
+            WHERE id = $1 AND org_id = $2
+            RETURNING id, org_id, contact_id, activity_type, subject, description,
+                activity_date, duration_minutes, status, created_by, created_at, updated_at
+        "#;
+
+        let result = sqlx::query_as::<_, ContactActivity>(query)
+            .bind(activity.id)
+            .bind(activity.org_id)
+            .bind(&activity.activity_type)
+            .bind(&activity.subject)
+            .bind(&activity.description)
+            .bind(activity.activity_date)
+            .bind(activity.duration_minutes)
+            .bind(&activity.status)
+            .fetch_optional(executor)
+            .await;
+
+        match result {
+            Ok(Some(activity)) => {
+                tracing::info!("Activity updated successfully with ID: {}", activity.id);
+                Ok(activity)
+            }
+            Ok(None) => Err(AppError::NotFound("Activity not found".to_string())),
+            Err(e) => {
+                tracing::error!("Error updating activity {}: {}", activity.id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Delete an activity
+    pub async fn delete(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM contact_activities WHERE id = $1 AND org_id = $2")
+            .bind(id)
+            .bind(org_id)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) => {
+                if query_result.rows_affected() == 0 {
+                    tracing::warn!("No activity found to delete with ID: {}", id);
+                    Err(AppError::NotFound("Activity not found".to_string()))
+                } else {
+                    tracing::info!("Activity deleted successfully with ID: {}", id);
+                    Ok(())
+                }
+            }
+            Err(e) => {
+                tracing::error!("Error deleting activity {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Page through a contact's activities, most recent first
+    pub async fn find_by_contact(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        contact_id: Uuid,
+        activity_type: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ContactActivity>, AppError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM contact_activities a
+            WHERE a.contact_id = $1 AND a.org_id = $2
+              AND ($3::TEXT IS NULL OR a.activity_type = $3)
+            ORDER BY COALESCE(a.activity_date, a.created_at) DESC, a.id
+            LIMIT $4 OFFSET $5
+            "#,
+            ACTIVITY_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, ContactActivity>(&query)
+            .bind(contact_id)
+            .bind(org_id)
+            .bind(activity_type)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(executor)
+            .await;
+
+        match result {
+            Ok(activities) => Ok(activities),
+            Err(e) => {
+                tracing::error!("Error finding activities for contact {}: {}", contact_id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Count a contact's activities
+    pub async fn count_by_contact(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        contact_id: Uuid,
+        activity_type: Option<&str>,
+    ) -> Result<i64, AppError> {
+        let query = r#"
+            SELECT COUNT(*)
+            FROM contact_activities
+            WHERE contact_id = $1 AND org_id = $2
+              AND ($3::TEXT IS NULL OR activity_type = $3)
+        "#;
+
+        let result = sqlx::query_scalar::<_, i64>(query)
+            .bind(contact_id)
+            .bind(org_id)
+            .bind(activity_type)
+            .fetch_one(executor)
+            .await;
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(e) => {
+                tracing::error!("Error counting activities for contact {}: {}", contact_id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Scheduled activities of an owner due at or after `now`, soonest first
+    pub async fn find_upcoming(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        owner_id: Uuid,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ContactActivity>, AppError> {
+        Self::find_scheduled(executor, org_id, owner_id, "a.activity_date >= $3", now, limit).await
+    }
+
+    /// Scheduled activities of an owner whose date has passed, oldest first
+    pub async fn find_overdue(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        owner_id: Uuid,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ContactActivity>, AppError> {
+        Self::find_scheduled(executor, org_id, owner_id, "a.activity_date < $3", now, limit).await
+    }
+
+    async fn find_scheduled(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        owner_id: Uuid,
+        date_condition: &str,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ContactActivity>, AppError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM contact_activities a
+            JOIN contacts c ON c.id = a.contact_id AND c.is_active = true
+            WHERE a.org_id = $1
+              AND a.created_by = $2
+              AND a.status = 'scheduled'
+              AND {}
+            ORDER BY a.activity_date ASC
+            LIMIT $4
+            "#,
+            ACTIVITY_COLUMNS, date_condition
+        );
+
+        let result = sqlx::query_as::<_, ContactActivity>(&query)
+            .bind(org_id)
+            .bind(owner_id)
+            .bind(now)
+            .bind(limit)
+            .fetch_all(executor)
+            .await;
+
+        match result {
+            Ok(activities) => Ok(activities),
+            Err(e) => {
+                tracing::error!("Error finding scheduled activities for owner {}: {}", owner_id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+// Contact Bulk Job Repository - Queue of bulk contact operations run by a worker
+
+use serde_json::Value as JsonValue;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::ContactBulkJob;
+
+pub struct ContactBulkJobRepository;
+
+impl ContactBulkJobRepository {
+    /// Queue a job
+    pub async fn create(executor: impl PgExecutor<'_>, job: &ContactBulkJob) -> Result<ContactBulkJob, AppError> {
+        let result = sqlx::query_as::<_, ContactBulkJob>(
+            r#"
+            INSERT INTO contact_bulk_jobs (id, org_id, requested_by, request, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(job.id)
+        .bind(job.org_id)
+        .bind(job.requested_by)
+        .bind(&job.request)
+        .bind(&job.status)
+        .bind(job.created_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find a job of an organization
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<ContactBulkJob>, AppError> {
+        let result = sqlx::query_as::<_, ContactBulkJob>("SELECT * FROM contact_bulk_jobs WHERE id = $1 AND org_id = $2")
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(executor)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Claim the oldest queued job, marking it running
+    ///
+    /// Concurrent workers skip each other's claimed rows.
+    pub async fn claim_next(executor: impl PgExecutor<'_>) -> Result<Option<ContactBulkJob>, AppError> {
+        let result = sqlx::query_as::<_, ContactBulkJob>(
+            r#"
+            UPDATE contact_bulk_jobs
+            SET status = 'running', started_at = NOW()
+            WHERE id = (
+                SELECT id FROM contact_bulk_jobs
+                WHERE status = 'queued'
+                ORDER BY created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Record the outcome of a job that processed its selection
+    pub async fn complete(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        succeeded: i32,
+        failed: i32,
+        failures: JsonValue,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE contact_bulk_jobs
+            SET status = 'completed', total = $2 + $3, succeeded = $2, failed = $3, failures = $4, finished_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(succeeded)
+        .bind(failed)
+        .bind(failures)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record why a job could not run
+    pub async fn fail(executor: impl PgExecutor<'_>, id: Uuid, error: &str) -> Result<(), AppError> {
+        sqlx::query("UPDATE contact_bulk_jobs SET status = 'failed', error = $2, finished_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(error)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+}
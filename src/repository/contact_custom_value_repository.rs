@@ -1,20 +1,25 @@
-use sqlx::PgPool;
+use sqlx::PgExecutor;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::{ContactCustomValue, CustomField};
+use crate::repository::custom_field_repository::CUSTOM_FIELD_COLUMNS;
 
 pub struct ContactCustomValueRepository;
 
 impl ContactCustomValueRepository {
     /// Create a new contact custom value
-    pub async fn create(pool: &PgPool, custom_value: &ContactCustomValue) -> Result<ContactCustomValue, AppError> {
+    ///
+    /// The insert only happens when both the contact and the custom field belong to `org_id`.
+    pub async fn create(executor: impl PgExecutor<'_>, org_id: Uuid, custom_value: &ContactCustomValue) -> Result<ContactCustomValue, AppError> {
         let query = r#"
             INSERT INTO contact_custom_values (
                 id, contact_id, custom_field_id, value, value_json, 
                 value_number, value_date, value_boolean, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+            WHERE EXISTS (SELECT 1 FROM contacts WHERE id = $2 AND org_id = $11)
+              AND EXISTS (SELECT 1 FROM custom_fields WHERE id = $3 AND org_id = $11)
             RETURNING *
         "#;
 
@@ -29,7 +34,8 @@ impl ContactCustomValueRepository {
             .bind(&custom_value.value_boolean)
             .bind(&custom_value.created_at)
             .bind(&custom_value.updated_at)
-            .fetch_one(pool)
+            .bind(org_id)
+            .fetch_one(executor)
             .await;
 
         match result {
@@ -37,6 +43,15 @@ impl ContactCustomValueRepository {
                 tracing::info!("Contact custom value created successfully with ID: {}", custom_value.id);
                 Ok(custom_value)
             }
+            Err(sqlx::Error::RowNotFound) => {
+                tracing::warn!(
+                    "Contact {} or custom field {} not found in organization {}",
+                    custom_value.contact_id,
+                    custom_value.custom_field_id,
+                    org_id
+                );
+                Err(AppError::NotFound("Contact or custom field not found".to_string()))
+            }
             Err(e) => {
                 tracing::error!("Error creating contact custom value: {}", e);
                 Err(AppError::DatabaseError(e))
@@ -44,17 +59,19 @@ impl ContactCustomValueRepository {
         }
     }
 
-    /// Get all custom values for a contact
-    pub async fn find_by_contact_id(pool: &PgPool, contact_id: Uuid) -> Result<Vec<ContactCustomValue>, AppError> {
+    /// Get all custom values for a contact within an organization
+    pub async fn find_by_contact_id(executor: impl PgExecutor<'_>, org_id: Uuid, contact_id: Uuid) -> Result<Vec<ContactCustomValue>, AppError> {
         let query = r#"
-            SELECT * FROM contact_custom_values 
-            WHERE contact_id = $1
-            ORDER BY created_at ASC
+            SELECT ccv.* FROM contact_custom_values ccv
+            JOIN contacts c ON ccv.contact_id = c.id
+            WHERE ccv.contact_id = $1 AND c.org_id = $2
+            ORDER BY ccv.created_at ASC
         "#;
 
         let result = sqlx::query_as::<_, ContactCustomValue>(query)
             .bind(contact_id)
-            .fetch_all(pool)
+            .bind(org_id)
+            .fetch_all(executor)
             .await;
 
         match result {
@@ -66,16 +83,20 @@ impl ContactCustomValueRepository {
         }
     }
 
-    /// Get custom fields for contact module
-    pub async fn get_contact_custom_fields(pool: &PgPool) -> Result<Vec<CustomField>, AppError> {
-        let query = r#"
-            SELECT * FROM custom_fields 
-            WHERE module = 'contact' AND is_active = true
+    /// Get an organization's custom fields for the contact module
+    pub async fn get_contact_custom_fields(executor: impl PgExecutor<'_>, org_id: Uuid) -> Result<Vec<CustomField>, AppError> {
+        let query = format!(
+            r#"
+            SELECT {} FROM custom_fields
+            WHERE module = 'contact' AND org_id = $1 AND is_active = true
             ORDER BY display_order ASC, label ASC
-        "#;
+            "#,
+            CUSTOM_FIELD_COLUMNS
+        );
 
-        let result = sqlx::query_as::<_, CustomField>(query)
-            .fetch_all(pool)
+        let result = sqlx::query_as::<_, CustomField>(&query)
+            .bind(org_id)
+            .fetch_all(executor)
             .await;
 
         match result {
@@ -87,16 +108,20 @@ impl ContactCustomValueRepository {
         }
     }
 
-    /// Find custom field by field name
-    pub async fn find_custom_field_by_name(pool: &PgPool, field_name: &str) -> Result<Option<CustomField>, AppError> {
-        let query = r#"
-            SELECT * FROM custom_fields 
-            WHERE module = 'contact' AND field_name = $1 AND is_active = true
-        "#;
-
-        let result = sqlx::query_as::<_, CustomField>(query)
+    /// Find an organization's custom field by field name
+    pub async fn find_custom_field_by_name(executor: impl PgExecutor<'_>, org_id: Uuid, field_name: &str) -> Result<Option<CustomField>, AppError> {
+        let query = format!(
+            r#"
+            SELECT {} FROM custom_fields
+            WHERE module = 'contact' AND org_id = $1 AND field_name = $2 AND is_active = true
+            "#,
+            CUSTOM_FIELD_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, CustomField>(&query)
+            .bind(org_id)
             .bind(field_name)
-            .fetch_optional(pool)
+            .fetch_optional(executor)
             .await;
 
         match result {
@@ -109,13 +134,17 @@ impl ContactCustomValueRepository {
     }
 
     /// Update or create custom value (upsert)
-    pub async fn upsert(pool: &PgPool, custom_value: &ContactCustomValue) -> Result<ContactCustomValue, AppError> {
+    ///
+    /// The write only happens when both the contact and the custom field belong to `org_id`.
+    pub async fn upsert(executor: impl PgExecutor<'_>, org_id: Uuid, custom_value: &ContactCustomValue) -> Result<ContactCustomValue, AppError> {
         let query = r#"
             INSERT INTO contact_custom_values (
                 id, contact_id, custom_field_id, value, value_json, 
                 value_number, value_date, value_boolean, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+            WHERE EXISTS (SELECT 1 FROM contacts WHERE id = $2 AND org_id = $11)
+              AND EXISTS (SELECT 1 FROM custom_fields WHERE id = $3 AND org_id = $11)
             ON CONFLICT (contact_id, custom_field_id) 
             DO UPDATE SET
                 value = EXCLUDED.value,
@@ -138,7 +167,8 @@ impl ContactCustomValueRepository {
             .bind(&custom_value.value_boolean)
             .bind(&custom_value.created_at)
             .bind(&custom_value.updated_at)
-            .fetch_one(pool)
+            .bind(org_id)
+            .fetch_one(executor)
             .await;
 
         match result {
@@ -146,6 +176,15 @@ impl ContactCustomValueRepository {
                 tracing::info!("Contact custom value upserted successfully with ID: {}", custom_value.id);
                 Ok(custom_value)
             }
+            Err(sqlx::Error::RowNotFound) => {
+                tracing::warn!(
+                    "Contact {} or custom field {} not found in organization {}",
+                    custom_value.contact_id,
+                    custom_value.custom_field_id,
+                    org_id
+                );
+                Err(AppError::NotFound("Contact or custom field not found".to_string()))
+            }
             Err(e) => {
                 tracing::error!("Error upserting contact custom value: {}", e);
                 Err(AppError::DatabaseError(e))
@@ -153,21 +192,24 @@ impl ContactCustomValueRepository {
         }
     }
 
-    /// Delete a custom field value for a specific contact and field
+    /// Delete a custom field value for a specific contact and field within an organization
     pub async fn delete_by_contact_and_field(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
         contact_id: Uuid,
         custom_field_id: Uuid,
     ) -> Result<(), AppError> {
         let query = r#"
             DELETE FROM contact_custom_values
             WHERE contact_id = $1 AND custom_field_id = $2
+              AND contact_id IN (SELECT id FROM contacts WHERE org_id = $3)
         "#;
 
         let result = sqlx::query(query)
             .bind(contact_id)
             .bind(custom_field_id)
-            .execute(pool)
+            .bind(org_id)
+            .execute(executor)
             .await;
 
         match result {
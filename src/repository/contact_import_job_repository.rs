@@ -0,0 +1,135 @@
+// Contact Import Job Repository - Queue of CSV contact imports run by a worker
+
+use serde_json::Value as JsonValue;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::ContactImportJob;
+
+pub struct ContactImportJobRepository;
+
+impl ContactImportJobRepository {
+    /// Queue a job
+    pub async fn create(executor: impl PgExecutor<'_>, job: &ContactImportJob) -> Result<ContactImportJob, AppError> {
+        let result = sqlx::query_as::<_, ContactImportJob>(
+            r#"
+            INSERT INTO contact_import_jobs (id, org_id, requested_by, file_name, content, options, status, total_rows, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+        )
+        .bind(job.id)
+        .bind(job.org_id)
+        .bind(job.requested_by)
+        .bind(&job.file_name)
+        .bind(&job.content)
+        .bind(&job.options)
+        .bind(&job.status)
+        .bind(job.total_rows)
+        .bind(job.created_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find a job of an organization
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<ContactImportJob>, AppError> {
+        let result = sqlx::query_as::<_, ContactImportJob>("SELECT * FROM contact_import_jobs WHERE id = $1 AND org_id = $2")
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(executor)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Claim the oldest queued job, marking it running
+    ///
+    /// Concurrent workers skip each other's claimed rows.
+    pub async fn claim_next(executor: impl PgExecutor<'_>) -> Result<Option<ContactImportJob>, AppError> {
+        let result = sqlx::query_as::<_, ContactImportJob>(
+            r#"
+            UPDATE contact_import_jobs
+            SET status = 'running', started_at = NOW()
+            WHERE id = (
+                SELECT id FROM contact_import_jobs
+                WHERE status = 'queued'
+                ORDER BY created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Store the counts of a running job, as (processed, created, updated, skipped, failed) rows
+    pub async fn update_progress(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        counts: (i32, i32, i32, i32, i32),
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE contact_import_jobs
+            SET processed_rows = $2, created_rows = $3, updated_rows = $4, skipped_rows = $5, failed_rows = $6
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(counts.0)
+        .bind(counts.1)
+        .bind(counts.2)
+        .bind(counts.3)
+        .bind(counts.4)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record the outcome of a job that went through every row, with counts as in `update_progress`
+    pub async fn complete(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        counts: (i32, i32, i32, i32, i32),
+        errors: JsonValue,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE contact_import_jobs
+            SET status = 'completed', total_rows = $2, processed_rows = $2, created_rows = $3, updated_rows = $4,
+                skipped_rows = $5, failed_rows = $6, errors = $7, finished_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(counts.0)
+        .bind(counts.1)
+        .bind(counts.2)
+        .bind(counts.3)
+        .bind(counts.4)
+        .bind(errors)
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record why a job could not run
+    pub async fn fail(executor: impl PgExecutor<'_>, id: Uuid, error: &str) -> Result<(), AppError> {
+        sqlx::query("UPDATE contact_import_jobs SET status = 'failed', error = $2, finished_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(error)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+}
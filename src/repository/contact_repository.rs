@@ -1,21 +1,24 @@
-use sqlx::PgPool;
+use sqlx::PgExecutor;
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::Contact;
 
+/// Unique index on `(org_id, email)`; violating it means the email is taken within the organization
+const ORG_EMAIL_INDEX: &str = "idx_contacts_org_email";
+
 pub struct ContactRepository;
 
 impl ContactRepository {
     /// Create a new contact
-    pub async fn create(pool: &PgPool, contact: &Contact) -> Result<Contact, AppError> {
+    pub async fn create(executor: impl PgExecutor<'_>, contact: &Contact) -> Result<Contact, AppError> {
         let query = r#"
             INSERT INTO contacts (
                 id, first_name, last_name, email, phone, company, job_title,
                 address, city, state, postal_code, country, notes,
-                lead_source, lead_status, owner_id, is_active, created_at, updated_at
+                lead_source, lead_status, owner_id, is_active, created_at, updated_at, org_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
             RETURNING *
         "#;
 
@@ -39,7 +42,8 @@ impl ContactRepository {
             .bind(&contact.is_active)
             .bind(&contact.created_at)
             .bind(&contact.updated_at)
-            .fetch_one(pool)
+            .bind(contact.org_id)
+            .fetch_one(executor)
             .await;
 
         match result {
@@ -47,14 +51,11 @@ impl ContactRepository {
                 tracing::info!("Contact created successfully with ID: {}", contact.id);
                 Ok(contact)
             }
-            Err(sqlx::Error::Database(db_err)) => {
-                if db_err.constraint().is_some() {
-                    tracing::warn!("Contact creation failed - email already exists: {}", contact.email);
-                    Err(AppError::ValidationError("Email already exists".to_string()))
-                } else {
-                    tracing::error!("Database error creating contact: {}", db_err);
-                    Err(AppError::DatabaseError(sqlx::Error::Database(db_err)))
-                }
+            Err(sqlx::Error::Database(db_err))
+                if db_err.code().as_deref() == Some("23505") && db_err.constraint() == Some(ORG_EMAIL_INDEX) =>
+            {
+                tracing::warn!("Contact creation failed - email already exists: {}", contact.email);
+                Err(AppError::Conflict("Email already exists".to_string()))
             }
             Err(e) => {
                 tracing::error!("Error creating contact: {}", e);
@@ -63,13 +64,14 @@ impl ContactRepository {
         }
     }
 
-    /// Find contact by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Contact>, AppError> {
-        let query = "SELECT * FROM contacts WHERE id = $1 AND is_active = true";
+    /// Find contact by ID within an organization
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<Contact>, AppError> {
+        let query = "SELECT * FROM contacts WHERE id = $1 AND org_id = $2 AND is_active = true";
         
         let result = sqlx::query_as::<_, Contact>(query)
             .bind(id)
-            .fetch_optional(pool)
+            .bind(org_id)
+            .fetch_optional(executor)
             .await;
 
         match result {
@@ -81,13 +83,56 @@ impl ContactRepository {
         }
     }
 
-    /// Find contact by email
-    pub async fn find_by_email(pool: &PgPool, email: &str) -> Result<Option<Contact>, AppError> {
-        let query = "SELECT * FROM contacts WHERE email = $1 AND is_active = true";
+    /// Lock a contact of an organization for the rest of the transaction, deleted or not
+    pub async fn lock_by_id(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<Contact>, AppError> {
+        let query = "SELECT * FROM contacts WHERE id = $1 AND org_id = $2 FOR UPDATE";
+
+        let result = sqlx::query_as::<_, Contact>(query)
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(executor)
+            .await;
+
+        match result {
+            Ok(contact) => Ok(contact),
+            Err(e) => {
+                tracing::error!("Error locking contact {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Find contacts of an organization by ID, including deleted ones
+    pub async fn find_by_ids_including_inactive(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        ids: &[Uuid],
+    ) -> Result<Vec<Contact>, AppError> {
+        let query = "SELECT * FROM contacts WHERE id = ANY($1) AND org_id = $2";
+
+        let result = sqlx::query_as::<_, Contact>(query)
+            .bind(ids)
+            .bind(org_id)
+            .fetch_all(executor)
+            .await;
+
+        match result {
+            Ok(contacts) => Ok(contacts),
+            Err(e) => {
+                tracing::error!("Error finding {} contacts by ID: {}", ids.len(), e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Find contact by email within an organization
+    pub async fn find_by_email(executor: impl PgExecutor<'_>, org_id: Uuid, email: &str) -> Result<Option<Contact>, AppError> {
+        let query = "SELECT * FROM contacts WHERE email = $1 AND org_id = $2 AND is_active = true";
         
         let result = sqlx::query_as::<_, Contact>(query)
             .bind(email)
-            .fetch_optional(pool)
+            .bind(org_id)
+            .fetch_optional(executor)
             .await;
 
         match result {
@@ -99,13 +144,14 @@ impl ContactRepository {
         }
     }
 
-    /// Check if email exists
-    pub async fn email_exists(pool: &PgPool, email: &str) -> Result<bool, AppError> {
-        let query = "SELECT EXISTS(SELECT 1 FROM contacts WHERE email = $1 AND is_active = true)";
+    /// Check if email exists within an organization
+    pub async fn email_exists(executor: impl PgExecutor<'_>, org_id: Uuid, email: &str) -> Result<bool, AppError> {
+        let query = "SELECT EXISTS(SELECT 1 FROM contacts WHERE email = $1 AND org_id = $2 AND is_active = true)";
 
         let result = sqlx::query_scalar::<_, bool>(query)
             .bind(email)
-            .fetch_one(pool)
+            .bind(org_id)
+            .fetch_one(executor)
             .await;
 
         match result {
@@ -118,7 +164,7 @@ impl ContactRepository {
     }
 
     /// Update an existing contact
-    pub async fn update(pool: &PgPool, contact: &Contact) -> Result<Contact, AppError> {
+    pub async fn update(executor: impl PgExecutor<'_>, contact: &Contact) -> Result<Contact, AppError> {
         let query = r#"
             UPDATE contacts SET
                 first_name = $2,
@@ -136,7 +182,7 @@ impl ContactRepository {
                 lead_source = $14,
                 lead_status = $15,
                 updated_at = $16
-            WHERE id = $1 AND is_active = true
+            WHERE id = $1 AND org_id = $17 AND is_active = true
             RETURNING *
         "#;
 
@@ -157,7 +203,8 @@ impl ContactRepository {
             .bind(&contact.lead_source)
             .bind(&contact.lead_status)
             .bind(&contact.updated_at)
-            .fetch_one(pool)
+            .bind(contact.org_id)
+            .fetch_one(executor)
             .await;
 
         match result {
@@ -169,6 +216,12 @@ impl ContactRepository {
                 tracing::warn!("No contact found to update with ID: {}", contact.id);
                 Err(AppError::NotFound("Contact not found".to_string()))
             }
+            Err(sqlx::Error::Database(db_err))
+                if db_err.code().as_deref() == Some("23505") && db_err.constraint() == Some(ORG_EMAIL_INDEX) =>
+            {
+                tracing::warn!("Contact update failed - email already exists: {}", contact.email);
+                Err(AppError::Conflict("Email already exists".to_string()))
+            }
             Err(e) => {
                 tracing::error!("Error updating contact {}: {}", contact.id, e);
                 Err(AppError::DatabaseError(e))
@@ -177,16 +230,17 @@ impl ContactRepository {
     }
 
     /// Soft delete contact by setting is_active to false
-    pub async fn soft_delete(pool: &PgPool, contact_id: Uuid) -> Result<(), AppError> {
+    pub async fn soft_delete(executor: impl PgExecutor<'_>, org_id: Uuid, contact_id: Uuid) -> Result<(), AppError> {
         let query = r#"
             UPDATE contacts
             SET is_active = false, updated_at = NOW()
-            WHERE id = $1 AND is_active = true
+            WHERE id = $1 AND org_id = $2 AND is_active = true
         "#;
 
         let result = sqlx::query(query)
             .bind(contact_id)
-            .execute(pool)
+            .bind(org_id)
+            .execute(executor)
             .await;
 
         match result {
@@ -205,4 +259,65 @@ impl ContactRepository {
             }
         }
     }
+
+    /// Hand an active contact to another owner
+    pub async fn set_owner(executor: impl PgExecutor<'_>, org_id: Uuid, contact_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let query = r#"
+            UPDATE contacts
+            SET owner_id = $3, updated_at = NOW()
+            WHERE id = $1 AND org_id = $2 AND is_active = true
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(contact_id)
+            .bind(org_id)
+            .bind(owner_id)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) if query_result.rows_affected() == 0 => {
+                tracing::warn!("No contact found to reassign with ID: {}", contact_id);
+                Err(AppError::NotFound("Contact not found".to_string()))
+            }
+            Ok(_) => {
+                tracing::info!("Contact {} assigned to owner {}", contact_id, owner_id);
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Error changing owner of contact {}: {}", contact_id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Undo a soft delete
+    pub async fn restore(executor: impl PgExecutor<'_>, org_id: Uuid, contact_id: Uuid) -> Result<(), AppError> {
+        let query = r#"
+            UPDATE contacts
+            SET is_active = true, updated_at = NOW()
+            WHERE id = $1 AND org_id = $2 AND is_active = false
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(contact_id)
+            .bind(org_id)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) if query_result.rows_affected() == 0 => {
+                tracing::warn!("No deleted contact found to restore with ID: {}", contact_id);
+                Err(AppError::NotFound("Contact not found".to_string()))
+            }
+            Ok(_) => {
+                tracing::info!("Contact restored successfully with ID: {}", contact_id);
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Error restoring contact {}: {}", contact_id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
 }
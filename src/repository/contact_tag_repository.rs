@@ -0,0 +1,261 @@
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::{ContactTag, ContactTagWithCount};
+
+pub struct ContactTagRepository;
+
+impl ContactTagRepository {
+    /// Create a new tag
+    pub async fn create(executor: impl PgExecutor<'_>, tag: &ContactTag) -> Result<ContactTag, AppError> {
+        let query = r#"
+            INSERT INTO contact_tags (id, org_id, name, color, description, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, org_id, name, color, description, created_at, updated_at
+        "#;
+
+        let result = sqlx::query_as::<_, ContactTag>(query)
+            .bind(tag.id)
+            .bind(tag.org_id)
+            .bind(&tag.name)
+            .bind(&tag.color)
+            .bind(&tag.description)
+            .bind(tag.created_at)
+            .bind(tag.updated_at)
+            .fetch_one(executor)
+            .await;
+
+        match result {
+            Ok(tag) => {
+                tracing::info!("Tag created successfully with ID: {}", tag.id);
+                Ok(tag)
+            }
+            Err(sqlx::Error::Database(db_err)) if db_err.constraint().is_some() => {
+                tracing::warn!("Tag creation failed - name already exists: {}", tag.name);
+                Err(AppError::Conflict("Tag name already exists".to_string()))
+            }
+            Err(e) => {
+                tracing::error!("Error creating tag: {}", e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Find tag by ID within an organization
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<ContactTag>, AppError> {
+        let query = r#"
+            SELECT id, org_id, name, color, description, created_at, updated_at
+            FROM contact_tags
+            WHERE id = $1 AND org_id = $2
+        "#;
+
+        let result = sqlx::query_as::<_, ContactTag>(query)
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(executor)
+            .await;
+
+        match result {
+            Ok(tag) => Ok(tag),
+            Err(e) => {
+                tracing::error!("Error finding tag by ID {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// List all tags of an organization with the number of active contacts carrying each
+    pub async fn find_all_with_counts(executor: impl PgExecutor<'_>, org_id: Uuid) -> Result<Vec<ContactTagWithCount>, AppError> {
+        let query = r#"
+            SELECT
+                t.id, t.org_id, t.name, t.color, t.description, t.created_at, t.updated_at,
+                COUNT(c.id) AS contact_count
+            FROM contact_tags t
+            LEFT JOIN contact_tag_assignments cta ON cta.tag_id = t.id
+            LEFT JOIN contacts c ON c.id = cta.contact_id AND c.is_active = true
+            WHERE t.org_id = $1
+            GROUP BY t.id, t.org_id, t.name, t.color, t.description, t.created_at, t.updated_at
+            ORDER BY t.name
+        "#;
+
+        let result = sqlx::query_as::<_, ContactTagWithCount>(query)
+            .bind(org_id)
+            .fetch_all(executor)
+            .await;
+
+        match result {
+            Ok(tags) => Ok(tags),
+            Err(e) => {
+                tracing::error!("Error listing tags for organization {}: {}", org_id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// List the tags assigned to a contact
+    pub async fn find_by_contact(executor: impl PgExecutor<'_>, org_id: Uuid, contact_id: Uuid) -> Result<Vec<ContactTag>, AppError> {
+        let query = r#"
+            SELECT t.id, t.org_id, t.name, t.color, t.description, t.created_at, t.updated_at
+            FROM contact_tags t
+            JOIN contact_tag_assignments cta ON cta.tag_id = t.id
+            WHERE cta.contact_id = $1 AND t.org_id = $2
+            ORDER BY t.name
+        "#;
+
+        let result = sqlx::query_as::<_, ContactTag>(query)
+            .bind(contact_id)
+            .bind(org_id)
+            .fetch_all(executor)
+            .await;
+
+        match result {
+            Ok(tags) => Ok(tags),
+            Err(e) => {
+                tracing::error!("Error finding tags for contact {}: {}", contact_id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Update an existing tag
+    pub async fn update(executor: impl PgExecutor<'_>, tag: &ContactTag) -> Result<ContactTag, AppError> {
+        let query = r#"
+            UPDATE contact_tags SET
+                name = $3,
+                color = $4,
+                description = $5,
+                updated_at = NOW()
+            WHERE id = $1 AND org_id = $2
+            RETURNING id, org_id, name, color, description, created_at, updated_at
+        "#;
+
+        let result = sqlx::query_as::<_, ContactTag>(query)
+            .bind(tag.id)
+            .bind(tag.org_id)
+            .bind(&tag.name)
+            .bind(&tag.color)
+            .bind(&tag.description)
+            .fetch_optional(executor)
+            .await;
+
+        match result {
+            Ok(Some(tag)) => {
+                tracing::info!("Tag updated successfully with ID: {}", tag.id);
+                Ok(tag)
+            }
+            Ok(None) => Err(AppError::NotFound("Tag not found".to_string())),
+            Err(sqlx::Error::Database(db_err)) if db_err.constraint().is_some() => {
+                tracing::warn!("Tag update failed - name already exists: {}", tag.name);
+                Err(AppError::Conflict("Tag name already exists".to_string()))
+            }
+            Err(e) => {
+                tracing::error!("Error updating tag {}: {}", tag.id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Delete a tag (assignments are removed by the foreign key cascade)
+    pub async fn delete(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let query = "DELETE FROM contact_tags WHERE id = $1 AND org_id = $2";
+
+        let result = sqlx::query(query)
+            .bind(id)
+            .bind(org_id)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) => {
+                if query_result.rows_affected() == 0 {
+                    tracing::warn!("No tag found to delete with ID: {}", id);
+                    Err(AppError::NotFound("Tag not found".to_string()))
+                } else {
+                    tracing::info!("Tag deleted successfully with ID: {}", id);
+                    Ok(())
+                }
+            }
+            Err(e) => {
+                tracing::error!("Error deleting tag {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Assign tags to contacts, skipping pairs that are already assigned
+    ///
+    /// Only active contacts and tags belonging to `org_id` are considered, so IDs from
+    /// other organizations are silently ignored. Returns the number of new assignments.
+    pub async fn assign(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        contact_ids: &[Uuid],
+        tag_ids: &[Uuid],
+        assigned_by: Uuid,
+    ) -> Result<u64, AppError> {
+        let query = r#"
+            INSERT INTO contact_tag_assignments (contact_id, tag_id, assigned_by)
+            SELECT c.id, t.id, $4
+            FROM contacts c
+            JOIN contact_tags t ON t.org_id = c.org_id
+            WHERE c.org_id = $1
+              AND c.is_active = true
+              AND c.id = ANY($2)
+              AND t.id = ANY($3)
+            ON CONFLICT (contact_id, tag_id) DO NOTHING
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(org_id)
+            .bind(contact_ids)
+            .bind(tag_ids)
+            .bind(assigned_by)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) => {
+                tracing::info!("Assigned {} tag(s) in organization {}", query_result.rows_affected(), org_id);
+                Ok(query_result.rows_affected())
+            }
+            Err(e) => {
+                tracing::error!("Error assigning tags: {}", e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Remove tags from contacts. Returns the number of removed assignments.
+    pub async fn unassign(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        contact_ids: &[Uuid],
+        tag_ids: &[Uuid],
+    ) -> Result<u64, AppError> {
+        let query = r#"
+            DELETE FROM contact_tag_assignments
+            WHERE contact_id = ANY($2)
+              AND tag_id = ANY($3)
+              AND tag_id IN (SELECT id FROM contact_tags WHERE org_id = $1)
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(org_id)
+            .bind(contact_ids)
+            .bind(tag_ids)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) => {
+                tracing::info!("Removed {} tag assignment(s) in organization {}", query_result.rows_affected(), org_id);
+                Ok(query_result.rows_affected())
+            }
+            Err(e) => {
+                tracing::error!("Error removing tag assignments: {}", e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use serde_json::Value as JsonValue;
+use sqlx::{PgConnection, PgExecutor};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::{ContactCustomValue, CustomField};
+
+// display_order is INTEGER, which is INT8 on CockroachDB but INT4 on PostgreSQL
+pub(crate) const CUSTOM_FIELD_COLUMNS: &str = r#"
+    id, org_id, module, label, field_name, field_type, is_required, is_active,
+    options, validation_rules, default_value, help_text, display_order::BIGINT AS display_order,
+    created_by, created_at, updated_at
+"#;
+
+/// A stored value rewritten by an options change; `None` values remove the row
+pub struct CustomValueChange {
+    pub id: Uuid,
+    pub value: Option<String>,
+    pub value_json: Option<JsonValue>,
+}
+
+pub struct CustomFieldRepository;
+
+impl CustomFieldRepository {
+    /// Create a new custom field
+    pub async fn create(executor: impl PgExecutor<'_>, custom_field: &CustomField) -> Result<CustomField, AppError> {
+        let query = format!(
+            r#"
+            INSERT INTO custom_fields (
+                id, org_id, module, label, field_name, field_type, is_required, is_active,
+                options, validation_rules, default_value, help_text, display_order,
+                created_by, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            RETURNING {}
+            "#,
+            CUSTOM_FIELD_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, CustomField>(&query)
+            .bind(custom_field.id)
+            .bind(custom_field.org_id)
+            .bind(&custom_field.module)
+            .bind(&custom_field.label)
+            .bind(&custom_field.field_name)
+            .bind(&custom_field.field_type)
+            .bind(custom_field.is_required)
+            .bind(custom_field.is_active)
+            .bind(&custom_field.options)
+            .bind(&custom_field.validation_rules)
+            .bind(&custom_field.default_value)
+            .bind(&custom_field.help_text)
+            .bind(custom_field.display_order)
+            .bind(custom_field.created_by)
+            .bind(custom_field.created_at)
+            .bind(custom_field.updated_at)
+            .fetch_one(executor)
+            .await;
+
+        match result {
+            Ok(custom_field) => {
+                tracing::info!("Custom field created successfully with ID: {}", custom_field.id);
+                Ok(custom_field)
+            }
+            Err(e) => {
+                tracing::error!("Error creating custom field: {}", e);
+                if e.to_string().contains("duplicate key") {
+                    Err(AppError::ValidationError(
+                        "A custom field with this name already exists for this module".to_string(),
+                    ))
+                } else {
+                    Err(AppError::DatabaseError(e))
+                }
+            }
+        }
+    }
+
+    /// Find custom field by ID within an organization
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<CustomField>, AppError> {
+        let query = format!(
+            "SELECT {} FROM custom_fields WHERE id = $1 AND org_id = $2",
+            CUSTOM_FIELD_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, CustomField>(&query)
+            .bind(id)
+            .bind(org_id)
+            .fetch_optional(executor)
+            .await;
+
+        match result {
+            Ok(custom_field) => Ok(custom_field),
+            Err(e) => {
+                tracing::error!("Error finding custom field by ID {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Check whether a field name is taken in a module
+    pub async fn field_exists(executor: impl PgExecutor<'_>, org_id: Uuid, module: &str, field_name: &str) -> Result<bool, AppError> {
+        let query = r#"
+            SELECT EXISTS(
+                SELECT 1 FROM custom_fields WHERE org_id = $1 AND module = $2 AND field_name = $3
+            )
+        "#;
+
+        let result = sqlx::query_scalar::<_, bool>(query)
+            .bind(org_id)
+            .bind(module)
+            .bind(field_name)
+            .fetch_one(executor)
+            .await;
+
+        match result {
+            Ok(exists) => Ok(exists),
+            Err(e) => {
+                tracing::error!("Error checking custom field {}: {}", field_name, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// List a module's custom fields in display order
+    pub async fn find_by_module(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        module: &str,
+        include_inactive: bool,
+    ) -> Result<Vec<CustomField>, AppError> {
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM custom_fields
+            WHERE org_id = $1 AND module = $2 AND ($3 OR is_active = true)
+            ORDER BY display_order ASC, label ASC
+            "#,
+            CUSTOM_FIELD_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, CustomField>(&query)
+            .bind(org_id)
+            .bind(module)
+            .bind(include_inactive)
+            .fetch_all(executor)
+            .await;
+
+        match result {
+            Ok(custom_fields) => Ok(custom_fields),
+            Err(e) => {
+                tracing::error!("Error listing custom fields for module {}: {}", module, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Update the editable attributes of a custom field
+    pub async fn update(executor: impl PgExecutor<'_>, custom_field: &CustomField) -> Result<CustomField, AppError> {
+        let query = format!(
+            r#"
+            UPDATE custom_fields SET
+                label = $3,
+                is_required = $4,
+                is_active = $5,
+                validation_rules = $6,
+                default_value = $7,
+                help_text = $8,
+                display_order = $9,
+                updated_at = NOW()
+            WHERE id = $1 AND org_id = $2
+            RETURNING {}
+            "#,
+            CUSTOM_FIELD_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, CustomField>(&query)
+            .bind(custom_field.id)
+            .bind(custom_field.org_id)
+            .bind(&custom_field.label)
+            .bind(custom_field.is_required)
+            .bind(custom_field.is_active)
+            .bind(&custom_field.validation_rules)
+            .bind(&custom_field.default_value)
+            .bind(&custom_field.help_text)
+            .bind(custom_field.display_order)
+            .fetch_optional(executor)
+            .await;
+
+        match result {
+            Ok(Some(custom_field)) => {
+                tracing::info!("Custom field updated successfully with ID: {}", custom_field.id);
+                Ok(custom_field)
+            }
+            Ok(None) => Err(AppError::NotFound("Custom field not found".to_string())),
+            Err(e) => {
+                tracing::error!("Error updating custom field {}: {}", custom_field.id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Set `display_order` to each field's 1-based position in `field_ids`
+    pub async fn reorder(executor: impl PgExecutor<'_>, org_id: Uuid, module: &str, field_ids: &[Uuid]) -> Result<u64, AppError> {
+        let query = r#"
+            UPDATE custom_fields cf
+            SET display_order = ordered.position, updated_at = NOW()
+            FROM (
+                SELECT id, ordinality AS position
+                FROM UNNEST($3::UUID[]) WITH ORDINALITY AS t(id, ordinality)
+            ) ordered
+            WHERE cf.id = ordered.id AND cf.org_id = $1 AND cf.module = $2
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(org_id)
+            .bind(module)
+            .bind(field_ids)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) => {
+                tracing::info!("Reordered {} custom fields in module {}", query_result.rows_affected(), module);
+                Ok(query_result.rows_affected())
+            }
+            Err(e) => {
+                tracing::error!("Error reordering custom fields in module {}: {}", module, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Hard-delete a custom field; its stored values cascade
+    pub async fn delete(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM custom_fields WHERE id = $1 AND org_id = $2")
+            .bind(id)
+            .bind(org_id)
+            .execute(executor)
+            .await;
+
+        match result {
+            Ok(query_result) => {
+                if query_result.rows_affected() == 0 {
+                    tracing::warn!("No custom field found to delete with ID: {}", id);
+                    Err(AppError::NotFound("Custom field not found".to_string()))
+                } else {
+                    tracing::info!("Custom field deleted successfully with ID: {}", id);
+                    Ok(())
+                }
+            }
+            Err(e) => {
+                tracing::error!("Error deleting custom field {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Count values stored for a custom field
+    pub async fn count_values(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<i64, AppError> {
+        let query = r#"
+            SELECT COUNT(*)
+            FROM contact_custom_values ccv
+            JOIN custom_fields cf ON ccv.custom_field_id = cf.id
+            WHERE cf.id = $1 AND cf.org_id = $2
+        "#;
+
+        let result = sqlx::query_scalar::<_, i64>(query)
+            .bind(id)
+            .bind(org_id)
+            .fetch_one(executor)
+            .await;
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(e) => {
+                tracing::error!("Error counting values of custom field {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// All values stored for a custom field
+    pub async fn find_values(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Vec<ContactCustomValue>, AppError> {
+        let query = r#"
+            SELECT ccv.*
+            FROM contact_custom_values ccv
+            JOIN custom_fields cf ON ccv.custom_field_id = cf.id
+            WHERE cf.id = $1 AND cf.org_id = $2
+        "#;
+
+        let result = sqlx::query_as::<_, ContactCustomValue>(query)
+            .bind(id)
+            .bind(org_id)
+            .fetch_all(executor)
+            .await;
+
+        match result {
+            Ok(values) => Ok(values),
+            Err(e) => {
+                tracing::error!("Error finding values of custom field {}: {}", id, e);
+                Err(AppError::DatabaseError(e))
+            }
+        }
+    }
+
+    /// Replace a select field's options and rewrite its stored values
+    pub async fn replace_options(
+        conn: &mut PgConnection,
+        custom_field: &CustomField,
+        changes: &[CustomValueChange],
+    ) -> Result<CustomField, AppError> {
+        for change in changes {
+            let result = match (&change.value, &change.value_json) {
+                (None, None) => {
+                    sqlx::query("DELETE FROM contact_custom_values WHERE id = $1 AND custom_field_id = $2")
+                        .bind(change.id)
+                        .bind(custom_field.id)
+                        .execute(&mut *conn)
+                        .await
+                }
+                _ => {
+                    sqlx::query(
+                        r#"
+                        UPDATE contact_custom_values
+                        SET value = $3, value_json = $4, updated_at = NOW()
+                        WHERE id = $1 AND custom_field_id = $2
+                        "#,
+                    )
+                    .bind(change.id)
+                    .bind(custom_field.id)
+                    .bind(&change.value)
+                    .bind(&change.value_json)
+                    .execute(&mut *conn)
+                    .await
+                }
+            };
+
+            if let Err(e) = result {
+                tracing::error!("Error migrating value {} of custom field {}: {}", change.id, custom_field.id, e);
+                return Err(AppError::DatabaseError(e));
+            }
+        }
+
+        let query = format!(
+            r#"
+            UPDATE custom_fields SET options = $3, updated_at = NOW()
+            WHERE id = $1 AND org_id = $2
+            RETURNING {}
+            "#,
+            CUSTOM_FIELD_COLUMNS
+        );
+
+        let result = sqlx::query_as::<_, CustomField>(&query)
+            .bind(custom_field.id)
+            .bind(custom_field.org_id)
+            .bind(&custom_field.options)
+            .fetch_optional(&mut *conn)
+            .await;
+
+        let updated_field = match result {
+            Ok(Some(updated_field)) => updated_field,
+            Ok(None) => return Err(AppError::NotFound("Custom field not found".to_string())),
+            Err(e) => {
+                tracing::error!("Error updating options of custom field {}: {}", custom_field.id, e);
+                return Err(AppError::DatabaseError(e));
+            }
+        };
+
+        tracing::info!(
+            "Replaced options of custom field {} and migrated {} values",
+            updated_field.id,
+            changes.len()
+        );
+        Ok(updated_field)
+    }
+}
@@ -0,0 +1,61 @@
+// Email Outbox Repository - Queue emails for asynchronous delivery
+
+use serde_json::Value as JsonValue;
+use sqlx::PgExecutor;
+
+use crate::errors::AppError;
+use crate::models::OutboxEmail;
+
+pub struct EmailOutboxRepository;
+
+impl EmailOutboxRepository {
+    /// Queue an email; a mail worker delivers pending rows
+    pub async fn enqueue(
+        executor: impl PgExecutor<'_>,
+        recipient: &str,
+        template: &str,
+        subject: &str,
+        payload: JsonValue,
+    ) -> Result<OutboxEmail, AppError> {
+        let result = sqlx::query_as::<_, OutboxEmail>(
+            r#"
+            INSERT INTO email_outbox (recipient, template, subject, payload)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(recipient)
+        .bind(template)
+        .bind(subject)
+        .bind(payload)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Claim up to `limit` pending emails, oldest first, marking them sent
+    ///
+    /// Used by the local stand-in worker, whose delivery (printing) cannot fail.
+    pub async fn take_pending(executor: impl PgExecutor<'_>, limit: i64) -> Result<Vec<OutboxEmail>, AppError> {
+        let results = sqlx::query_as::<_, OutboxEmail>(
+            r#"
+            UPDATE email_outbox
+            SET status = 'sent', attempts = attempts + 1, sent_at = NOW()
+            WHERE id IN (
+                SELECT id FROM email_outbox
+                WHERE status = 'pending'
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(results)
+    }
+}
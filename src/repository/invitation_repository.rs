@@ -0,0 +1,162 @@
+// Invitation Repository - Database operations for organization invitations
+
+use chrono::{DateTime, Utc};
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::{Invitation, INVITATION_PENDING};
+
+pub struct InvitationRepository;
+
+impl InvitationRepository {
+    /// Insert a new invitation
+    pub async fn create(executor: impl PgExecutor<'_>, invitation: &Invitation) -> Result<Invitation, AppError> {
+        let result = sqlx::query_as::<_, Invitation>(
+            r#"
+            INSERT INTO organization_invitations (
+                id, org_id, email, role_id, invited_by, token_hash, status, expires_at, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(invitation.id)
+        .bind(invitation.org_id)
+        .bind(&invitation.email)
+        .bind(invitation.role_id)
+        .bind(invitation.invited_by)
+        .bind(&invitation.token_hash)
+        .bind(&invitation.status)
+        .bind(invitation.expires_at)
+        .bind(invitation.created_at)
+        .bind(invitation.updated_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find an invitation of an organization
+    pub async fn find_by_id_for_org(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<Invitation>, AppError> {
+        let result = sqlx::query_as::<_, Invitation>(
+            "SELECT * FROM organization_invitations WHERE id = $1 AND org_id = $2",
+        )
+        .bind(id)
+        .bind(org_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find the invitation a link token belongs to
+    pub async fn find_by_token_hash(executor: impl PgExecutor<'_>, token_hash: &str) -> Result<Option<Invitation>, AppError> {
+        let result = sqlx::query_as::<_, Invitation>(
+            "SELECT * FROM organization_invitations WHERE token_hash = $1",
+        )
+        .bind(token_hash)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find the pending invitation for an email, if any (there is at most one per organization)
+    pub async fn find_pending_by_email(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        email: &str,
+    ) -> Result<Option<Invitation>, AppError> {
+        let result = sqlx::query_as::<_, Invitation>(
+            "SELECT * FROM organization_invitations WHERE org_id = $1 AND email = $2 AND status = $3",
+        )
+        .bind(org_id)
+        .bind(email)
+        .bind(INVITATION_PENDING)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Invitations of an organization, newest first, optionally filtered by status
+    pub async fn find_all_for_org(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        status: Option<&str>,
+    ) -> Result<Vec<Invitation>, AppError> {
+        let results = sqlx::query_as::<_, Invitation>(
+            r#"
+            SELECT *
+            FROM organization_invitations
+            WHERE org_id = $1 AND ($2::text IS NULL OR status = $2)
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(org_id)
+        .bind(status)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Replace the token of a pending invitation and push back its expiry
+    ///
+    /// Links carrying the previous token stop working. Returns `None` if the invitation is no longer pending.
+    pub async fn rotate_token(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<Invitation>, AppError> {
+        let result = sqlx::query_as::<_, Invitation>(
+            r#"
+            UPDATE organization_invitations
+            SET token_hash = $2, expires_at = $3, updated_at = NOW()
+            WHERE id = $1 AND status = $4
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .bind(INVITATION_PENDING)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Move a pending invitation to `status`, recording who accepted it
+    ///
+    /// Returns `None` if the invitation was no longer pending, so concurrent responses cannot both succeed.
+    pub async fn respond(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        status: &str,
+        accepted_by: Option<Uuid>,
+    ) -> Result<Option<Invitation>, AppError> {
+        let result = sqlx::query_as::<_, Invitation>(
+            r#"
+            UPDATE organization_invitations
+            SET status = $2, accepted_by = $3, responded_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status = $4
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(accepted_by)
+        .bind(INVITATION_PENDING)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+}
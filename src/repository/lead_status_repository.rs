@@ -0,0 +1,51 @@
+// Lead Status Repository - Database operations for an organization's sales pipeline
+
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::LeadStatus;
+
+pub struct LeadStatusRepository;
+
+impl LeadStatusRepository {
+    /// Add a lead status to an organization's pipeline
+    pub async fn create(executor: impl PgExecutor<'_>, lead_status: &LeadStatus) -> Result<LeadStatus, AppError> {
+        let result = sqlx::query_as::<_, LeadStatus>(
+            r#"
+            INSERT INTO lead_statuses (id, org_id, name, label, display_order, is_closed, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, org_id, name, label, display_order, is_closed, created_at, updated_at
+            "#,
+        )
+        .bind(lead_status.id)
+        .bind(lead_status.org_id)
+        .bind(&lead_status.name)
+        .bind(&lead_status.label)
+        .bind(lead_status.display_order)
+        .bind(lead_status.is_closed)
+        .bind(lead_status.created_at)
+        .bind(lead_status.updated_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// An organization's lead statuses in pipeline order
+    pub async fn find_by_org(executor: impl PgExecutor<'_>, org_id: Uuid) -> Result<Vec<LeadStatus>, AppError> {
+        let result = sqlx::query_as::<_, LeadStatus>(
+            r#"
+            SELECT id, org_id, name, label, display_order, is_closed, created_at, updated_at
+            FROM lead_statuses
+            WHERE org_id = $1
+            ORDER BY display_order, name
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,104 @@
+// Login Throttle Repository - Database operations for failed login tracking
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgExecutor;
+
+use crate::errors::AppError;
+use crate::models::LoginThrottle;
+
+pub struct LoginThrottleRepository;
+
+impl LoginThrottleRepository {
+    /// Throttles of the given subjects that are locked right now
+    pub async fn find_locked(
+        executor: impl PgExecutor<'_>,
+        subjects: &[(&str, String)],
+    ) -> Result<Vec<LoginThrottle>, AppError> {
+        let (scopes, subjects): (Vec<&str>, Vec<&str>) =
+            subjects.iter().map(|(scope, subject)| (*scope, subject.as_str())).unzip();
+
+        let result = sqlx::query_as::<_, LoginThrottle>(
+            r#"
+            SELECT t.*
+            FROM login_throttles t
+            JOIN UNNEST($1::text[], $2::text[]) AS s(scope, subject)
+                ON t.scope = s.scope AND t.subject = s.subject
+            WHERE t.locked_until > NOW()
+            "#,
+        )
+        .bind(scopes)
+        .bind(subjects)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Count a failed login and return the updated throttle
+    ///
+    /// The count starts over when the previous failure, and any lockout it caused, ended more than
+    /// `window` ago.
+    pub async fn record_failure(
+        executor: impl PgExecutor<'_>,
+        scope: &str,
+        subject: &str,
+        window: Duration,
+    ) -> Result<LoginThrottle, AppError> {
+        let result = sqlx::query_as::<_, LoginThrottle>(
+            r#"
+            INSERT INTO login_throttles (scope, subject, failed_attempts, last_failed_at)
+            VALUES ($1, $2, 1, NOW())
+            ON CONFLICT (scope, subject) DO UPDATE SET
+                failed_attempts = CASE
+                    WHEN GREATEST(login_throttles.last_failed_at, login_throttles.locked_until)
+                        < NOW() - make_interval(secs => $3)
+                    THEN 1
+                    ELSE login_throttles.failed_attempts + 1
+                END,
+                last_failed_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(scope)
+        .bind(subject)
+        .bind(window.num_seconds() as f64)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Refuse logins for a subject until the given time
+    pub async fn lock(
+        executor: impl PgExecutor<'_>,
+        scope: &str,
+        subject: &str,
+        locked_until: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        sqlx::query("UPDATE login_throttles SET locked_until = $3 WHERE scope = $1 AND subject = $2")
+            .bind(scope)
+            .bind(subject)
+            .bind(locked_until)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Forget a subject's failures and lift its lockout, returning whether it was locked
+    pub async fn clear(executor: impl PgExecutor<'_>, scope: &str, subject: &str) -> Result<bool, AppError> {
+        let was_locked = sqlx::query_scalar::<_, bool>(
+            r#"
+            DELETE FROM login_throttles
+            WHERE scope = $1 AND subject = $2
+            RETURNING COALESCE(locked_until > NOW(), false)
+            "#,
+        )
+        .bind(scope)
+        .bind(subject)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(was_locked.unwrap_or(false))
+    }
+}
@@ -1,14 +1,42 @@
 // Repository module - Data access layer
+pub mod audit_log_repository;
 pub mod contact_repository;
+pub mod contact_activity_repository;
+pub mod contact_bulk_job_repository;
 pub mod contact_custom_value_repository;
+pub mod contact_import_job_repository;
+pub mod contact_tag_repository;
+pub mod custom_field_repository;
+pub mod email_outbox_repository;
+pub mod invitation_repository;
+pub mod lead_status_repository;
+pub mod login_throttle_repository;
 pub mod organization_repository;
+pub mod password_history_repository;
 pub mod role_repository;
 pub mod user_organization_repository;
 pub mod user_repository;
+pub mod user_mfa_repository;
+pub mod user_session_repository;
+pub mod user_token_repository;
 
+pub use audit_log_repository::*;
 pub use contact_repository::*;
+pub use contact_activity_repository::*;
+pub use contact_bulk_job_repository::*;
 pub use contact_custom_value_repository::*;
+pub use contact_import_job_repository::*;
+pub use contact_tag_repository::*;
+pub use custom_field_repository::*;
+pub use email_outbox_repository::*;
+pub use invitation_repository::*;
+pub use lead_status_repository::*;
+pub use login_throttle_repository::*;
 pub use organization_repository::*;
+pub use password_history_repository::*;
 pub use role_repository::*;
 pub use user_organization_repository::*;
 pub use user_repository::*;
+pub use user_mfa_repository::*;
+pub use user_session_repository::*;
+pub use user_token_repository::*;
@@ -1,16 +1,17 @@
 // Organization Repository - Database operations for organizations
 
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, PgConnection, PgExecutor};
 use uuid::Uuid;
 
 use crate::errors::AppError;
-use crate::models::Organization;
+use crate::models::{Organization, OrganizationSettings, INVITATION_PENDING, INVITATION_REVOKED};
 
 pub struct OrganizationRepository;
 
 impl OrganizationRepository {
     /// Create organization table if it doesn't exist
-    pub async fn create_table(pool: &PgPool) -> Result<(), AppError> {
+    pub async fn create_table(executor: impl PgExecutor<'_>) -> Result<(), AppError> {
         let query = r#"
             CREATE TABLE IF NOT EXISTS organization (
                 id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
@@ -21,7 +22,7 @@ impl OrganizationRepository {
             )
         "#;
 
-        sqlx::query(query).execute(pool).await?;
+        sqlx::query(query).execute(executor).await?;
         
         tracing::info!("Organization table created successfully");
         
@@ -29,54 +30,205 @@ impl OrganizationRepository {
     }
 
     /// Insert a new organization into the database
-    pub async fn create(pool: &PgPool, name: String, country: Option<String>, timezone: Option<String>) -> Result<Organization, AppError> {
-        let result = sqlx::query_as!(
-            Organization,
+    pub async fn create(executor: impl PgExecutor<'_>, organization: &Organization) -> Result<Organization, AppError> {
+        let result = sqlx::query_as::<_, Organization>(
             r#"
-            INSERT INTO organization (name, country, timezone)
-            VALUES ($1, $2, $3)
+            INSERT INTO organization (id, name, country, timezone, created_at)
+            VALUES ($1, $2, $3, $4, $5)
             RETURNING id, name, country, timezone, created_at
             "#,
-            name.trim(),
-            country,
-            timezone
         )
-        .fetch_one(pool)
+        .bind(organization.id)
+        .bind(&organization.name)
+        .bind(&organization.country)
+        .bind(&organization.timezone)
+        .bind(organization.created_at)
+        .fetch_one(executor)
         .await?;
 
         Ok(result)
     }
 
-    /// Find organization by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Organization>, AppError> {
-        let result = sqlx::query_as!(
-            Organization,
+    /// Find organization by ID; deleted organizations are not found
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<Organization>, AppError> {
+        let result = sqlx::query_as::<_, Organization>(
             r#"
             SELECT id, name, country, timezone, created_at
             FROM organization
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
-            id
         )
-        .fetch_optional(pool)
+        .bind(id)
+        .fetch_optional(executor)
         .await?;
 
         Ok(result)
     }
 
-    /// Get all organizations
-    pub async fn find_all(pool: &PgPool) -> Result<Vec<Organization>, AppError> {
-        let results = sqlx::query_as!(
-            Organization,
+    /// Get all organizations that are not deleted
+    pub async fn find_all(executor: impl PgExecutor<'_>) -> Result<Vec<Organization>, AppError> {
+        let results = sqlx::query_as::<_, Organization>(
             r#"
             SELECT id, name, country, timezone, created_at
             FROM organization
+            WHERE deleted_at IS NULL
             ORDER BY created_at DESC
-            "#
+            "#,
         )
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         Ok(results)
     }
+
+    /// Replace an organization's details; `None` if it does not exist or is deleted
+    pub async fn update(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        name: &str,
+        country: Option<String>,
+        timezone: Option<String>,
+    ) -> Result<Option<Organization>, AppError> {
+        let result = sqlx::query_as::<_, Organization>(
+            r#"
+            UPDATE organization
+            SET name = $2, country = $3, timezone = $4, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING id, name, country, timezone, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(country)
+        .bind(timezone)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// The organization's settings document; `None` if it does not exist or is deleted
+    pub async fn find_settings(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<OrganizationSettings>, AppError> {
+        let result = sqlx::query_scalar::<_, Json<OrganizationSettings>>(
+            "SELECT settings FROM organization WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result.map(|settings| settings.0))
+    }
+
+    /// Replace the organization's settings document, returning the stored one
+    pub async fn update_settings(
+        executor: impl PgExecutor<'_>,
+        id: Uuid,
+        settings: &OrganizationSettings,
+    ) -> Result<Option<OrganizationSettings>, AppError> {
+        let result = sqlx::query_scalar::<_, Json<OrganizationSettings>>(
+            r#"
+            UPDATE organization
+            SET settings = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING settings
+            "#,
+        )
+        .bind(id)
+        .bind(Json(settings))
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result.map(|settings| settings.0))
+    }
+
+    /// Mark an organization deleted until `purge_after`, returning when it was deleted
+    ///
+    /// Its pending invitations are revoked as well. `None` if it does not exist or is already deleted.
+    pub async fn soft_delete(
+        conn: &mut PgConnection,
+        id: Uuid,
+        purge_after: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, AppError> {
+        let deleted_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            r#"
+            UPDATE organization
+            SET deleted_at = NOW(), purge_after = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING deleted_at
+            "#,
+        )
+        .bind(id)
+        .bind(purge_after)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        if deleted_at.is_some() {
+            sqlx::query(
+                r#"
+                UPDATE organization_invitations
+                SET status = $2, responded_at = NOW(), updated_at = NOW()
+                WHERE org_id = $1 AND status = $3
+                "#,
+            )
+            .bind(id)
+            .bind(INVITATION_REVOKED)
+            .bind(INVITATION_PENDING)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(deleted_at)
+    }
+
+    /// Undo a deletion whose grace period has not run out; false if there is none
+    pub async fn restore(executor: impl PgExecutor<'_>, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE organization
+            SET deleted_at = NULL, purge_after = NULL, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NOT NULL AND purge_after > NOW()
+            "#,
+        )
+        .bind(id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Permanently delete organizations whose grace period has run out, returning their IDs
+    ///
+    /// Memberships, roles, invitations and all CRM data go with them through `ON DELETE CASCADE`.
+    pub async fn purge_deleted(executor: impl PgExecutor<'_>) -> Result<Vec<Uuid>, AppError> {
+        let result = sqlx::query_scalar::<_, Uuid>(
+            "DELETE FROM organization WHERE deleted_at IS NOT NULL AND purge_after <= NOW() RETURNING id",
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Whether the organization requires MFA for its most privileged roles; `None` if it does not exist
+    pub async fn find_require_mfa(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<bool>, AppError> {
+        let result = sqlx::query_scalar::<_, bool>("SELECT require_mfa FROM organization WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(executor)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Turn the MFA requirement on or off, returning the stored value; `None` if the organization does not exist
+    pub async fn update_require_mfa(executor: impl PgExecutor<'_>, id: Uuid, require_mfa: bool) -> Result<Option<bool>, AppError> {
+        let result = sqlx::query_scalar::<_, bool>(
+            "UPDATE organization SET require_mfa = $2, updated_at = NOW() WHERE id = $1 AND deleted_at IS NULL RETURNING require_mfa",
+        )
+        .bind(id)
+        .bind(require_mfa)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
 }
@@ -0,0 +1,57 @@
+// Password History Repository - Database operations for previously used password hashes
+
+use sqlx::{PgConnection, PgExecutor};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+pub struct PasswordHistoryRepository;
+
+impl PasswordHistoryRepository {
+    /// Hashes of a user's most recent passwords, newest first
+    pub async fn find_recent(executor: impl PgExecutor<'_>, user_id: Uuid, limit: usize) -> Result<Vec<String>, AppError> {
+        let result = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT password_hash
+            FROM password_history
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Remember a newly set password, keeping only the `keep` most recent entries
+    pub async fn record(conn: &mut PgConnection, user_id: Uuid, password_hash: &str, keep: usize) -> Result<(), AppError> {
+        sqlx::query("INSERT INTO password_history (user_id, password_hash) VALUES ($1, $2)")
+            .bind(user_id)
+            .bind(password_hash)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM password_history
+            WHERE user_id = $1
+              AND id NOT IN (
+                  SELECT id FROM password_history
+                  WHERE user_id = $1
+                  ORDER BY created_at DESC
+                  LIMIT $2
+              )
+            "#,
+        )
+        .bind(user_id)
+        .bind(keep as i64)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+}
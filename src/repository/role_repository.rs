@@ -1,6 +1,6 @@
 // Role Repository - Database operations for roles
 
-use sqlx::{PgPool, Row};
+use sqlx::PgExecutor;
 use uuid::Uuid;
 
 use crate::errors::AppError;
@@ -10,149 +10,161 @@ pub struct RoleRepository;
 
 impl RoleRepository {
     /// Find role by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Role>, AppError> {
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<Role>, AppError> {
         let result = sqlx::query_as::<_, Role>(
             r#"
-            SELECT id, name, description, permissions, created_at, updated_at
+            SELECT id, org_id, name, description, permissions, created_at, updated_at
             FROM roles
             WHERE id = $1
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?;
 
         Ok(result)
     }
 
-    /// Find role by name
-    pub async fn find_by_name(pool: &PgPool, name: &str) -> Result<Option<Role>, AppError> {
+    /// Find a role visible to an organization (built-in or its own custom role) by ID
+    pub async fn find_by_id_for_org(executor: impl PgExecutor<'_>, org_id: Uuid, id: Uuid) -> Result<Option<Role>, AppError> {
         let result = sqlx::query_as::<_, Role>(
             r#"
-            SELECT id, name, description, permissions, created_at, updated_at
+            SELECT id, org_id, name, description, permissions, created_at, updated_at
             FROM roles
-            WHERE name = $1
+            WHERE id = $1 AND (org_id IS NULL OR org_id = $2)
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find a role visible to an organization by name
+    ///
+    /// Custom role names never shadow built-in ones, so at most one role matches.
+    pub async fn find_by_name(executor: impl PgExecutor<'_>, org_id: Uuid, name: &str) -> Result<Option<Role>, AppError> {
+        let result = sqlx::query_as::<_, Role>(
+            r#"
+            SELECT id, org_id, name, description, permissions, created_at, updated_at
+            FROM roles
+            WHERE name = $1 AND (org_id IS NULL OR org_id = $2)
             "#,
         )
         .bind(name)
-        .fetch_optional(pool)
+        .bind(org_id)
+        .fetch_optional(executor)
         .await?;
 
         Ok(result)
     }
 
-    /// Get all roles
-    pub async fn find_all(pool: &PgPool) -> Result<Vec<Role>, AppError> {
+    /// Get all built-in roles
+    pub async fn find_all(executor: impl PgExecutor<'_>) -> Result<Vec<Role>, AppError> {
         let results = sqlx::query_as::<_, Role>(
             r#"
-            SELECT id, name, description, permissions, created_at, updated_at
+            SELECT id, org_id, name, description, permissions, created_at, updated_at
             FROM roles
+            WHERE org_id IS NULL
             ORDER BY name
             "#,
         )
-        .fetch_all(pool)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(results)
+    }
+
+    /// Get the built-in roles followed by the organization's custom roles
+    pub async fn find_all_for_org(executor: impl PgExecutor<'_>, org_id: Uuid) -> Result<Vec<Role>, AppError> {
+        let results = sqlx::query_as::<_, Role>(
+            r#"
+            SELECT id, org_id, name, description, permissions, created_at, updated_at
+            FROM roles
+            WHERE org_id IS NULL OR org_id = $1
+            ORDER BY org_id NULLS FIRST, name
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(executor)
         .await?;
 
         Ok(results)
     }
 
-    /// Create a new role
+    /// Create a new role; `org_id` is `None` only for built-in roles
     pub async fn create(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
+        org_id: Option<Uuid>,
         name: String,
         description: Option<String>,
         permissions: serde_json::Value,
     ) -> Result<Role, AppError> {
         let result = sqlx::query_as::<_, Role>(
             r#"
-            INSERT INTO roles (name, description, permissions)
-            VALUES ($1, $2, $3)
-            RETURNING id, name, description, permissions, created_at, updated_at
+            INSERT INTO roles (org_id, name, description, permissions)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, org_id, name, description, permissions, created_at, updated_at
             "#,
         )
+        .bind(org_id)
         .bind(name)
         .bind(description)
         .bind(permissions)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(result)
     }
 
-    /// Update role
+    /// Update role; fields left as `None` keep their current value
     pub async fn update(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         id: Uuid,
         name: Option<String>,
         description: Option<String>,
         permissions: Option<serde_json::Value>,
     ) -> Result<Role, AppError> {
-        let mut query = "UPDATE roles SET updated_at = NOW()".to_string();
-        let mut param_count = 1;
-        let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send + Sync>> = vec![];
-
-        if let Some(name) = name {
-            query.push_str(&format!(", name = ${}", param_count));
-            params.push(Box::new(name));
-            param_count += 1;
-        }
-
-        if let Some(description) = description {
-            query.push_str(&format!(", description = ${}", param_count));
-            params.push(Box::new(description));
-            param_count += 1;
-        }
-
-        if let Some(permissions) = permissions {
-            query.push_str(&format!(", permissions = ${}", param_count));
-            params.push(Box::new(permissions));
-            param_count += 1;
-        }
-
-        query.push_str(&format!(" WHERE id = ${} RETURNING id, name, description, permissions, created_at, updated_at", param_count));
-
-        // For simplicity, let's use a more straightforward approach
-        let result = if let (Some(name), Some(desc), Some(perms)) = (
-            params.get(0).and_then(|_| Some("name")),
-            params.get(1).and_then(|_| Some("desc")),
-            params.get(2).and_then(|_| Some("perms"))
-        ) {
-            // This is a simplified version - in production you'd want to handle dynamic queries properly
-            sqlx::query_as::<_, Role>(
-                r#"
-                UPDATE roles 
-                SET updated_at = NOW()
-                WHERE id = $1
-                RETURNING id, name, description, permissions, created_at, updated_at
-                "#,
-            )
-            .bind(id)
-            .fetch_one(pool)
-            .await?
-        } else {
-            sqlx::query_as::<_, Role>(
-                r#"
-                UPDATE roles 
-                SET updated_at = NOW()
-                WHERE id = $1
-                RETURNING id, name, description, permissions, created_at, updated_at
-                "#,
-            )
-            .bind(id)
-            .fetch_one(pool)
-            .await?
-        };
+        let result = sqlx::query_as::<_, Role>(
+            r#"
+            UPDATE roles
+            SET name = COALESCE($2, name),
+                description = COALESCE($3, description),
+                permissions = COALESCE($4, permissions),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, org_id, name, description, permissions, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(description)
+        .bind(permissions)
+        .fetch_one(executor)
+        .await?;
 
         Ok(result)
     }
 
     /// Delete role
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    pub async fn delete(executor: impl PgExecutor<'_>, id: Uuid) -> Result<(), AppError> {
         sqlx::query("DELETE FROM roles WHERE id = $1")
             .bind(id)
-            .execute(pool)
+            .execute(executor)
             .await?;
 
         Ok(())
     }
+
+    /// Number of memberships (of any status) that reference the role
+    pub async fn count_members(executor: impl PgExecutor<'_>, id: Uuid) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM user_organizations WHERE role_id = $1")
+            .bind(id)
+            .fetch_one(executor)
+            .await?;
+
+        Ok(count)
+    }
 }
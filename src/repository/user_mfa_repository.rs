@@ -0,0 +1,151 @@
+// User MFA Repository - Database operations for TOTP enrollments and recovery codes
+
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::UserMfa;
+
+pub struct UserMfaRepository;
+
+impl UserMfaRepository {
+    pub async fn find(executor: impl PgExecutor<'_>, user_id: Uuid) -> Result<Option<UserMfa>, AppError> {
+        let result = sqlx::query_as::<_, UserMfa>("SELECT * FROM user_mfa WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(executor)
+            .await?;
+
+        Ok(result)
+    }
+
+    /// Store a new secret awaiting confirmation, replacing an unconfirmed one
+    ///
+    /// Returns `None` when the user already has MFA enabled, which is left untouched.
+    pub async fn start_enrollment(executor: impl PgExecutor<'_>, user_id: Uuid, secret: &str) -> Result<Option<UserMfa>, AppError> {
+        let result = sqlx::query_as::<_, UserMfa>(
+            r#"
+            INSERT INTO user_mfa (user_id, secret)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET
+                secret = EXCLUDED.secret,
+                last_used_step = NULL,
+                created_at = NOW(),
+                updated_at = NOW()
+            WHERE user_mfa.enabled_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(secret)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Accept a code's time step if it is newer than the last one used, returning whether it was
+    ///
+    /// Checking and recording in one statement stops the same code from being used twice concurrently.
+    pub async fn use_step(executor: impl PgExecutor<'_>, user_id: Uuid, step: i64) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_mfa
+            SET last_used_step = $2, updated_at = NOW()
+            WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Confirm an enrollment so it protects future logins
+    pub async fn enable(executor: impl PgExecutor<'_>, user_id: Uuid) -> Result<UserMfa, AppError> {
+        let result = sqlx::query_as::<_, UserMfa>(
+            "UPDATE user_mfa SET enabled_at = NOW(), updated_at = NOW() WHERE user_id = $1 RETURNING *",
+        )
+        .bind(user_id)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Remove the enrollment and its recovery codes
+    pub async fn delete(pool: &PgPool, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM mfa_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+        sqlx::query("DELETE FROM user_mfa WHERE user_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replace every recovery code of a user with new ones
+    pub async fn replace_recovery_codes(pool: &PgPool, user_id: Uuid, code_hashes: &[String]) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM mfa_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO mfa_recovery_codes (user_id, code_hash)
+            SELECT $1, code_hash FROM UNNEST($2::text[]) AS code_hash
+            "#,
+        )
+        .bind(user_id)
+        .bind(code_hashes)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Use up an unused recovery code, returning whether one matched
+    pub async fn consume_recovery_code(executor: impl PgExecutor<'_>, user_id: Uuid, code_hash: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE mfa_recovery_codes SET used_at = NOW() WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(code_hash)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn count_unused_recovery_codes(executor: impl PgExecutor<'_>, user_id: Uuid) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM mfa_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Organizations requiring MFA for privileged roles in which the user has an active membership
+    pub async fn find_requiring_org_ids(executor: impl PgExecutor<'_>, user_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        let org_ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT uo.org_id
+            FROM user_organizations uo
+            JOIN organization o ON o.id = uo.org_id
+            WHERE uo.user_id = $1 AND uo.status = 'active' AND o.require_mfa AND o.deleted_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(org_ids)
+    }
+}
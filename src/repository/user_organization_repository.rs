@@ -1,17 +1,19 @@
 // UserOrganization Repository - Database operations for user-organization relationships
 
-use sqlx::{PgPool, Row};
+use sqlx::{PgExecutor, Row};
 use uuid::Uuid;
 
 use crate::errors::AppError;
 use crate::models::{UserOrganization, UserOrganizationWithDetails};
 
+/// Membership writes change what a member may do; callers make them in a `UnitOfWork` and
+/// call `invalidate_permissions` so cached permissions are dropped once they commit.
 pub struct UserOrganizationRepository;
 
 impl UserOrganizationRepository {
     /// Create user-organization relationship
     pub async fn create(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         user_id: Uuid,
         org_id: Uuid,
         role_id: Uuid,
@@ -30,14 +32,14 @@ impl UserOrganizationRepository {
         .bind(org_id)
         .bind(role_id)
         .bind(status)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(result)
     }
 
     /// Find user-organization relationship by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<UserOrganization>, AppError> {
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<UserOrganization>, AppError> {
         let result = sqlx::query_as::<_, UserOrganization>(
             r#"
             SELECT id, user_id, org_id, role_id, status, joined_at, created_at, updated_at
@@ -46,7 +48,7 @@ impl UserOrganizationRepository {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?;
 
         Ok(result)
@@ -54,7 +56,7 @@ impl UserOrganizationRepository {
 
     /// Find user-organization relationship by user_id and org_id
     pub async fn find_by_user_and_org(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         user_id: Uuid,
         org_id: Uuid,
     ) -> Result<Option<UserOrganization>, AppError> {
@@ -67,7 +69,7 @@ impl UserOrganizationRepository {
         )
         .bind(user_id)
         .bind(org_id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?;
 
         Ok(result)
@@ -75,7 +77,7 @@ impl UserOrganizationRepository {
 
     /// Get all user-organization relationships with details
     pub async fn find_all_with_details(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         user_id: Option<Uuid>,
         org_id: Option<Uuid>,
         status: Option<String>,
@@ -93,24 +95,24 @@ impl UserOrganizationRepository {
             JOIN users u ON uo.user_id = u.id
             JOIN organization o ON uo.org_id = o.id
             JOIN roles r ON uo.role_id = r.id
-            WHERE 1=1
+            WHERE o.deleted_at IS NULL
         "#.to_string();
 
         let mut param_count = 1;
         let mut conditions = Vec::new();
 
         if user_id.is_some() {
-            conditions.push(format!("AND uo.user_id = ${}", param_count));
+            conditions.push(format!(" AND uo.user_id = ${}", param_count));
             param_count += 1;
         }
 
         if org_id.is_some() {
-            conditions.push(format!("AND uo.org_id = ${}", param_count));
+            conditions.push(format!(" AND uo.org_id = ${}", param_count));
             param_count += 1;
         }
 
         if status.is_some() {
-            conditions.push(format!("AND uo.status = ${}", param_count));
+            conditions.push(format!(" AND uo.status = ${}", param_count));
             param_count += 1;
         }
 
@@ -138,7 +140,7 @@ impl UserOrganizationRepository {
         let results = db_query
             .bind(limit)
             .bind(offset)
-            .fetch_all(pool)
+            .fetch_all(executor)
             .await?;
 
         Ok(results)
@@ -146,7 +148,7 @@ impl UserOrganizationRepository {
 
     /// Get organizations for a user
     pub async fn find_organizations_for_user(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         user_id: Uuid,
         status: Option<String>,
     ) -> Result<Vec<UserOrganizationWithDetails>, AppError> {
@@ -161,7 +163,7 @@ impl UserOrganizationRepository {
             JOIN users u ON uo.user_id = u.id
             JOIN organization o ON uo.org_id = o.id
             JOIN roles r ON uo.role_id = r.id
-            WHERE uo.user_id = $1
+            WHERE uo.user_id = $1 AND o.deleted_at IS NULL
         "#.to_string();
 
         if let Some(_) = status {
@@ -176,14 +178,14 @@ impl UserOrganizationRepository {
             db_query = db_query.bind(stat);
         }
 
-        let results = db_query.fetch_all(pool).await?;
+        let results = db_query.fetch_all(executor).await?;
 
         Ok(results)
     }
 
     /// Get users for an organization
     pub async fn find_users_for_organization(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         org_id: Uuid,
         status: Option<String>,
     ) -> Result<Vec<UserOrganizationWithDetails>, AppError> {
@@ -198,7 +200,7 @@ impl UserOrganizationRepository {
             JOIN users u ON uo.user_id = u.id
             JOIN organization o ON uo.org_id = o.id
             JOIN roles r ON uo.role_id = r.id
-            WHERE uo.org_id = $1
+            WHERE uo.org_id = $1 AND o.deleted_at IS NULL
         "#.to_string();
 
         if let Some(_) = status {
@@ -213,20 +215,20 @@ impl UserOrganizationRepository {
             db_query = db_query.bind(stat);
         }
 
-        let results = db_query.fetch_all(pool).await?;
+        let results = db_query.fetch_all(executor).await?;
 
         Ok(results)
     }
 
     /// Update user-organization relationship
     pub async fn update(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         id: Uuid,
         role_id: Option<Uuid>,
         status: Option<String>,
     ) -> Result<UserOrganization, AppError> {
         // Simple approach - update based on what's provided
-        match (role_id, status) {
+        let result = match (role_id, status) {
             (Some(rid), Some(stat)) => {
                 sqlx::query_as::<_, UserOrganization>(
                     r#"
@@ -239,7 +241,7 @@ impl UserOrganizationRepository {
                 .bind(rid)
                 .bind(stat)
                 .bind(id)
-                .fetch_one(pool)
+                .fetch_one(executor)
                 .await
             }
             (Some(rid), None) => {
@@ -253,7 +255,7 @@ impl UserOrganizationRepository {
                 )
                 .bind(rid)
                 .bind(id)
-                .fetch_one(pool)
+                .fetch_one(executor)
                 .await
             }
             (None, Some(stat)) => {
@@ -267,7 +269,7 @@ impl UserOrganizationRepository {
                 )
                 .bind(stat)
                 .bind(id)
-                .fetch_one(pool)
+                .fetch_one(executor)
                 .await
             }
             (None, None) => {
@@ -280,45 +282,118 @@ impl UserOrganizationRepository {
                     "#,
                 )
                 .bind(id)
-                .fetch_one(pool)
+                .fetch_one(executor)
                 .await
             }
         }
-        .map_err(AppError::from)
+        .map_err(AppError::from)?;
+
+        Ok(result)
+    }
+
+    /// Persist an accepted invitation on an existing membership: its role, status and `joined_at`
+    pub async fn save_acceptance(executor: impl PgExecutor<'_>, membership: &UserOrganization) -> Result<UserOrganization, AppError> {
+        let result = sqlx::query_as::<_, UserOrganization>(
+            r#"
+            UPDATE user_organizations
+            SET role_id = $2, status = $3, joined_at = $4, updated_at = $5
+            WHERE id = $1
+            RETURNING id, user_id, org_id, role_id, status, joined_at, created_at, updated_at
+            "#,
+        )
+        .bind(membership.id)
+        .bind(membership.role_id)
+        .bind(&membership.status)
+        .bind(membership.joined_at)
+        .bind(membership.updated_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Move every membership of an organization from one status to another, returning their users
+    pub async fn update_status_for_org(
+        executor: impl PgExecutor<'_>,
+        org_id: Uuid,
+        from_status: &str,
+        to_status: &str,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let user_ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE user_organizations
+            SET status = $3, updated_at = NOW()
+            WHERE org_id = $1 AND status = $2
+            RETURNING user_id
+            "#,
+        )
+        .bind(org_id)
+        .bind(from_status)
+        .bind(to_status)
+        .fetch_all(executor)
+        .await?;
+
+        Ok(user_ids)
     }
 
     /// Delete user-organization relationship
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<(), AppError> {
+    pub async fn delete(executor: impl PgExecutor<'_>, id: Uuid) -> Result<(), AppError> {
         sqlx::query("DELETE FROM user_organizations WHERE id = $1")
             .bind(id)
-            .execute(pool)
+            .execute(executor)
             .await?;
 
         Ok(())
     }
 
+    /// Count active members of an organization holding a role, locking their memberships
+    ///
+    /// The locks last until the surrounding transaction ends.
+    pub async fn lock_active_with_role(executor: impl PgExecutor<'_>, org_id: Uuid, role_id: Uuid) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM (
+                SELECT id FROM user_organizations
+                WHERE org_id = $1 AND role_id = $2 AND status = 'active'
+                FOR UPDATE
+            ) AS locked
+            "#,
+        )
+        .bind(org_id)
+        .bind(role_id)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(count)
+    }
+
     /// Count total relationships with filters
     pub async fn count_with_filters(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         user_id: Option<Uuid>,
         org_id: Option<Uuid>,
         status: Option<String>,
     ) -> Result<i64, AppError> {
-        let mut query = "SELECT COUNT(*) FROM user_organizations WHERE 1=1".to_string();
+        let mut query = r#"
+            SELECT COUNT(*)
+            FROM user_organizations uo
+            JOIN organization o ON uo.org_id = o.id
+            WHERE o.deleted_at IS NULL
+        "#.to_string();
         let mut param_count = 1;
 
         if user_id.is_some() {
-            query.push_str(&format!(" AND user_id = ${}", param_count));
+            query.push_str(&format!(" AND uo.user_id = ${}", param_count));
             param_count += 1;
         }
 
         if org_id.is_some() {
-            query.push_str(&format!(" AND org_id = ${}", param_count));
+            query.push_str(&format!(" AND uo.org_id = ${}", param_count));
             param_count += 1;
         }
 
         if status.is_some() {
-            query.push_str(&format!(" AND status = ${}", param_count));
+            query.push_str(&format!(" AND uo.status = ${}", param_count));
         }
 
         let mut db_query = sqlx::query(&query);
@@ -333,7 +408,7 @@ impl UserOrganizationRepository {
             db_query = db_query.bind(stat);
         }
 
-        let result = db_query.fetch_one(pool).await?;
+        let result = db_query.fetch_one(executor).await?;
         let count: i64 = result.get(0);
 
         Ok(count)
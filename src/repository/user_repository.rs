@@ -1,10 +1,10 @@
 // User Repository - Database operations for users
 
-use sqlx::{PgPool, Row};
+use sqlx::{PgExecutor, PgPool, Row};
 use uuid::Uuid;
 
 use crate::errors::AppError;
-use crate::models::User;
+use crate::models::{User, USER_STATUS_PENDING_VERIFICATION};
 
 pub struct UserRepository;
 
@@ -43,29 +43,31 @@ impl UserRepository {
 
     /// Insert a new user into the database
     pub async fn create(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         name: String,
         email: String,
         password: String,
+        status: &str,
     ) -> Result<User, AppError> {
         let result = sqlx::query_as::<_, User>(
             r#"
             INSERT INTO users (name, email, password, status)
-            VALUES ($1, $2, $3, 'active')
+            VALUES ($1, $2, $3, $4)
             RETURNING id, name, email, password, status, created_at, updated_at
             "#,
         )
         .bind(name.trim())
         .bind(email.trim().to_lowercase())
         .bind(password)
-        .fetch_one(pool)
+        .bind(status)
+        .fetch_one(executor)
         .await?;
 
         Ok(result)
     }
 
     /// Find user by ID
-    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<User>, AppError> {
+    pub async fn find_by_id(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Option<User>, AppError> {
         let result = sqlx::query_as::<_, User>(
             r#"
             SELECT id, name, email, password, status, created_at, updated_at
@@ -74,14 +76,14 @@ impl UserRepository {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?;
 
         Ok(result)
     }
 
     /// Find user by email
-    pub async fn find_by_email(pool: &PgPool, email: &str) -> Result<Option<User>, AppError> {
+    pub async fn find_by_email(executor: impl PgExecutor<'_>, email: &str) -> Result<Option<User>, AppError> {
         let result = sqlx::query_as::<_, User>(
             r#"
             SELECT id, name, email, password, status, created_at, updated_at
@@ -90,7 +92,7 @@ impl UserRepository {
             "#,
         )
         .bind(email.trim().to_lowercase())
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?;
 
         Ok(result)
@@ -98,7 +100,7 @@ impl UserRepository {
 
     /// Get all users with pagination
     pub async fn find_all(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<User>, AppError> {
@@ -112,7 +114,7 @@ impl UserRepository {
         )
         .bind(limit)
         .bind(offset)
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         Ok(results)
@@ -120,7 +122,7 @@ impl UserRepository {
 
     /// Update user status
     pub async fn update_status(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         id: Uuid,
         status: &str,
     ) -> Result<User, AppError> {
@@ -134,21 +136,21 @@ impl UserRepository {
         )
         .bind(status)
         .bind(id)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(result)
     }
 
     /// Check if email exists
-    pub async fn email_exists(pool: &PgPool, email: &str) -> Result<bool, AppError> {
+    pub async fn email_exists(executor: impl PgExecutor<'_>, email: &str) -> Result<bool, AppError> {
         let result = sqlx::query(
             r#"
             SELECT EXISTS(SELECT 1 FROM users WHERE email = $1) as exists
             "#,
         )
         .bind(email.trim().to_lowercase())
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         let exists: bool = result.get("exists");
@@ -157,7 +159,7 @@ impl UserRepository {
 
     /// Update user password
     pub async fn update_password(
-        pool: &PgPool,
+        executor: impl PgExecutor<'_>,
         id: Uuid,
         hashed_password: &str,
     ) -> Result<User, AppError> {
@@ -171,7 +173,27 @@ impl UserRepository {
         )
         .bind(hashed_password)
         .bind(id)
-        .fetch_one(pool)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Record that the user proved control of their email, activating a `pending_verification` account
+    pub async fn mark_email_verified(executor: impl PgExecutor<'_>, id: Uuid) -> Result<User, AppError> {
+        let result = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users
+            SET email_verified_at = COALESCE(email_verified_at, NOW()),
+                status = CASE WHEN status = $2 THEN 'active' ELSE status END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, name, email, password, status, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(USER_STATUS_PENDING_VERIFICATION)
+        .fetch_one(executor)
         .await?;
 
         Ok(result)
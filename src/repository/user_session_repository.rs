@@ -0,0 +1,145 @@
+// User Session Repository - Database operations for login sessions
+
+use chrono::{DateTime, Utc};
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::{SessionUser, UserSession};
+
+pub struct UserSessionRepository;
+
+impl UserSessionRepository {
+    /// Insert a new session
+    pub async fn create(executor: impl PgExecutor<'_>, session: &UserSession) -> Result<UserSession, AppError> {
+        let result = sqlx::query_as::<_, UserSession>(
+            r#"
+            INSERT INTO user_sessions (
+                id, user_id, org_id, refresh_token_hash, user_agent, expires_at, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(session.id)
+        .bind(session.user_id)
+        .bind(session.org_id)
+        .bind(&session.refresh_token_hash)
+        .bind(&session.user_agent)
+        .bind(session.expires_at)
+        .bind(session.created_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Load a session together with the current state of its user
+    pub async fn find_with_user(executor: impl PgExecutor<'_>, session_id: Uuid) -> Result<Option<SessionUser>, AppError> {
+        let result = sqlx::query_as::<_, SessionUser>(
+            r#"
+            SELECT s.id AS session_id, s.user_id, s.org_id, u.name, u.email, u.status,
+                   s.expires_at, s.revoked_at
+            FROM user_sessions s
+            JOIN users u ON u.id = s.user_id
+            WHERE s.id = $1
+            "#,
+        )
+        .bind(session_id)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Find the session whose current or previous refresh token has this hash
+    pub async fn find_by_refresh_token_hash(
+        executor: impl PgExecutor<'_>,
+        refresh_token_hash: &str,
+    ) -> Result<Option<UserSession>, AppError> {
+        let result = sqlx::query_as::<_, UserSession>(
+            r#"
+            SELECT *
+            FROM user_sessions
+            WHERE refresh_token_hash = $1 OR previous_refresh_token_hash = $1
+            LIMIT 1
+            "#,
+        )
+        .bind(refresh_token_hash)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Replace the refresh token of an active session, only if it still holds `current_hash`
+    ///
+    /// Returns `None` when a concurrent refresh rotated the token first.
+    pub async fn rotate(
+        executor: impl PgExecutor<'_>,
+        session_id: Uuid,
+        current_hash: &str,
+        new_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<UserSession>, AppError> {
+        let result = sqlx::query_as::<_, UserSession>(
+            r#"
+            UPDATE user_sessions
+            SET previous_refresh_token_hash = refresh_token_hash,
+                refresh_token_hash = $3,
+                expires_at = $4,
+                last_used_at = NOW()
+            WHERE id = $1 AND refresh_token_hash = $2 AND revoked_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(session_id)
+        .bind(current_hash)
+        .bind(new_hash)
+        .bind(expires_at)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Remember the organization selected for a session
+    pub async fn set_organization(executor: impl PgExecutor<'_>, session_id: Uuid, org_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE user_sessions SET org_id = $2 WHERE id = $1")
+            .bind(session_id)
+            .bind(org_id)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Revoke a single session of a user
+    pub async fn revoke(executor: impl PgExecutor<'_>, session_id: Uuid, user_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_sessions SET revoked_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Revoke every open session of a user, returning how many were revoked
+    pub async fn revoke_all_for_user(executor: impl PgExecutor<'_>, user_id: Uuid) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE user_sessions SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(executor)
+        .await?;
+
+        tracing::info!("Revoked {} sessions of user {}", result.rows_affected(), user_id);
+        Ok(result.rows_affected())
+    }
+}
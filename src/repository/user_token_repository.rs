@@ -0,0 +1,82 @@
+// User Token Repository - Database operations for password reset, email verification and pending MFA tokens
+
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::UserToken;
+
+pub struct UserTokenRepository;
+
+impl UserTokenRepository {
+    /// Insert a new token
+    pub async fn create(executor: impl PgExecutor<'_>, token: &UserToken) -> Result<UserToken, AppError> {
+        let result = sqlx::query_as::<_, UserToken>(
+            r#"
+            INSERT INTO user_tokens (id, user_id, purpose, token_hash, expires_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(token.id)
+        .bind(token.user_id)
+        .bind(&token.purpose)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .bind(token.created_at)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Look up an unused, unexpired token without using it up
+    pub async fn find_active(executor: impl PgExecutor<'_>, purpose: &str, token_hash: &str) -> Result<Option<UserToken>, AppError> {
+        let result = sqlx::query_as::<_, UserToken>(
+            r#"
+            SELECT * FROM user_tokens
+            WHERE token_hash = $1 AND purpose = $2 AND used_at IS NULL AND expires_at > NOW()
+            "#,
+        )
+        .bind(token_hash)
+        .bind(purpose)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Mark an unused, unexpired token as used and return it
+    ///
+    /// Returns `None` for unknown, expired or already used tokens, so a token works at most once
+    /// even when presented concurrently.
+    pub async fn consume(executor: impl PgExecutor<'_>, purpose: &str, token_hash: &str) -> Result<Option<UserToken>, AppError> {
+        let result = sqlx::query_as::<_, UserToken>(
+            r#"
+            UPDATE user_tokens
+            SET used_at = NOW()
+            WHERE token_hash = $1 AND purpose = $2 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(token_hash)
+        .bind(purpose)
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Retire every unused token of a user for a purpose, e.g. before issuing a new one
+    pub async fn invalidate_for_user(executor: impl PgExecutor<'_>, user_id: Uuid, purpose: &str) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE user_tokens SET used_at = NOW() WHERE user_id = $1 AND purpose = $2 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(purpose)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,39 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::controllers::{
+    activate_mfa, disable_mfa, enroll_mfa, forgot_password, jwks, logout, logout_all, mfa_status,
+    refresh_token, regenerate_recovery_codes, reset_password, setup_mfa, verify_email, verify_mfa,
+};
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::AppState;
+
+/// Session and MFA routes for the signed-in user's own account
+pub fn auth_routes_with_permissions() -> PermissionRoutes {
+    let caller = RequirePermission::authenticated();
+
+    PermissionRoutes::new()
+        .post("/auth/logout", logout, caller)
+        .post("/auth/logout-all", logout_all, caller)
+        .get("/auth/mfa", mfa_status, caller)
+        .post("/auth/mfa/enroll", enroll_mfa, caller)
+        .post("/auth/mfa/activate", activate_mfa, caller)
+        .post("/auth/mfa/disable", disable_mfa, caller)
+        .post("/auth/mfa/recovery-codes", regenerate_recovery_codes, caller)
+}
+
+/// Token refresh, account recovery, MFA login and public signing keys (public - no access token required)
+pub fn public_auth_routes() -> Router<AppState> {
+    Router::new()
+        .route("/auth/refresh", post(refresh_token))
+        .route("/auth/forgot-password", post(forgot_password))
+        .route("/auth/reset-password", post(reset_password))
+        .route("/auth/verify-email", post(verify_email))
+        // The second login step; the mfa_token from the password step is the credential
+        .route("/auth/mfa/setup", post(setup_mfa))
+        .route("/auth/mfa/verify", post(verify_mfa))
+        .route("/.well-known/jwks.json", get(jwks))
+}
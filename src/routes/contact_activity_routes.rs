@@ -0,0 +1,26 @@
+use crate::controllers::contact_activity_controller::{
+    complete_activity, create_activity, delete_activity, get_activity, get_contact_timeline,
+    get_overdue_activities, get_upcoming_activities, update_activity,
+};
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::{ACTIVITIES_CREATE, ACTIVITIES_DELETE, ACTIVITIES_READ, ACTIVITIES_UPDATE};
+
+/// Create contact activity routes with permissions (for AppState)
+pub fn contact_activity_routes_with_permissions() -> PermissionRoutes {
+    let read = RequirePermission::new(ACTIVITIES_READ);
+    let update = RequirePermission::new(ACTIVITIES_UPDATE);
+
+    PermissionRoutes::new()
+        // Activities of a single contact
+        .post("/contacts/:id/activities", create_activity, RequirePermission::new(ACTIVITIES_CREATE))
+        .get("/contacts/:id/timeline", get_contact_timeline, read)
+        // Per-owner work queues
+        .get("/activities/upcoming", get_upcoming_activities, read)
+        .get("/activities/overdue", get_overdue_activities, read)
+        // Single activity management
+        .get("/activities/:id", get_activity, read)
+        .put("/activities/:id", update_activity, update)
+        .delete("/activities/:id", delete_activity, RequirePermission::new(ACTIVITIES_DELETE))
+        .post("/activities/:id/complete", complete_activity, update)
+}
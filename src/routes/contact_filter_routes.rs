@@ -1,22 +1,22 @@
-use axum::{
-    routing::{get, post},
-    Router,
-};
-
 use crate::controllers::contact_filter_controller::{
     filter_contacts, get_filter_fields, get_filter_presets, validate_filter,
 };
-use crate::AppState;
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::CONTACTS_READ;
 
 /// Create contact filter routes with permissions (for AppState)
-pub fn contact_filter_routes_with_permissions() -> Router<AppState> {
-    Router::new()
+pub fn contact_filter_routes_with_permissions() -> PermissionRoutes {
+    // `contacts:read_own` is enough; the filter then only returns the caller's contacts
+    let read = RequirePermission::owned(CONTACTS_READ);
+
+    PermissionRoutes::new()
         // Main filter endpoint
-        .route("/contacts/filter", post(filter_contacts))
+        .post("/contacts/filter", filter_contacts, read)
         // Get available filter fields and their types
-        .route("/contacts/filter/fields", get(get_filter_fields))
+        .get("/contacts/filter/fields", get_filter_fields, read)
         // Get filter presets/templates
-        .route("/contacts/filter/presets", get(get_filter_presets))
+        .get("/contacts/filter/presets", get_filter_presets, read)
         // Validate filter structure
-        .route("/contacts/filter/validate", post(validate_filter))
+        .post("/contacts/filter/validate", validate_filter, read)
 }
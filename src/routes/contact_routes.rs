@@ -1,29 +1,38 @@
-use axum::{
-    routing::{get, post, put, patch, delete},
-    Router,
-};
-use sqlx::PgPool;
-use crate::AppState;
+use axum::extract::DefaultBodyLimit;
+use axum::handler::Handler;
 
-use crate::controllers::{create_contact, get_contact, update_contact, patch_contact, delete_contact};
+use crate::controllers::{create_contact, get_contact, update_contact, patch_contact, delete_contact, list_lead_statuses, bulk_update_contacts, get_contact_bulk_job, import_contacts, get_contact_import_job, download_contact_import_errors};
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::{ContactImportService, CONTACTS_BULK_UPDATE, CONTACTS_CREATE, CONTACTS_DELETE, CONTACTS_IMPORT, CONTACTS_READ, CONTACTS_UPDATE};
 
 /// Create contact routes with permissions (for AppState)
-pub fn contact_routes_with_permissions() -> Router<AppState> {
-    Router::new()
+pub fn contact_routes_with_permissions() -> PermissionRoutes {
+    PermissionRoutes::new()
         // Create contact
-        .route("/contacts", post(create_contact))
-        // View single contact
-        .route("/contacts/:id", get(get_contact))
+        .post("/contacts", create_contact, RequirePermission::new(CONTACTS_CREATE))
+        // View single contact (`contacts:read_own` limits it to owned contacts)
+        .get("/contacts/:id", get_contact, RequirePermission::owned(CONTACTS_READ))
         // Update contact (full replacement)
-        .route("/contacts/:id", put(update_contact))
+        .put("/contacts/:id", update_contact, RequirePermission::owned(CONTACTS_UPDATE))
         // Patch contact (partial update with merge semantics)
-        .route("/contacts/:id", patch(patch_contact))
+        .patch("/contacts/:id", patch_contact, RequirePermission::owned(CONTACTS_UPDATE))
         // Delete contact
-        .route("/contacts/:id", delete(delete_contact))
-}
-
-/// Create empty contact routes for PgPool compatibility
-pub fn contact_routes() -> Router<PgPool> {
-    Router::new()
-        // No routes - all functionality moved to permission-based routes
+        .delete("/contacts/:id", delete_contact, RequirePermission::owned(CONTACTS_DELETE))
+        // Apply one action to many contacts (each action also checks its own permission per contact)
+        .post("/contacts/bulk", bulk_update_contacts, RequirePermission::new(CONTACTS_BULK_UPDATE))
+        // Progress of a bulk operation queued as a job
+        .get("/contacts/bulk/:job_id", get_contact_bulk_job, RequirePermission::new(CONTACTS_BULK_UPDATE))
+        // Import contacts from an uploaded CSV file, up to `CONTACT_IMPORT_MAX_BYTES`
+        .post(
+            "/contacts/import",
+            import_contacts.layer(DefaultBodyLimit::max(ContactImportService::max_upload_bytes())),
+            RequirePermission::new(CONTACTS_IMPORT),
+        )
+        // Progress of an import queued as a job
+        .get("/contacts/import/:job_id", get_contact_import_job, RequirePermission::new(CONTACTS_IMPORT))
+        // Rows of an import job that failed, as CSV
+        .get("/contacts/import/:job_id/errors", download_contact_import_errors, RequirePermission::new(CONTACTS_IMPORT))
+        // Lead statuses contacts can be in
+        .get("/lead-statuses", list_lead_statuses, RequirePermission::new(CONTACTS_READ))
 }
@@ -0,0 +1,28 @@
+use crate::controllers::contact_tag_controller::{
+    assign_contact_tags, bulk_assign_tags, bulk_unassign_tags, create_tag, delete_tag, get_contact_tags,
+    get_tag, list_tags, unassign_contact_tag, update_tag,
+};
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::{CONTACTS_READ, CONTACTS_UPDATE};
+
+/// Create contact tag routes with permissions (for AppState)
+pub fn contact_tag_routes_with_permissions() -> PermissionRoutes {
+    let read = RequirePermission::new(CONTACTS_READ);
+    let update = RequirePermission::new(CONTACTS_UPDATE);
+
+    PermissionRoutes::new()
+        // Tag management
+        .get("/tags", list_tags, read)
+        .post("/tags", create_tag, update)
+        .get("/tags/:id", get_tag, read)
+        .put("/tags/:id", update_tag, update)
+        .delete("/tags/:id", delete_tag, update)
+        // Bulk tagging
+        .post("/tags/bulk-assign", bulk_assign_tags, update)
+        .post("/tags/bulk-unassign", bulk_unassign_tags, update)
+        // Tags of a single contact
+        .get("/contacts/:id/tags", get_contact_tags, read)
+        .post("/contacts/:id/tags", assign_contact_tags, update)
+        .delete("/contacts/:id/tags/:tag_id", unassign_contact_tag, update)
+}
@@ -1,13 +1,21 @@
-use axum::{
-    routing::{get, post},
-    Router,
+use crate::controllers::custom_field_controller::{
+    create_custom_field, deactivate_custom_field, delete_custom_field, get_custom_fields_by_module,
+    reorder_custom_fields, update_custom_field, update_custom_field_options,
 };
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::CUSTOM_FIELDS_MANAGE;
 
-use crate::controllers::custom_field_controller::{create_custom_field, get_custom_fields_by_module};
-use crate::AppState;
+/// Create custom field routes with permissions (for AppState)
+pub fn custom_field_routes_with_permissions() -> PermissionRoutes {
+    let manage = RequirePermission::new(CUSTOM_FIELDS_MANAGE);
 
-pub fn custom_field_routes() -> Router<AppState> {
-    Router::new()
-        .route("/custom-fields", post(create_custom_field))
-        .route("/custom-fields/:module", get(get_custom_fields_by_module))
-}
\ No newline at end of file
+    PermissionRoutes::new()
+        .post("/custom-fields", create_custom_field, manage)
+        .get("/custom-fields/:module", get_custom_fields_by_module, manage)
+        .put("/custom-fields/:module/order", reorder_custom_fields, manage)
+        .put("/custom-fields/:module/:id", update_custom_field, manage)
+        .delete("/custom-fields/:module/:id", delete_custom_field, manage)
+        .post("/custom-fields/:module/:id/deactivate", deactivate_custom_field, manage)
+        .put("/custom-fields/:module/:id/options", update_custom_field_options, manage)
+}
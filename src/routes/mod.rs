@@ -1,11 +1,23 @@
 // Routes module - Organize API routes by feature
+pub mod auth_routes;
+pub mod contact_activity_routes;
 pub mod contact_routes;
 pub mod contact_filter_routes;
+pub mod contact_tag_routes;
+pub mod custom_field_routes;
 pub mod organization_routes;
+pub mod permission_routes;
+pub mod role_routes;
 pub mod user_routes;
 pub mod user_organization_routes;
 
-pub use contact_routes::{contact_routes, contact_routes_with_permissions};
+pub use auth_routes::{auth_routes_with_permissions, public_auth_routes};
+pub use contact_activity_routes::contact_activity_routes_with_permissions;
+pub use contact_routes::contact_routes_with_permissions;
+pub use contact_tag_routes::contact_tag_routes_with_permissions;
+pub use custom_field_routes::custom_field_routes_with_permissions;
 pub use organization_routes::*;
+pub use permission_routes::PermissionRoutes;
+pub use role_routes::role_routes_with_permissions;
 pub use user_routes::*;
 pub use user_organization_routes::*;
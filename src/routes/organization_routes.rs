@@ -1,70 +1,43 @@
-use axum::{
-    routing::{get, post, put, delete},
-    Router,
-};
-use sqlx::PgPool;
+use axum::Router;
 
 use crate::controllers::{
     create_organization,
-    get_organization_users
+    delete_organization,
+    get_organization,
+    get_organization_settings,
+    get_organization_users,
+    get_mfa_policy,
+    restore_organization,
+    update_mfa_policy,
+    update_organization,
+    update_organization_settings
 };
-
-/// Create organization-related routes (protected - require authentication)
-pub fn organization_routes() -> Router<PgPool> {
-    Router::new()
-        // Organization management
-        .route("/organizations", post(create_organization))
-        .route("/organizations/:org_id/users", get(get_organization_users))
-        
-        // Future organization routes
-        // .route("/organizations", get(get_organizations))
-        // .route("/organizations/:id", get(get_organization_by_id))
-        // .route("/organizations/:id", put(update_organization))
-        // .route("/organizations/:id", delete(delete_organization))
-        // .route("/organizations/:id/settings", get(get_organization_settings))
-        // .route("/organizations/:id/settings", put(update_organization_settings))
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::{ORG_DELETE, ORG_READ, ORG_WRITE, USERS_READ};
+use crate::AppState;
+
+/// Create organization management routes with permissions (for AppState)
+pub fn organization_routes_with_permissions() -> PermissionRoutes {
+    PermissionRoutes::new()
+        // Any signed-in user can create an organization and becomes its admin
+        .post("/organizations", create_organization, RequirePermission::authenticated())
+        .get("/organizations/:org_id", get_organization, RequirePermission::new(ORG_READ))
+        .put("/organizations/:org_id", update_organization, RequirePermission::new(ORG_WRITE))
+        .delete("/organizations/:org_id", delete_organization, RequirePermission::new(ORG_DELETE))
+        // Deleted organizations cannot be selected, so restoring checks `org:delete` itself
+        .post("/organizations/:org_id/restore", restore_organization, RequirePermission::authenticated())
+        .get("/organizations/:org_id/users", get_organization_users, RequirePermission::new(USERS_READ))
+        .get("/organizations/:org_id/settings", get_organization_settings, RequirePermission::new(ORG_READ))
+        .put("/organizations/:org_id/settings", update_organization_settings, RequirePermission::new(ORG_WRITE))
+        .get("/organizations/:org_id/mfa-policy", get_mfa_policy, RequirePermission::new(ORG_READ))
+        .put("/organizations/:org_id/mfa-policy", update_mfa_policy, RequirePermission::new(ORG_WRITE))
 }
 
 /// Create public organization routes (no authentication required)
-pub fn public_organization_routes() -> Router<PgPool> {
+pub fn public_organization_routes() -> Router<AppState> {
     Router::new()
         // Currently no public organization routes
         // Future: public organization info, signup pages, etc.
         // .route("/organizations/public/:slug", get(get_public_organization_info))
 }
-
-/// Organization route configuration
-pub struct OrganizationRouteConfig {
-    pub enable_organization_creation: bool,
-    pub enable_organization_management: bool,
-    pub enable_public_info: bool,
-}
-
-impl Default for OrganizationRouteConfig {
-    fn default() -> Self {
-        Self {
-            enable_organization_creation: true,
-            enable_organization_management: true,
-            enable_public_info: false,
-        }
-    }
-}
-
-/// Create organization routes with configuration
-pub fn organization_routes_with_config(config: OrganizationRouteConfig) -> Router<PgPool> {
-    let mut router = Router::new();
-
-    if config.enable_organization_creation {
-        router = router.route("/organizations", post(create_organization));
-    }
-
-    if config.enable_organization_management {
-        router = router.route("/organizations/:org_id/users", get(get_organization_users));
-    }
-
-    if config.enable_public_info {
-        router = router.merge(public_organization_routes());
-    }
-
-    router
-}
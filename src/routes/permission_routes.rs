@@ -0,0 +1,231 @@
+use axum::{
+    handler::Handler,
+    http::Method,
+    middleware::from_fn_with_state,
+    routing::{on, MethodFilter, MethodRouter},
+    Router,
+};
+use std::collections::HashSet;
+
+use crate::middleware::{jwt_auth_middleware, require_permission, RequirePermission};
+use crate::services::PermissionRegistry;
+use crate::AppState;
+
+struct PermissionRoute {
+    method: Method,
+    path: &'static str,
+    handler: MethodRouter<AppState>,
+    policy: RequirePermission,
+}
+
+/// Routes that can only be declared together with the permission they require
+#[derive(Default)]
+pub struct PermissionRoutes {
+    routes: Vec<PermissionRoute>,
+}
+
+impl PermissionRoutes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get<H, T>(self, path: &'static str, handler: H, policy: RequirePermission) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(Method::GET, MethodFilter::GET, path, handler, policy)
+    }
+
+    pub fn post<H, T>(self, path: &'static str, handler: H, policy: RequirePermission) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(Method::POST, MethodFilter::POST, path, handler, policy)
+    }
+
+    pub fn put<H, T>(self, path: &'static str, handler: H, policy: RequirePermission) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(Method::PUT, MethodFilter::PUT, path, handler, policy)
+    }
+
+    pub fn patch<H, T>(self, path: &'static str, handler: H, policy: RequirePermission) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(Method::PATCH, MethodFilter::PATCH, path, handler, policy)
+    }
+
+    pub fn delete<H, T>(self, path: &'static str, handler: H, policy: RequirePermission) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.route(Method::DELETE, MethodFilter::DELETE, path, handler, policy)
+    }
+
+    pub fn merge(mut self, other: PermissionRoutes) -> Self {
+        self.routes.extend(other.routes);
+        self
+    }
+
+    /// Startup check: every route is declared once, requires a registered permission and,
+    /// when it acts on an account, names that account in its path
+    pub fn check(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        let mut problems = Vec::new();
+
+        for route in &self.routes {
+            if !seen.insert((route.method.clone(), route.path)) {
+                problems.push(format!("{} {} is declared more than once", route.method, route.path));
+            }
+
+            if let Some(permission) = route.policy.permission() {
+                if !PermissionRegistry::is_registered(permission) {
+                    problems.push(format!(
+                        "{} {} requires unknown permission '{}'",
+                        route.method, route.path, permission
+                    ));
+                }
+            }
+
+            let names_account = route
+                .path
+                .split('/')
+                .any(|segment| segment == ":id" || segment == ":user_id");
+            if route.policy.is_account() && !names_account {
+                problems.push(format!(
+                    "{} {} acts on an account but has no :id or :user_id segment",
+                    route.method, route.path
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join("; "))
+        }
+    }
+
+    /// Method, path and policy of every route
+    pub fn policies(&self) -> impl Iterator<Item = (&Method, &'static str, RequirePermission)> + '_ {
+        self.routes
+            .iter()
+            .map(|route| (&route.method, route.path, route.policy))
+    }
+
+    /// Build the router, authenticating every route and guarding it with its policy
+    ///
+    /// This is the only router behind `jwt_auth_middleware`, so an authenticated route cannot
+    /// be mounted without a policy.
+    pub fn into_router(self, state: AppState) -> Router<AppState> {
+        let router = self.routes.into_iter().fold(Router::new(), |router, route| {
+            let guard = from_fn_with_state((state.clone(), route.policy), require_permission);
+            router.route(route.path, route.handler.route_layer(guard))
+        });
+
+        router.layer(from_fn_with_state(state.db, jwt_auth_middleware))
+    }
+
+    fn route<H, T>(
+        mut self,
+        method: Method,
+        filter: MethodFilter,
+        path: &'static str,
+        handler: H,
+        policy: RequirePermission,
+    ) -> Self
+    where
+        H: Handler<T, AppState>,
+        T: 'static,
+    {
+        self.routes.push(PermissionRoute {
+            method,
+            path,
+            handler: on(filter, handler),
+            policy,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::{
+        auth_routes_with_permissions, contact_activity_routes_with_permissions,
+        contact_filter_routes::contact_filter_routes_with_permissions, contact_routes_with_permissions,
+        contact_tag_routes_with_permissions, custom_field_routes_with_permissions, invitation_routes_with_permissions,
+        member_routes_with_permissions, organization_routes_with_permissions, role_routes_with_permissions,
+        user_organization_routes_with_permissions, user_routes_with_permissions,
+    };
+    use crate::services::{CONTACTS_DELETE, CONTACTS_READ, CONTACTS_UPDATE, USERS_WRITE};
+
+    async fn handler() {}
+
+    #[test]
+    fn test_application_routes_pass_check() {
+        let routes = PermissionRoutes::new()
+            .merge(user_routes_with_permissions())
+            .merge(auth_routes_with_permissions())
+            .merge(user_organization_routes_with_permissions())
+            .merge(contact_routes_with_permissions())
+            .merge(contact_filter_routes_with_permissions())
+            .merge(contact_tag_routes_with_permissions())
+            .merge(contact_activity_routes_with_permissions())
+            .merge(custom_field_routes_with_permissions())
+            .merge(role_routes_with_permissions())
+            .merge(invitation_routes_with_permissions())
+            .merge(member_routes_with_permissions())
+            .merge(organization_routes_with_permissions());
+
+        assert_eq!(routes.check(), Ok(()));
+        assert!(routes.policies().any(|(method, path, policy)| {
+            method == Method::DELETE && path == "/contacts/:id" && policy == RequirePermission::owned(CONTACTS_DELETE)
+        }));
+        assert!(routes.policies().any(|(method, path, policy)| {
+            method == Method::PUT && path == "/users/:id/password" && policy == RequirePermission::own_account()
+        }));
+    }
+
+    #[test]
+    fn test_same_path_with_different_methods_is_allowed() {
+        let routes = PermissionRoutes::new()
+            .get("/things/:id", handler, RequirePermission::new(CONTACTS_READ))
+            .put("/things/:id", handler, RequirePermission::new(CONTACTS_UPDATE));
+
+        assert_eq!(routes.check(), Ok(()));
+    }
+
+    #[test]
+    fn test_duplicate_route_rejected() {
+        let routes = PermissionRoutes::new()
+            .get("/things", handler, RequirePermission::new(CONTACTS_READ))
+            .merge(PermissionRoutes::new().get("/things", handler, RequirePermission::new(CONTACTS_UPDATE)));
+
+        assert!(routes.check().unwrap_err().contains("GET /things is declared more than once"));
+    }
+
+    #[test]
+    fn test_account_route_must_name_the_account() {
+        let routes = PermissionRoutes::new()
+            .put("/things/:id", handler, RequirePermission::account(USERS_WRITE))
+            .put("/things", handler, RequirePermission::own_account());
+
+        assert!(routes.check().unwrap_err().contains("PUT /things acts on an account"));
+        assert!(!routes.check().unwrap_err().contains("/things/:id"));
+    }
+
+    #[test]
+    fn test_unknown_permission_rejected() {
+        let routes = PermissionRoutes::new().get("/things", handler, RequirePermission::new("contact:read"));
+
+        assert!(routes.check().unwrap_err().contains("unknown permission 'contact:read'"));
+    }
+}
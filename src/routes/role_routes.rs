@@ -0,0 +1,17 @@
+use crate::controllers::role_controller::{create_role, delete_role, get_role, list_roles, update_role};
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::{ROLES_MANAGE, ROLES_READ};
+
+/// Create organization role routes with permissions (for AppState)
+pub fn role_routes_with_permissions() -> PermissionRoutes {
+    let read = RequirePermission::new(ROLES_READ);
+    let manage = RequirePermission::new(ROLES_MANAGE);
+
+    PermissionRoutes::new()
+        .get("/organizations/:org_id/roles", list_roles, read)
+        .post("/organizations/:org_id/roles", create_role, manage)
+        .get("/organizations/:org_id/roles/:role_id", get_role, read)
+        .put("/organizations/:org_id/roles/:role_id", update_role, manage)
+        .delete("/organizations/:org_id/roles/:role_id", delete_role, manage)
+}
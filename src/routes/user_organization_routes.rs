@@ -1,84 +1,61 @@
 use axum::{
-    routing::{get, post, put, delete},
+    routing::{get, post},
     Router,
 };
-use sqlx::PgPool;
 
 use crate::controllers::{
     add_user_to_organization,
-    invite_user_to_organization,
     update_user_organization,
-    remove_user_from_organization
+    remove_user_from_organization,
+    create_invitation,
+    list_invitations,
+    resend_invitation,
+    revoke_invitation,
+    unlock_member,
+    get_invitation_info,
+    accept_invitation,
+    decline_invitation
 };
-
-/// Create user-organization relationship routes (protected - require authentication)
-pub fn user_organization_routes() -> Router<PgPool> {
-    Router::new()
+use crate::middleware::RequirePermission;
+use crate::routes::PermissionRoutes;
+use crate::services::{MEMBERS_INVITE, USERS_WRITE};
+use crate::AppState;
+
+/// Create user-organization relationship routes with permissions (for AppState)
+///
+/// Memberships are managed within the active organization only.
+pub fn user_organization_routes_with_permissions() -> PermissionRoutes {
+    let manage = RequirePermission::new(USERS_WRITE);
+
+    PermissionRoutes::new()
         // User-Organization relationship management
-        .route("/user-organizations", post(add_user_to_organization))
-        .route("/user-organizations/invite", post(invite_user_to_organization))
-        .route("/user-organizations/:id", put(update_user_organization))
-        .route("/user-organizations/:id", delete(remove_user_from_organization))
-        
-        // Future user-organization routes
-        // .route("/user-organizations", get(get_user_organizations_list))
-        // .route("/user-organizations/:id", get(get_user_organization_by_id))
-        // .route("/user-organizations/bulk-invite", post(bulk_invite_users))
-        // .route("/user-organizations/accept-invitation/:token", post(accept_invitation))
-        // .route("/user-organizations/decline-invitation/:token", post(decline_invitation))
+        .post("/user-organizations", add_user_to_organization, manage)
+        .put("/user-organizations/:id", update_user_organization, manage)
+        .delete("/user-organizations/:id", remove_user_from_organization, manage)
 }
 
-/// Create public user-organization routes (no authentication required)
-pub fn public_user_organization_routes() -> Router<PgPool> {
-    Router::new()
-        // Public invitation handling
-        // .route("/invitations/:token", get(get_invitation_info))
-        // .route("/invitations/:token/accept", post(accept_public_invitation))
-        // .route("/invitations/:token/decline", post(decline_public_invitation))
-}
+/// Create invitation management routes with permissions (for AppState)
+pub fn invitation_routes_with_permissions() -> PermissionRoutes {
+    let invite = RequirePermission::new(MEMBERS_INVITE);
 
-/// User-Organization route configuration
-pub struct UserOrganizationRouteConfig {
-    pub enable_user_management: bool,
-    pub enable_invitations: bool,
-    pub enable_bulk_operations: bool,
-    pub enable_public_invitations: bool,
+    PermissionRoutes::new()
+        .get("/organizations/:org_id/invitations", list_invitations, invite)
+        .post("/organizations/:org_id/invitations", create_invitation, invite)
+        .post("/organizations/:org_id/invitations/:invitation_id/resend", resend_invitation, invite)
+        .delete("/organizations/:org_id/invitations/:invitation_id", revoke_invitation, invite)
 }
 
-impl Default for UserOrganizationRouteConfig {
-    fn default() -> Self {
-        Self {
-            enable_user_management: true,
-            enable_invitations: true,
-            enable_bulk_operations: false,
-            enable_public_invitations: false,
-        }
-    }
+/// Create member account routes with permissions (for AppState)
+pub fn member_routes_with_permissions() -> PermissionRoutes {
+    PermissionRoutes::new()
+        .post("/organizations/:org_id/members/:user_id/unlock", unlock_member, RequirePermission::new(USERS_WRITE))
 }
 
-/// Create user-organization routes with configuration
-pub fn user_organization_routes_with_config(config: UserOrganizationRouteConfig) -> Router<PgPool> {
-    let mut router = Router::new();
-
-    if config.enable_user_management {
-        router = router
-            .route("/user-organizations", post(add_user_to_organization))
-            .route("/user-organizations/:id", put(update_user_organization))
-            .route("/user-organizations/:id", delete(remove_user_from_organization));
-    }
-
-    if config.enable_invitations {
-        router = router.route("/user-organizations/invite", post(invite_user_to_organization));
-    }
-
-    if config.enable_public_invitations {
-        router = router.merge(public_user_organization_routes());
-    }
-
-    // Future bulk operations
-    // if config.enable_bulk_operations {
-    //     router = router.route("/user-organizations/bulk-invite", post(bulk_invite_users));
-    // }
-
-    router
+/// Create public user-organization routes (no authentication required)
+pub fn public_user_organization_routes() -> Router<AppState> {
+    Router::new()
+        // Public invitation handling; the token in the path is the credential
+        .route("/invitations/:token", get(get_invitation_info))
+        .route("/invitations/:token/accept", post(accept_invitation))
+        .route("/invitations/:token/decline", post(decline_invitation))
 }
@@ -0,0 +1,148 @@
+// Account Service - Password reset and email verification by emailed single-use tokens
+
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool};
+use std::env;
+
+use crate::dto::{AccountMessageResponse, ForgotPasswordRequest, ResetPasswordRequest, VerifyEmailRequest};
+use crate::errors::AppError;
+use crate::models::{
+    User, UserToken, EMAIL_TEMPLATE_EMAIL_VERIFICATION, EMAIL_TEMPLATE_PASSWORD_RESET,
+    TOKEN_PURPOSE_EMAIL_VERIFICATION, TOKEN_PURPOSE_PASSWORD_RESET,
+};
+use crate::repository::{UserRepository, UserSessionRepository, UserTokenRepository};
+use crate::services::{LoginProtectionService, OutboxService, PasswordService};
+use crate::utils::{
+    email_verification_ttl, format_timestamp, generate_secret_token, hash_secret_token,
+    password_reset_ttl,
+};
+use crate::AppState;
+
+pub struct AccountService;
+
+impl AccountService {
+    /// Whether new accounts stay `pending_verification` until their email is verified
+    /// (`REQUIRE_EMAIL_VERIFICATION`, default false)
+    pub fn email_verification_required() -> bool {
+        env::var("REQUIRE_EMAIL_VERIFICATION")
+            .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false)
+    }
+
+    /// Email a password reset link if the address belongs to an account that may log in
+    ///
+    /// The response is the same whether or not the account exists, so it cannot be used to probe for emails.
+    pub async fn forgot_password(
+        pool: &PgPool,
+        request: ForgotPasswordRequest,
+    ) -> Result<AccountMessageResponse, AppError> {
+        let response = AccountMessageResponse {
+            message: "If an account exists for this email, a password reset link has been sent".to_string(),
+        };
+
+        let user = match UserRepository::find_by_email(pool, &request.email).await? {
+            Some(user) if user.is_active() || user.is_pending_verification() => user,
+            _ => return Ok(response),
+        };
+
+        // Only the most recent link works
+        UserTokenRepository::invalidate_for_user(pool, user.id, TOKEN_PURPOSE_PASSWORD_RESET).await?;
+        let (token, expires_at) =
+            Self::issue_token(pool, &user, TOKEN_PURPOSE_PASSWORD_RESET, password_reset_ttl()).await?;
+
+        let payload = serde_json::json!({
+            "name": user.name,
+            "reset_url": OutboxService::app_url(&format!("reset-password?token={}", token)),
+            "expires_at": expires_at,
+        });
+        OutboxService::queue(pool, &user.email, EMAIL_TEMPLATE_PASSWORD_RESET, "Reset your password", payload).await?;
+
+        Ok(response)
+    }
+
+    /// Set a new password with a reset token, signing the user out everywhere
+    ///
+    /// Using the emailed link also proves control of the address, so it verifies a pending account
+    /// and lifts any login lockout.
+    pub async fn reset_password(
+        state: &AppState,
+        request: ResetPasswordRequest,
+    ) -> Result<AccountMessageResponse, AppError> {
+        // Reject a weak or reused password before spending the token
+        let token_hash = hash_secret_token(request.token.trim());
+        let token = UserTokenRepository::find_active(&state.db, TOKEN_PURPOSE_PASSWORD_RESET, &token_hash)
+            .await?
+            .ok_or_else(|| AppError::ValidationError("Invalid or expired token".to_string()))?;
+        let hashed_password =
+            PasswordService::hash_replacement(&state.db, token.user_id, "new_password", &request.new_password).await?;
+
+        // The token is spent only if the password, verification, unlock and sign-out all commit with it
+        let mut uow = state.unit_of_work().await?;
+        let token = Self::consume_token(uow.conn(), TOKEN_PURPOSE_PASSWORD_RESET, &request.token).await?;
+
+        PasswordService::set_password(uow.conn(), token.user_id, &hashed_password).await?;
+        let user = UserRepository::mark_email_verified(uow.conn(), token.user_id).await?;
+        LoginProtectionService::reset_account(uow.conn(), &user.email).await?;
+        let revoked_sessions = UserSessionRepository::revoke_all_for_user(uow.conn(), token.user_id).await?;
+        uow.commit().await?;
+
+        tracing::info!("User {} reset their password, revoking {} session(s)", token.user_id, revoked_sessions);
+        Ok(AccountMessageResponse {
+            message: "Password has been reset; log in with the new password".to_string(),
+        })
+    }
+
+    /// Verify an email address with the token from the verification email
+    pub async fn verify_email(state: &AppState, request: VerifyEmailRequest) -> Result<AccountMessageResponse, AppError> {
+        let mut uow = state.unit_of_work().await?;
+        let token = Self::consume_token(uow.conn(), TOKEN_PURPOSE_EMAIL_VERIFICATION, &request.token).await?;
+        let user = UserRepository::mark_email_verified(uow.conn(), token.user_id).await?;
+        uow.commit().await?;
+
+        tracing::info!("User {} verified their email address", user.id);
+        Ok(AccountMessageResponse {
+            message: "Email address verified".to_string(),
+        })
+    }
+
+    /// Email a verification link to a newly registered user
+    pub async fn send_verification_email(pool: &PgPool, user: &User) -> Result<(), AppError> {
+        UserTokenRepository::invalidate_for_user(pool, user.id, TOKEN_PURPOSE_EMAIL_VERIFICATION).await?;
+        let (token, expires_at) =
+            Self::issue_token(pool, user, TOKEN_PURPOSE_EMAIL_VERIFICATION, email_verification_ttl()).await?;
+
+        let payload = serde_json::json!({
+            "name": user.name,
+            "verify_url": OutboxService::app_url(&format!("verify-email?token={}", token)),
+            "expires_at": expires_at,
+        });
+        OutboxService::queue(
+            pool,
+            &user.email,
+            EMAIL_TEMPLATE_EMAIL_VERIFICATION,
+            "Verify your email address",
+            payload,
+        )
+        .await
+    }
+
+    /// Store the hash of a new token, returning the token and its formatted expiry
+    async fn issue_token(
+        pool: &PgPool,
+        user: &User,
+        purpose: &str,
+        ttl: chrono::Duration,
+    ) -> Result<(String, String), AppError> {
+        let token = generate_secret_token();
+        let user_token = UserToken::new(user.id, purpose, hash_secret_token(&token), Utc::now() + ttl);
+        let user_token = UserTokenRepository::create(pool, &user_token).await?;
+
+        Ok((token, format_timestamp(Some(user_token.expires_at))))
+    }
+
+    async fn consume_token(executor: impl PgExecutor<'_>, purpose: &str, token: &str) -> Result<UserToken, AppError> {
+        UserTokenRepository::consume(executor, purpose, &hash_secret_token(token.trim()))
+            .await?
+            .ok_or_else(|| AppError::ValidationError("Invalid or expired token".to_string()))
+    }
+}
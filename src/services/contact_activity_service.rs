@@ -0,0 +1,206 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::contact_activity_dto::{
+    ActivityResponse, CompleteActivityRequest, CreateActivityRequest, TimelineQueryParams, TimelineResponse,
+    UpdateActivityRequest,
+};
+use crate::dto::contact_filter_dto::PaginationInfo;
+use crate::errors::AppError;
+use crate::models::ContactActivity;
+use crate::repository::{ContactActivityRepository, ContactRepository};
+
+const DEFAULT_TIMELINE_LIMIT: u32 = 20;
+const MAX_TIMELINE_LIMIT: u32 = 100;
+const DEFAULT_QUEUE_LIMIT: u32 = 50;
+
+pub struct ContactActivityService;
+
+impl ContactActivityService {
+    /// Log or schedule an activity for a contact
+    pub async fn create_activity(
+        pool: &PgPool,
+        org_id: Uuid,
+        contact_id: Uuid,
+        request: CreateActivityRequest,
+        created_by: Uuid,
+    ) -> Result<ActivityResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Activity validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        if ContactRepository::find_by_id(pool, org_id, contact_id).await?.is_none() {
+            return Err(AppError::NotFound("Contact not found".to_string()));
+        }
+
+        let mut activity = ContactActivity::new(org_id, contact_id, request.activity_type, Some(created_by));
+        activity.subject = request.subject;
+        activity.description = request.description;
+        activity.activity_date = request.activity_date;
+        activity.duration_minutes = request.duration_minutes;
+        activity.status = match request.status {
+            Some(status) => status,
+            None if request.activity_date.is_some_and(|date| date > Utc::now()) => "scheduled".to_string(),
+            None => "completed".to_string(),
+        };
+
+        if activity.status == "scheduled" && activity.activity_date.is_none() {
+            return Err(AppError::ValidationError(
+                "Scheduled activities require an activity_date".to_string(),
+            ));
+        }
+
+        let created_activity = ContactActivityRepository::create(pool, &activity).await?;
+        Ok(ActivityResponse::from(created_activity))
+    }
+
+    /// Get a single activity
+    pub async fn get_activity(pool: &PgPool, org_id: Uuid, activity_id: Uuid) -> Result<ActivityResponse, AppError> {
+        let activity = Self::find_activity(pool, org_id, activity_id).await?;
+        Ok(ActivityResponse::from(activity))
+    }
+
+    /// Update an activity (only provided fields are changed)
+    pub async fn update_activity(
+        pool: &PgPool,
+        org_id: Uuid,
+        activity_id: Uuid,
+        request: UpdateActivityRequest,
+    ) -> Result<ActivityResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Activity validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let mut activity = Self::find_activity(pool, org_id, activity_id).await?;
+
+        if let Some(activity_type) = request.activity_type {
+            activity.activity_type = activity_type;
+        }
+        if let Some(subject) = request.subject {
+            activity.subject = Some(subject);
+        }
+        if let Some(description) = request.description {
+            activity.description = Some(description);
+        }
+        if let Some(activity_date) = request.activity_date {
+            activity.activity_date = Some(activity_date);
+        }
+        if let Some(duration_minutes) = request.duration_minutes {
+            activity.duration_minutes = Some(duration_minutes);
+        }
+        if let Some(status) = request.status {
+            activity.status = status;
+        }
+
+        if activity.status == "scheduled" && activity.activity_date.is_none() {
+            return Err(AppError::ValidationError(
+                "Scheduled activities require an activity_date".to_string(),
+            ));
+        }
+
+        let updated_activity = ContactActivityRepository::update(pool, &activity).await?;
+        Ok(ActivityResponse::from(updated_activity))
+    }
+
+    /// Mark an activity as completed
+    pub async fn complete_activity(
+        pool: &PgPool,
+        org_id: Uuid,
+        activity_id: Uuid,
+        request: CompleteActivityRequest,
+    ) -> Result<ActivityResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Activity completion validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let mut activity = Self::find_activity(pool, org_id, activity_id).await?;
+
+        if activity.status == "cancelled" {
+            return Err(AppError::ValidationError("Cancelled activities cannot be completed".to_string()));
+        }
+
+        activity.complete();
+        if let Some(duration_minutes) = request.duration_minutes {
+            activity.duration_minutes = Some(duration_minutes);
+        }
+        if let Some(description) = request.description {
+            activity.description = Some(description);
+        }
+
+        let updated_activity = ContactActivityRepository::update(pool, &activity).await?;
+        Ok(ActivityResponse::from(updated_activity))
+    }
+
+    /// Delete an activity
+    pub async fn delete_activity(pool: &PgPool, org_id: Uuid, activity_id: Uuid) -> Result<(), AppError> {
+        ContactActivityRepository::delete(pool, org_id, activity_id).await
+    }
+
+    /// Paginated activity timeline of a contact, most recent first
+    pub async fn get_timeline(
+        pool: &PgPool,
+        org_id: Uuid,
+        contact_id: Uuid,
+        params: TimelineQueryParams,
+    ) -> Result<TimelineResponse, AppError> {
+        if ContactRepository::find_by_id(pool, org_id, contact_id).await?.is_none() {
+            return Err(AppError::NotFound("Contact not found".to_string()));
+        }
+
+        let page = params.page.unwrap_or(1).max(1);
+        let limit = params.limit.unwrap_or(DEFAULT_TIMELINE_LIMIT).clamp(1, MAX_TIMELINE_LIMIT);
+        let offset = (page - 1) as i64 * limit as i64;
+        let activity_type = params.activity_type.as_deref();
+
+        let activities = ContactActivityRepository::find_by_contact(
+            pool,
+            org_id,
+            contact_id,
+            activity_type,
+            limit as i64,
+            offset,
+        ).await?;
+        let total_count = ContactActivityRepository::count_by_contact(pool, org_id, contact_id, activity_type).await? as u64;
+
+        Ok(TimelineResponse {
+            data: activities.into_iter().map(ActivityResponse::from).collect(),
+            pagination: PaginationInfo::new(page, limit, total_count),
+            total_count,
+        })
+    }
+
+    /// Scheduled activities of an owner that are still ahead
+    pub async fn get_upcoming(
+        pool: &PgPool,
+        org_id: Uuid,
+        owner_id: Uuid,
+        limit: Option<u32>,
+    ) -> Result<Vec<ActivityResponse>, AppError> {
+        let limit = limit.unwrap_or(DEFAULT_QUEUE_LIMIT).clamp(1, MAX_TIMELINE_LIMIT) as i64;
+        let activities = ContactActivityRepository::find_upcoming(pool, org_id, owner_id, Utc::now(), limit).await?;
+        Ok(activities.into_iter().map(ActivityResponse::from).collect())
+    }
+
+    /// Scheduled activities of an owner whose date has passed
+    pub async fn get_overdue(
+        pool: &PgPool,
+        org_id: Uuid,
+        owner_id: Uuid,
+        limit: Option<u32>,
+    ) -> Result<Vec<ActivityResponse>, AppError> {
+        let limit = limit.unwrap_or(DEFAULT_QUEUE_LIMIT).clamp(1, MAX_TIMELINE_LIMIT) as i64;
+        let activities = ContactActivityRepository::find_overdue(pool, org_id, owner_id, Utc::now(), limit).await?;
+        Ok(activities.into_iter().map(ActivityResponse::from).collect())
+    }
+
+    async fn find_activity(pool: &PgPool, org_id: Uuid, activity_id: Uuid) -> Result<ContactActivity, AppError> {
+        ContactActivityRepository::find_by_id(pool, org_id, activity_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Activity not found".to_string()))
+    }
+}
@@ -0,0 +1,405 @@
+// Contact bulk service - One action applied to many contacts, in the request or by a worker
+
+use chrono::Utc;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::contact_bulk_dto::*;
+use crate::errors::AppError;
+use crate::models::{Contact, ContactBulkJob, PermissionScope, PermissionSet};
+use crate::repository::{
+    ContactBulkJobRepository, ContactCustomValueRepository, ContactRepository, ContactTagRepository,
+    LeadStatusRepository, UserOrganizationRepository,
+};
+use crate::services::contact_filter_service::ContactFilterService;
+use crate::services::custom_field_validation_service::{CustomFieldValidationService, CustomFieldWriteMode};
+use crate::services::{
+    ContactService, ContactTagService, PermissionService, CONTACTS_ASSIGN_OWNER, CONTACTS_BULK_UPDATE,
+    CONTACTS_DELETE, CONTACTS_UPDATE,
+};
+use crate::utils::positive_int_from_env;
+use crate::AppState;
+
+/// Selections larger than this are queued as a job unless `CONTACT_BULK_SYNC_LIMIT` says otherwise
+const DEFAULT_SYNC_LIMIT: i64 = 500;
+
+const ITEM_SUCCEEDED: &str = "succeeded";
+const ITEM_FAILED: &str = "failed";
+
+/// What submitting a bulk request led to
+#[derive(Debug)]
+pub enum BulkContactOutcome {
+    DryRun(BulkDryRunResponse),
+    Completed(BulkContactResponse),
+    Queued(ContactBulkJob),
+}
+
+pub struct ContactBulkService;
+
+impl ContactBulkService {
+    /// Run a bulk request, or queue it as a job when the selection exceeds the synchronous limit
+    ///
+    /// Each contact is changed in its own transaction, so one failing contact never undoes
+    /// the others; its failure is reported in the results instead.
+    pub async fn submit(
+        state: &AppState,
+        org_id: Uuid,
+        user_id: Uuid,
+        permissions: &PermissionSet,
+        request: BulkContactRequest,
+    ) -> Result<BulkContactOutcome, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Bulk contact validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        Self::check_request(&state.db, org_id, permissions, &request).await?;
+
+        let contact_ids = Self::select(&state.db, org_id, user_id, permissions, &request).await?;
+        let queued = contact_ids.len() > Self::sync_limit();
+
+        if request.dry_run {
+            let contacts = ContactRepository::find_by_ids_including_inactive(&state.db, org_id, &contact_ids).await?;
+            let affected = contacts
+                .iter()
+                .filter(|contact| Self::check_contact(contact, user_id, permissions, &request.action).is_ok())
+                .count();
+
+            return Ok(BulkContactOutcome::DryRun(BulkDryRunResponse {
+                matched: contact_ids.len(),
+                affected,
+                queued,
+            }));
+        }
+
+        if queued {
+            let payload = serde_json::to_value(&request)
+                .map_err(|e| AppError::InternalServerError(format!("Failed to queue bulk request: {}", e)))?;
+            let job = ContactBulkJobRepository::create(&state.db, &ContactBulkJob::new(org_id, user_id, payload)).await?;
+
+            tracing::info!(
+                "Queued bulk contact job {} for {} contacts in organization {}",
+                job.id,
+                contact_ids.len(),
+                org_id
+            );
+            return Ok(BulkContactOutcome::Queued(job));
+        }
+
+        let results = Self::apply(state, org_id, user_id, permissions, &request.action, &contact_ids).await;
+        Ok(BulkContactOutcome::Completed(Self::summarize(results)))
+    }
+
+    /// Get a queued bulk job of the organization
+    pub async fn get_job(pool: &PgPool, org_id: Uuid, job_id: Uuid) -> Result<ContactBulkJob, AppError> {
+        ContactBulkJobRepository::find_by_id(pool, org_id, job_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Bulk job not found".to_string()))
+    }
+
+    /// Process a job claimed by the worker and record its outcome
+    ///
+    /// The selection is resolved again and the requester's current permissions apply.
+    pub async fn run_job(state: &AppState, job: &ContactBulkJob) -> Result<ContactBulkJob, AppError> {
+        match Self::process_job(state, job).await {
+            Ok(response) => {
+                let failures: Vec<BulkItemResult> = response
+                    .results
+                    .into_iter()
+                    .filter(|result| result.status == ITEM_FAILED)
+                    .collect();
+                let failures = serde_json::to_value(failures)
+                    .map_err(|e| AppError::InternalServerError(format!("Failed to record bulk job failures: {}", e)))?;
+
+                ContactBulkJobRepository::complete(&state.db, job.id, response.succeeded as i32, response.failed as i32, failures)
+                    .await?;
+            }
+            Err(error) => {
+                tracing::warn!("Bulk contact job {} failed: {:?}", job.id, error);
+                ContactBulkJobRepository::fail(&state.db, job.id, &error.summary()).await?;
+            }
+        }
+
+        Self::get_job(&state.db, job.org_id, job.id).await
+    }
+
+    async fn process_job(state: &AppState, job: &ContactBulkJob) -> Result<BulkContactResponse, AppError> {
+        let request: BulkContactRequest = serde_json::from_value(job.request.clone())
+            .map_err(|e| AppError::InternalServerError(format!("Unreadable bulk job request: {}", e)))?;
+        let user_id = job
+            .requested_by
+            .ok_or_else(|| AppError::Forbidden("The user who requested the job no longer exists".to_string()))?;
+
+        let permissions = PermissionService::permission_set(&state.db, user_id, job.org_id).await?;
+        if !permissions.allows(CONTACTS_BULK_UPDATE) {
+            return Err(AppError::Forbidden(format!("Permission '{}' required", CONTACTS_BULK_UPDATE)));
+        }
+
+        Self::check_request(&state.db, job.org_id, &permissions, &request).await?;
+
+        let contact_ids = Self::select(&state.db, job.org_id, user_id, &permissions, &request).await?;
+        let results = Self::apply(state, job.org_id, user_id, &permissions, &request.action, &contact_ids).await;
+
+        Ok(Self::summarize(results))
+    }
+
+    /// Reject requests that would fail for every contact before any is touched
+    async fn check_request(
+        pool: &PgPool,
+        org_id: Uuid,
+        permissions: &PermissionSet,
+        request: &BulkContactRequest,
+    ) -> Result<(), AppError> {
+        if request.ids.is_some() == request.filter.is_some() {
+            return Err(AppError::field("ids", "selector", "Provide either ids or a filter"));
+        }
+
+        if matches!(request.action, BulkContactAction::Restore) && request.filter.is_some() {
+            return Err(AppError::field(
+                "filter",
+                "unsupported",
+                "Restoring needs explicit ids; filters only match active contacts",
+            ));
+        }
+
+        let permission = Self::required_permission(&request.action);
+        if permissions.scope(permission).is_none() {
+            return Err(AppError::Forbidden(format!("Permission '{}' required", permission)));
+        }
+
+        match &request.action {
+            BulkContactAction::UpdateFields { fields } => {
+                fields.validate()?;
+                if fields.is_empty() {
+                    return Err(AppError::field("action.fields", "required", "At least one field is required"));
+                }
+            }
+            BulkContactAction::SetCustomFields { custom_fields } => {
+                if custom_fields.is_empty() {
+                    return Err(AppError::field("action.custom_fields", "required", "At least one custom field is required"));
+                }
+                let field_definitions = ContactCustomValueRepository::get_contact_custom_fields(pool, org_id).await?;
+                CustomFieldValidationService::validate_values(Uuid::nil(), &field_definitions, custom_fields, CustomFieldWriteMode::Patch)?;
+            }
+            BulkContactAction::ChangeOwner { owner_id } => {
+                let membership = UserOrganizationRepository::find_by_user_and_org(pool, *owner_id, org_id).await?;
+                if !membership.map(|membership| membership.status == "active").unwrap_or(false) {
+                    return Err(AppError::field(
+                        "action.owner_id",
+                        "not_member",
+                        "The new owner must be an active member of the organization",
+                    ));
+                }
+            }
+            BulkContactAction::ChangeLeadStatus { lead_status } => {
+                let statuses = LeadStatusRepository::find_by_org(pool, org_id).await?;
+                ContactService::check_lead_status("action.lead_status", &statuses, lead_status)?;
+            }
+            BulkContactAction::AddTags { tag_ids } | BulkContactAction::RemoveTags { tag_ids } => {
+                if tag_ids.is_empty() || tag_ids.len() > 100 {
+                    return Err(AppError::field("action.tag_ids", "length", "Between 1 and 100 tag IDs are required"));
+                }
+                ContactTagService::ensure_tags_exist(pool, org_id, tag_ids).await?;
+            }
+            BulkContactAction::Delete | BulkContactAction::Restore => {}
+        }
+
+        Ok(())
+    }
+
+    /// IDs of the selected contacts, in request order for explicit ids
+    ///
+    /// A filter only selects the caller's own contacts when the action's permission is
+    /// limited to owned contacts, and may not match more than `MAX_BULK_SELECTION` of them.
+    async fn select(
+        pool: &PgPool,
+        org_id: Uuid,
+        user_id: Uuid,
+        permissions: &PermissionSet,
+        request: &BulkContactRequest,
+    ) -> Result<Vec<Uuid>, AppError> {
+        if let Some(ids) = &request.ids {
+            let mut seen = HashSet::new();
+            return Ok(ids.iter().copied().filter(|id| seen.insert(*id)).collect());
+        }
+
+        let Some(filter) = &request.filter else {
+            return Ok(Vec::new());
+        };
+
+        let owner_id = match permissions.scope(Self::required_permission(&request.action)) {
+            Some(PermissionScope::Own) => Some(user_id),
+            _ => None,
+        };
+        let contact_ids = ContactFilterService::matching_ids(pool, org_id, owner_id, filter, MAX_BULK_SELECTION + 1).await?;
+        if contact_ids.len() > MAX_BULK_SELECTION {
+            return Err(AppError::field(
+                "filter",
+                "too_many_matches",
+                format!("The filter matches more than {} contacts; narrow it down", MAX_BULK_SELECTION),
+            ));
+        }
+        Ok(contact_ids)
+    }
+
+    async fn apply(
+        state: &AppState,
+        org_id: Uuid,
+        user_id: Uuid,
+        permissions: &PermissionSet,
+        action: &BulkContactAction,
+        contact_ids: &[Uuid],
+    ) -> Vec<BulkItemResult> {
+        let mut results = Vec::with_capacity(contact_ids.len());
+
+        for contact_id in contact_ids {
+            let result = match Self::apply_one(state, org_id, user_id, permissions, action, *contact_id).await {
+                Ok(()) => BulkItemResult {
+                    contact_id: *contact_id,
+                    status: ITEM_SUCCEEDED.to_string(),
+                    code: None,
+                    error: None,
+                },
+                Err(error) => {
+                    tracing::warn!("Bulk action on contact {} failed: {:?}", contact_id, error);
+                    BulkItemResult {
+                        contact_id: *contact_id,
+                        status: ITEM_FAILED.to_string(),
+                        code: Some(error.code().to_string()),
+                        error: Some(error.summary()),
+                    }
+                }
+            };
+            results.push(result);
+        }
+
+        results
+    }
+
+    async fn apply_one(
+        state: &AppState,
+        org_id: Uuid,
+        user_id: Uuid,
+        permissions: &PermissionSet,
+        action: &BulkContactAction,
+        contact_id: Uuid,
+    ) -> Result<(), AppError> {
+        let mut uow = state.unit_of_work().await?;
+
+        let mut contact = ContactRepository::lock_by_id(uow.conn(), org_id, contact_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Contact not found".to_string()))?;
+        Self::check_contact(&contact, user_id, permissions, action)?;
+
+        match action {
+            BulkContactAction::UpdateFields { fields } => {
+                Self::apply_fields(&mut contact, fields);
+                contact.updated_at = Utc::now();
+                ContactRepository::update(uow.conn(), &contact).await?;
+            }
+            BulkContactAction::SetCustomFields { custom_fields } => {
+                let field_definitions = ContactCustomValueRepository::get_contact_custom_fields(uow.conn(), org_id).await?;
+                let custom_values = CustomFieldValidationService::validate_values(
+                    contact.id,
+                    &field_definitions,
+                    custom_fields,
+                    CustomFieldWriteMode::Patch,
+                )?;
+                ContactService::write_custom_values(uow.conn(), org_id, contact.id, custom_values).await?;
+            }
+            BulkContactAction::ChangeOwner { owner_id } => {
+                ContactRepository::set_owner(uow.conn(), org_id, contact.id, *owner_id).await?;
+            }
+            BulkContactAction::ChangeLeadStatus { lead_status } => {
+                contact.lead_status = lead_status.clone();
+                contact.updated_at = Utc::now();
+                ContactRepository::update(uow.conn(), &contact).await?;
+            }
+            BulkContactAction::AddTags { tag_ids } => {
+                ContactTagRepository::assign(uow.conn(), org_id, &[contact.id], tag_ids, user_id).await?;
+            }
+            BulkContactAction::RemoveTags { tag_ids } => {
+                ContactTagRepository::unassign(uow.conn(), org_id, &[contact.id], tag_ids).await?;
+            }
+            BulkContactAction::Delete => {
+                ContactRepository::soft_delete(uow.conn(), org_id, contact.id).await?;
+            }
+            BulkContactAction::Restore => {
+                ContactRepository::restore(uow.conn(), org_id, contact.id).await?;
+            }
+        }
+
+        uow.commit().await
+    }
+
+    /// Whether the action can be applied to this contact by the caller
+    fn check_contact(
+        contact: &Contact,
+        user_id: Uuid,
+        permissions: &PermissionSet,
+        action: &BulkContactAction,
+    ) -> Result<(), AppError> {
+        match (action, contact.is_active) {
+            (BulkContactAction::Restore, true) => {
+                return Err(AppError::Conflict("Contact is not deleted".to_string()));
+            }
+            (BulkContactAction::Restore, false) => {}
+            (_, false) => return Err(AppError::NotFound("Contact not found".to_string())),
+            (_, true) => {}
+        }
+
+        if !permissions.allows_resource(Self::required_permission(action), user_id, contact.owner_id) {
+            return Err(AppError::Forbidden("You can only change contacts you own".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Set the given fields, clearing those given as empty strings
+    fn apply_fields(contact: &mut Contact, fields: &BulkContactFields) {
+        let updates = [
+            (&mut contact.phone, &fields.phone),
+            (&mut contact.company, &fields.company),
+            (&mut contact.job_title, &fields.job_title),
+            (&mut contact.address, &fields.address),
+            (&mut contact.city, &fields.city),
+            (&mut contact.state, &fields.state),
+            (&mut contact.postal_code, &fields.postal_code),
+            (&mut contact.country, &fields.country),
+            (&mut contact.notes, &fields.notes),
+            (&mut contact.lead_source, &fields.lead_source),
+        ];
+
+        for (current, update) in updates {
+            if let Some(value) = update {
+                *current = if value.trim().is_empty() { None } else { Some(value.clone()) };
+            }
+        }
+    }
+
+    fn required_permission(action: &BulkContactAction) -> &'static str {
+        match action {
+            BulkContactAction::ChangeOwner { .. } => CONTACTS_ASSIGN_OWNER,
+            BulkContactAction::Delete | BulkContactAction::Restore => CONTACTS_DELETE,
+            _ => CONTACTS_UPDATE,
+        }
+    }
+
+    fn summarize(results: Vec<BulkItemResult>) -> BulkContactResponse {
+        let succeeded = results.iter().filter(|result| result.status == ITEM_SUCCEEDED).count();
+
+        BulkContactResponse {
+            total: results.len(),
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        }
+    }
+
+    /// Most contacts changed within a request (`CONTACT_BULK_SYNC_LIMIT`, default 500)
+    pub fn sync_limit() -> usize {
+        positive_int_from_env("CONTACT_BULK_SYNC_LIMIT", DEFAULT_SYNC_LIMIT) as usize
+    }
+}
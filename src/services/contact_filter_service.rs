@@ -1,7 +1,9 @@
 use crate::dto::contact_filter_dto::*;
 use crate::errors::AppError;
-use sqlx::PgPool;
+use sqlx::postgres::PgArguments;
+use sqlx::{Arguments, PgPool};
 use std::collections::HashMap;
+use uuid::Uuid;
 use validator::Validate;
 
 pub struct ContactFilterService;
@@ -28,12 +30,14 @@ impl QueryBuilder {
     
     pub fn build_filter_query(
         &mut self,
+        org_id: Uuid,
+        owner_id: Option<Uuid>,
         filter: &ContactFilterRequest,
     ) -> Result<(String, Vec<serde_json::Value>), AppError> {
         // Start with base query
         let mut base_query = String::from(
             r#"
-            SELECT DISTINCT 
+            SELECT
                 c.id,
                 c.first_name,
                 c.last_name,
@@ -64,6 +68,7 @@ impl QueryBuilder {
         );
         
         // Build WHERE clause
+        let scope_clause = self.build_scope_clause(org_id, owner_id);
         if !filter.conditions.is_empty() {
             let root_filter = FilterNode::Group {
                 logic: filter.logic.clone(),
@@ -71,9 +76,9 @@ impl QueryBuilder {
             };
             
             let where_clause = self.build_where_clause(&root_filter)?;
-            base_query.push_str(&format!(" WHERE c.is_active = true AND ({})", where_clause));
+            base_query.push_str(&format!(" WHERE {} AND ({})", scope_clause, where_clause));
         } else {
-            base_query.push_str(" WHERE c.is_active = true");
+            base_query.push_str(&format!(" WHERE {}", scope_clause));
         }
         
         // Add GROUP BY for custom fields aggregation
@@ -111,6 +116,25 @@ impl QueryBuilder {
         Ok((base_query, self.parameters.clone()))
     }
     
+    /// Restrict the query to active contacts of the caller's organization, and to contacts
+    /// owned by `owner_id` when given
+    fn build_scope_clause(&mut self, org_id: Uuid, owner_id: Option<Uuid>) -> String {
+        let org_param = format!("${}", self.param_counter);
+        self.param_counter += 1;
+        self.parameters.push(serde_json::Value::String(org_id.to_string()));
+
+        let mut clause = format!("c.is_active = true AND c.org_id = {}::uuid", org_param);
+
+        if let Some(owner_id) = owner_id {
+            let owner_param = format!("${}", self.param_counter);
+            self.param_counter += 1;
+            self.parameters.push(serde_json::Value::String(owner_id.to_string()));
+            clause.push_str(&format!(" AND c.owner_id = {}::uuid", owner_param));
+        }
+
+        clause
+    }
+
     fn build_where_clause(&mut self, node: &FilterNode) -> Result<String, AppError> {
         match node {
             FilterNode::Condition { field, operator, value, field_type } => {
@@ -133,7 +157,46 @@ impl QueryBuilder {
                 
                 Ok(format!("({})", clauses.join(logic_op)))
             }
+            FilterNode::Tags { match_mode, tags } => self.build_tags_condition(match_mode, tags),
+        }
+    }
+
+    fn build_tags_condition(&mut self, match_mode: &TagMatch, tags: &[String]) -> Result<String, AppError> {
+        let mut names: Vec<&str> = tags.iter().map(|tag| tag.trim()).filter(|tag| !tag.is_empty()).collect();
+        names.sort_unstable();
+        names.dedup();
+
+        if names.is_empty() {
+            return Err(AppError::ValidationError("Tags filter requires at least one tag name".to_string()));
         }
+
+        let mut placeholders = Vec::new();
+        for name in &names {
+            placeholders.push(format!("${}", self.param_counter));
+            self.param_counter += 1;
+            self.parameters.push(serde_json::Value::String(name.to_string()));
+        }
+
+        let tagged = format!(
+            r#"SELECT 1 FROM contact_tag_assignments cta
+                    JOIN contact_tags ct ON cta.tag_id = ct.id
+                    WHERE cta.contact_id = c.id
+                    AND ct.org_id = c.org_id
+                    AND ct.name IN ({})"#,
+            placeholders.join(", ")
+        );
+
+        let condition = match match_mode {
+            TagMatch::Any => format!("EXISTS ({})", tagged),
+            TagMatch::None => format!("NOT EXISTS ({})", tagged),
+            TagMatch::All => format!(
+                "(SELECT COUNT(DISTINCT ct.name) FROM contact_tag_assignments cta JOIN contact_tags ct ON cta.tag_id = ct.id WHERE cta.contact_id = c.id AND ct.org_id = c.org_id AND ct.name IN ({})) = {}",
+                placeholders.join(", "),
+                names.len()
+            ),
+        };
+
+        Ok(condition)
     }
     
     fn build_condition_clause(
@@ -362,8 +425,12 @@ impl QueryBuilder {
 
 impl ContactFilterService {
     /// Filter contacts with complex nested conditions
+    ///
+    /// `owner_id` limits the results to that user's contacts (callers holding only `contacts:read_own`).
     pub async fn filter_contacts(
         pool: &PgPool,
+        org_id: Uuid,
+        owner_id: Option<Uuid>,
         filter_request: ContactFilterRequest,
     ) -> Result<ContactFilterResponse, AppError> {
         let start_time = std::time::Instant::now();
@@ -371,12 +438,12 @@ impl ContactFilterService {
         // Validate the filter request
         filter_request.validate().map_err(|e| {
             tracing::warn!("Contact filter validation failed: {:?}", e);
-            AppError::ValidationError(e.to_string())
+            AppError::from(e)
         })?;
 
         // Build the query
         let mut query_builder = QueryBuilder::new();
-        let (sql_query, parameters) = query_builder.build_filter_query(&filter_request)?;
+        let (sql_query, parameters) = query_builder.build_filter_query(org_id, owner_id, &filter_request)?;
 
         tracing::info!("Generated SQL query: {}", sql_query);
         tracing::debug!("Query parameters: {:?}", parameters);
@@ -385,7 +452,7 @@ impl ContactFilterService {
         let contacts = Self::execute_filter_query(pool, &sql_query, &parameters).await?;
 
         // Get total count for pagination
-        let total_count = Self::get_total_count(pool, &filter_request).await?;
+        let total_count = Self::get_total_count(pool, org_id, owner_id, &filter_request).await?;
 
         // Create pagination info
         let pagination = PaginationInfo::new(filter_request.page, filter_request.limit, total_count);
@@ -414,26 +481,7 @@ impl ContactFilterService {
         sql_query: &str,
         parameters: &[serde_json::Value],
     ) -> Result<Vec<ContactSummary>, AppError> {
-        let mut query = sqlx::query_as::<_, ContactSummaryRow>(sql_query);
-
-        // Bind parameters - convert to appropriate types
-        for param in parameters {
-            match param {
-                serde_json::Value::String(s) => query = query.bind(s),
-                serde_json::Value::Number(n) => {
-                    if let Some(i) = n.as_i64() {
-                        query = query.bind(i);
-                    } else if let Some(f) = n.as_f64() {
-                        query = query.bind(f);
-                    } else {
-                        query = query.bind(n.to_string());
-                    }
-                }
-                serde_json::Value::Bool(b) => query = query.bind(b),
-                serde_json::Value::Null => query = query.bind(Option::<String>::None),
-                _ => query = query.bind(param.to_string()),
-            }
-        }
+        let query = sqlx::query_as_with::<_, ContactSummaryRow, _>(sql_query, Self::arguments(parameters));
 
         let rows = query.fetch_all(pool).await.map_err(|e| {
             tracing::error!("Error executing filter query: {}", e);
@@ -461,8 +509,43 @@ impl ContactFilterService {
         Ok(contacts)
     }
 
+    /// IDs of the contacts matching the filter, ignoring its pagination and sorting
+    ///
+    /// At most `limit` IDs are returned, so callers can detect selections larger than they accept.
+    pub async fn matching_ids(
+        pool: &PgPool,
+        org_id: Uuid,
+        owner_id: Option<Uuid>,
+        filter_request: &ContactFilterRequest,
+        limit: usize,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let mut query_builder = QueryBuilder::new();
+
+        let mut id_query = String::from(
+            r#"
+            SELECT DISTINCT c.id
+            FROM contacts c
+            LEFT JOIN contact_custom_values ccv ON c.id = ccv.contact_id
+            LEFT JOIN custom_fields cf ON ccv.custom_field_id = cf.id
+            "#
+        );
+        id_query.push_str(&Self::build_where(&mut query_builder, org_id, owner_id, filter_request)?);
+        id_query.push_str(&format!(" ORDER BY c.id LIMIT {}", limit));
+
+        let query = sqlx::query_scalar_with::<_, Uuid, _>(&id_query, Self::arguments(&query_builder.parameters));
+
+        let ids = query.fetch_all(pool).await.map_err(|e| {
+            tracing::error!("Error executing matching ids query: {}", e);
+            AppError::DatabaseError(e)
+        })?;
+
+        Ok(ids)
+    }
+
     async fn get_total_count(
         pool: &PgPool,
+        org_id: Uuid,
+        owner_id: Option<Uuid>,
         filter_request: &ContactFilterRequest,
     ) -> Result<u64, AppError> {
         let mut query_builder = QueryBuilder::new();
@@ -477,46 +560,59 @@ impl ContactFilterService {
             "#
         );
 
-        // Build WHERE clause
-        if !filter_request.conditions.is_empty() {
-            let root_filter = FilterNode::Group {
-                logic: filter_request.logic.clone(),
-                conditions: filter_request.conditions.clone(),
-            };
+        count_query.push_str(&Self::build_where(&mut query_builder, org_id, owner_id, filter_request)?);
 
-            let where_clause = query_builder.build_where_clause(&root_filter)?;
-            count_query.push_str(&format!(" WHERE c.is_active = true AND ({})", where_clause));
-        } else {
-            count_query.push_str(" WHERE c.is_active = true");
+        let query = sqlx::query_scalar_with::<_, i64, _>(&count_query, Self::arguments(&query_builder.parameters));
+
+        let count = query.fetch_one(pool).await.map_err(|e| {
+            tracing::error!("Error executing count query: {}", e);
+            AppError::DatabaseError(e)
+        })?;
+
+        Ok(count as u64)
+    }
+
+    /// WHERE clause limiting the contacts to the organization, the owner if given, and the filter
+    fn build_where(
+        query_builder: &mut QueryBuilder,
+        org_id: Uuid,
+        owner_id: Option<Uuid>,
+        filter_request: &ContactFilterRequest,
+    ) -> Result<String, AppError> {
+        let scope_clause = query_builder.build_scope_clause(org_id, owner_id);
+        if filter_request.conditions.is_empty() {
+            return Ok(format!(" WHERE {}", scope_clause));
         }
 
-        let mut query = sqlx::query_scalar::<_, i64>(&count_query);
+        let root_filter = FilterNode::Group {
+            logic: filter_request.logic.clone(),
+            conditions: filter_request.conditions.clone(),
+        };
+        let where_clause = query_builder.build_where_clause(&root_filter)?;
+        Ok(format!(" WHERE {} AND ({})", scope_clause, where_clause))
+    }
 
-        // Bind parameters - convert to appropriate types
-        for param in &query_builder.parameters {
+    /// Bind the builder's parameters, converting each to the matching SQL type
+    fn arguments(parameters: &[serde_json::Value]) -> PgArguments {
+        let mut arguments = PgArguments::default();
+        for param in parameters {
             match param {
-                serde_json::Value::String(s) => query = query.bind(s),
+                serde_json::Value::String(s) => arguments.add(s.as_str()),
                 serde_json::Value::Number(n) => {
                     if let Some(i) = n.as_i64() {
-                        query = query.bind(i);
+                        arguments.add(i);
                     } else if let Some(f) = n.as_f64() {
-                        query = query.bind(f);
+                        arguments.add(f);
                     } else {
-                        query = query.bind(n.to_string());
+                        arguments.add(n.to_string());
                     }
                 }
-                serde_json::Value::Bool(b) => query = query.bind(b),
-                serde_json::Value::Null => query = query.bind(Option::<String>::None),
-                _ => query = query.bind(param.to_string()),
+                serde_json::Value::Bool(b) => arguments.add(*b),
+                serde_json::Value::Null => arguments.add(Option::<String>::None),
+                _ => arguments.add(param.to_string()),
             }
         }
-
-        let count = query.fetch_one(pool).await.map_err(|e| {
-            tracing::error!("Error executing count query: {}", e);
-            AppError::DatabaseError(e)
-        })?;
-
-        Ok(count as u64)
+        arguments
     }
 
     fn create_filter_summary(
@@ -547,7 +643,7 @@ impl ContactFilterService {
         let mut count = 0;
         for condition in conditions {
             match condition {
-                FilterNode::Condition { .. } => count += 1,
+                FilterNode::Condition { .. } | FilterNode::Tags { .. } => count += 1,
                 FilterNode::Group { conditions, .. } => {
                     count += Self::count_conditions(conditions);
                 }
@@ -573,6 +669,7 @@ impl ContactFilterService {
                 FilterNode::Group { conditions, .. } => {
                     Self::extract_fields_from_conditions(conditions, fields_used, custom_fields_used);
                 }
+                FilterNode::Tags { .. } => fields_used.push("tags".to_string()),
             }
         }
     }
@@ -0,0 +1,496 @@
+// Contact import service - CSV files of contacts, imported in the request or by a worker
+
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::contact_dto::{CreateContactRequest, UpdateContactRequest};
+use crate::dto::contact_import_dto::*;
+use crate::errors::AppError;
+use crate::models::{ContactImportJob, CustomField, LeadStatus, PermissionScope, PermissionSet};
+use crate::repository::{ContactCustomValueRepository, ContactImportJobRepository, ContactRepository, LeadStatusRepository};
+use crate::services::custom_field_validation_service::{CustomFieldValidationService, CustomFieldWriteMode};
+use crate::services::{ContactService, PermissionService, CONTACTS_IMPORT, CONTACTS_UPDATE};
+use crate::utils::positive_int_from_env;
+use crate::AppState;
+
+/// Contact fields a column can be imported into, besides custom fields
+const IMPORT_FIELDS: &[&str] = &[
+    "first_name", "last_name", "email", "phone", "company", "job_title", "address", "city",
+    "state", "postal_code", "country", "notes", "lead_source", "lead_status",
+];
+
+/// Fields every file must have a column for
+const REQUIRED_IMPORT_FIELDS: &[&str] = &["first_name", "last_name", "email"];
+
+/// Files with more rows are queued as a job unless `CONTACT_IMPORT_SYNC_LIMIT` says otherwise
+const DEFAULT_SYNC_LIMIT: i64 = 1000;
+
+/// Largest accepted upload unless `CONTACT_IMPORT_MAX_BYTES` says otherwise (10 MiB)
+const DEFAULT_MAX_UPLOAD_BYTES: i64 = 10 * 1024 * 1024;
+
+/// Rows between progress updates of a running job
+const PROGRESS_INTERVAL: usize = 100;
+
+/// What uploading a file led to
+#[derive(Debug)]
+pub enum ContactImportOutcome {
+    Completed(ContactImportReport),
+    Queued(ContactImportJob),
+}
+
+/// Where a column's values go
+#[derive(Debug, Clone)]
+enum ImportTarget {
+    Standard(&'static str),
+    Custom(String),
+}
+
+/// The file's columns resolved against the organization's fields
+#[derive(Debug)]
+struct ColumnMapping {
+    /// Target of each column, by position; `None` for ignored columns
+    targets: Vec<Option<ImportTarget>>,
+    headers: Vec<String>,
+}
+
+/// What happened to a row that did not fail
+enum RowOutcome {
+    Created,
+    Updated,
+    Skipped,
+}
+
+pub struct ContactImportService;
+
+impl ContactImportService {
+    /// Import a CSV file, or queue it as a job when it has more rows than the synchronous limit
+    ///
+    /// The column mapping is checked up front; rows are then imported one by one, each with
+    /// the validation of `POST /contacts`, and failing rows are reported without stopping the import.
+    pub async fn import(
+        state: &AppState,
+        org_id: Uuid,
+        user_id: Uuid,
+        permissions: &PermissionSet,
+        file_name: String,
+        content: String,
+        options: ContactImportOptions,
+    ) -> Result<ContactImportOutcome, AppError> {
+        let field_definitions = ContactCustomValueRepository::get_contact_custom_fields(&state.db, org_id).await?;
+        Self::map_columns(&content, &options.mapping, &field_definitions)?;
+
+        let total_rows = Self::reader(&content).records().count();
+        if total_rows == 0 {
+            return Err(AppError::field("file", "empty", "The file has no rows below its header"));
+        }
+
+        if total_rows > Self::sync_limit() {
+            let payload = serde_json::to_value(&options)
+                .map_err(|e| AppError::InternalServerError(format!("Failed to queue import: {}", e)))?;
+            let job = ContactImportJob::new(org_id, user_id, file_name, content, payload, total_rows as i32);
+            let job = ContactImportJobRepository::create(&state.db, &job).await?;
+
+            tracing::info!("Queued contact import job {} for {} rows in organization {}", job.id, total_rows, org_id);
+            return Ok(ContactImportOutcome::Queued(job));
+        }
+
+        let report = Self::process(state, org_id, user_id, permissions, &content, &options, None).await?;
+        Ok(ContactImportOutcome::Completed(report))
+    }
+
+    /// Get an import job of the organization
+    pub async fn get_job(pool: &PgPool, org_id: Uuid, job_id: Uuid) -> Result<ContactImportJob, AppError> {
+        ContactImportJobRepository::find_by_id(pool, org_id, job_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Import job not found".to_string()))
+    }
+
+    /// The rows of a job that could not be imported, as CSV with their line number and error
+    pub async fn error_csv(pool: &PgPool, org_id: Uuid, job_id: Uuid) -> Result<String, AppError> {
+        let job = Self::get_job(pool, org_id, job_id).await?;
+        let errors: Vec<ImportRowError> = serde_json::from_value(job.errors)
+            .map_err(|e| AppError::InternalServerError(format!("Unreadable import errors: {}", e)))?;
+        let headers: Vec<String> = Self::reader(&job.content)
+            .headers()
+            .map(|headers| headers.iter().map(str::to_string).collect())
+            .unwrap_or_default();
+
+        let mut writer = WriterBuilder::new().flexible(true).from_writer(Vec::new());
+        let csv_error = |e: csv::Error| AppError::InternalServerError(format!("Failed to write error CSV: {}", e));
+
+        let mut header_row = vec!["row".to_string()];
+        header_row.extend(headers.iter().cloned());
+        header_row.push("error".to_string());
+        writer.write_record(&header_row).map_err(csv_error)?;
+
+        for error in errors {
+            let mut record = vec![error.row.to_string()];
+            record.extend((0..headers.len()).map(|index| error.values.get(index).cloned().unwrap_or_default()));
+            record.push(error.error);
+            writer.write_record(&record).map_err(csv_error)?;
+        }
+
+        let bytes = writer
+            .into_inner()
+            .map_err(|e| AppError::InternalServerError(format!("Failed to write error CSV: {}", e)))?;
+        String::from_utf8(bytes).map_err(|e| AppError::InternalServerError(format!("Failed to write error CSV: {}", e)))
+    }
+
+    /// Process a job claimed by the worker and record its outcome
+    ///
+    /// The requester's permissions at this time apply.
+    pub async fn run_job(state: &AppState, job: &ContactImportJob) -> Result<ContactImportJob, AppError> {
+        match Self::process_job(state, job).await {
+            Ok(report) => {
+                let errors = serde_json::to_value(&report.errors)
+                    .map_err(|e| AppError::InternalServerError(format!("Failed to record import errors: {}", e)))?;
+                ContactImportJobRepository::complete(&state.db, job.id, report.counts(), errors).await?;
+            }
+            Err(error) => {
+                tracing::warn!("Contact import job {} failed: {:?}", job.id, error);
+                ContactImportJobRepository::fail(&state.db, job.id, &error.summary()).await?;
+            }
+        }
+
+        Self::get_job(&state.db, job.org_id, job.id).await
+    }
+
+    async fn process_job(state: &AppState, job: &ContactImportJob) -> Result<ContactImportReport, AppError> {
+        let options: ContactImportOptions = serde_json::from_value(job.options.clone())
+            .map_err(|e| AppError::InternalServerError(format!("Unreadable import options: {}", e)))?;
+        let user_id = job
+            .requested_by
+            .ok_or_else(|| AppError::Forbidden("The user who requested the import no longer exists".to_string()))?;
+
+        let permissions = PermissionService::permission_set(&state.db, user_id, job.org_id).await?;
+        if !permissions.allows(CONTACTS_IMPORT) {
+            return Err(AppError::Forbidden(format!("Permission '{}' required", CONTACTS_IMPORT)));
+        }
+
+        Self::process(state, job.org_id, user_id, &permissions, &job.content, &options, Some(job.id)).await
+    }
+
+    /// Import every row, storing progress on `job_id` when given
+    async fn process(
+        state: &AppState,
+        org_id: Uuid,
+        user_id: Uuid,
+        permissions: &PermissionSet,
+        content: &str,
+        options: &ContactImportOptions,
+        job_id: Option<Uuid>,
+    ) -> Result<ContactImportReport, AppError> {
+        let field_definitions = ContactCustomValueRepository::get_contact_custom_fields(&state.db, org_id).await?;
+        let mapping = Self::map_columns(content, &options.mapping, &field_definitions)?;
+        let lead_statuses = LeadStatusRepository::find_by_org(&state.db, org_id).await?;
+
+        let mut report = ContactImportReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+        for (header, target) in mapping.headers.iter().zip(&mapping.targets) {
+            match target {
+                Some(ImportTarget::Standard(field)) => report.columns.insert(header.clone(), field.to_string()),
+                Some(ImportTarget::Custom(field_name)) => report.columns.insert(header.clone(), field_name.clone()),
+                None => {
+                    report.ignored_columns.push(header.clone());
+                    None
+                }
+            };
+        }
+
+        let update_scope = permissions.scope(CONTACTS_UPDATE);
+        // Emails a dry run would have created, so later rows with them count as duplicates
+        let mut planned_emails = HashSet::new();
+
+        let mut reader = Self::reader(content);
+        for (index, record) in reader.records().enumerate() {
+            let row = record
+                .as_ref()
+                .ok()
+                .and_then(|record| record.position())
+                .map(|position| position.line())
+                .unwrap_or(index as u64 + 2);
+
+            let outcome = match &record {
+                Ok(record) => {
+                    Self::import_row(
+                        state,
+                        org_id,
+                        user_id,
+                        update_scope,
+                        &mapping,
+                        &field_definitions,
+                        &lead_statuses,
+                        options,
+                        record,
+                        &mut planned_emails,
+                    )
+                    .await
+                }
+                Err(e) => Err(AppError::ValidationError(format!("Unreadable row: {}", e))),
+            };
+
+            match outcome {
+                Ok(RowOutcome::Created) => report.created += 1,
+                Ok(RowOutcome::Updated) => report.updated += 1,
+                Ok(RowOutcome::Skipped) => report.skipped += 1,
+                Err(error) => {
+                    let values: Vec<String> = record
+                        .map(|record| record.iter().map(str::to_string).collect())
+                        .unwrap_or_default();
+                    report.failed += 1;
+                    report.errors.push(ImportRowError {
+                        row,
+                        email: mapping.value(&values, "email"),
+                        code: error.code().to_string(),
+                        error: error.summary(),
+                        values,
+                    });
+                }
+            }
+            report.total_rows += 1;
+
+            if let Some(job_id) = job_id {
+                if report.total_rows.is_multiple_of(PROGRESS_INTERVAL) {
+                    ContactImportJobRepository::update_progress(&state.db, job_id, report.counts()).await?;
+                }
+            }
+        }
+
+        tracing::info!(
+            "Imported contacts into organization {} (dry run: {}): {} created, {} updated, {} skipped, {} failed",
+            org_id,
+            options.dry_run,
+            report.created,
+            report.updated,
+            report.skipped,
+            report.failed
+        );
+
+        Ok(report)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn import_row(
+        state: &AppState,
+        org_id: Uuid,
+        user_id: Uuid,
+        update_scope: Option<PermissionScope>,
+        mapping: &ColumnMapping,
+        field_definitions: &[CustomField],
+        lead_statuses: &[LeadStatus],
+        options: &ContactImportOptions,
+        record: &StringRecord,
+        planned_emails: &mut HashSet<String>,
+    ) -> Result<RowOutcome, AppError> {
+        let values: Vec<String> = record.iter().map(str::to_string).collect();
+        let (standard, custom_fields) = mapping.split(&values);
+
+        let request = CreateContactRequest {
+            first_name: standard.get("first_name").cloned().unwrap_or_default(),
+            last_name: standard.get("last_name").cloned().unwrap_or_default(),
+            email: standard.get("email").cloned().unwrap_or_default(),
+            phone: standard.get("phone").cloned(),
+            company: standard.get("company").cloned(),
+            job_title: standard.get("job_title").cloned(),
+            address: standard.get("address").cloned(),
+            city: standard.get("city").cloned(),
+            state: standard.get("state").cloned(),
+            postal_code: standard.get("postal_code").cloned(),
+            country: standard.get("country").cloned(),
+            notes: standard.get("notes").cloned(),
+            lead_source: standard.get("lead_source").cloned(),
+            lead_status: standard.get("lead_status").cloned(),
+            custom_fields: Some(custom_fields.clone()),
+        };
+        request.validate()?;
+        if let Some(lead_status) = &request.lead_status {
+            ContactService::check_lead_status("lead_status", lead_statuses, lead_status)?;
+        }
+
+        let existing = ContactRepository::find_by_email(&state.db, org_id, &request.email).await?;
+        let planned = planned_emails.contains(&request.email);
+
+        if existing.is_none() && !planned {
+            CustomFieldValidationService::validate_values(Uuid::nil(), field_definitions, &custom_fields, CustomFieldWriteMode::Create)?;
+            if options.dry_run {
+                planned_emails.insert(request.email);
+            } else {
+                ContactService::create_contact(state, org_id, request, user_id).await?;
+            }
+            return Ok(RowOutcome::Created);
+        }
+
+        match options.duplicates {
+            DuplicateStrategy::Skip => Ok(RowOutcome::Skipped),
+            DuplicateStrategy::Create => {
+                // The insert is still attempted, so the row reports the same conflict as `POST /contacts`
+                if options.dry_run {
+                    return Err(AppError::Conflict("A contact with this email already exists".to_string()));
+                }
+                ContactService::create_contact(state, org_id, request, user_id).await?;
+                Ok(RowOutcome::Created)
+            }
+            DuplicateStrategy::Update => {
+                let Some(scope) = update_scope else {
+                    return Err(AppError::Forbidden(format!(
+                        "Permission '{}' required to update existing contacts",
+                        CONTACTS_UPDATE
+                    )));
+                };
+                CustomFieldValidationService::validate_values(Uuid::nil(), field_definitions, &custom_fields, CustomFieldWriteMode::Update)?;
+
+                // A contact planned by this dry run has no row yet and would be owned by the importer
+                let Some(contact) = existing else {
+                    return Ok(RowOutcome::Updated);
+                };
+                if scope == PermissionScope::Own && contact.owner_id != Some(user_id) {
+                    return Err(AppError::Forbidden("You can only update contacts you own".to_string()));
+                }
+
+                if !options.dry_run {
+                    let update = UpdateContactRequest {
+                        first_name: Some(request.first_name),
+                        last_name: Some(request.last_name),
+                        email: None,
+                        phone: request.phone,
+                        company: request.company,
+                        job_title: request.job_title,
+                        address: request.address,
+                        city: request.city,
+                        state: request.state,
+                        postal_code: request.postal_code,
+                        country: request.country,
+                        notes: request.notes,
+                        lead_source: request.lead_source,
+                        lead_status: request.lead_status,
+                        custom_fields: Some(custom_fields),
+                    };
+                    ContactService::update_contact(state, org_id, contact.id, update, user_id, scope).await?;
+                }
+                Ok(RowOutcome::Updated)
+            }
+        }
+    }
+
+    /// Resolve every header to a contact field, custom field or nothing
+    ///
+    /// Explicitly mapped headers use their target; the others match a field by name.
+    fn map_columns(
+        content: &str,
+        explicit: &HashMap<String, String>,
+        field_definitions: &[CustomField],
+    ) -> Result<ColumnMapping, AppError> {
+        let headers: Vec<String> = Self::reader(content)
+            .headers()
+            .map_err(|e| AppError::field("file", "invalid_csv", format!("Unreadable header row: {}", e)))?
+            .iter()
+            .map(|header| header.trim().to_string())
+            .collect();
+
+        let resolve = |name: &str| -> Option<ImportTarget> {
+            if let Some(field) = IMPORT_FIELDS.iter().find(|field| **field == name) {
+                return Some(ImportTarget::Standard(field));
+            }
+            field_definitions
+                .iter()
+                .find(|definition| definition.field_name == name)
+                .map(|definition| ImportTarget::Custom(definition.field_name.clone()))
+        };
+
+        for header in explicit.keys() {
+            if !headers.contains(header) {
+                return Err(AppError::field(format!("mapping.{}", header), "unknown_column", "The file has no such column"));
+            }
+        }
+
+        let mut targets = Vec::with_capacity(headers.len());
+        let mut mapped = HashSet::new();
+        for header in &headers {
+            let target = match explicit.get(header).map(|target| target.trim()) {
+                Some("") => None,
+                Some(target) => Some(resolve(target).ok_or_else(|| {
+                    AppError::field(
+                        format!("mapping.{}", header),
+                        "unknown_field",
+                        format!("'{}' is neither a contact field nor a custom field", target),
+                    )
+                })?),
+                None => resolve(&header.to_lowercase().replace([' ', '-'], "_")),
+            };
+
+            if let Some(target) = &target {
+                let name = match target {
+                    ImportTarget::Standard(field) => field.to_string(),
+                    ImportTarget::Custom(field_name) => field_name.clone(),
+                };
+                if !mapped.insert(name.clone()) {
+                    return Err(AppError::field(
+                        "mapping",
+                        "duplicate_field",
+                        format!("More than one column is mapped to {}", name),
+                    ));
+                }
+            }
+            targets.push(target);
+        }
+
+        for field in REQUIRED_IMPORT_FIELDS {
+            if !mapped.contains(*field) {
+                return Err(AppError::field("mapping", "required", format!("No column is mapped to {}", field)));
+            }
+        }
+
+        Ok(ColumnMapping { targets, headers })
+    }
+
+    /// Reader over the data rows; short rows are allowed and read as empty cells
+    fn reader(content: &str) -> csv::Reader<&[u8]> {
+        ReaderBuilder::new().flexible(true).from_reader(content.as_bytes())
+    }
+
+    /// Most rows imported within a request (`CONTACT_IMPORT_SYNC_LIMIT`, default 1000)
+    pub fn sync_limit() -> usize {
+        positive_int_from_env("CONTACT_IMPORT_SYNC_LIMIT", DEFAULT_SYNC_LIMIT) as usize
+    }
+
+    /// Largest accepted upload in bytes (`CONTACT_IMPORT_MAX_BYTES`, default 10 MiB)
+    pub fn max_upload_bytes() -> usize {
+        positive_int_from_env("CONTACT_IMPORT_MAX_BYTES", DEFAULT_MAX_UPLOAD_BYTES) as usize
+    }
+}
+
+impl ColumnMapping {
+    /// Non-empty values of a row, as (standard fields, custom fields)
+    fn split(&self, values: &[String]) -> (HashMap<&'static str, String>, HashMap<String, String>) {
+        let mut standard = HashMap::new();
+        let mut custom = HashMap::new();
+
+        for (target, value) in self.targets.iter().zip(values) {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match target {
+                Some(ImportTarget::Standard(field)) => {
+                    standard.insert(*field, value.to_string());
+                }
+                Some(ImportTarget::Custom(field_name)) => {
+                    custom.insert(field_name.clone(), value.to_string());
+                }
+                None => {}
+            }
+        }
+
+        (standard, custom)
+    }
+
+    /// Non-empty value of a standard field in a row
+    fn value(&self, values: &[String], field: &str) -> Option<String> {
+        self.split(values).0.remove(field)
+    }
+}
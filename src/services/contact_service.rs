@@ -1,12 +1,14 @@
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 use validator::Validate;
 use chrono::Utc;
 
 use crate::dto::contact_dto::{CreateContactRequest, ContactResponse, UpdateContactRequest, PatchContactRequest};
 use crate::errors::AppError;
-use crate::models::{Contact, ContactCustomValue};
-use crate::repository::{ContactRepository, ContactCustomValueRepository};
+use crate::models::{Contact, LeadStatus, PermissionScope};
+use crate::repository::{ContactRepository, ContactCustomValueRepository, LeadStatusRepository};
+use crate::services::custom_field_validation_service::{CustomFieldValidationService, CustomFieldWriteMode, CustomValueWrite};
+use crate::AppState;
 use std::collections::HashMap;
 
 pub struct ContactService;
@@ -14,58 +16,41 @@ pub struct ContactService;
 impl ContactService {
     /// Create a new contact
     pub async fn create_contact(
-        pool: &PgPool,
+        state: &AppState,
+        org_id: Uuid,
         request: CreateContactRequest,
-        _created_by: Uuid, // User ID from JWT token
+        created_by: Uuid, // User ID from JWT token
     ) -> Result<ContactResponse, AppError> {
         // Validate the request
         if let Err(validation_errors) = request.validate() {
             tracing::warn!("Contact validation failed: {:?}", validation_errors);
-            return Err(AppError::ValidationError(
-                "Invalid contact data".to_string()
-            ));
+            return Err(validation_errors.into());
+        }
+
+        if let Some(lead_status) = &request.lead_status {
+            Self::ensure_lead_status(&state.db, org_id, lead_status).await?;
         }
 
         // Check if email already exists
-        if ContactRepository::email_exists(pool, &request.email).await? {
+        if ContactRepository::email_exists(&state.db, org_id, &request.email).await? {
             tracing::warn!("Attempt to create contact with existing email: {}", request.email);
-            return Err(AppError::ValidationError(
+            return Err(AppError::Conflict(
                 "A contact with this email already exists".to_string()
             ));
         }
 
         // Create the contact model with the authenticated user as owner
-        let mut contact = Contact::new(
-            request.first_name,
-            request.last_name,
-            request.email,
-            request.phone,
-            request.company,
-            request.job_title,
-            Some(_created_by), // Set the authenticated user as the owner
-        );
+        let contact = request.to_contact(org_id, created_by);
 
-        // Set optional fields
-        contact.address = request.address;
-        contact.city = request.city;
-        contact.state = request.state;
-        contact.postal_code = request.postal_code;
-        contact.country = request.country;
-        contact.notes = request.notes;
-        contact.lead_source = request.lead_source;
-        
-        // Set lead status if provided, otherwise use default "new"
-        if let Some(status) = request.lead_status {
-            contact.lead_status = status;
-        }
+        // Validate custom fields (applying defaults) before anything is written
+        let custom_fields = request.custom_fields.unwrap_or_default();
+        let custom_values = Self::validate_custom_fields(&state.db, org_id, contact.id, &custom_fields, CustomFieldWriteMode::Create).await?;
 
-        // Save to database
-        let created_contact = ContactRepository::create(pool, &contact).await?;
-
-        // Handle custom fields if provided
-        if let Some(custom_fields) = request.custom_fields {
-            Self::save_custom_fields(pool, created_contact.id, custom_fields).await?;
-        }
+        // Save the contact and its custom values together
+        let mut uow = state.unit_of_work().await?;
+        let created_contact = ContactRepository::create(uow.conn(), &contact).await?;
+        Self::write_custom_values(uow.conn(), org_id, created_contact.id, custom_values).await?;
+        uow.commit().await?;
 
         tracing::info!(
             "Contact created successfully: {} {} ({})",
@@ -76,7 +61,7 @@ impl ContactService {
 
         // Get contact with custom fields for response
         let mut response = ContactResponse::from(created_contact.clone());
-        response.custom_fields = Self::get_custom_fields_for_contact(pool, created_contact.id).await?;
+        response.custom_fields = Self::get_custom_fields_for_contact(&state.db, org_id, created_contact.id).await?;
 
         Ok(response)
     }
@@ -84,15 +69,18 @@ impl ContactService {
     /// Get contact by ID
     pub async fn get_contact_by_id(
         pool: &PgPool,
+        org_id: Uuid,
         contact_id: Uuid,
-        _user_id: Uuid, // For future authorization checks
+        user_id: Uuid,
+        scope: PermissionScope,
     ) -> Result<ContactResponse, AppError> {
-        let contact = ContactRepository::find_by_id(pool, contact_id).await?;
+        let contact = ContactRepository::find_by_id(pool, org_id, contact_id).await?;
 
         match contact {
             Some(contact) => {
+                Self::ensure_owner(&contact, user_id, scope, "view")?;
                 let mut response = ContactResponse::from(contact);
-                response.custom_fields = Self::get_custom_fields_for_contact(pool, contact_id).await?;
+                response.custom_fields = Self::get_custom_fields_for_contact(pool, org_id, contact_id).await?;
                 Ok(response)
             }
             None => {
@@ -104,19 +92,21 @@ impl ContactService {
 
     /// Update an existing contact
     pub async fn update_contact(
-        pool: &PgPool,
+        state: &AppState,
+        org_id: Uuid,
         contact_id: Uuid,
         request: UpdateContactRequest,
-        _user_id: Uuid, // For future authorization checks
+        user_id: Uuid,
+        scope: PermissionScope,
     ) -> Result<ContactResponse, AppError> {
         // Validate the request
         request.validate().map_err(|e| {
             tracing::warn!("Contact update validation failed: {:?}", e);
-            AppError::ValidationError(e.to_string())
+            AppError::from(e)
         })?;
 
         // Get the existing contact
-        let existing_contact = ContactRepository::find_by_id(pool, contact_id).await?;
+        let existing_contact = ContactRepository::find_by_id(&state.db, org_id, contact_id).await?;
 
         let mut contact = match existing_contact {
             Some(contact) => {
@@ -131,6 +121,7 @@ impl ContactService {
                 return Err(AppError::NotFound("Contact not found".to_string()));
             }
         };
+        Self::ensure_owner(&contact, user_id, scope, "update")?;
 
         // Update fields if provided
         if let Some(first_name) = request.first_name {
@@ -173,29 +164,26 @@ impl ContactService {
             contact.lead_source = Some(lead_source);
         }
         if let Some(lead_status) = request.lead_status {
-            // Validate lead status
-            let valid_statuses = [
-                "new", "contacted", "qualified", "proposal",
-                "negotiation", "closed_won", "closed_lost"
-            ];
-            if !valid_statuses.contains(&lead_status.as_str()) {
-                return Err(AppError::ValidationError(
-                    "Invalid lead status. Must be one of: new, contacted, qualified, proposal, negotiation, closed_won, closed_lost".to_string()
-                ));
-            }
+            Self::ensure_lead_status(&state.db, org_id, &lead_status).await?;
             contact.lead_status = lead_status;
         }
 
+        // Validate custom fields if provided
+        let custom_values = match &request.custom_fields {
+            Some(custom_fields) => {
+                Self::validate_custom_fields(&state.db, org_id, contact.id, custom_fields, CustomFieldWriteMode::Update).await?
+            }
+            None => Vec::new(),
+        };
+
         // Update the timestamp
         contact.updated_at = Utc::now();
 
-        // Save to database
-        let updated_contact = ContactRepository::update(pool, &contact).await?;
-
-        // Handle custom fields if provided
-        if let Some(custom_fields) = request.custom_fields {
-            Self::save_custom_fields(pool, updated_contact.id, custom_fields).await?;
-        }
+        // Save the contact and its custom values together
+        let mut uow = state.unit_of_work().await?;
+        let updated_contact = ContactRepository::update(uow.conn(), &contact).await?;
+        Self::write_custom_values(uow.conn(), org_id, updated_contact.id, custom_values).await?;
+        uow.commit().await?;
 
         tracing::info!(
             "Contact updated successfully: {} {} ({})",
@@ -206,26 +194,28 @@ impl ContactService {
 
         // Get contact with custom fields for response
         let mut response = ContactResponse::from(updated_contact.clone());
-        response.custom_fields = Self::get_custom_fields_for_contact(pool, updated_contact.id).await?;
+        response.custom_fields = Self::get_custom_fields_for_contact(&state.db, org_id, updated_contact.id).await?;
 
         Ok(response)
     }
 
     /// Patch an existing contact (partial update with merge semantics)
     pub async fn patch_contact(
-        pool: &PgPool,
+        state: &AppState,
+        org_id: Uuid,
         contact_id: Uuid,
         request: PatchContactRequest,
-        _user_id: Uuid, // For future authorization checks
+        user_id: Uuid,
+        scope: PermissionScope,
     ) -> Result<ContactResponse, AppError> {
         // Validate the request
         request.validate().map_err(|e| {
             tracing::warn!("Contact patch validation failed: {:?}", e);
-            AppError::ValidationError(e.to_string())
+            AppError::from(e)
         })?;
 
         // Get the existing contact
-        let existing_contact = ContactRepository::find_by_id(pool, contact_id).await?;
+        let existing_contact = ContactRepository::find_by_id(&state.db, org_id, contact_id).await?;
 
         let mut contact = match existing_contact {
             Some(contact) => {
@@ -240,6 +230,7 @@ impl ContactService {
                 return Err(AppError::NotFound("Contact not found".to_string()));
             }
         };
+        Self::ensure_owner(&contact, user_id, scope, "patch")?;
 
         // Apply patches if provided
         if let Some(first_name) = request.first_name {
@@ -282,29 +273,26 @@ impl ContactService {
             contact.lead_source = if lead_source.trim().is_empty() { None } else { Some(lead_source) };
         }
         if let Some(lead_status) = request.lead_status {
-            // Validate lead status
-            let valid_statuses = [
-                "new", "contacted", "qualified", "proposal",
-                "negotiation", "closed_won", "closed_lost"
-            ];
-            if !valid_statuses.contains(&lead_status.as_str()) {
-                return Err(AppError::ValidationError(
-                    "Invalid lead status. Must be one of: new, contacted, qualified, proposal, negotiation, closed_won, closed_lost".to_string()
-                ));
-            }
+            Self::ensure_lead_status(&state.db, org_id, &lead_status).await?;
             contact.lead_status = lead_status;
         }
 
+        // Validate custom fields if provided (merge semantics for PATCH)
+        let custom_values = match &request.custom_fields {
+            Some(custom_fields) => {
+                Self::validate_custom_fields(&state.db, org_id, contact.id, custom_fields, CustomFieldWriteMode::Patch).await?
+            }
+            None => Vec::new(),
+        };
+
         // Update the timestamp
         contact.updated_at = Utc::now();
 
-        // Save to database
-        let updated_contact = ContactRepository::update(pool, &contact).await?;
-
-        // Handle custom fields if provided (merge semantics for PATCH)
-        if let Some(custom_fields) = request.custom_fields {
-            Self::patch_custom_fields(pool, updated_contact.id, custom_fields).await?;
-        }
+        // Save the contact and its custom values together
+        let mut uow = state.unit_of_work().await?;
+        let updated_contact = ContactRepository::update(uow.conn(), &contact).await?;
+        Self::write_custom_values(uow.conn(), org_id, updated_contact.id, custom_values).await?;
+        uow.commit().await?;
 
         tracing::info!(
             "Contact patched successfully: {} {} ({})",
@@ -315,7 +303,7 @@ impl ContactService {
 
         // Get contact with custom fields for response
         let mut response = ContactResponse::from(updated_contact.clone());
-        response.custom_fields = Self::get_custom_fields_for_contact(pool, updated_contact.id).await?;
+        response.custom_fields = Self::get_custom_fields_for_contact(&state.db, org_id, updated_contact.id).await?;
 
         Ok(response)
     }
@@ -323,11 +311,13 @@ impl ContactService {
     /// Delete contact by ID (soft delete by setting is_active to false)
     pub async fn delete_contact(
         pool: &PgPool,
+        org_id: Uuid,
         contact_id: Uuid,
-        _user_id: Uuid, // For future authorization checks
+        user_id: Uuid,
+        scope: PermissionScope,
     ) -> Result<(), AppError> {
         // Check if contact exists and is active
-        let contact = ContactRepository::find_by_id(pool, contact_id).await?;
+        let contact = ContactRepository::find_by_id(pool, org_id, contact_id).await?;
 
         match contact {
             Some(contact) => {
@@ -335,9 +325,10 @@ impl ContactService {
                     tracing::warn!("Attempt to delete already inactive contact: {}", contact_id);
                     return Err(AppError::NotFound("Contact not found".to_string()));
                 }
+                Self::ensure_owner(&contact, user_id, scope, "delete")?;
 
                 // Soft delete by setting is_active to false
-                ContactRepository::soft_delete(pool, contact_id).await?;
+                ContactRepository::soft_delete(pool, org_id, contact_id).await?;
 
                 tracing::info!(
                     "Contact soft deleted successfully: {} {} ({})",
@@ -355,82 +346,77 @@ impl ContactService {
         }
     }
 
+    /// Reject callers whose permission only covers contacts they own
+    fn ensure_owner(contact: &Contact, user_id: Uuid, scope: PermissionScope, action: &str) -> Result<(), AppError> {
+        if scope == PermissionScope::Own && contact.owner_id != Some(user_id) {
+            tracing::warn!("User {} may not {} contact {} owned by {:?}", user_id, action, contact.id, contact.owner_id);
+            return Err(AppError::Forbidden(format!("You can only {} contacts you own", action)));
+        }
+        Ok(())
+    }
+
     /// Check if contact exists by email
     pub async fn contact_exists_by_email(
         pool: &PgPool,
+        org_id: Uuid,
         email: &str,
     ) -> Result<bool, AppError> {
-        ContactRepository::email_exists(pool, email).await
+        ContactRepository::email_exists(pool, org_id, email).await
     }
 
-    /// Save custom field values for a contact
-    async fn save_custom_fields(
-        pool: &PgPool,
-        contact_id: Uuid,
-        custom_fields: HashMap<String, String>,
-    ) -> Result<(), AppError> {
-        for (field_name, field_value) in custom_fields {
-            // Skip empty values
-            if field_value.trim().is_empty() {
-                continue;
-            }
-
-            // Find the custom field definition
-            let custom_field = ContactCustomValueRepository::find_custom_field_by_name(pool, &field_name).await?;
-
-            if let Some(field) = custom_field {
-                // Create custom value
-                let mut custom_value = ContactCustomValue::new(contact_id, field.id);
+    /// Lead statuses of the organization's pipeline, in order
+    pub async fn list_lead_statuses(pool: &PgPool, org_id: Uuid) -> Result<Vec<LeadStatus>, AppError> {
+        LeadStatusRepository::find_by_org(pool, org_id).await
+    }
 
-                // Set value based on field type
-                if let Err(error) = custom_value.set_value(&field.field_type, &field_value) {
-                    tracing::warn!("Invalid value for field {}: {}", field_name, error);
-                    continue; // Skip invalid values
-                }
+    /// Reject a lead status that is not part of the organization's pipeline, reported against `field`
+    pub fn check_lead_status(field: &str, statuses: &[LeadStatus], lead_status: &str) -> Result<(), AppError> {
+        if statuses.iter().any(|status| status.name == lead_status) {
+            return Ok(());
+        }
 
-                // Save to database
-                ContactCustomValueRepository::upsert(pool, &custom_value).await?;
+        let names: Vec<&str> = statuses.iter().map(|status| status.name.as_str()).collect();
+        Err(AppError::field(
+            field,
+            "invalid_lead_status",
+            format!("Must be one of: {}", names.join(", ")),
+        ))
+    }
 
-                tracing::info!("Saved custom field {} = {} for contact {}", field_name, field_value, contact_id);
-            } else {
-                tracing::warn!("Custom field '{}' not found, skipping", field_name);
-            }
-        }
-        Ok(())
+    async fn ensure_lead_status(pool: &PgPool, org_id: Uuid, lead_status: &str) -> Result<(), AppError> {
+        let statuses = LeadStatusRepository::find_by_org(pool, org_id).await?;
+        Self::check_lead_status("lead_status", &statuses, lead_status)
     }
 
-    /// Patch custom fields (merge semantics - add/update fields, remove if empty)
-    async fn patch_custom_fields(
+    /// Check submitted custom field values against the organization's field definitions
+    async fn validate_custom_fields(
         pool: &PgPool,
+        org_id: Uuid,
         contact_id: Uuid,
-        custom_fields: HashMap<String, String>,
+        custom_fields: &HashMap<String, String>,
+        mode: CustomFieldWriteMode,
+    ) -> Result<Vec<CustomValueWrite>, AppError> {
+        let field_definitions = ContactCustomValueRepository::get_contact_custom_fields(pool, org_id).await?;
+        CustomFieldValidationService::validate_values(contact_id, &field_definitions, custom_fields, mode)
+    }
+
+    /// Store validated custom field values for a contact
+    pub async fn write_custom_values(
+        conn: &mut PgConnection,
+        org_id: Uuid,
+        contact_id: Uuid,
+        custom_values: Vec<CustomValueWrite>,
     ) -> Result<(), AppError> {
-        for (field_name, field_value) in custom_fields {
-            // Find the custom field definition
-            let custom_field = ContactCustomValueRepository::find_custom_field_by_name(pool, &field_name).await?;
-
-            if let Some(field) = custom_field {
-                if field_value.trim().is_empty() {
-                    // Remove the custom field value if empty string is provided
-                    ContactCustomValueRepository::delete_by_contact_and_field(pool, contact_id, field.id).await?;
-                    tracing::info!("Removed custom field {} for contact {}", field_name, contact_id);
-                } else {
-                    // Create or update custom value
-                    let mut custom_value = ContactCustomValue::new(contact_id, field.id);
-
-                    // Set value based on field type
-                    if let Err(error) = custom_value.set_value(&field.field_type, &field_value) {
-                        tracing::warn!("Invalid value for field {}: {}", field_name, error);
-                        continue; // Skip invalid values
-                    }
-
-                    // Save to database
-                    ContactCustomValueRepository::upsert(pool, &custom_value).await?;
-
-                    tracing::info!("Patched custom field {} = {} for contact {}", field_name, field_value, contact_id);
+        for custom_value in custom_values {
+            match custom_value {
+                CustomValueWrite::Upsert(custom_value) => {
+                    ContactCustomValueRepository::upsert(&mut *conn, org_id, &custom_value).await?;
+                    tracing::info!("Saved custom field {} for contact {}", custom_value.custom_field_id, contact_id);
+                }
+                CustomValueWrite::Remove(custom_field_id) => {
+                    ContactCustomValueRepository::delete_by_contact_and_field(&mut *conn, org_id, contact_id, custom_field_id).await?;
+                    tracing::info!("Removed custom field {} for contact {}", custom_field_id, contact_id);
                 }
-            } else {
-                tracing::warn!("Custom field '{}' not found, skipping", field_name);
             }
         }
         Ok(())
@@ -439,9 +425,10 @@ impl ContactService {
     /// Get custom field values for a contact
     async fn get_custom_fields_for_contact(
         pool: &PgPool,
+        org_id: Uuid,
         contact_id: Uuid,
     ) -> Result<Option<HashMap<String, String>>, AppError> {
-        let custom_values = ContactCustomValueRepository::find_by_contact_id(pool, contact_id).await?;
+        let custom_values = ContactCustomValueRepository::find_by_contact_id(pool, org_id, contact_id).await?;
 
         if custom_values.is_empty() {
             return Ok(None);
@@ -450,7 +437,7 @@ impl ContactService {
         let mut fields_map = HashMap::new();
 
         // Get all custom fields to map IDs to field names
-        let custom_fields = ContactCustomValueRepository::get_contact_custom_fields(pool).await?;
+        let custom_fields = ContactCustomValueRepository::get_contact_custom_fields(pool, org_id).await?;
         let field_map: HashMap<Uuid, String> = custom_fields
             .into_iter()
             .map(|f| (f.id, f.field_name))
@@ -0,0 +1,204 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::contact_tag_dto::{
+    AssignTagsRequest, BulkTagRequest, BulkTagResponse, CreateTagRequest, TagResponse, UpdateTagRequest,
+};
+use crate::errors::AppError;
+use crate::models::ContactTag;
+use crate::repository::{ContactRepository, ContactTagRepository};
+
+pub struct ContactTagService;
+
+impl ContactTagService {
+    /// List the organization's tags with contact counts
+    pub async fn list_tags(pool: &PgPool, org_id: Uuid) -> Result<Vec<TagResponse>, AppError> {
+        let tags = ContactTagRepository::find_all_with_counts(pool, org_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Get a single tag
+    pub async fn get_tag(pool: &PgPool, org_id: Uuid, tag_id: Uuid) -> Result<TagResponse, AppError> {
+        let tag = Self::find_tag(pool, org_id, tag_id).await?;
+        Ok(TagResponse::from(tag))
+    }
+
+    /// Create a new tag
+    pub async fn create_tag(
+        pool: &PgPool,
+        org_id: Uuid,
+        request: CreateTagRequest,
+    ) -> Result<TagResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Tag validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let name = request.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::ValidationError("Tag name is required".to_string()));
+        }
+
+        let tag = ContactTag::new(org_id, name, request.color, request.description);
+        let created_tag = ContactTagRepository::create(pool, &tag).await?;
+
+        Ok(TagResponse::from(created_tag))
+    }
+
+    /// Update a tag (only provided fields are changed)
+    pub async fn update_tag(
+        pool: &PgPool,
+        org_id: Uuid,
+        tag_id: Uuid,
+        request: UpdateTagRequest,
+    ) -> Result<TagResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Tag validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let mut tag = Self::find_tag(pool, org_id, tag_id).await?;
+
+        if let Some(name) = request.name {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err(AppError::ValidationError("Tag name is required".to_string()));
+            }
+            tag.name = name;
+        }
+        if let Some(color) = request.color {
+            tag.color = Some(color);
+        }
+        if let Some(description) = request.description {
+            tag.description = Some(description);
+        }
+
+        let updated_tag = ContactTagRepository::update(pool, &tag).await?;
+        Ok(TagResponse::from(updated_tag))
+    }
+
+    /// Delete a tag and all of its assignments
+    pub async fn delete_tag(pool: &PgPool, org_id: Uuid, tag_id: Uuid) -> Result<(), AppError> {
+        ContactTagRepository::delete(pool, org_id, tag_id).await
+    }
+
+    /// List the tags assigned to a contact
+    pub async fn get_contact_tags(
+        pool: &PgPool,
+        org_id: Uuid,
+        contact_id: Uuid,
+    ) -> Result<Vec<TagResponse>, AppError> {
+        Self::ensure_contact_exists(pool, org_id, contact_id).await?;
+
+        let tags = ContactTagRepository::find_by_contact(pool, org_id, contact_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Assign tags to a single contact and return its resulting tags
+    pub async fn assign_tags(
+        pool: &PgPool,
+        org_id: Uuid,
+        contact_id: Uuid,
+        request: AssignTagsRequest,
+        assigned_by: Uuid,
+    ) -> Result<Vec<TagResponse>, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Tag assignment validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        Self::ensure_contact_exists(pool, org_id, contact_id).await?;
+        Self::ensure_tags_exist(pool, org_id, &request.tag_ids).await?;
+
+        ContactTagRepository::assign(pool, org_id, &[contact_id], &request.tag_ids, assigned_by).await?;
+
+        let tags = ContactTagRepository::find_by_contact(pool, org_id, contact_id).await?;
+        Ok(tags.into_iter().map(TagResponse::from).collect())
+    }
+
+    /// Remove a tag from a contact
+    pub async fn unassign_tag(
+        pool: &PgPool,
+        org_id: Uuid,
+        contact_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<(), AppError> {
+        let removed = ContactTagRepository::unassign(pool, org_id, &[contact_id], &[tag_id]).await?;
+
+        if removed == 0 {
+            return Err(AppError::NotFound("Tag is not assigned to this contact".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Assign tags to many contacts at once
+    pub async fn bulk_assign(
+        pool: &PgPool,
+        org_id: Uuid,
+        request: BulkTagRequest,
+        assigned_by: Uuid,
+    ) -> Result<BulkTagResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Bulk tag validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        Self::ensure_tags_exist(pool, org_id, &request.tag_ids).await?;
+
+        let affected = ContactTagRepository::assign(
+            pool,
+            org_id,
+            &request.contact_ids,
+            &request.tag_ids,
+            assigned_by,
+        ).await?;
+
+        Ok(BulkTagResponse { affected })
+    }
+
+    /// Remove tags from many contacts at once
+    pub async fn bulk_unassign(
+        pool: &PgPool,
+        org_id: Uuid,
+        request: BulkTagRequest,
+    ) -> Result<BulkTagResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Bulk tag validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let affected = ContactTagRepository::unassign(
+            pool,
+            org_id,
+            &request.contact_ids,
+            &request.tag_ids,
+        ).await?;
+
+        Ok(BulkTagResponse { affected })
+    }
+
+    async fn find_tag(pool: &PgPool, org_id: Uuid, tag_id: Uuid) -> Result<ContactTag, AppError> {
+        ContactTagRepository::find_by_id(pool, org_id, tag_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tag not found".to_string()))
+    }
+
+    async fn ensure_contact_exists(pool: &PgPool, org_id: Uuid, contact_id: Uuid) -> Result<(), AppError> {
+        match ContactRepository::find_by_id(pool, org_id, contact_id).await? {
+            Some(_) => Ok(()),
+            None => Err(AppError::NotFound("Contact not found".to_string())),
+        }
+    }
+
+    pub async fn ensure_tags_exist(pool: &PgPool, org_id: Uuid, tag_ids: &[Uuid]) -> Result<(), AppError> {
+        for tag_id in tag_ids {
+            if ContactTagRepository::find_by_id(pool, org_id, *tag_id).await?.is_none() {
+                return Err(AppError::NotFound(format!("Tag {} not found", tag_id)));
+            }
+        }
+
+        Ok(())
+    }
+}
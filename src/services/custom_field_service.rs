@@ -1,100 +1,350 @@
+use std::collections::HashSet;
+
 use sqlx::PgPool;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::dto::custom_field_dto::{CreateCustomFieldRequest, CustomFieldResponse};
+use crate::dto::custom_field_dto::{
+    CreateCustomFieldRequest, CustomFieldOptionsResponse, CustomFieldResponse, ReorderCustomFieldsRequest,
+    UpdateCustomFieldOptionsRequest, UpdateCustomFieldRequest,
+};
 use crate::errors::AppError;
-use crate::repositories::CustomFieldRepository;
+use crate::models::{CustomField, FIELD_TYPES};
+use crate::repository::{CustomFieldRepository, CustomValueChange};
+use crate::services::custom_field_validation_service::CustomFieldValidationService;
+use crate::AppState;
 
 pub struct CustomFieldService;
 
 impl CustomFieldService {
     pub async fn create_custom_field(
         pool: &PgPool,
+        org_id: Uuid,
         request: CreateCustomFieldRequest,
         created_by: Uuid,
     ) -> Result<CustomFieldResponse, AppError> {
         // Validate the request
         if let Err(validation_errors) = request.validate() {
             tracing::warn!("Custom field validation failed: {:?}", validation_errors);
-            return Err(AppError::ValidationError(
-                "Invalid custom field data".to_string()
-            ));
+            return Err(validation_errors.into());
         }
 
         // Validate field type
-        let valid_field_types = vec![
-            "text", "textarea", "number", "email", "phone", "date", 
-            "boolean", "select", "multi_select"
-        ];
-        
-        if !valid_field_types.contains(&request.field_type.as_str()) {
-            return Err(AppError::ValidationError(
-                format!("Invalid field type. Must be one of: {}", valid_field_types.join(", "))
+        if !FIELD_TYPES.contains(&request.field_type.as_str()) {
+            return Err(AppError::field(
+                "field_type",
+                "invalid_option",
+                format!("Must be one of: {}", FIELD_TYPES.join(", ")),
             ));
         }
 
         // Check if field already exists for this module
-        if CustomFieldRepository::field_exists(pool, &request.module, &request.field_name).await? {
+        if CustomFieldRepository::field_exists(pool, org_id, &request.module, &request.field_name).await? {
             return Err(AppError::ValidationError(
                 "A custom field with this name already exists for this module".to_string()
             ));
         }
 
-        // Validate options for select fields
-        if (request.field_type == "select" || request.field_type == "multi_select") && request.options.is_none() {
-            return Err(AppError::ValidationError(
-                "Options are required for select and multi_select field types".to_string()
-            ));
-        }
-
-        // Create the custom field
-        let custom_field = CustomFieldRepository::create(
-            pool,
+        let mut custom_field = CustomField::new(
+            org_id,
             request.module,
             request.label,
             request.field_name,
             request.field_type,
-            request.is_required.unwrap_or(false),
-            request.is_active.unwrap_or(true),
-            request.options,
-            request.validation_rules,
-            request.default_value,
-            request.help_text,
-            request.display_order.unwrap_or(0),
             Some(created_by),
-        ).await?;
+        );
+
+        // Validate options for select fields
+        if custom_field.is_select() {
+            let options = Self::normalize_options(request.options.unwrap_or_default())?;
+            custom_field.options = Some(serde_json::json!({ "options": options }));
+        } else if request.options.is_some() {
+            return Err(AppError::ValidationError(
+                "Options are only supported for select and multi_select field types".to_string()
+            ));
+        }
 
-        // Convert to response DTO
-        Ok(Self::to_response(custom_field))
+        custom_field.is_required = request.is_required.unwrap_or(false);
+        custom_field.is_active = request.is_active.unwrap_or(true);
+        custom_field.validation_rules = request.validation_rules;
+        custom_field.default_value = request.default_value;
+        custom_field.help_text = request.help_text;
+        custom_field.display_order = request.display_order.unwrap_or(0);
+        CustomFieldValidationService::validate_definition(&custom_field)?;
+
+        let created_field = CustomFieldRepository::create(pool, &custom_field).await?;
+        Ok(CustomFieldResponse::from(created_field))
     }
 
     pub async fn get_custom_fields_by_module(
         pool: &PgPool,
+        org_id: Uuid,
         module: &str,
+        include_inactive: bool,
     ) -> Result<Vec<CustomFieldResponse>, AppError> {
-        let custom_fields = CustomFieldRepository::get_by_module(pool, module).await?;
-        
-        Ok(custom_fields.into_iter().map(Self::to_response).collect())
+        let custom_fields = CustomFieldRepository::find_by_module(pool, org_id, module, include_inactive).await?;
+
+        Ok(custom_fields.into_iter().map(CustomFieldResponse::from).collect())
+    }
+
+    /// Update the editable attributes of a custom field (only provided fields are changed)
+    pub async fn update_custom_field(
+        pool: &PgPool,
+        org_id: Uuid,
+        module: &str,
+        field_id: Uuid,
+        request: UpdateCustomFieldRequest,
+    ) -> Result<CustomFieldResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Custom field validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let mut custom_field = Self::find_field(pool, org_id, module, field_id).await?;
+
+        if let Some(label) = request.label {
+            custom_field.label = label;
+        }
+        if let Some(is_required) = request.is_required {
+            custom_field.is_required = is_required;
+        }
+        if let Some(is_active) = request.is_active {
+            custom_field.is_active = is_active;
+        }
+        if let Some(validation_rules) = request.validation_rules {
+            custom_field.validation_rules = Some(validation_rules);
+        }
+        if let Some(default_value) = request.default_value {
+            custom_field.default_value = Some(default_value);
+        }
+        if let Some(help_text) = request.help_text {
+            custom_field.help_text = Some(help_text);
+        }
+        if let Some(display_order) = request.display_order {
+            custom_field.display_order = display_order;
+        }
+        CustomFieldValidationService::validate_definition(&custom_field)?;
+
+        let updated_field = CustomFieldRepository::update(pool, &custom_field).await?;
+        Ok(CustomFieldResponse::from(updated_field))
+    }
+
+    /// Reorder a module's fields by their position in the request
+    pub async fn reorder_custom_fields(
+        pool: &PgPool,
+        org_id: Uuid,
+        module: &str,
+        request: ReorderCustomFieldsRequest,
+    ) -> Result<Vec<CustomFieldResponse>, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Custom field reorder validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let unique_ids: HashSet<Uuid> = request.field_ids.iter().copied().collect();
+        if unique_ids.len() != request.field_ids.len() {
+            return Err(AppError::ValidationError("Field IDs must not repeat".to_string()));
+        }
+
+        let module_fields = CustomFieldRepository::find_by_module(pool, org_id, module, true).await?;
+        let module_ids: HashSet<Uuid> = module_fields.iter().map(|field| field.id).collect();
+        if let Some(unknown_id) = request.field_ids.iter().find(|id| !module_ids.contains(id)) {
+            return Err(AppError::NotFound(format!("Custom field {} not found in module {}", unknown_id, module)));
+        }
+
+        CustomFieldRepository::reorder(pool, org_id, module, &request.field_ids).await?;
+        Self::get_custom_fields_by_module(pool, org_id, module, true).await
     }
 
-    fn to_response(custom_field: crate::models::CustomField) -> CustomFieldResponse {
-        CustomFieldResponse {
-            id: custom_field.id,
-            module: custom_field.module,
-            label: custom_field.label,
-            field_name: custom_field.field_name,
-            field_type: custom_field.field_type,
-            is_required: custom_field.is_required,
-            is_active: custom_field.is_active,
-            options: custom_field.options,
-            validation_rules: custom_field.validation_rules,
-            default_value: custom_field.default_value,
-            help_text: custom_field.help_text,
-            display_order: custom_field.display_order,
-            created_by: custom_field.created_by,
-            created_at: custom_field.created_at.to_rfc3339(),
-            updated_at: custom_field.updated_at.to_rfc3339(),
+    /// Hide a custom field from contacts while keeping its stored values
+    pub async fn deactivate_custom_field(
+        pool: &PgPool,
+        org_id: Uuid,
+        module: &str,
+        field_id: Uuid,
+    ) -> Result<CustomFieldResponse, AppError> {
+        let mut custom_field = Self::find_field(pool, org_id, module, field_id).await?;
+        custom_field.is_active = false;
+
+        let updated_field = CustomFieldRepository::update(pool, &custom_field).await?;
+        Ok(CustomFieldResponse::from(updated_field))
+    }
+
+    /// Hard-delete a custom field; refused while values exist unless `force` is set
+    pub async fn delete_custom_field(
+        pool: &PgPool,
+        org_id: Uuid,
+        module: &str,
+        field_id: Uuid,
+        force: bool,
+    ) -> Result<(), AppError> {
+        let custom_field = Self::find_field(pool, org_id, module, field_id).await?;
+
+        let value_count = CustomFieldRepository::count_values(pool, org_id, custom_field.id).await?;
+        if value_count > 0 && !force {
+            return Err(AppError::ValidationError(format!(
+                "Custom field '{}' has {} stored values; deactivate it instead or delete with force=true",
+                custom_field.field_name, value_count
+            )));
+        }
+
+        if value_count > 0 {
+            tracing::warn!(
+                "Force deleting custom field {} with {} stored values",
+                custom_field.id,
+                value_count
+            );
+        }
+
+        CustomFieldRepository::delete(pool, org_id, custom_field.id).await
+    }
+
+    /// Replace the options of a select field, migrating stored values through `value_mapping`
+    ///
+    /// Values that would fall outside the new options without a mapping reject the change.
+    pub async fn update_options(
+        state: &AppState,
+        org_id: Uuid,
+        module: &str,
+        field_id: Uuid,
+        request: UpdateCustomFieldOptionsRequest,
+    ) -> Result<CustomFieldOptionsResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Custom field options validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let mut custom_field = Self::find_field(&state.db, org_id, module, field_id).await?;
+        if !custom_field.is_select() {
+            return Err(AppError::ValidationError(
+                "Options can only be changed on select and multi_select fields".to_string(),
+            ));
         }
+
+        let options = Self::normalize_options(request.options)?;
+        if let Some(target) = request
+            .value_mapping
+            .values()
+            .flatten()
+            .find(|target| !options.contains(target))
+        {
+            return Err(AppError::ValidationError(format!(
+                "Mapped value '{}' is not one of the new options",
+                target
+            )));
+        }
+
+        // Resolve a single stored option: Some(Some) keeps or renames, Some(None) clears, None is unmapped
+        let resolve = |value: &str| -> Option<Option<String>> {
+            match request.value_mapping.get(value) {
+                Some(target) => Some(target.clone()),
+                None if options.iter().any(|option| option == value) => Some(Some(value.to_string())),
+                None => None,
+            }
+        };
+
+        let mut changes = Vec::new();
+        let mut unmapped = HashSet::new();
+        let mut cleared_values = 0;
+
+        for stored in CustomFieldRepository::find_values(&state.db, org_id, custom_field.id).await? {
+            if custom_field.field_type == "multi_select" {
+                let previous: Vec<String> = stored
+                    .value_json
+                    .clone()
+                    .and_then(|json| serde_json::from_value(json).ok())
+                    .or_else(|| stored.value.as_deref().and_then(|value| serde_json::from_str(value).ok()))
+                    .unwrap_or_default();
+
+                let mut migrated: Vec<String> = Vec::new();
+                for value in &previous {
+                    match resolve(value) {
+                        Some(Some(target)) if !migrated.contains(&target) => migrated.push(target),
+                        Some(_) => {}
+                        None => {
+                            unmapped.insert(value.clone());
+                        }
+                    }
+                }
+
+                if migrated == previous {
+                    continue;
+                }
+
+                if migrated.is_empty() {
+                    cleared_values += 1;
+                    changes.push(CustomValueChange { id: stored.id, value: None, value_json: None });
+                } else {
+                    changes.push(CustomValueChange {
+                        id: stored.id,
+                        value: Some(serde_json::to_string(&migrated).unwrap_or_default()),
+                        value_json: Some(serde_json::json!(migrated)),
+                    });
+                }
+            } else if let Some(previous) = stored.value.as_deref() {
+                match resolve(previous) {
+                    Some(Some(target)) if target == previous => {}
+                    Some(Some(target)) => {
+                        changes.push(CustomValueChange { id: stored.id, value: Some(target), value_json: None });
+                    }
+                    Some(None) => {
+                        cleared_values += 1;
+                        changes.push(CustomValueChange { id: stored.id, value: None, value_json: None });
+                    }
+                    None => {
+                        unmapped.insert(previous.to_string());
+                    }
+                }
+            }
+        }
+
+        if !unmapped.is_empty() {
+            let mut unmapped: Vec<String> = unmapped.into_iter().collect();
+            unmapped.sort();
+            return Err(AppError::ValidationError(format!(
+                "Stored values use removed options without a value_mapping: {}",
+                unmapped.join(", ")
+            )));
+        }
+
+        custom_field.options = Some(serde_json::json!({ "options": options }));
+        CustomFieldValidationService::validate_definition(&custom_field)?;
+        let mut uow = state.unit_of_work().await?;
+        let updated_field = CustomFieldRepository::replace_options(uow.conn(), &custom_field, &changes).await?;
+        uow.commit().await?;
+
+        Ok(CustomFieldOptionsResponse {
+            field: CustomFieldResponse::from(updated_field),
+            migrated_values: changes.len() as u64 - cleared_values,
+            cleared_values,
+        })
+    }
+
+    async fn find_field(pool: &PgPool, org_id: Uuid, module: &str, field_id: Uuid) -> Result<CustomField, AppError> {
+        CustomFieldRepository::find_by_id(pool, org_id, field_id)
+            .await?
+            .filter(|custom_field| custom_field.module == module)
+            .ok_or_else(|| AppError::NotFound("Custom field not found".to_string()))
+    }
+
+    /// Trim options and reject empty, duplicate or missing ones
+    fn normalize_options(options: Vec<String>) -> Result<Vec<String>, AppError> {
+        let options: Vec<String> = options.into_iter().map(|option| option.trim().to_string()).collect();
+
+        if options.is_empty() {
+            return Err(AppError::ValidationError(
+                "Options are required for select and multi_select field types".to_string()
+            ));
+        }
+        if options.iter().any(|option| option.is_empty()) {
+            return Err(AppError::ValidationError("Options must not be empty".to_string()));
+        }
+
+        let unique: HashSet<&String> = options.iter().collect();
+        if unique.len() != options.len() {
+            return Err(AppError::ValidationError("Options must be unique".to_string()));
+        }
+
+        Ok(options)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+
+use bigdecimal::BigDecimal;
+use uuid::Uuid;
+
+use crate::errors::{AppError, FieldError, FieldErrors};
+use crate::models::{ContactCustomValue, CustomField, ValidationRules};
+
+/// Numbers are stored as NUMERIC(15,4), so their magnitude must stay below 10^11
+const NUMBER_LIMIT: i64 = 100_000_000_000;
+
+/// How submitted custom field values relate to the values already stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomFieldWriteMode {
+    /// New contact: defaults are applied and required fields must be present
+    Create,
+    /// Full update: provided values replace stored ones, empty values are ignored
+    Update,
+    /// Partial update: an empty value removes the stored one
+    Patch,
+}
+
+/// A validated change to one custom value of a contact
+#[derive(Debug)]
+pub enum CustomValueWrite {
+    Upsert(ContactCustomValue),
+    Remove(Uuid),
+}
+
+pub struct CustomFieldValidationService;
+
+impl CustomFieldValidationService {
+    /// Validate submitted values against the organization's active field definitions
+    ///
+    /// Unknown field names are skipped. All failures are collected and returned together,
+    /// keyed as `custom_fields.<field_name>`.
+    pub fn validate_values(
+        contact_id: Uuid,
+        fields: &[CustomField],
+        submitted: &HashMap<String, String>,
+        mode: CustomFieldWriteMode,
+    ) -> Result<Vec<CustomValueWrite>, AppError> {
+        let mut writes = Vec::new();
+        let mut errors = FieldErrors::new();
+
+        for field_name in submitted.keys() {
+            if !fields.iter().any(|field| &field.field_name == field_name) {
+                tracing::warn!("Custom field '{}' not found, skipping", field_name);
+            }
+        }
+
+        for field in fields {
+            let submitted_value = submitted.get(&field.field_name).map(|value| value.trim());
+
+            let default_value = field.default_value.as_deref().map(str::trim).filter(|value| !value.is_empty());
+
+            let value = match (submitted_value, mode, default_value) {
+                (Some(value), _, _) if !value.is_empty() => value,
+                (_, CustomFieldWriteMode::Create, Some(default_value)) => default_value,
+                (None, CustomFieldWriteMode::Update | CustomFieldWriteMode::Patch, _) => continue,
+                (Some(_), CustomFieldWriteMode::Update, _) if !field.is_required => continue,
+                _ if field.is_required => {
+                    errors.insert(Self::path(field), vec![FieldError::new("required", format!("{} is required", field.label))]);
+                    continue;
+                }
+                (Some(_), CustomFieldWriteMode::Patch, _) => {
+                    writes.push(CustomValueWrite::Remove(field.id));
+                    continue;
+                }
+                _ => continue,
+            };
+
+            match Self::validate_value(contact_id, field, value) {
+                Ok(custom_value) => writes.push(CustomValueWrite::Upsert(custom_value)),
+                Err(error) => {
+                    errors.insert(Self::path(field), vec![error]);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(writes)
+        } else {
+            Err(AppError::FieldValidation(errors))
+        }
+    }
+
+    /// Check a field definition: its rules must parse and its default value must satisfy them
+    pub fn validate_definition(field: &CustomField) -> Result<(), AppError> {
+        field.rules().map_err(AppError::ValidationError)?;
+
+        if let Some(default_value) = field.default_value.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+            if let Err(error) = Self::validate_value(Uuid::nil(), field, default_value) {
+                return Err(AppError::ValidationError(format!("Invalid default value: {}", error.message)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse a single value for its field type and check it against the field's rules
+    fn validate_value(contact_id: Uuid, field: &CustomField, value: &str) -> Result<ContactCustomValue, FieldError> {
+        let mut custom_value = ContactCustomValue::new(contact_id, field.id);
+        custom_value
+            .set_value(&field.field_type, value)
+            .map_err(|message| FieldError::new("invalid_type", message))?;
+
+        // Stored rules are checked on write of the definition; tolerate legacy rows that predate it
+        let rules = field.rules().unwrap_or_else(|message| {
+            tracing::warn!("Ignoring rules of custom field {}: {}", field.field_name, message);
+            ValidationRules::default()
+        });
+
+        let selections: Vec<String> = match field.field_type.as_str() {
+            "multi_select" => serde_json::from_str(value).unwrap_or_default(),
+            _ => vec![value.to_string()],
+        };
+
+        if field.is_select() {
+            let options = field.option_values();
+            if let Some(invalid) = selections.iter().find(|selection| !options.contains(selection)) {
+                return Err(FieldError::new("invalid_option", format!("'{}' is not an option of {}", invalid, field.label)));
+            }
+        }
+        if let Some(options) = &rules.options {
+            if let Some(invalid) = selections.iter().find(|selection| !options.contains(selection)) {
+                return Err(FieldError::new("invalid_option", format!("'{}' is not allowed for {}", invalid, field.label)));
+            }
+        }
+
+        match field.field_type.as_str() {
+            "number" => {
+                let limit = BigDecimal::from(NUMBER_LIMIT);
+                if custom_value.value_number.as_ref().is_some_and(|number| number.round(4).abs() >= limit) {
+                    return Err(FieldError::new(
+                        "out_of_range",
+                        format!("{} must be greater than -{} and less than {}", field.label, NUMBER_LIMIT, NUMBER_LIMIT),
+                    ));
+                }
+                let number: f64 = value.parse().unwrap_or_default();
+                if let Some(min) = rules.min.filter(|min| number < *min) {
+                    return Err(FieldError::new("min", format!("{} must be at least {}", field.label, min)));
+                }
+                if let Some(max) = rules.max.filter(|max| number > *max) {
+                    return Err(FieldError::new("max", format!("{} must be at most {}", field.label, max)));
+                }
+            }
+            "date" => {
+                if let Some(date) = custom_value.value_date {
+                    if let Some(min_date) = rules.min_date.filter(|min_date| date < *min_date) {
+                        return Err(FieldError::new("min_date", format!("{} must be on or after {}", field.label, min_date)));
+                    }
+                    if let Some(max_date) = rules.max_date.filter(|max_date| date > *max_date) {
+                        return Err(FieldError::new("max_date", format!("{} must be on or before {}", field.label, max_date)));
+                    }
+                }
+            }
+            "boolean" => {}
+            _ => {
+                let (length, unit) = match field.field_type.as_str() {
+                    "multi_select" => (selections.len(), "selections"),
+                    _ => (value.chars().count(), "characters"),
+                };
+                if let Some(min_length) = rules.min_length.filter(|min_length| length < *min_length) {
+                    return Err(FieldError::new("min_length", format!("{} needs at least {} {}", field.label, min_length, unit)));
+                }
+                if let Some(max_length) = rules.max_length.filter(|max_length| length > *max_length) {
+                    return Err(FieldError::new("max_length", format!("{} allows at most {} {}", field.label, max_length, unit)));
+                }
+
+                if field.field_type != "multi_select" {
+                    if let Some(pattern) = rules.pattern.as_deref().and_then(|pattern| regex::Regex::new(pattern).ok()) {
+                        if !pattern.is_match(value) {
+                            return Err(FieldError::new("pattern", format!("{} has an invalid format", field.label)));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(custom_value)
+    }
+
+    fn path(field: &CustomField) -> String {
+        format!("custom_fields.{}", field.field_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(field_name: &str, field_type: &str) -> CustomField {
+        CustomField::new(
+            Uuid::new_v4(),
+            "contact".to_string(),
+            field_name.to_string(),
+            field_name.to_string(),
+            field_type.to_string(),
+            None,
+        )
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn error_codes(result: Result<Vec<CustomValueWrite>, AppError>) -> Vec<(String, String)> {
+        match result {
+            Err(AppError::FieldValidation(errors)) => errors
+                .into_iter()
+                .flat_map(|(field, errors)| errors.into_iter().map(move |error| (field.clone(), error.code)))
+                .collect(),
+            other => panic!("expected field errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_required_and_default_values() {
+        let mut required = field("tier", "text");
+        required.is_required = true;
+        let mut defaulted = field("source", "text");
+        defaulted.default_value = Some("web".to_string());
+        let fields = vec![required, defaulted];
+
+        let codes = error_codes(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[]), CustomFieldWriteMode::Create,
+        ));
+        assert_eq!(codes, vec![("custom_fields.tier".to_string(), "required".to_string())]);
+
+        let writes = CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[("tier", "gold")]), CustomFieldWriteMode::Create,
+        ).unwrap();
+        assert_eq!(writes.len(), 2);
+
+        // Updates leave missing fields alone, patches cannot clear required ones
+        assert!(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[]), CustomFieldWriteMode::Update,
+        ).unwrap().is_empty());
+        let codes = error_codes(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[("tier", "")]), CustomFieldWriteMode::Patch,
+        ));
+        assert_eq!(codes, vec![("custom_fields.tier".to_string(), "required".to_string())]);
+    }
+
+    #[test]
+    fn test_patch_empty_value_removes() {
+        let fields = vec![field("notes", "text")];
+        let writes = CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[("notes", " ")]), CustomFieldWriteMode::Patch,
+        ).unwrap();
+        assert!(matches!(writes.as_slice(), [CustomValueWrite::Remove(id)] if *id == fields[0].id));
+    }
+
+    #[test]
+    fn test_rule_violations_are_collected() {
+        let mut revenue = field("revenue", "number");
+        revenue.validation_rules = Some(json!({"min": 0, "max": 100}));
+        let mut profile = field("profile", "text");
+        profile.validation_rules = Some(json!({"pattern": "^https://", "max_length": 20}));
+        let mut industry = field("industry", "select");
+        industry.options = Some(json!({"options": ["Tech", "Finance"]}));
+        let mut skills = field("skills", "multi_select");
+        skills.options = Some(json!({"options": ["Go", "Rust", "Java"]}));
+        skills.validation_rules = Some(json!({"max_length": 2}));
+        let mut contacted = field("contacted", "date");
+        contacted.validation_rules = Some(json!({"min_date": "2020-01-01"}));
+        let fields = vec![revenue, profile, industry, skills, contacted];
+
+        let codes = error_codes(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(),
+            &fields,
+            &values(&[
+                ("revenue", "150"),
+                ("profile", "ftp://example.com"),
+                ("industry", "Retail"),
+                ("skills", r#"["Go", "Rust", "Java"]"#),
+                ("contacted", "2019-12-31"),
+            ]),
+            CustomFieldWriteMode::Create,
+        ));
+        assert_eq!(
+            codes,
+            vec![
+                ("custom_fields.contacted".to_string(), "min_date".to_string()),
+                ("custom_fields.industry".to_string(), "invalid_option".to_string()),
+                ("custom_fields.profile".to_string(), "pattern".to_string()),
+                ("custom_fields.revenue".to_string(), "max".to_string()),
+                ("custom_fields.skills".to_string(), "max_length".to_string()),
+            ]
+        );
+
+        assert!(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(),
+            &fields,
+            &values(&[
+                ("revenue", "42"),
+                ("profile", "https://x.io"),
+                ("industry", "Tech"),
+                ("skills", r#"["Go"]"#),
+                ("contacted", "2024-05-01"),
+            ]),
+            CustomFieldWriteMode::Create,
+        ).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_type_and_definitions() {
+        let fields = vec![field("revenue", "number")];
+        let codes = error_codes(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[("revenue", "lots")]), CustomFieldWriteMode::Update,
+        ));
+        assert_eq!(codes, vec![("custom_fields.revenue".to_string(), "invalid_type".to_string())]);
+
+        // Fields without a max still cannot exceed what the column stores
+        let codes = error_codes(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[("revenue", "100000000000")]), CustomFieldWriteMode::Update,
+        ));
+        assert_eq!(codes, vec![("custom_fields.revenue".to_string(), "out_of_range".to_string())]);
+        assert!(CustomFieldValidationService::validate_values(
+            Uuid::new_v4(), &fields, &values(&[("revenue", "-99999999999.9999")]), CustomFieldWriteMode::Update,
+        ).is_ok());
+
+        let mut definition = field("code", "text");
+        definition.validation_rules = Some(json!({"pattern": "("}));
+        assert!(CustomFieldValidationService::validate_definition(&definition).is_err());
+
+        definition.validation_rules = Some(json!({"maxlength": 3}));
+        assert!(CustomFieldValidationService::validate_definition(&definition).is_err());
+
+        definition.validation_rules = Some(json!({"max_length": 3}));
+        definition.default_value = Some("toolong".to_string());
+        assert!(CustomFieldValidationService::validate_definition(&definition).is_err());
+    }
+}
@@ -0,0 +1,364 @@
+// Invitation Service - Invite people to an organization by email and respond to invitations
+
+use chrono::Utc;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::{
+    AcceptInvitationRequest, AcceptInvitationResponse, CreateInvitationRequest, CreateUserRequest,
+    InvitationInfoResponse, InvitationResponse,
+};
+use crate::errors::AppError;
+use crate::models::{
+    Invitation, PermissionSet, Role, EMAIL_TEMPLATE_INVITATION, INVITATION_ACCEPTED, INVITATION_DECLINED,
+    INVITATION_REVOKED,
+};
+use crate::repository::{
+    InvitationRepository, OrganizationRepository, RoleRepository,
+    UserOrganizationRepository, UserRepository,
+};
+use crate::services::{OutboxService, RoleService, UserOrganizationService, UserService};
+use crate::utils::{format_timestamp, generate_secret_token, hash_secret_token, invitation_ttl, JwtUser};
+use crate::AppState;
+
+pub struct InvitationService;
+
+impl InvitationService {
+    /// Invite an email address to the organization and queue the invitation email
+    pub async fn create_invitation(
+        state: &AppState,
+        org_id: Uuid,
+        inviter: &JwtUser,
+        inviter_permissions: &PermissionSet,
+        request: CreateInvitationRequest,
+    ) -> Result<InvitationResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Invitation validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let email = request.email.trim().to_lowercase();
+
+        let role = RoleRepository::find_by_name(&state.db, org_id, &request.role_name)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Role '{}' not found", request.role_name)))?;
+        RoleService::ensure_can_assign(inviter_permissions, &role)?;
+
+        if let Some(user) = UserRepository::find_by_email(&state.db, &email).await? {
+            Self::ensure_not_member(&state.db, user.id, org_id).await?;
+        }
+
+        if InvitationRepository::find_pending_by_email(&state.db, org_id, &email).await?.is_some() {
+            return Err(AppError::Conflict(format!(
+                "An invitation for '{}' is already pending; resend it instead",
+                email
+            )));
+        }
+
+        let token = generate_secret_token();
+        let invitation = Invitation::new(
+            org_id,
+            email,
+            role.id,
+            Some(inviter.id),
+            hash_secret_token(&token),
+            Utc::now() + invitation_ttl(),
+        );
+        let mut uow = state.unit_of_work().await?;
+        let invitation = InvitationRepository::create(uow.conn(), &invitation).await?;
+        Self::queue_invitation_email(uow.conn(), &invitation, &role, inviter, &token).await?;
+        uow.commit().await?;
+
+        tracing::info!("User {} invited {} to organization {}", inviter.id, invitation.email, org_id);
+        Ok(Self::to_response(invitation, &role))
+    }
+
+    /// Invitations of an organization, newest first
+    pub async fn list_invitations(
+        pool: &PgPool,
+        org_id: Uuid,
+        status: Option<String>,
+    ) -> Result<Vec<InvitationResponse>, AppError> {
+        let invitations = InvitationRepository::find_all_for_org(pool, org_id, status.as_deref()).await?;
+        let roles = RoleRepository::find_all_for_org(pool, org_id).await?;
+
+        let mut responses = Vec::with_capacity(invitations.len());
+        for invitation in invitations {
+            let role = match roles.iter().find(|role| role.id == invitation.role_id) {
+                Some(role) => role.clone(),
+                None => Self::find_role(pool, invitation.role_id).await?,
+            };
+            responses.push(Self::to_response(invitation, &role));
+        }
+
+        Ok(responses)
+    }
+
+    /// Send a pending invitation again with a fresh token and expiry; earlier links stop working
+    pub async fn resend_invitation(
+        state: &AppState,
+        org_id: Uuid,
+        invitation_id: Uuid,
+        inviter: &JwtUser,
+    ) -> Result<InvitationResponse, AppError> {
+        let invitation = Self::find_invitation(&state.db, org_id, invitation_id).await?;
+        Self::ensure_pending(&invitation)?;
+
+        let role = Self::find_role(&state.db, invitation.role_id).await?;
+
+        let token = generate_secret_token();
+        let mut uow = state.unit_of_work().await?;
+        let invitation = InvitationRepository::rotate_token(
+            uow.conn(),
+            invitation.id,
+            &hash_secret_token(&token),
+            Utc::now() + invitation_ttl(),
+        )
+        .await?
+        .ok_or_else(|| AppError::Conflict("Invitation is no longer pending".to_string()))?;
+
+        Self::queue_invitation_email(uow.conn(), &invitation, &role, inviter, &token).await?;
+        uow.commit().await?;
+
+        tracing::info!("User {} resent invitation {} to {}", inviter.id, invitation.id, invitation.email);
+        Ok(Self::to_response(invitation, &role))
+    }
+
+    /// Revoke a pending invitation so its link can no longer be used
+    pub async fn revoke_invitation(pool: &PgPool, org_id: Uuid, invitation_id: Uuid) -> Result<(), AppError> {
+        let invitation = Self::find_invitation(pool, org_id, invitation_id).await?;
+        Self::ensure_pending(&invitation)?;
+
+        InvitationRepository::respond(pool, invitation.id, INVITATION_REVOKED, None)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Invitation is no longer pending".to_string()))?;
+
+        tracing::info!("Revoked invitation {} to {}", invitation.id, invitation.email);
+        Ok(())
+    }
+
+    /// Describe the invitation behind a link
+    pub async fn get_invitation_info(pool: &PgPool, token: &str) -> Result<InvitationInfoResponse, AppError> {
+        let invitation = Self::find_open_invitation(pool, token).await?;
+
+        let organization = OrganizationRepository::find_by_id(pool, invitation.org_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))?;
+        let role = Self::find_role(pool, invitation.role_id).await?;
+        let account_exists = UserRepository::find_by_email(pool, &invitation.email).await?.is_some();
+
+        Ok(InvitationInfoResponse {
+            email: invitation.email,
+            organization_id: organization.id,
+            organization_name: organization.name,
+            role_name: role.name,
+            expires_at: format_timestamp(Some(invitation.expires_at)),
+            account_exists,
+        })
+    }
+
+    /// Accept an invitation, creating the invitee's account first when the email has none
+    ///
+    /// Holding the link proves control of the invited email, so no login is required.
+    pub async fn accept_invitation(
+        state: &AppState,
+        token: &str,
+        request: AcceptInvitationRequest,
+        user_agent: Option<String>,
+    ) -> Result<AcceptInvitationResponse, AppError> {
+        let invitation = Self::find_open_invitation(&state.db, token).await?;
+
+        let existing_user = UserRepository::find_by_email(&state.db, &invitation.email).await?;
+
+        // The account, the answer, the membership and the verified email are recorded together
+        let mut uow = state.unit_of_work().await?;
+
+        let mut verify_email = false;
+        let (user_id, created_user) = match existing_user {
+            Some(user) => {
+                if user.is_pending_verification() {
+                    verify_email = true;
+                } else if !user.is_active() {
+                    return Err(AppError::Forbidden(format!("User account is {}", user.status)));
+                }
+                Self::ensure_not_member(&state.db, user.id, invitation.org_id).await?;
+                (user.id, None)
+            }
+            None => {
+                if let Err(validation_errors) = request.validate() {
+                    return Err(validation_errors.into());
+                }
+                let (name, password) = match (request.name, request.password) {
+                    (Some(name), Some(password)) => (name, password),
+                    (None, _) => {
+                        return Err(AppError::field("name", "required", "Name is required to create an account"))
+                    }
+                    (_, None) => {
+                        return Err(AppError::field("password", "required", "Password is required to create an account"))
+                    }
+                };
+
+                let user = UserService::create_verified_user(
+                    uow.conn(),
+                    CreateUserRequest { name, email: invitation.email.clone(), password },
+                )
+                .await?;
+                (user.id, Some(user))
+            }
+        };
+
+        InvitationRepository::respond(uow.conn(), invitation.id, INVITATION_ACCEPTED, Some(user_id))
+            .await?
+            .ok_or_else(|| AppError::Conflict("Invitation is no longer pending".to_string()))?;
+
+        if verify_email {
+            UserRepository::mark_email_verified(uow.conn(), user_id).await?;
+        }
+
+        // A former or previously invited member keeps their row and receives the invited role
+        match UserOrganizationRepository::find_by_user_and_org(uow.conn(), user_id, invitation.org_id).await? {
+            Some(mut membership) => {
+                membership.update_role(invitation.role_id);
+                membership.accept_invitation();
+                UserOrganizationRepository::save_acceptance(uow.conn(), &membership).await?;
+            }
+            None => {
+                UserOrganizationRepository::create(
+                    uow.conn(),
+                    user_id,
+                    invitation.org_id,
+                    invitation.role_id,
+                    Some("active".to_string()),
+                )
+                .await?;
+            }
+        }
+
+        uow.invalidate_permissions(user_id, invitation.org_id);
+        uow.commit().await?;
+
+        tracing::info!("User {} accepted invitation {} to organization {}", user_id, invitation.id, invitation.org_id);
+
+        let account = match created_user {
+            Some(user) => Some(UserService::creation_response(&state.db, user, user_agent).await?),
+            None => None,
+        };
+
+        let membership = UserOrganizationService::get_membership_detail(&state.db, user_id, invitation.org_id).await?;
+        Ok(AcceptInvitationResponse {
+            membership,
+            account_created: account.is_some(),
+            token: account.as_ref().and_then(|account| account.token.clone()),
+            refresh_token: account.and_then(|account| account.refresh_token),
+        })
+    }
+
+    /// Decline an invitation
+    pub async fn decline_invitation(pool: &PgPool, token: &str) -> Result<(), AppError> {
+        let invitation = Self::find_open_invitation(pool, token).await?;
+
+        InvitationRepository::respond(pool, invitation.id, INVITATION_DECLINED, None)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Invitation is no longer pending".to_string()))?;
+
+        tracing::info!("Invitation {} to {} was declined", invitation.id, invitation.email);
+        Ok(())
+    }
+
+    async fn find_invitation(pool: &PgPool, org_id: Uuid, invitation_id: Uuid) -> Result<Invitation, AppError> {
+        InvitationRepository::find_by_id_for_org(pool, org_id, invitation_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))
+    }
+
+    /// The invitation a link token belongs to, if it can still be answered
+    async fn find_open_invitation(pool: &PgPool, token: &str) -> Result<Invitation, AppError> {
+        let invitation = InvitationRepository::find_by_token_hash(pool, &hash_secret_token(token.trim()))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))?;
+
+        Self::ensure_pending(&invitation)?;
+        if invitation.is_expired() {
+            return Err(AppError::Conflict("Invitation has expired".to_string()));
+        }
+
+        Ok(invitation)
+    }
+
+    async fn find_role(pool: &PgPool, role_id: Uuid) -> Result<Role, AppError> {
+        RoleRepository::find_by_id(pool, role_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Role not found".to_string()))
+    }
+
+    fn ensure_pending(invitation: &Invitation) -> Result<(), AppError> {
+        if invitation.is_pending() {
+            Ok(())
+        } else {
+            Err(AppError::Conflict(format!("Invitation has already been {}", invitation.status)))
+        }
+    }
+
+    async fn ensure_not_member(pool: &PgPool, user_id: Uuid, org_id: Uuid) -> Result<(), AppError> {
+        match UserOrganizationRepository::find_by_user_and_org(pool, user_id, org_id).await? {
+            Some(membership) if membership.is_active() => {
+                Err(AppError::Conflict("User is already a member of this organization".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Record the invitation email in the outbox; the link carries the only copy of the token
+    async fn queue_invitation_email(
+        conn: &mut PgConnection,
+        invitation: &Invitation,
+        role: &Role,
+        inviter: &JwtUser,
+        token: &str,
+    ) -> Result<(), AppError> {
+        let organization = OrganizationRepository::find_by_id(&mut *conn, invitation.org_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Organization with id {} not found", invitation.org_id)))?;
+
+        let payload = serde_json::json!({
+            "invitation_id": invitation.id,
+            "organization_name": organization.name,
+            "role_name": role.name,
+            "invited_by_name": inviter.name,
+            "accept_url": OutboxService::app_url(&format!("invitations/{}", token)),
+            "expires_at": format_timestamp(Some(invitation.expires_at)),
+        });
+
+        OutboxService::queue(
+            conn,
+            &invitation.email,
+            EMAIL_TEMPLATE_INVITATION,
+            &format!("You have been invited to join {}", organization.name),
+            payload,
+        )
+        .await
+    }
+
+    fn to_response(invitation: Invitation, role: &Role) -> InvitationResponse {
+        let status = if invitation.is_pending() && invitation.is_expired() {
+            "expired".to_string()
+        } else {
+            invitation.status.clone()
+        };
+
+        InvitationResponse {
+            id: invitation.id,
+            org_id: invitation.org_id,
+            email: invitation.email,
+            role_id: invitation.role_id,
+            role_name: role.name.clone(),
+            status,
+            invited_by: invitation.invited_by,
+            accepted_by: invitation.accepted_by,
+            expires_at: format_timestamp(Some(invitation.expires_at)),
+            responded_at: invitation.responded_at.map(|responded_at| format_timestamp(Some(responded_at))),
+            created_at: format_timestamp(Some(invitation.created_at)),
+        }
+    }
+}
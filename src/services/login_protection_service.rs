@@ -0,0 +1,201 @@
+// Login Protection Service - Failed login tracking, temporary lockout and admin unlock
+
+use chrono::{Duration, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::{
+    AuditEntry, LoginThrottle, AUDIT_ACCOUNT_LOCKED, AUDIT_ACCOUNT_UNLOCKED, AUDIT_IP_LOCKED,
+    THROTTLE_SCOPE_ACCOUNT, THROTTLE_SCOPE_IP,
+};
+use crate::repository::{AuditLogRepository, LoginThrottleRepository, UserOrganizationRepository, UserRepository};
+use crate::utils::{positive_int_from_env, JwtUser};
+
+/// When failures lock a subject out, and for how long
+#[derive(Debug, Clone, Copy)]
+struct LockoutPolicy {
+    /// Failures within the window that trigger the first lockout
+    max_failures: i32,
+    /// First lockout; every further failure doubles it
+    base_lockout: Duration,
+    max_lockout: Duration,
+    /// Quiet period after which failures are forgotten
+    window: Duration,
+}
+
+impl LockoutPolicy {
+    /// Accounts lock after a few failures (`LOGIN_MAX_FAILURES_PER_ACCOUNT`, default 5)
+    fn account() -> Self {
+        Self::with_max_failures(positive_int_from_env("LOGIN_MAX_FAILURES_PER_ACCOUNT", 5))
+    }
+
+    /// An IP may fail more often, since one address can front many people (`LOGIN_MAX_FAILURES_PER_IP`, default 50)
+    fn ip() -> Self {
+        Self::with_max_failures(positive_int_from_env("LOGIN_MAX_FAILURES_PER_IP", 50))
+    }
+
+    /// Shared timings: `LOGIN_LOCKOUT_BASE_SECONDS` (30), `LOGIN_LOCKOUT_MAX_MINUTES` (60)
+    /// and `LOGIN_FAILURE_WINDOW_MINUTES` (15)
+    fn with_max_failures(max_failures: i64) -> Self {
+        Self {
+            max_failures: max_failures.min(i32::MAX as i64) as i32,
+            base_lockout: Duration::seconds(positive_int_from_env("LOGIN_LOCKOUT_BASE_SECONDS", 30)),
+            max_lockout: Duration::minutes(positive_int_from_env("LOGIN_LOCKOUT_MAX_MINUTES", 60)),
+            window: Duration::minutes(positive_int_from_env("LOGIN_FAILURE_WINDOW_MINUTES", 15)),
+        }
+    }
+
+    /// Lockout earned by a run of failures, doubling with each failure past the limit
+    fn lockout_for(&self, failed_attempts: i32) -> Option<Duration> {
+        if failed_attempts < self.max_failures {
+            return None;
+        }
+
+        // Past 2^20 the cap applies long before, and the shift stays in range
+        let doublings = (failed_attempts - self.max_failures).min(20) as u32;
+        let lockout = self.base_lockout * (1i32 << doublings);
+        Some(lockout.min(self.max_lockout))
+    }
+}
+
+pub struct LoginProtectionService;
+
+impl LoginProtectionService {
+    /// Refuse a login attempt while the email or the client IP is locked out
+    ///
+    /// Locks are keyed by the normalized email rather than the account, so unknown emails lock the same
+    /// way and the response never reveals whether an account exists.
+    pub async fn ensure_not_locked(pool: &PgPool, email: &str, client_ip: Option<&str>) -> Result<(), AppError> {
+        let locked = LoginThrottleRepository::find_locked(pool, &Self::subjects(email, client_ip)).await?;
+
+        match locked.iter().map(LoginThrottle::retry_after_secs).max() {
+            Some(retry_after) => Err(AppError::TooManyRequests {
+                message: "Too many failed login attempts; try again later".to_string(),
+                retry_after,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Count a failed login against the email and the client IP, locking whichever reached its limit
+    pub async fn record_failure(
+        pool: &PgPool,
+        email: &str,
+        client_ip: Option<&str>,
+        user_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        for (scope, subject) in Self::subjects(email, client_ip) {
+            let policy = if scope == THROTTLE_SCOPE_IP { LockoutPolicy::ip() } else { LockoutPolicy::account() };
+            let throttle = LoginThrottleRepository::record_failure(pool, scope, &subject, policy.window).await?;
+
+            let Some(lockout) = policy.lockout_for(throttle.failed_attempts) else {
+                continue;
+            };
+            let locked_until = Utc::now() + lockout;
+            LoginThrottleRepository::lock(pool, scope, &subject, locked_until).await?;
+
+            let (action, target_user_id) = if scope == THROTTLE_SCOPE_IP {
+                (AUDIT_IP_LOCKED, None)
+            } else {
+                (AUDIT_ACCOUNT_LOCKED, user_id)
+            };
+            let details = serde_json::json!({
+                "subject": subject,
+                "failed_attempts": throttle.failed_attempts,
+                "locked_until": locked_until,
+            });
+            let entry = AuditEntry::new(action, None, target_user_id, client_ip.map(str::to_string), details);
+            AuditLogRepository::create(pool, &entry).await?;
+
+            tracing::warn!(
+                "Locked {} {} for {}s after {} failed logins",
+                scope,
+                subject,
+                lockout.num_seconds(),
+                throttle.failed_attempts
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Forget an account's failures after a successful login or a password reset
+    ///
+    /// The IP's count is kept, so an attacker cannot reset it by logging into an account of their own.
+    pub async fn reset_account(executor: impl PgExecutor<'_>, email: &str) -> Result<(), AppError> {
+        LoginThrottleRepository::clear(executor, THROTTLE_SCOPE_ACCOUNT, &Self::normalize(email)).await?;
+        Ok(())
+    }
+
+    /// Lift the lockout of a member of the caller's organization, returning whether it was locked
+    pub async fn unlock_member(
+        pool: &PgPool,
+        org_id: Uuid,
+        actor: &JwtUser,
+        user_id: Uuid,
+        client_ip: Option<String>,
+    ) -> Result<bool, AppError> {
+        // Admins only reach accounts that belong to their organization
+        UserOrganizationRepository::find_by_user_and_org(pool, user_id, org_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User is not a member of this organization".to_string()))?;
+        let user = UserRepository::find_by_id(pool, user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let was_locked = LoginThrottleRepository::clear(pool, THROTTLE_SCOPE_ACCOUNT, &Self::normalize(&user.email)).await?;
+
+        let details = serde_json::json!({ "org_id": org_id, "was_locked": was_locked });
+        let entry = AuditEntry::new(AUDIT_ACCOUNT_UNLOCKED, Some(actor.id), Some(user.id), client_ip, details);
+        AuditLogRepository::create(pool, &entry).await?;
+
+        tracing::info!("User {} unlocked the account of user {} (was locked: {})", actor.id, user.id, was_locked);
+        Ok(was_locked)
+    }
+
+    fn subjects(email: &str, client_ip: Option<&str>) -> Vec<(&'static str, String)> {
+        let mut subjects = vec![(THROTTLE_SCOPE_ACCOUNT, Self::normalize(email))];
+        if let Some(ip) = client_ip {
+            subjects.push((THROTTLE_SCOPE_IP, ip.to_string()));
+        }
+        subjects
+    }
+
+    fn normalize(email: &str) -> String {
+        email.trim().to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> LockoutPolicy {
+        LockoutPolicy {
+            max_failures: 5,
+            base_lockout: Duration::seconds(30),
+            max_lockout: Duration::minutes(60),
+            window: Duration::minutes(15),
+        }
+    }
+
+    #[test]
+    fn test_no_lockout_below_the_limit() {
+        assert_eq!(policy().lockout_for(1), None);
+        assert_eq!(policy().lockout_for(4), None);
+    }
+
+    #[test]
+    fn test_lockout_doubles_with_each_further_failure() {
+        assert_eq!(policy().lockout_for(5), Some(Duration::seconds(30)));
+        assert_eq!(policy().lockout_for(6), Some(Duration::seconds(60)));
+        assert_eq!(policy().lockout_for(8), Some(Duration::seconds(240)));
+    }
+
+    #[test]
+    fn test_lockout_is_capped() {
+        assert_eq!(policy().lockout_for(12), Some(Duration::minutes(60)));
+        assert_eq!(policy().lockout_for(i32::MAX), Some(Duration::minutes(60)));
+    }
+}
@@ -0,0 +1,350 @@
+// MFA Service - TOTP enrollment, recovery codes and the second step of login
+
+use chrono::Utc;
+use sqlx::PgPool;
+use std::env;
+use uuid::Uuid;
+
+use crate::dto::{
+    MfaChallengeResponse, MfaCodeRequest, MfaEnrollmentResponse, MfaPolicyResponse, MfaRecoveryCodesResponse,
+    MfaSetupRequest, MfaStatusResponse, MfaVerifyRequest, UpdateMfaPolicyRequest,
+};
+use crate::errors::AppError;
+use crate::models::{
+    AuditEntry, User, UserMfa, UserToken, AUDIT_MFA_DISABLED, AUDIT_MFA_ENABLED, AUDIT_MFA_POLICY_UPDATED,
+    TOKEN_PURPOSE_MFA_PENDING,
+};
+use crate::repository::{
+    AuditLogRepository, OrganizationRepository, UserMfaRepository, UserRepository, UserTokenRepository,
+};
+use crate::services::{LoginProtectionService, PermissionService, ORG_DELETE, USERS_DELETE};
+use crate::utils::{
+    generate_secret_token, generate_totp_secret, hash_secret_token, mfa_pending_ttl, totp_provisioning_uri,
+    verify_totp, JwtUser,
+};
+
+/// Roles holding any of these must use MFA in organizations that require it
+pub const MFA_REQUIRED_PERMISSIONS: [&str; 2] = [USERS_DELETE, ORG_DELETE];
+
+const RECOVERY_CODE_COUNT: usize = 10;
+
+pub struct MfaService;
+
+impl MfaService {
+    pub async fn status(pool: &PgPool, user_id: Uuid) -> Result<MfaStatusResponse, AppError> {
+        let mfa = UserMfaRepository::find(pool, user_id).await?;
+        let enabled = mfa.as_ref().is_some_and(UserMfa::is_enabled);
+
+        Ok(MfaStatusResponse {
+            enabled,
+            enrollment_pending: mfa.is_some() && !enabled,
+            required: Self::is_required(pool, user_id).await?,
+            recovery_codes_remaining: UserMfaRepository::count_unused_recovery_codes(pool, user_id).await?,
+        })
+    }
+
+    /// Issue a new TOTP secret; MFA protects logins once `activate` confirms a code from it
+    pub async fn begin_enrollment(pool: &PgPool, user_id: Uuid, email: &str) -> Result<MfaEnrollmentResponse, AppError> {
+        let secret = generate_totp_secret();
+        UserMfaRepository::start_enrollment(pool, user_id, &secret)
+            .await?
+            .ok_or_else(|| AppError::Conflict("MFA is already enabled".to_string()))?;
+
+        Ok(MfaEnrollmentResponse {
+            provisioning_uri: totp_provisioning_uri(&Self::issuer(), email, &secret),
+            secret,
+        })
+    }
+
+    /// Confirm an enrollment with a first code, returning the recovery codes
+    ///
+    /// Wrong codes here, and when disabling MFA or replacing recovery codes, count towards the login lockout.
+    pub async fn activate(
+        pool: &PgPool,
+        user: &JwtUser,
+        request: MfaCodeRequest,
+    ) -> Result<MfaRecoveryCodesResponse, AppError> {
+        let mfa = Self::pending_enrollment(pool, user.id).await?;
+
+        LoginProtectionService::ensure_not_locked(pool, &user.email, None).await?;
+        if !Self::check_totp(pool, &mfa, request.code.as_deref()).await? {
+            return Err(Self::reject_code(pool, user).await);
+        }
+
+        let recovery_codes = Self::enable(pool, user.id).await?;
+        Ok(MfaRecoveryCodesResponse { recovery_codes })
+    }
+
+    /// Turn MFA off after checking a second factor, unless an organization requires it
+    pub async fn disable(pool: &PgPool, user: &JwtUser, request: MfaCodeRequest) -> Result<(), AppError> {
+        let user_id = user.id;
+        let mfa = Self::enabled_mfa(pool, user_id).await?;
+
+        if Self::is_required(pool, user_id).await? {
+            return Err(AppError::Conflict(
+                "MFA is required by an organization you belong to and cannot be turned off".to_string(),
+            ));
+        }
+
+        LoginProtectionService::ensure_not_locked(pool, &user.email, None).await?;
+        if !Self::check_second_factor(pool, &mfa, request.code.as_deref(), request.recovery_code.as_deref()).await? {
+            return Err(Self::reject_code(pool, user).await);
+        }
+
+        UserMfaRepository::delete(pool, user_id).await?;
+        Self::audit(pool, AUDIT_MFA_DISABLED, Some(user_id), Some(user_id), serde_json::json!({})).await?;
+
+        tracing::info!("User {} turned off MFA", user_id);
+        Ok(())
+    }
+
+    /// Replace the recovery codes after checking a TOTP code
+    pub async fn regenerate_recovery_codes(
+        pool: &PgPool,
+        user: &JwtUser,
+        request: MfaCodeRequest,
+    ) -> Result<MfaRecoveryCodesResponse, AppError> {
+        let mfa = Self::enabled_mfa(pool, user.id).await?;
+
+        LoginProtectionService::ensure_not_locked(pool, &user.email, None).await?;
+        if !Self::check_totp(pool, &mfa, request.code.as_deref()).await? {
+            return Err(Self::reject_code(pool, user).await);
+        }
+
+        let recovery_codes = Self::issue_recovery_codes(pool, user.id).await?;
+        Ok(MfaRecoveryCodesResponse { recovery_codes })
+    }
+
+    /// The second step a login needs after the right password, if any
+    ///
+    /// Users with MFA enabled must enter a code; users whose organization requires MFA but who have not
+    /// enrolled must set it up before they get a session.
+    pub async fn login_challenge(pool: &PgPool, user: &User) -> Result<Option<MfaChallengeResponse>, AppError> {
+        let enabled = UserMfaRepository::find(pool, user.id).await?.is_some_and(|mfa| mfa.is_enabled());
+        if !enabled && !Self::is_required(pool, user.id).await? {
+            return Ok(None);
+        }
+
+        // Only the latest password step can be completed
+        UserTokenRepository::invalidate_for_user(pool, user.id, TOKEN_PURPOSE_MFA_PENDING).await?;
+
+        let mfa_token = generate_secret_token();
+        let ttl = mfa_pending_ttl();
+        let pending = UserToken::new(user.id, TOKEN_PURPOSE_MFA_PENDING, hash_secret_token(&mfa_token), Utc::now() + ttl);
+        UserTokenRepository::create(pool, &pending).await?;
+
+        Ok(Some(MfaChallengeResponse {
+            mfa_token,
+            expires_in: ttl.num_seconds(),
+            enrollment_required: !enabled,
+        }))
+    }
+
+    /// Issue a TOTP secret to a user who must enroll before finishing their login
+    pub async fn setup_for_login(pool: &PgPool, request: MfaSetupRequest) -> Result<MfaEnrollmentResponse, AppError> {
+        let pending = Self::pending_login(pool, &request.mfa_token).await?;
+        let user = Self::login_user(pool, pending.user_id).await?;
+
+        Self::begin_enrollment(pool, user.id, &user.email).await
+    }
+
+    /// Check the second factor of a login and use up its mfa_pending token
+    ///
+    /// Returns the user to start a session for, and the recovery codes when this also confirmed a required
+    /// enrollment. Wrong codes count towards the login lockout like wrong passwords.
+    pub async fn verify_login(
+        pool: &PgPool,
+        request: MfaVerifyRequest,
+        client_ip: Option<&str>,
+    ) -> Result<(User, Option<Vec<String>>), AppError> {
+        let pending = Self::pending_login(pool, &request.mfa_token).await?;
+        let user = Self::login_user(pool, pending.user_id).await?;
+        LoginProtectionService::ensure_not_locked(pool, &user.email, client_ip).await?;
+
+        let mfa = UserMfaRepository::find(pool, user.id)
+            .await?
+            .ok_or_else(|| AppError::ValidationError("Set up MFA with /auth/mfa/setup first".to_string()))?;
+
+        // A required enrollment is confirmed by the first code, but recovery codes do not exist yet
+        let valid = if mfa.is_enabled() {
+            Self::check_second_factor(pool, &mfa, request.code.as_deref(), request.recovery_code.as_deref()).await?
+        } else {
+            Self::check_totp(pool, &mfa, request.code.as_deref()).await?
+        };
+
+        if !valid {
+            LoginProtectionService::record_failure(pool, &user.email, client_ip, Some(user.id)).await?;
+            return Err(AppError::Unauthorized("Invalid verification code".to_string()));
+        }
+
+        UserTokenRepository::consume(pool, TOKEN_PURPOSE_MFA_PENDING, &pending.token_hash)
+            .await?
+            .ok_or_else(Self::invalid_mfa_token)?;
+
+        let recovery_codes = if mfa.is_enabled() { None } else { Some(Self::enable(pool, user.id).await?) };
+
+        Ok((user, recovery_codes))
+    }
+
+    pub async fn policy(pool: &PgPool, org_id: Uuid) -> Result<MfaPolicyResponse, AppError> {
+        let require_mfa = OrganizationRepository::find_require_mfa(pool, org_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+        Ok(Self::policy_response(org_id, require_mfa))
+    }
+
+    /// Require, or stop requiring, MFA for the organization's most privileged roles
+    ///
+    /// Affected members are asked to enroll at their next login; current sessions are not ended.
+    pub async fn update_policy(
+        pool: &PgPool,
+        org_id: Uuid,
+        actor: &JwtUser,
+        request: UpdateMfaPolicyRequest,
+    ) -> Result<MfaPolicyResponse, AppError> {
+        let require_mfa = OrganizationRepository::update_require_mfa(pool, org_id, request.require_mfa)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+        let details = serde_json::json!({ "org_id": org_id, "require_mfa": require_mfa });
+        Self::audit(pool, AUDIT_MFA_POLICY_UPDATED, Some(actor.id), None, details).await?;
+
+        tracing::info!("User {} set require_mfa={} for organization {}", actor.id, require_mfa, org_id);
+        Ok(Self::policy_response(org_id, require_mfa))
+    }
+
+    fn policy_response(org_id: Uuid, require_mfa: bool) -> MfaPolicyResponse {
+        MfaPolicyResponse {
+            org_id,
+            require_mfa,
+            applies_to_permissions: MFA_REQUIRED_PERMISSIONS.iter().map(|permission| permission.to_string()).collect(),
+        }
+    }
+
+    /// Whether an organization requiring MFA gives the user any of `MFA_REQUIRED_PERMISSIONS`
+    ///
+    /// Checked through the member's permission set, so `*` and `<resource>:*` grants count.
+    async fn is_required(pool: &PgPool, user_id: Uuid) -> Result<bool, AppError> {
+        for org_id in UserMfaRepository::find_requiring_org_ids(pool, user_id).await? {
+            let permissions = PermissionService::permission_set(pool, user_id, org_id).await?;
+            if permissions.allows_any(&MFA_REQUIRED_PERMISSIONS) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Enable a confirmed enrollment and hand out its first recovery codes
+    async fn enable(pool: &PgPool, user_id: Uuid) -> Result<Vec<String>, AppError> {
+        UserMfaRepository::enable(pool, user_id).await?;
+        let recovery_codes = Self::issue_recovery_codes(pool, user_id).await?;
+        Self::audit(pool, AUDIT_MFA_ENABLED, Some(user_id), Some(user_id), serde_json::json!({})).await?;
+
+        tracing::info!("User {} turned on MFA", user_id);
+        Ok(recovery_codes)
+    }
+
+    async fn issue_recovery_codes(pool: &PgPool, user_id: Uuid) -> Result<Vec<String>, AppError> {
+        let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| {
+                let code = hex::encode(rand::random::<[u8; 5]>());
+                format!("{}-{}", &code[..5], &code[5..])
+            })
+            .collect();
+        let hashes: Vec<String> = recovery_codes.iter().map(|code| Self::hash_recovery_code(code)).collect();
+
+        UserMfaRepository::replace_recovery_codes(pool, user_id, &hashes).await?;
+        Ok(recovery_codes)
+    }
+
+    /// A TOTP code, or failing that a recovery code, which is used up
+    async fn check_second_factor(
+        pool: &PgPool,
+        mfa: &UserMfa,
+        code: Option<&str>,
+        recovery_code: Option<&str>,
+    ) -> Result<bool, AppError> {
+        match (code, recovery_code) {
+            (Some(_), _) => Self::check_totp(pool, mfa, code).await,
+            (None, Some(recovery_code)) => {
+                UserMfaRepository::consume_recovery_code(pool, mfa.user_id, &Self::hash_recovery_code(recovery_code)).await
+            }
+            (None, None) => Err(AppError::ValidationError("A code or recovery_code is required".to_string())),
+        }
+    }
+
+    /// Check a TOTP code, refusing one whose time step was already used
+    async fn check_totp(pool: &PgPool, mfa: &UserMfa, code: Option<&str>) -> Result<bool, AppError> {
+        let code = code.ok_or_else(|| AppError::ValidationError("A code is required".to_string()))?;
+
+        match verify_totp(&mfa.secret, code, Utc::now()) {
+            Some(step) => UserMfaRepository::use_step(pool, mfa.user_id, step).await,
+            None => Ok(false),
+        }
+    }
+
+    async fn pending_enrollment(pool: &PgPool, user_id: Uuid) -> Result<UserMfa, AppError> {
+        match UserMfaRepository::find(pool, user_id).await? {
+            Some(mfa) if mfa.is_enabled() => Err(AppError::Conflict("MFA is already enabled".to_string())),
+            Some(mfa) => Ok(mfa),
+            None => Err(AppError::ValidationError("Start MFA enrollment first".to_string())),
+        }
+    }
+
+    async fn enabled_mfa(pool: &PgPool, user_id: Uuid) -> Result<UserMfa, AppError> {
+        UserMfaRepository::find(pool, user_id)
+            .await?
+            .filter(UserMfa::is_enabled)
+            .ok_or_else(|| AppError::ValidationError("MFA is not enabled".to_string()))
+    }
+
+    async fn pending_login(pool: &PgPool, mfa_token: &str) -> Result<UserToken, AppError> {
+        UserTokenRepository::find_active(pool, TOKEN_PURPOSE_MFA_PENDING, &hash_secret_token(mfa_token.trim()))
+            .await?
+            .ok_or_else(Self::invalid_mfa_token)
+    }
+
+    /// The user behind a pending login, who must still be allowed to log in
+    async fn login_user(pool: &PgPool, user_id: Uuid) -> Result<User, AppError> {
+        UserRepository::find_by_id(pool, user_id)
+            .await?
+            .filter(User::is_active)
+            .ok_or_else(Self::invalid_mfa_token)
+    }
+
+    async fn audit(
+        pool: &PgPool,
+        action: &str,
+        actor_id: Option<Uuid>,
+        target_user_id: Option<Uuid>,
+        details: serde_json::Value,
+    ) -> Result<(), AppError> {
+        AuditLogRepository::create(pool, &AuditEntry::new(action, actor_id, target_user_id, None, details)).await?;
+        Ok(())
+    }
+
+    fn hash_recovery_code(code: &str) -> String {
+        let normalized: String = code.chars().filter(char::is_ascii_alphanumeric).collect();
+        hash_secret_token(&normalized.to_lowercase())
+    }
+
+    /// Name shown next to the account in authenticator apps (`MFA_ISSUER`, default "Survey")
+    fn issuer() -> String {
+        env::var("MFA_ISSUER").unwrap_or_else(|_| "Survey".to_string())
+    }
+
+    /// Count a wrong code from a signed-in user towards their login lockout, so a stolen session
+    /// cannot be used to guess codes
+    async fn reject_code(pool: &PgPool, user: &JwtUser) -> AppError {
+        match LoginProtectionService::record_failure(pool, &user.email, None, Some(user.id)).await {
+            Ok(()) => AppError::ValidationError("Invalid verification code".to_string()),
+            Err(error) => error,
+        }
+    }
+
+    fn invalid_mfa_token() -> AppError {
+        AppError::Unauthorized("Invalid or expired MFA token".to_string())
+    }
+}
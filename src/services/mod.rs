@@ -1,13 +1,45 @@
 // Services module - Business logic layer
+pub mod account_service;
+pub mod contact_activity_service;
+pub mod contact_bulk_service;
 pub mod contact_service;
 pub mod contact_filter_service;
+pub mod contact_import_service;
+pub mod contact_tag_service;
+pub mod custom_field_service;
+pub mod custom_field_validation_service;
+pub mod invitation_service;
+pub mod login_protection_service;
+pub mod mfa_service;
 pub mod organization_service;
+pub mod outbox_service;
+pub mod password_service;
+pub mod permission_cache;
+pub mod permission_registry;
 pub mod permission_service;
+pub mod role_service;
+pub mod session_service;
 pub mod user_organization_service;
 pub mod user_service;
 
+pub use account_service::*;
+pub use contact_activity_service::*;
+pub use contact_bulk_service::*;
 pub use contact_service::*;
+pub use contact_import_service::*;
+pub use contact_tag_service::*;
+pub use custom_field_service::*;
+pub use custom_field_validation_service::*;
+pub use invitation_service::*;
+pub use login_protection_service::*;
+pub use mfa_service::*;
 pub use organization_service::*;
+pub use outbox_service::*;
+pub use password_service::*;
+pub use permission_cache::*;
+pub use permission_registry::*;
 pub use permission_service::*;
+pub use role_service::*;
+pub use session_service::*;
 pub use user_organization_service::*;
 pub use user_service::*;
@@ -1,20 +1,35 @@
 // Organization Service - Business logic for organizations
 
+use chrono::{Duration, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
+use validator::Validate;
 
-use crate::dto::{CreateOrganizationRequest, OrganizationResponse};
+use crate::dto::{
+    CreateOrganizationRequest, OrganizationDeletionResponse, OrganizationResponse, OrganizationSettingsResponse,
+    UpdateOrganizationRequest,
+};
 use crate::errors::AppError;
-use crate::models::Organization;
-use crate::repository::OrganizationRepository;
-use crate::utils::format_timestamp;
+use crate::models::{
+    AuditEntry, ContactTag, CustomField, LeadStatus, Organization, OrganizationSettings, PermissionSet, ADMIN_ROLE,
+    AUDIT_ORG_DELETED, AUDIT_ORG_PURGED, AUDIT_ORG_RESTORED, AUDIT_ORG_SETTINGS_UPDATED, AUDIT_ORG_UPDATED,
+    MEMBERSHIP_ORG_DELETED,
+};
+use crate::repository::{
+    AuditLogRepository, ContactTagRepository, CustomFieldRepository, LeadStatusRepository, OrganizationRepository,
+    RoleRepository, UserOrganizationRepository,
+};
+use crate::services::ORG_DELETE;
+use crate::utils::{format_timestamp, positive_int_from_env, JwtUser};
+use crate::AppState;
 
 pub struct OrganizationService;
 
 impl OrganizationService {
-    /// Create a new organization with validation
+    /// Create a new organization with its creator as admin and the default custom fields, tags and lead statuses
     pub async fn create_organization(
-        pool: &PgPool,
+        state: &AppState,
+        creator: &JwtUser,
         request: CreateOrganizationRequest,
     ) -> Result<OrganizationResponse, AppError> {
         // Validate required fields
@@ -31,15 +46,51 @@ impl OrganizationService {
             ));
         }
 
-        // Create organization using repository
+        // Country and timezone must be known to the IANA time zone database
+        request.validate()?;
+
+        // All or nothing: an organization without its admin would be unmanageable
+        let mut uow = state.unit_of_work().await?;
+
         let organization = OrganizationRepository::create(
-            pool,
-            request.name.trim().to_string(),
-            request.country,
-            request.timezone,
+            uow.conn(),
+            &Organization::new(
+                request.name.trim().to_string(),
+                normalize_country(request.country),
+                normalize_timezone(request.timezone),
+            ),
         )
         .await?;
 
+        // A new organization has no custom roles yet, so this is the built-in admin role
+        let admin_role = RoleRepository::find_by_name(uow.conn(), organization.id, ADMIN_ROLE)
+            .await?
+            .ok_or_else(|| AppError::InternalServerError(format!("Built-in role '{}' is missing", ADMIN_ROLE)))?;
+
+        UserOrganizationRepository::create(
+            uow.conn(),
+            creator.id,
+            organization.id,
+            admin_role.id,
+            Some("active".to_string()),
+        )
+        .await?;
+        uow.invalidate_permissions(creator.id, organization.id);
+
+        for custom_field in CustomField::defaults(organization.id, Some(creator.id)) {
+            CustomFieldRepository::create(uow.conn(), &custom_field).await?;
+        }
+        for tag in ContactTag::defaults(organization.id) {
+            ContactTagRepository::create(uow.conn(), &tag).await?;
+        }
+        for lead_status in LeadStatus::defaults(organization.id) {
+            LeadStatusRepository::create(uow.conn(), &lead_status).await?;
+        }
+
+        uow.commit().await?;
+
+        tracing::info!("Organization {} created by user {}", organization.id, creator.id);
+
         // Convert to response DTO
         Ok(Self::to_response(organization))
     }
@@ -61,6 +112,185 @@ impl OrganizationService {
         Ok(organizations.into_iter().map(Self::to_response).collect())
     }
 
+    /// Get an organization that has not been deleted
+    pub async fn get_organization(pool: &PgPool, org_id: Uuid) -> Result<OrganizationResponse, AppError> {
+        let organization = OrganizationRepository::find_by_id(pool, org_id)
+            .await?
+            .ok_or_else(|| Self::not_found(org_id))?;
+
+        Ok(Self::to_response(organization))
+    }
+
+    /// Rename an organization and replace its country and timezone
+    pub async fn update_organization(
+        pool: &PgPool,
+        org_id: Uuid,
+        actor: &JwtUser,
+        request: UpdateOrganizationRequest,
+    ) -> Result<OrganizationResponse, AppError> {
+        request.validate()?;
+
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(AppError::field("name", "required", "Organization name cannot be empty"));
+        }
+
+        let organization = OrganizationRepository::update(
+            pool,
+            org_id,
+            name,
+            normalize_country(request.country),
+            normalize_timezone(request.timezone),
+        )
+        .await?
+        .ok_or_else(|| Self::not_found(org_id))?;
+
+        let details = serde_json::json!({
+            "org_id": org_id,
+            "name": organization.name,
+            "country": organization.country,
+            "timezone": organization.timezone,
+        });
+        Self::audit(pool, AUDIT_ORG_UPDATED, actor, details).await?;
+
+        tracing::info!("User {} updated organization {}", actor.id, org_id);
+        Ok(Self::to_response(organization))
+    }
+
+    pub async fn get_settings(pool: &PgPool, org_id: Uuid) -> Result<OrganizationSettingsResponse, AppError> {
+        let settings = OrganizationRepository::find_settings(pool, org_id)
+            .await?
+            .ok_or_else(|| Self::not_found(org_id))?;
+
+        Ok(OrganizationSettingsResponse { org_id, settings })
+    }
+
+    /// Replace the settings document; settings left out of the request return to their defaults
+    pub async fn update_settings(
+        pool: &PgPool,
+        org_id: Uuid,
+        actor: &JwtUser,
+        settings: OrganizationSettings,
+    ) -> Result<OrganizationSettingsResponse, AppError> {
+        settings.validate()?;
+
+        let settings = OrganizationRepository::update_settings(pool, org_id, &settings)
+            .await?
+            .ok_or_else(|| Self::not_found(org_id))?;
+
+        let details = serde_json::json!({ "org_id": org_id, "settings": settings });
+        Self::audit(pool, AUDIT_ORG_SETTINGS_UPDATED, actor, details).await?;
+
+        tracing::info!("User {} updated the settings of organization {}", actor.id, org_id);
+        Ok(OrganizationSettingsResponse { org_id, settings })
+    }
+
+    /// Delete an organization, keeping its data for the grace period in case it is restored
+    ///
+    /// Members lose access to the organization and its data at once: their memberships are set aside
+    /// and its pending invitations are revoked. `purge_deleted` removes it for good once the grace
+    /// period has run out.
+    pub async fn delete_organization(
+        state: &AppState,
+        org_id: Uuid,
+        actor: &JwtUser,
+    ) -> Result<OrganizationDeletionResponse, AppError> {
+        let purge_after = Utc::now() + Self::deletion_grace_period();
+        let mut uow = state.unit_of_work().await?;
+        let deleted_at = OrganizationRepository::soft_delete(uow.conn(), org_id, purge_after)
+            .await?
+            .ok_or_else(|| Self::not_found(org_id))?;
+        let members =
+            UserOrganizationRepository::update_status_for_org(uow.conn(), org_id, "active", MEMBERSHIP_ORG_DELETED).await?;
+        for user_id in members {
+            uow.invalidate_permissions(user_id, org_id);
+        }
+        uow.commit().await?;
+
+        let details = serde_json::json!({ "org_id": org_id, "purge_after": purge_after });
+        Self::audit(&state.db, AUDIT_ORG_DELETED, actor, details).await?;
+
+        tracing::warn!("User {} deleted organization {}; purging after {}", actor.id, org_id, purge_after);
+        Ok(OrganizationDeletionResponse {
+            org_id,
+            deleted_at: format_timestamp(Some(deleted_at)),
+            purge_after: format_timestamp(Some(purge_after)),
+        })
+    }
+
+    /// Restore a deleted organization within its grace period
+    ///
+    /// Deleted organizations cannot be selected as the active organization, so the caller's
+    /// `org:delete` permission is checked here against the role of the membership the deletion
+    /// set aside. Every membership set aside is active again afterwards.
+    pub async fn restore_organization(
+        state: &AppState,
+        org_id: Uuid,
+        actor: &JwtUser,
+    ) -> Result<OrganizationResponse, AppError> {
+        let membership = UserOrganizationRepository::find_by_user_and_org(&state.db, actor.id, org_id)
+            .await?
+            .filter(|membership| membership.is_active() || membership.is_org_deleted())
+            .ok_or_else(|| {
+                AppError::Forbidden(format!("User is not an active member of organization {}", org_id))
+            })?;
+
+        let role = RoleRepository::find_by_id(&state.db, membership.role_id)
+            .await?
+            .ok_or_else(|| AppError::InternalServerError(format!("Role {} is missing", membership.role_id)))?;
+        let permissions = PermissionSet::new(role.get_permissions().into_iter().collect());
+        if !permissions.allows(ORG_DELETE) {
+            return Err(AppError::Forbidden(format!("Permission '{}' required", ORG_DELETE)));
+        }
+
+        let mut uow = state.unit_of_work().await?;
+        if !OrganizationRepository::restore(uow.conn(), org_id).await? {
+            return Err(AppError::NotFound(format!(
+                "Organization {} is not awaiting deletion",
+                org_id
+            )));
+        }
+        let members =
+            UserOrganizationRepository::update_status_for_org(uow.conn(), org_id, MEMBERSHIP_ORG_DELETED, "active").await?;
+        for user_id in members {
+            uow.invalidate_permissions(user_id, org_id);
+        }
+        uow.commit().await?;
+
+        Self::audit(&state.db, AUDIT_ORG_RESTORED, actor, serde_json::json!({ "org_id": org_id })).await?;
+
+        tracing::info!("User {} restored organization {}", actor.id, org_id);
+        Self::get_organization(&state.db, org_id).await
+    }
+
+    /// Permanently delete organizations whose grace period has run out, returning how many were removed
+    pub async fn purge_deleted(pool: &PgPool) -> Result<usize, AppError> {
+        let purged = OrganizationRepository::purge_deleted(pool).await?;
+
+        for org_id in &purged {
+            let entry = AuditEntry::new(AUDIT_ORG_PURGED, None, None, None, serde_json::json!({ "org_id": org_id }));
+            AuditLogRepository::create(pool, &entry).await?;
+            tracing::warn!("Purged organization {}", org_id);
+        }
+
+        Ok(purged.len())
+    }
+
+    /// How long a deleted organization can be restored (`ORG_DELETION_GRACE_DAYS`, default 30)
+    pub fn deletion_grace_period() -> Duration {
+        Duration::days(positive_int_from_env("ORG_DELETION_GRACE_DAYS", 30))
+    }
+
+    async fn audit(pool: &PgPool, action: &str, actor: &JwtUser, details: serde_json::Value) -> Result<(), AppError> {
+        let entry = AuditEntry::new(action, Some(actor.id), None, None, details);
+        AuditLogRepository::create(pool, &entry).await?;
+        Ok(())
+    }
+
+    fn not_found(org_id: Uuid) -> AppError {
+        AppError::NotFound(format!("Organization with id {} not found", org_id))
+    }
+
     /// Convert Organization model to response DTO
     fn to_response(organization: Organization) -> OrganizationResponse {
         OrganizationResponse {
@@ -72,3 +302,12 @@ impl OrganizationService {
         }
     }
 }
+
+/// Store country codes in upper case, as the IANA time zone database lists them
+fn normalize_country(country: Option<String>) -> Option<String> {
+    country.map(|country| country.trim().to_uppercase()).filter(|country| !country.is_empty())
+}
+
+fn normalize_timezone(timezone: Option<String>) -> Option<String> {
+    timezone.map(|timezone| timezone.trim().to_string()).filter(|timezone| !timezone.is_empty())
+}
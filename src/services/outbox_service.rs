@@ -0,0 +1,34 @@
+// Outbox Service - Queue transactional emails and build the links they carry
+
+use serde_json::Value as JsonValue;
+use sqlx::PgExecutor;
+use std::env;
+
+use crate::errors::AppError;
+use crate::repository::EmailOutboxRepository;
+
+/// Frontend origin that emailed links point to
+const DEFAULT_APP_BASE_URL: &str = "http://localhost:3000";
+
+pub struct OutboxService;
+
+impl OutboxService {
+    /// Record an email in the outbox; a mail worker (or `cargo run --bin drain_outbox` locally) delivers it
+    pub async fn queue(
+        executor: impl PgExecutor<'_>,
+        recipient: &str,
+        template: &str,
+        subject: &str,
+        payload: JsonValue,
+    ) -> Result<(), AppError> {
+        let email = EmailOutboxRepository::enqueue(executor, recipient, template, subject, payload).await?;
+        tracing::info!("Queued {} email {} for {}", template, email.id, recipient);
+        Ok(())
+    }
+
+    /// Absolute link to a frontend page (`APP_BASE_URL`, default `http://localhost:3000`)
+    pub fn app_url(path: &str) -> String {
+        let base_url = env::var("APP_BASE_URL").unwrap_or_else(|_| DEFAULT_APP_BASE_URL.to_string());
+        format!("{}/{}", base_url.trim_end_matches('/'), path.trim_start_matches('/'))
+    }
+}
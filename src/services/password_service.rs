@@ -0,0 +1,68 @@
+// Password Service - Password policy, reuse history and hash upgrades
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::models::User;
+use crate::repository::{PasswordHistoryRepository, UserRepository};
+use crate::utils::{hash_password, password_needs_rehash, password_policy, verify_password};
+
+pub struct PasswordService;
+
+impl PasswordService {
+    /// Check a password for a new account and hash it
+    pub fn hash_new(field: &str, password: &str) -> Result<String, AppError> {
+        password_policy().check(field, password)?;
+        hash_password(password)
+    }
+
+    /// Check a replacement password against the policy and the user's recent passwords, and hash it
+    pub async fn hash_replacement(pool: &PgPool, user_id: Uuid, field: &str, password: &str) -> Result<String, AppError> {
+        let policy = password_policy();
+        policy.check(field, password)?;
+
+        for previous_hash in PasswordHistoryRepository::find_recent(pool, user_id, policy.history_size).await? {
+            if verify_password(password, &previous_hash)? {
+                return Err(AppError::field(
+                    field,
+                    "reused",
+                    format!("Password must differ from your last {} passwords", policy.history_size),
+                ));
+            }
+        }
+
+        hash_password(password)
+    }
+
+    /// Store a hash from `hash_new` or `hash_replacement` as the user's password
+    pub async fn set_password(conn: &mut PgConnection, user_id: Uuid, password_hash: &str) -> Result<User, AppError> {
+        let user = UserRepository::update_password(&mut *conn, user_id, password_hash).await?;
+        Self::remember(conn, user_id, password_hash).await?;
+        Ok(user)
+    }
+
+    /// Add a hash to the user's password history
+    pub async fn remember(conn: &mut PgConnection, user_id: Uuid, password_hash: &str) -> Result<(), AppError> {
+        PasswordHistoryRepository::record(conn, user_id, password_hash, password_policy().history_size).await
+    }
+
+    /// Replace a hash that just verified when it uses an older scheme or weaker settings
+    ///
+    /// The login goes ahead even if the upgrade fails; it is retried on the next one.
+    pub async fn upgrade_hash(pool: &PgPool, user: &User, password: &str) {
+        if !password_needs_rehash(&user.password) {
+            return;
+        }
+
+        let upgraded = match hash_password(password) {
+            Ok(password_hash) => UserRepository::update_password(pool, user.id, &password_hash).await,
+            Err(e) => Err(e),
+        };
+
+        match upgraded {
+            Ok(_) => tracing::info!("Upgraded the password hash of user {}", user.id),
+            Err(e) => tracing::warn!("Failed to upgrade the password hash of user {}: {:?}", user.id, e),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+// Permission Cache - In-process TTL cache of resolved permission sets per (user, organization)
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::models::PermissionSet;
+
+const DEFAULT_TTL_SECONDS: u64 = 60;
+
+struct CachedPermissions {
+    permissions: PermissionSet,
+    expires_at: Instant,
+}
+
+type CacheKey = (Uuid, Uuid);
+
+static ENTRIES: OnceLock<RwLock<HashMap<CacheKey, CachedPermissions>>> = OnceLock::new();
+static TTL: OnceLock<Duration> = OnceLock::new();
+
+/// Bumped on every invalidation so a load that raced with a role or membership change is not stored
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub struct PermissionCache;
+
+impl PermissionCache {
+    /// Cached permissions of a user in an organization, if still fresh
+    pub fn get(user_id: Uuid, org_id: Uuid) -> Option<PermissionSet> {
+        let entries = entries().read().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(&(user_id, org_id))
+            .filter(|cached| cached.expires_at > Instant::now())
+            .map(|cached| cached.permissions.clone())
+    }
+
+    /// Current generation; read it before loading permissions and pass it to `insert`
+    pub fn generation() -> u64 {
+        GENERATION.load(Ordering::SeqCst)
+    }
+
+    /// Store permissions loaded at `generation`, unless the cache was invalidated since
+    pub fn insert(user_id: Uuid, org_id: Uuid, permissions: PermissionSet, generation: u64) {
+        let ttl = ttl();
+        if ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = entries().write().unwrap_or_else(|e| e.into_inner());
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+
+        let now = Instant::now();
+        entries.retain(|_, cached| cached.expires_at > now);
+        entries.insert(
+            (user_id, org_id),
+            CachedPermissions {
+                permissions,
+                expires_at: now + ttl,
+            },
+        );
+    }
+
+    /// Forget a user's permissions in one organization (membership added, changed or removed)
+    pub fn invalidate(user_id: Uuid, org_id: Uuid) {
+        let mut entries = entries().write().unwrap_or_else(|e| e.into_inner());
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+        entries.remove(&(user_id, org_id));
+    }
+
+    /// Forget every cached permission set (a role's permissions changed)
+    pub fn invalidate_all() {
+        let mut entries = entries().write().unwrap_or_else(|e| e.into_inner());
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+        entries.clear();
+    }
+}
+
+fn entries() -> &'static RwLock<HashMap<CacheKey, CachedPermissions>> {
+    ENTRIES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Cache lifetime (`PERMISSION_CACHE_TTL_SECONDS`, default 60; 0 disables caching)
+fn ttl() -> Duration {
+    *TTL.get_or_init(|| {
+        let seconds = match env::var("PERMISSION_CACHE_TTL_SECONDS") {
+            Ok(value) => value.parse::<u64>().unwrap_or_else(|_| {
+                tracing::warn!(
+                    "Invalid PERMISSION_CACHE_TTL_SECONDS '{}', using default {}",
+                    value,
+                    DEFAULT_TTL_SECONDS
+                );
+                DEFAULT_TTL_SECONDS
+            }),
+            Err(_) => DEFAULT_TTL_SECONDS,
+        };
+        Duration::from_secs(seconds)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // The cache is process-wide; keep these tests from invalidating each other's entries
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    fn permissions(permission: &str) -> PermissionSet {
+        std::iter::once(permission.to_string()).collect()
+    }
+
+    #[test]
+    fn test_insert_and_invalidate() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        let (user_id, org_id, other_org_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        PermissionCache::insert(user_id, org_id, permissions("contacts:read"), PermissionCache::generation());
+        PermissionCache::insert(user_id, other_org_id, permissions("*"), PermissionCache::generation());
+        assert_eq!(PermissionCache::get(user_id, org_id), Some(permissions("contacts:read")));
+
+        PermissionCache::invalidate(user_id, org_id);
+        assert_eq!(PermissionCache::get(user_id, org_id), None);
+        assert_eq!(PermissionCache::get(user_id, other_org_id), Some(permissions("*")));
+    }
+
+    #[test]
+    fn test_stale_load_not_stored() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        let (user_id, org_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // A role changed while these permissions were being loaded
+        let generation = PermissionCache::generation();
+        PermissionCache::invalidate_all();
+        PermissionCache::insert(user_id, org_id, permissions("contacts:read"), generation);
+
+        assert_eq!(PermissionCache::get(user_id, org_id), None);
+    }
+}
@@ -0,0 +1,200 @@
+// Permission Registry - Every permission a role can grant, with its description
+
+use serde::Serialize;
+
+use crate::errors::AppError;
+
+/// User administration permissions
+pub const USERS_READ: &str = "users:read";
+pub const USERS_WRITE: &str = "users:write";
+pub const USERS_CREATE: &str = "users:create";
+pub const USERS_DELETE: &str = "users:delete";
+
+/// Organization permissions
+pub const ORG_READ: &str = "org:read";
+pub const ORG_WRITE: &str = "org:write";
+pub const ORG_CREATE: &str = "org:create";
+pub const ORG_DELETE: &str = "org:delete";
+
+/// Organization membership permissions
+pub const MEMBERS_INVITE: &str = "members:invite";
+
+/// Contact permissions
+pub const CONTACTS_READ: &str = "contacts:read";
+pub const CONTACTS_CREATE: &str = "contacts:create";
+pub const CONTACTS_UPDATE: &str = "contacts:update";
+pub const CONTACTS_DELETE: &str = "contacts:delete";
+pub const CONTACTS_EXPORT: &str = "contacts:export";
+pub const CONTACTS_IMPORT: &str = "contacts:import";
+pub const CONTACTS_ASSIGN_OWNER: &str = "contacts:assign_owner";
+pub const CONTACTS_BULK_UPDATE: &str = "contacts:bulk_update";
+
+/// Activity timeline permissions
+pub const ACTIVITIES_READ: &str = "activities:read";
+pub const ACTIVITIES_CREATE: &str = "activities:create";
+pub const ACTIVITIES_UPDATE: &str = "activities:update";
+pub const ACTIVITIES_DELETE: &str = "activities:delete";
+
+/// Custom field definition management
+pub const CUSTOM_FIELDS_MANAGE: &str = "custom_fields:manage";
+
+/// Role management within an organization
+pub const ROLES_READ: &str = "roles:read";
+pub const ROLES_MANAGE: &str = "roles:manage";
+
+/// Report permissions
+pub const REPORTS_READ: &str = "reports:read";
+pub const REPORTS_CREATE: &str = "reports:create";
+pub const REPORTS_EXPORT: &str = "reports:export";
+
+/// A grantable permission; its resource is the part of the name before `:`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PermissionDefinition {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+impl PermissionDefinition {
+    const fn new(name: &'static str, description: &'static str) -> Self {
+        Self { name, description }
+    }
+
+    pub fn resource(&self) -> &'static str {
+        self.name.split_once(':').map_or(self.name, |(resource, _)| resource)
+    }
+}
+
+/// Every permission known to the application, grouped by resource
+pub const PERMISSIONS: &[PermissionDefinition] = &[
+    PermissionDefinition::new(USERS_READ, "View users"),
+    PermissionDefinition::new(USERS_WRITE, "Edit users"),
+    PermissionDefinition::new(USERS_CREATE, "Create users"),
+    PermissionDefinition::new(USERS_DELETE, "Delete users"),
+    PermissionDefinition::new(ORG_READ, "View the organization"),
+    PermissionDefinition::new(ORG_WRITE, "Edit the organization"),
+    PermissionDefinition::new(ORG_CREATE, "Create organizations"),
+    PermissionDefinition::new(ORG_DELETE, "Delete the organization"),
+    PermissionDefinition::new(MEMBERS_INVITE, "Invite people and resend or revoke invitations"),
+    PermissionDefinition::new(CONTACTS_READ, "View contacts"),
+    PermissionDefinition::new(CONTACTS_CREATE, "Create contacts"),
+    PermissionDefinition::new(CONTACTS_UPDATE, "Edit contacts and their tags"),
+    PermissionDefinition::new(CONTACTS_DELETE, "Delete contacts"),
+    PermissionDefinition::new(CONTACTS_EXPORT, "Export contacts"),
+    PermissionDefinition::new(CONTACTS_IMPORT, "Import contacts"),
+    PermissionDefinition::new(CONTACTS_ASSIGN_OWNER, "Change a contact's owner"),
+    PermissionDefinition::new(CONTACTS_BULK_UPDATE, "Update many contacts at once"),
+    PermissionDefinition::new(ACTIVITIES_READ, "View contact activities"),
+    PermissionDefinition::new(ACTIVITIES_CREATE, "Log and schedule activities"),
+    PermissionDefinition::new(ACTIVITIES_UPDATE, "Edit and complete activities"),
+    PermissionDefinition::new(ACTIVITIES_DELETE, "Delete activities"),
+    PermissionDefinition::new(CUSTOM_FIELDS_MANAGE, "Manage custom field definitions"),
+    PermissionDefinition::new(ROLES_READ, "View roles"),
+    PermissionDefinition::new(ROLES_MANAGE, "Create, edit and delete custom roles"),
+    PermissionDefinition::new(REPORTS_READ, "View reports"),
+    PermissionDefinition::new(REPORTS_CREATE, "Create reports"),
+    PermissionDefinition::new(REPORTS_EXPORT, "Export reports"),
+];
+
+pub struct PermissionRegistry;
+
+impl PermissionRegistry {
+    pub fn all() -> &'static [PermissionDefinition] {
+        PERMISSIONS
+    }
+
+    pub fn get(name: &str) -> Option<&'static PermissionDefinition> {
+        PERMISSIONS.iter().find(|definition| definition.name == name)
+    }
+
+    pub fn is_registered(name: &str) -> bool {
+        Self::get(name).is_some()
+    }
+
+    /// Resources in registry order
+    pub fn resources() -> Vec<&'static str> {
+        let mut resources: Vec<&'static str> = Vec::new();
+        for definition in PERMISSIONS {
+            if !resources.contains(&definition.resource()) {
+                resources.push(definition.resource());
+            }
+        }
+        resources
+    }
+
+    /// Whether a role may grant `permission`: a registered permission, its `_own` form,
+    /// `*`, or `<resource>:*` for a registered resource
+    pub fn is_grantable(permission: &str) -> bool {
+        if permission == "*" || Self::is_registered(permission) {
+            return true;
+        }
+
+        if let Some(unscoped) = permission.strip_suffix("_own") {
+            return Self::is_registered(unscoped);
+        }
+
+        match permission.strip_suffix(":*") {
+            Some(resource) => PERMISSIONS.iter().any(|definition| definition.resource() == resource),
+            None => false,
+        }
+    }
+
+    /// JSON array for a role's `permissions` column
+    ///
+    /// Fails on a permission a role cannot grant, so seed data cannot drift from the registry.
+    pub fn to_json(permissions: &[&str]) -> Result<serde_json::Value, AppError> {
+        if let Some(unknown) = permissions.iter().find(|permission| !Self::is_grantable(permission)) {
+            return Err(AppError::InternalServerError(format!("Unknown permission '{}'", unknown)));
+        }
+        Ok(serde_json::json!(permissions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_names_are_unique() {
+        for (index, definition) in PERMISSIONS.iter().enumerate() {
+            assert!(
+                PERMISSIONS[index + 1..].iter().all(|other| other.name != definition.name),
+                "{} is registered twice",
+                definition.name
+            );
+            assert_ne!(definition.resource(), definition.name);
+        }
+    }
+
+    #[test]
+    fn test_grantable_permissions() {
+        assert!(PermissionRegistry::is_grantable("*"));
+        assert!(PermissionRegistry::is_grantable("contacts:*"));
+        assert!(PermissionRegistry::is_grantable("contacts:update_own"));
+        assert!(PermissionRegistry::is_grantable(REPORTS_EXPORT));
+
+        assert!(!PermissionRegistry::is_grantable("contact:read"));
+        assert!(!PermissionRegistry::is_grantable("invoices:*"));
+        assert!(!PermissionRegistry::is_grantable("contacts:fly_own"));
+        assert!(!PermissionRegistry::is_grantable(""));
+    }
+
+    #[test]
+    fn test_resources_and_json() {
+        let resources = PermissionRegistry::resources();
+        assert_eq!(resources.first(), Some(&"users"));
+        assert!(resources.contains(&"custom_fields"));
+
+        assert_eq!(
+            PermissionRegistry::to_json(&[CONTACTS_READ, "contacts:update_own"]).unwrap(),
+            serde_json::json!(["contacts:read", "contacts:update_own"])
+        );
+    }
+
+    #[test]
+    fn test_json_rejects_unknown_permission() {
+        match PermissionRegistry::to_json(&["contact:read"]) {
+            Err(AppError::InternalServerError(message)) => assert_eq!(message, "Unknown permission 'contact:read'"),
+            other => panic!("expected an unknown permission error, got {:?}", other),
+        }
+    }
+}
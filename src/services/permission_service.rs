@@ -1,7 +1,9 @@
 // Permission service for handling role-based access control
 
+use crate::dto::{EffectivePermission, MyPermissionsResponse};
 use crate::errors::AppError;
-use crate::models::{Role, User, UserOrganization};
+use crate::models::{PermissionSet, Role, User, UserOrganization};
+use crate::services::{PermissionCache, PermissionRegistry};
 use serde_json::Value as JsonValue;
 use sqlx::PgPool;
 use std::collections::HashSet;
@@ -10,6 +12,46 @@ use uuid::Uuid;
 pub struct PermissionService;
 
 impl PermissionService {
+    /// Registered permissions a set grants, in registry order
+    pub fn effective_permissions(permissions: &PermissionSet) -> Vec<EffectivePermission> {
+        PermissionRegistry::all()
+            .iter()
+            .filter_map(|definition| {
+                permissions.scope(definition.name).map(|scope| EffectivePermission {
+                    name: definition.name,
+                    resource: definition.resource(),
+                    description: definition.description,
+                    scope,
+                })
+            })
+            .collect()
+    }
+
+    /// The caller's roles, grants and effective permissions in an organization
+    pub async fn my_permissions(
+        pool: &PgPool,
+        user_id: Uuid,
+        org_id: Uuid,
+        permissions: &PermissionSet,
+    ) -> Result<MyPermissionsResponse, AppError> {
+        let roles = Self::get_user_roles(pool, user_id, org_id)
+            .await?
+            .into_iter()
+            .map(|role| role.name)
+            .collect();
+
+        let mut granted: Vec<String> = permissions.permissions().iter().cloned().collect();
+        granted.sort();
+
+        Ok(MyPermissionsResponse {
+            user_id,
+            org_id,
+            roles,
+            granted,
+            permissions: Self::effective_permissions(permissions),
+        })
+    }
+
     /// Get all permissions for a user in a specific organization
     pub async fn get_user_permissions(
         pool: &PgPool,
@@ -44,35 +86,32 @@ impl PermissionService {
         Ok(permissions)
     }
 
-    /// Check if user has a specific permission in an organization
-    pub async fn has_permission(
+    /// Resolve a user's permissions in an organization, served from the permission cache when fresh
+    pub async fn permission_set(
         pool: &PgPool,
         user_id: Uuid,
         org_id: Uuid,
-        required_permission: &str,
-    ) -> Result<bool, AppError> {
-        let permissions = Self::get_user_permissions(pool, user_id, org_id).await?;
-
-        // Check for wildcard permission
-        if permissions.contains("*") {
-            return Ok(true);
+    ) -> Result<PermissionSet, AppError> {
+        if let Some(permissions) = PermissionCache::get(user_id, org_id) {
+            return Ok(permissions);
         }
 
-        // Check for exact permission
-        if permissions.contains(required_permission) {
-            return Ok(true);
-        }
+        let generation = PermissionCache::generation();
+        let permissions = PermissionSet::new(Self::get_user_permissions(pool, user_id, org_id).await?);
+        PermissionCache::insert(user_id, org_id, permissions.clone(), generation);
 
-        // Check for resource-level wildcard (e.g., "contacts:*")
-        let parts: Vec<&str> = required_permission.split(':').collect();
-        if parts.len() >= 2 {
-            let resource_wildcard = format!("{}:*", parts[0]);
-            if permissions.contains(&resource_wildcard) {
-                return Ok(true);
-            }
-        }
+        Ok(permissions)
+    }
 
-        Ok(false)
+    /// Check if user has a specific permission in an organization
+    pub async fn has_permission(
+        pool: &PgPool,
+        user_id: Uuid,
+        org_id: Uuid,
+        required_permission: &str,
+    ) -> Result<bool, AppError> {
+        let permissions = Self::permission_set(pool, user_id, org_id).await?;
+        Ok(permissions.allows(required_permission))
     }
 
     /// Check if user has any of the specified permissions
@@ -82,12 +121,8 @@ impl PermissionService {
         org_id: Uuid,
         required_permissions: &[&str],
     ) -> Result<bool, AppError> {
-        for permission in required_permissions {
-            if Self::has_permission(pool, user_id, org_id, permission).await? {
-                return Ok(true);
-            }
-        }
-        Ok(false)
+        let permissions = Self::permission_set(pool, user_id, org_id).await?;
+        Ok(permissions.allows_any(required_permissions))
     }
 
     /// Check if user has all of the specified permissions
@@ -97,12 +132,8 @@ impl PermissionService {
         org_id: Uuid,
         required_permissions: &[&str],
     ) -> Result<bool, AppError> {
-        for permission in required_permissions {
-            if !Self::has_permission(pool, user_id, org_id, permission).await? {
-                return Ok(false);
-            }
-        }
-        Ok(true)
+        let permissions = Self::permission_set(pool, user_id, org_id).await?;
+        Ok(permissions.allows_all(required_permissions))
     }
 
     /// Require permission (throws error if not authorized)
@@ -113,7 +144,7 @@ impl PermissionService {
         required_permission: &str,
     ) -> Result<(), AppError> {
         if !Self::has_permission(pool, user_id, org_id, required_permission).await? {
-            return Err(AppError::Unauthorized(format!(
+            return Err(AppError::Forbidden(format!(
                 "Permission '{}' required",
                 required_permission
             )));
@@ -121,7 +152,7 @@ impl PermissionService {
         Ok(())
     }
 
-    /// Check resource ownership (for "own" scoped permissions)
+    /// Check resource ownership (full permission, or its `_own` form for owned resources)
     pub async fn can_access_resource(
         pool: &PgPool,
         user_id: Uuid,
@@ -129,20 +160,8 @@ impl PermissionService {
         resource_owner_id: Option<Uuid>,
         base_permission: &str,
     ) -> Result<bool, AppError> {
-        // Check if user has full permission
-        if Self::has_permission(pool, user_id, org_id, base_permission).await? {
-            return Ok(true);
-        }
-
-        // Check if user has "own" permission and owns the resource
-        let own_permission = format!("{}:own", base_permission);
-        if Self::has_permission(pool, user_id, org_id, &own_permission).await? {
-            if let Some(owner_id) = resource_owner_id {
-                return Ok(owner_id == user_id);
-            }
-        }
-
-        Ok(false)
+        let permissions = Self::permission_set(pool, user_id, org_id).await?;
+        Ok(permissions.allows_resource(base_permission, user_id, resource_owner_id))
     }
 
     /// Get user's roles in an organization
@@ -152,7 +171,7 @@ impl PermissionService {
         org_id: Uuid,
     ) -> Result<Vec<Role>, AppError> {
         let query = r#"
-            SELECT r.id, r.name, r.description, r.permissions, r.created_at, r.updated_at
+            SELECT r.id, r.org_id, r.name, r.description, r.permissions, r.created_at, r.updated_at
             FROM user_organizations uo
             JOIN roles r ON uo.role_id = r.id
             WHERE uo.user_id = $1 AND uo.org_id = $2 AND uo.status = 'active'
@@ -188,3 +207,4 @@ macro_rules! has_permission {
         .await?
     };
 }
+
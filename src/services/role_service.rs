@@ -0,0 +1,190 @@
+// Role Service - Custom per-organization roles
+
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::dto::role_dto::{CreateRoleRequest, RoleResponse, UpdateRoleRequest};
+use crate::errors::AppError;
+use crate::models::{PermissionSet, Role, OWNER_ROLE};
+use crate::repository::RoleRepository;
+use crate::services::{PermissionCache, PermissionRegistry};
+
+pub struct RoleService;
+
+impl RoleService {
+    /// Built-in roles and the organization's custom roles
+    pub async fn list_roles(pool: &PgPool, org_id: Uuid) -> Result<Vec<RoleResponse>, AppError> {
+        let roles = RoleRepository::find_all_for_org(pool, org_id).await?;
+        Ok(roles.into_iter().map(RoleResponse::from).collect())
+    }
+
+    pub async fn get_role(pool: &PgPool, org_id: Uuid, role_id: Uuid) -> Result<RoleResponse, AppError> {
+        let role = Self::find_role(pool, org_id, role_id).await?;
+        Ok(RoleResponse::from(role))
+    }
+
+    pub async fn create_role(
+        pool: &PgPool,
+        org_id: Uuid,
+        caller_permissions: &PermissionSet,
+        request: CreateRoleRequest,
+    ) -> Result<RoleResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Role validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let name = request.name.trim().to_string();
+        Self::ensure_name_available(pool, org_id, &name, None).await?;
+        let permissions = Self::normalize_permissions(request.permissions, caller_permissions)?;
+
+        let role = RoleRepository::create(
+            pool,
+            Some(org_id),
+            name,
+            request.description,
+            serde_json::json!(permissions),
+        ).await?;
+
+        tracing::info!("Created role {} ({}) in organization {}", role.name, role.id, org_id);
+        Ok(RoleResponse::from(role))
+    }
+
+    pub async fn update_role(
+        pool: &PgPool,
+        org_id: Uuid,
+        role_id: Uuid,
+        caller_permissions: &PermissionSet,
+        request: UpdateRoleRequest,
+    ) -> Result<RoleResponse, AppError> {
+        if let Err(validation_errors) = request.validate() {
+            tracing::warn!("Role validation failed: {:?}", validation_errors);
+            return Err(validation_errors.into());
+        }
+
+        let role = Self::find_custom_role(pool, org_id, role_id).await?;
+
+        let name = request.name.map(|name| name.trim().to_string());
+        if let Some(name) = &name {
+            Self::ensure_name_available(pool, org_id, name, Some(role.id)).await?;
+        }
+
+        let permissions = match request.permissions {
+            Some(permissions) => Some(serde_json::json!(Self::normalize_permissions(permissions, caller_permissions)?)),
+            None => None,
+        };
+
+        let role = RoleRepository::update(pool, role.id, name, request.description, permissions).await?;
+
+        // Every member holding this role may have gained or lost permissions
+        PermissionCache::invalidate_all();
+
+        Ok(RoleResponse::from(role))
+    }
+
+    /// Delete a custom role that no membership references
+    pub async fn delete_role(pool: &PgPool, org_id: Uuid, role_id: Uuid) -> Result<(), AppError> {
+        let role = Self::find_custom_role(pool, org_id, role_id).await?;
+
+        let members = RoleRepository::count_members(pool, role.id).await?;
+        if members > 0 {
+            return Err(AppError::Conflict(format!(
+                "Role '{}' is still assigned to {} member(s)",
+                role.name, members
+            )));
+        }
+
+        RoleRepository::delete(pool, role.id).await?;
+        PermissionCache::invalidate_all();
+        tracing::info!("Deleted role {} ({}) in organization {}", role.name, role.id, org_id);
+        Ok(())
+    }
+
+    async fn find_role(pool: &PgPool, org_id: Uuid, role_id: Uuid) -> Result<Role, AppError> {
+        RoleRepository::find_by_id_for_org(pool, org_id, role_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Role not found".to_string()))
+    }
+
+    /// Custom role of the organization; built-in roles are read-only
+    async fn find_custom_role(pool: &PgPool, org_id: Uuid, role_id: Uuid) -> Result<Role, AppError> {
+        let role = Self::find_role(pool, org_id, role_id).await?;
+        if role.is_builtin() {
+            return Err(AppError::Forbidden(format!("Built-in role '{}' cannot be modified", role.name)));
+        }
+        Ok(role)
+    }
+
+    /// Names are unique across the built-in roles and the organization's own roles
+    async fn ensure_name_available(
+        pool: &PgPool,
+        org_id: Uuid,
+        name: &str,
+        current_role_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        match RoleRepository::find_by_name(pool, org_id, name).await? {
+            Some(existing) if Some(existing.id) != current_role_id => {
+                Err(AppError::Conflict(format!("Role '{}' already exists", name)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Callers can only hand out roles within their own permissions; the owner role needs `*`
+    ///
+    /// Applies to invitations as well as to memberships added or changed directly.
+    pub fn ensure_can_assign(caller_permissions: &PermissionSet, role: &Role) -> Result<(), AppError> {
+        if role.is_builtin() && role.name == OWNER_ROLE && !caller_permissions.allows("*") {
+            return Err(AppError::Forbidden("Only an owner can make someone an owner".to_string()));
+        }
+
+        if !caller_permissions.covers(&role.get_permissions()) {
+            return Err(AppError::Forbidden(format!(
+                "Role '{}' grants permissions you do not hold",
+                role.name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Trim and de-duplicate permissions, rejecting any missing from the permission registry
+    ///
+    /// Callers can only grant what they hold themselves, so `roles:manage` cannot be used to escalate.
+    fn normalize_permissions(permissions: Vec<String>, caller_permissions: &PermissionSet) -> Result<Vec<String>, AppError> {
+        let mut normalized: Vec<String> = Vec::with_capacity(permissions.len());
+        let mut unknown = Vec::new();
+
+        for permission in permissions {
+            let permission = permission.trim().to_string();
+            if !PermissionRegistry::is_grantable(&permission) {
+                unknown.push(permission);
+            } else if !normalized.contains(&permission) {
+                normalized.push(permission);
+            }
+        }
+
+        if !unknown.is_empty() {
+            return Err(AppError::field(
+                "permissions",
+                "unknown_permission",
+                format!("Unknown permissions: {}", unknown.join(", ")),
+            ));
+        }
+
+        let not_held: Vec<&str> = normalized
+            .iter()
+            .filter(|permission| !caller_permissions.allows(permission))
+            .map(String::as_str)
+            .collect();
+        if !not_held.is_empty() {
+            return Err(AppError::Forbidden(format!(
+                "You cannot grant permissions you do not hold: {}",
+                not_held.join(", ")
+            )));
+        }
+
+        Ok(normalized)
+    }
+}
@@ -0,0 +1,147 @@
+// Session Service - Access/refresh token issuance and server-side revocation
+
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::dto::{AuthTokensResponse, LogoutAllResponse, RefreshTokenRequest};
+use crate::errors::AppError;
+use crate::models::{User, UserSession};
+use crate::repository::{UserRepository, UserSessionRepository};
+use crate::utils::{
+    access_token_ttl, generate_organization_token, generate_refresh_token, generate_token,
+    hash_refresh_token, refresh_token_ttl, validate_token, Claims, JwtUser,
+};
+
+pub struct SessionService;
+
+impl SessionService {
+    /// Open a session for a user who just authenticated, returning its first token pair
+    pub async fn start_session(
+        pool: &PgPool,
+        user: &User,
+        user_agent: Option<String>,
+    ) -> Result<AuthTokensResponse, AppError> {
+        let refresh_token = generate_refresh_token();
+        let session = UserSession::new(
+            user.id,
+            None,
+            hash_refresh_token(&refresh_token),
+            user_agent,
+            Utc::now() + refresh_token_ttl(),
+        );
+        let session = UserSessionRepository::create(pool, &session).await?;
+
+        tracing::info!("Started session {} for user {}", session.id, user.id);
+        Self::token_pair(user, &session, refresh_token)
+    }
+
+    /// Validate an access token against its session and the user's current status
+    ///
+    /// The returned user reflects the database, not the claims embedded in the token.
+    pub async fn authenticate(pool: &PgPool, token: &str) -> Result<(Claims, JwtUser), AppError> {
+        let claims = validate_token(token)?;
+        let session_id = claims.session_id()?;
+
+        let session = UserSessionRepository::find_with_user(pool, session_id)
+            .await?
+            .filter(|session| session.user_id.to_string() == claims.sub && session.is_active())
+            .ok_or_else(|| AppError::Unauthorized("Session has been revoked or has expired".to_string()))?;
+
+        if session.status != "active" {
+            return Err(AppError::Forbidden(format!("User account is {}", session.status)));
+        }
+
+        let user = JwtUser {
+            id: session.user_id,
+            email: session.email,
+            name: session.name,
+            status: session.status,
+            session_id,
+        };
+
+        Ok((claims, user))
+    }
+
+    /// Exchange a refresh token for a new token pair, rotating the refresh token
+    ///
+    /// Presenting a refresh token that was already rotated out revokes the whole session,
+    /// since either the client or an attacker holds a copy of a used token.
+    pub async fn refresh(pool: &PgPool, request: RefreshTokenRequest) -> Result<AuthTokensResponse, AppError> {
+        let presented_hash = hash_refresh_token(request.refresh_token.trim());
+
+        let session = UserSessionRepository::find_by_refresh_token_hash(pool, &presented_hash)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        if session.refresh_token_hash != presented_hash {
+            tracing::warn!("Refresh token reuse detected for session {}, revoking it", session.id);
+            UserSessionRepository::revoke(pool, session.id, session.user_id).await?;
+            return Err(AppError::Unauthorized("Refresh token has already been used".to_string()));
+        }
+
+        if !session.is_active() {
+            return Err(AppError::Unauthorized("Session has been revoked or has expired".to_string()));
+        }
+
+        let user = UserRepository::find_by_id(pool, session.user_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        if !user.is_active() {
+            UserSessionRepository::revoke_all_for_user(pool, user.id).await?;
+            return Err(AppError::Forbidden(format!("User account is {}", user.status)));
+        }
+
+        let refresh_token = generate_refresh_token();
+        let session = UserSessionRepository::rotate(
+            pool,
+            session.id,
+            &presented_hash,
+            &hash_refresh_token(&refresh_token),
+            Utc::now() + refresh_token_ttl(),
+        )
+        .await?
+        .ok_or_else(|| AppError::Unauthorized("Refresh token has already been used".to_string()))?;
+
+        Self::token_pair(&user, &session, refresh_token)
+    }
+
+    /// Revoke the session the caller's access token belongs to
+    pub async fn logout(pool: &PgPool, user: &JwtUser) -> Result<(), AppError> {
+        UserSessionRepository::revoke(pool, user.session_id, user.id).await?;
+        tracing::info!("User {} logged out of session {}", user.id, user.session_id);
+        Ok(())
+    }
+
+    /// Revoke every session of a user ("log out of all devices")
+    pub async fn logout_all(pool: &PgPool, user_id: Uuid) -> Result<LogoutAllResponse, AppError> {
+        let revoked_sessions = UserSessionRepository::revoke_all_for_user(pool, user_id).await?;
+        Ok(LogoutAllResponse { revoked_sessions })
+    }
+
+    fn token_pair(
+        user: &User,
+        session: &UserSession,
+        refresh_token: String,
+    ) -> Result<AuthTokensResponse, AppError> {
+        let token = match session.org_id {
+            Some(org_id) => generate_organization_token(
+                user.id,
+                user.email.clone(),
+                user.name.clone(),
+                user.status.clone(),
+                session.id,
+                org_id,
+            )?,
+            None => generate_token(user.id, user.email.clone(), user.name.clone(), user.status.clone(), session.id)?,
+        };
+
+        Ok(AuthTokensResponse {
+            token,
+            refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: access_token_ttl().num_seconds(),
+        })
+    }
+}
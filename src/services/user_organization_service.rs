@@ -1,51 +1,65 @@
 // UserOrganization Service - Business logic for user-organization relationships
 
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::dto::{
-    CreateUserOrganizationRequest, InviteUserToOrganizationRequest, 
+    CreateUserOrganizationRequest,
     UpdateUserOrganizationRequest, UserOrganizationDetailResponse,
     UserOrganizationListResponse, UserOrganizationResponse,
-    UserInfo, OrganizationInfo, RoleInfo
+    UserInfo, OrganizationInfo, RoleInfo,
+    SwitchOrganizationRequest, SwitchOrganizationResponse
 };
 use crate::errors::AppError;
-use crate::models::{UserOrganization, UserOrganizationWithDetails};
+use crate::models::{PermissionSet, UserOrganization, UserOrganizationWithDetails, ADMIN_ROLE};
 use crate::repository::{
-    RoleRepository, UserOrganizationRepository, UserRepository, OrganizationRepository
+    RoleRepository, UserOrganizationRepository, UserRepository, UserSessionRepository, OrganizationRepository
 };
-use crate::utils::format_timestamp;
+use crate::services::RoleService;
+use crate::utils::{format_timestamp, generate_organization_token, JwtUser};
+use crate::AppState;
 
 pub struct UserOrganizationService;
 
 impl UserOrganizationService {
-    /// Add user to organization
+    /// Add user to the caller's active organization, with a role within the caller's permissions
     pub async fn add_user_to_organization(
-        pool: &PgPool,
+        state: &AppState,
+        org_id: Uuid,
+        caller_permissions: &PermissionSet,
         request: CreateUserOrganizationRequest,
     ) -> Result<UserOrganizationDetailResponse, AppError> {
+        if request.org_id != org_id {
+            return Err(AppError::Forbidden(
+                "Members can only be added to the active organization".to_string()
+            ));
+        }
+
         // Validate user exists
-        let user = UserRepository::find_by_id(pool, request.user_id).await?;
+        let user = UserRepository::find_by_id(&state.db, request.user_id).await?;
         if user.is_none() {
             return Err(AppError::NotFound(format!("User with id {} not found", request.user_id)));
         }
 
         // Validate organization exists
-        let org = OrganizationRepository::find_by_id(pool, request.org_id).await?;
+        let org = OrganizationRepository::find_by_id(&state.db, request.org_id).await?;
         if org.is_none() {
             return Err(AppError::NotFound(format!("Organization with id {} not found", request.org_id)));
         }
 
         // Get role by name
-        let role = RoleRepository::find_by_name(pool, &request.role_name).await?;
+        let role = RoleRepository::find_by_name(&state.db, request.org_id, &request.role_name).await?;
         let role = match role {
             Some(role) => role,
             None => return Err(AppError::NotFound(format!("Role '{}' not found", request.role_name))),
         };
+        RoleService::ensure_can_assign(caller_permissions, &role)?;
+
+        let mut uow = state.unit_of_work().await?;
 
         // Check if relationship already exists
         let existing = UserOrganizationRepository::find_by_user_and_org(
-            pool, 
+            uow.conn(),
             request.user_id, 
             request.org_id
         ).await?;
@@ -58,16 +72,18 @@ impl UserOrganizationService {
 
         // Create the relationship
         let _user_org = UserOrganizationRepository::create(
-            pool,
+            uow.conn(),
             request.user_id,
             request.org_id,
             role.id,
             Some("active".to_string()),
         ).await?;
+        uow.invalidate_permissions(request.user_id, request.org_id);
+        uow.commit().await?;
 
         // Get detailed information for response
         let detailed = UserOrganizationRepository::find_all_with_details(
-            pool,
+            &state.db,
             Some(request.user_id),
             Some(request.org_id),
             None,
@@ -82,67 +98,27 @@ impl UserOrganizationService {
         }
     }
 
-    /// Invite user to organization
-    pub async fn invite_user_to_organization(
+    /// Membership of a user in an organization with user, organization and role details
+    pub async fn get_membership_detail(
         pool: &PgPool,
-        request: InviteUserToOrganizationRequest,
+        user_id: Uuid,
+        org_id: Uuid,
     ) -> Result<UserOrganizationDetailResponse, AppError> {
-        // Find user by email
-        let user = UserRepository::find_by_email(pool, &request.email).await?;
-        let user = match user {
-            Some(user) => user,
-            None => return Err(AppError::NotFound(format!("User with email '{}' not found", request.email))),
-        };
-
-        // Validate organization exists
-        let org = OrganizationRepository::find_by_id(pool, request.org_id).await?;
-        if org.is_none() {
-            return Err(AppError::NotFound(format!("Organization with id {} not found", request.org_id)));
-        }
-
-        // Get role by name
-        let role = RoleRepository::find_by_name(pool, &request.role_name).await?;
-        let role = match role {
-            Some(role) => role,
-            None => return Err(AppError::NotFound(format!("Role '{}' not found", request.role_name))),
-        };
-
-        // Check if relationship already exists
-        let existing = UserOrganizationRepository::find_by_user_and_org(
-            pool, 
-            user.id, 
-            request.org_id
-        ).await?;
-
-        if existing.is_some() {
-            return Err(AppError::ValidationError(
-                "User is already associated with this organization".to_string()
-            ));
-        }
-
-        // Create invitation
-        let _user_org = UserOrganizationRepository::create(
-            pool,
-            user.id,
-            request.org_id,
-            role.id,
-            Some("invited".to_string()),
-        ).await?;
-
-        // Get detailed information for response
         let detailed = UserOrganizationRepository::find_all_with_details(
             pool,
-            Some(user.id),
-            Some(request.org_id),
+            Some(user_id),
+            Some(org_id),
             None,
             1,
             0,
         ).await?;
 
-        if let Some(detail) = detailed.first() {
-            Ok(Self::to_detail_response(detail.clone()))
-        } else {
-            Err(AppError::InternalServerError("Failed to retrieve created invitation".to_string()))
+        match detailed.into_iter().next() {
+            Some(detail) => Ok(Self::to_detail_response(detail)),
+            None => Err(AppError::NotFound(format!(
+                "User {} is not a member of organization {}",
+                user_id, org_id
+            ))),
         }
     }
 
@@ -166,6 +142,46 @@ impl UserOrganizationService {
         Ok(responses)
     }
 
+    /// Switch the active organization, minting a token that carries it
+    pub async fn switch_organization(
+        pool: &PgPool,
+        user: &JwtUser,
+        request: SwitchOrganizationRequest,
+    ) -> Result<SwitchOrganizationResponse, AppError> {
+        let membership = UserOrganizationRepository::find_by_user_and_org(
+            pool,
+            user.id,
+            request.org_id
+        ).await?;
+
+        match membership {
+            Some(membership) if membership.is_active() => {}
+            _ => {
+                return Err(AppError::Forbidden(format!(
+                    "User is not an active member of organization {}",
+                    request.org_id
+                )));
+            }
+        }
+
+        // Refreshed tokens of this session keep the selected organization
+        UserSessionRepository::set_organization(pool, user.session_id, request.org_id).await?;
+
+        let token = generate_organization_token(
+            user.id,
+            user.email.clone(),
+            user.name.clone(),
+            user.status.clone(),
+            user.session_id,
+            request.org_id,
+        )?;
+
+        Ok(SwitchOrganizationResponse {
+            org_id: request.org_id,
+            token,
+        })
+    }
+
     /// Get organization users
     pub async fn get_organization_users(
         pool: &PgPool,
@@ -186,41 +202,49 @@ impl UserOrganizationService {
         Ok(responses)
     }
 
-    /// Update user organization relationship
+    /// Update user organization relationship in the caller's active organization
     pub async fn update_user_organization(
-        pool: &PgPool,
+        state: &AppState,
+        org_id: Uuid,
+        caller_permissions: &PermissionSet,
         id: Uuid,
         request: UpdateUserOrganizationRequest,
     ) -> Result<UserOrganizationDetailResponse, AppError> {
-        // Check if relationship exists
-        let existing = UserOrganizationRepository::find_by_id(pool, id).await?;
-        if existing.is_none() {
-            return Err(AppError::NotFound(format!("User organization relationship with id {} not found", id)));
-        }
+        let mut uow = state.unit_of_work().await?;
+
+        let existing = Self::find_membership(uow.conn(), org_id, id).await?;
 
         let mut role_id = None;
         
         // Resolve role name to role ID if provided
         if let Some(role_name) = &request.role_name {
-            let role = RoleRepository::find_by_name(pool, role_name).await?;
-            role_id = Some(match role {
-                Some(role) => role.id,
+            let role = match RoleRepository::find_by_name(uow.conn(), existing.org_id, role_name).await? {
+                Some(role) => role,
                 None => return Err(AppError::NotFound(format!("Role '{}' not found", role_name))),
-            });
+            };
+            RoleService::ensure_can_assign(caller_permissions, &role)?;
+            role_id = Some(role.id);
+        }
+
+        let demoted = role_id.is_some_and(|role_id| role_id != existing.role_id);
+        let deactivated = request.status.as_deref().is_some_and(|status| status != "active");
+        if demoted || deactivated {
+            Self::ensure_not_last_admin(uow.conn(), &existing).await?;
         }
 
         // Update the relationship
         let _updated = UserOrganizationRepository::update(
-            pool,
+            uow.conn(),
             id,
             role_id,
             request.status,
         ).await?;
+        uow.invalidate_permissions(existing.user_id, existing.org_id);
+        uow.commit().await?;
 
         // Get detailed information for response
-        let existing = existing.unwrap();
         let detailed = UserOrganizationRepository::find_all_with_details(
-            pool,
+            &state.db,
             Some(existing.user_id),
             Some(existing.org_id),
             None,
@@ -235,18 +259,53 @@ impl UserOrganizationService {
         }
     }
 
-    /// Remove user from organization
+    /// Remove user from the caller's active organization
     pub async fn remove_user_from_organization(
-        pool: &PgPool,
+        state: &AppState,
+        org_id: Uuid,
         id: Uuid,
     ) -> Result<(), AppError> {
-        // Check if relationship exists
-        let existing = UserOrganizationRepository::find_by_id(pool, id).await?;
-        if existing.is_none() {
-            return Err(AppError::NotFound(format!("User organization relationship with id {} not found", id)));
+        let mut uow = state.unit_of_work().await?;
+
+        let existing = Self::find_membership(uow.conn(), org_id, id).await?;
+
+        Self::ensure_not_last_admin(uow.conn(), &existing).await?;
+
+        UserOrganizationRepository::delete(uow.conn(), id).await?;
+        uow.invalidate_permissions(existing.user_id, existing.org_id);
+        uow.commit().await
+
+    }
+
+    /// Membership by id, treating memberships of other organizations as missing
+    async fn find_membership(conn: &mut PgConnection, org_id: Uuid, id: Uuid) -> Result<UserOrganization, AppError> {
+        match UserOrganizationRepository::find_by_id(conn, id).await? {
+            Some(existing) if existing.org_id == org_id => Ok(existing),
+            _ => Err(AppError::NotFound(format!("User organization relationship with id {} not found", id))),
+        }
+    }
+
+    /// Refuse to demote, deactivate or remove the last active admin of an organization
+    ///
+    /// The admins' memberships stay locked until the transaction ends, so two admins cannot
+    /// demote each other at the same time.
+    async fn ensure_not_last_admin(conn: &mut PgConnection, membership: &UserOrganization) -> Result<(), AppError> {
+        if !membership.is_active() {
+            return Ok(());
+        }
+
+        match RoleRepository::find_by_id(&mut *conn, membership.role_id).await? {
+            Some(role) if role.is_builtin() && role.name == ADMIN_ROLE => {}
+            _ => return Ok(()),
+        }
+
+        let admins = UserOrganizationRepository::lock_active_with_role(conn, membership.org_id, membership.role_id).await?;
+        if admins <= 1 {
+            return Err(AppError::Conflict(
+                "Organization must keep at least one active admin".to_string()
+            ));
         }
 
-        UserOrganizationRepository::delete(pool, id).await?;
         Ok(())
     }
 
@@ -1,22 +1,82 @@
 // User Service - Business logic for users
 
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
-use crate::dto::{CreateUserRequest, LoginRequest, LoginResponse, UpdatePasswordRequest, UserCreationResponse, UserResponse};
+use crate::dto::{
+    CreateUserRequest, LoginRequest, LoginResponse, MfaLoginResponse, MfaVerifyRequest, UpdatePasswordRequest,
+    UserCreationResponse, UserResponse,
+};
 use crate::errors::AppError;
-use crate::models::User;
-use crate::repository::UserRepository;
-use crate::utils::{format_timestamp, generate_token, hash_password, verify_password};
+use crate::models::{User, USER_STATUS_PENDING_VERIFICATION};
+use crate::repository::{UserRepository, UserSessionRepository};
+use crate::services::{AccountService, LoginProtectionService, MfaService, PasswordService, SessionService};
+use crate::utils::{format_timestamp, verify_password, verify_password_against_dummy, JwtUser};
+use crate::AppState;
 
 pub struct UserService;
 
 impl UserService {
-    /// Create a new user with validation
+    /// Register a new user and email them a verification link
+    ///
+    /// With `REQUIRE_EMAIL_VERIFICATION=true` the account stays `pending_verification`, and cannot log in,
+    /// until the link is used; no session is started for it.
     pub async fn create_user(
-        pool: &PgPool,
+        state: &AppState,
         request: CreateUserRequest,
+        user_agent: Option<String>,
+    ) -> Result<UserCreationResponse, AppError> {
+        let status = if AccountService::email_verification_required() {
+            USER_STATUS_PENDING_VERIFICATION
+        } else {
+            "active"
+        };
+
+        let mut uow = state.unit_of_work().await?;
+        let user = Self::insert_user(uow.conn(), request, status).await?;
+        uow.commit().await?;
+
+        AccountService::send_verification_email(&state.db, &user).await?;
+        Self::creation_response(&state.db, user, user_agent).await
+    }
+
+    /// Create an account for an email address the caller has already proven, e.g. by an invitation link
+    ///
+    /// Runs on the caller's connection so the account is only kept if the rest of its work commits.
+    pub async fn create_verified_user(conn: &mut PgConnection, request: CreateUserRequest) -> Result<User, AppError> {
+        let user = Self::insert_user(&mut *conn, request, "active").await?;
+        UserRepository::mark_email_verified(&mut *conn, user.id).await
+    }
+
+    /// Response for a newly created account, signing it in when it may log in
+    pub async fn creation_response(
+        pool: &PgPool,
+        user: User,
+        user_agent: Option<String>,
     ) -> Result<UserCreationResponse, AppError> {
+        if !user.is_active() {
+            return Ok(UserCreationResponse {
+                user: Self::to_response(user),
+                token: None,
+                refresh_token: None,
+                expires_in: None,
+            });
+        }
+
+        // Sign the new user in
+        let tokens = SessionService::start_session(pool, &user, user_agent).await?;
+
+        // Convert to response DTO with tokens
+        Ok(UserCreationResponse {
+            user: Self::to_response(user),
+            token: Some(tokens.token),
+            refresh_token: Some(tokens.refresh_token),
+            expires_in: Some(tokens.expires_in),
+        })
+    }
+
+    /// Validate a registration and store the user with its first password history entry
+    async fn insert_user(conn: &mut PgConnection, request: CreateUserRequest, status: &str) -> Result<User, AppError> {
         // Validate required fields
         if request.name.trim().is_empty() {
             return Err(AppError::ValidationError(
@@ -51,32 +111,27 @@ impl UserService {
         }
 
         // Check if email already exists
-        if UserRepository::email_exists(pool, &request.email).await? {
-            return Err(AppError::ValidationError(
+        if UserRepository::email_exists(&mut *conn, &request.email).await? {
+            return Err(AppError::Conflict(
                 "Email already exists".to_string(),
             ));
         }
 
-        // Hash password before storing
-        let hashed_password = hash_password(&request.password)?;
+        // Check the password against the policy and hash it before storing
+        let hashed_password = PasswordService::hash_new("password", &request.password)?;
 
         // Create user using repository
         let user = UserRepository::create(
-            pool,
+            &mut *conn,
             request.name.trim().to_string(),
             request.email.trim().to_lowercase(),
-            hashed_password,
+            hashed_password.clone(),
+            status,
         )
         .await?;
+        PasswordService::remember(conn, user.id, &hashed_password).await?;
 
-        // Generate JWT token
-        let token = generate_token(user.id, user.email.clone(), user.name.clone(), user.status.clone())?;
-
-        // Convert to response DTO with token
-        Ok(UserCreationResponse {
-            user: Self::to_response(user),
-            token,
-        })
+        Ok(user)
     }
 
     /// Get user by ID
@@ -123,7 +178,7 @@ impl UserService {
         status: String,
     ) -> Result<UserResponse, AppError> {
         // Validate status
-        let valid_statuses = ["active", "inactive", "suspended", "pending"];
+        let valid_statuses = ["active", "inactive", "suspended", "pending", USER_STATUS_PENDING_VERIFICATION];
         if !valid_statuses.contains(&status.as_str()) {
             return Err(AppError::ValidationError(
                 format!("Invalid status. Must be one of: {}", valid_statuses.join(", "))
@@ -138,6 +193,11 @@ impl UserService {
 
         // Update user status
         let user = UserRepository::update_status(pool, id, &status).await?;
+
+        // Users who are no longer active lose their sessions
+        if !user.is_active() {
+            UserSessionRepository::revoke_all_for_user(pool, id).await?;
+        }
         
         Ok(Self::to_response(user))
     }
@@ -152,14 +212,21 @@ impl UserService {
 
         // Soft delete by setting status to 'inactive'
         UserRepository::update_status(pool, id, "inactive").await?;
+        UserSessionRepository::revoke_all_for_user(pool, id).await?;
 
         Ok(())
     }
 
     /// Login user with email and password
+    ///
+    /// Failures count towards a temporary lockout of the email and the client IP, and every failure
+    /// gets the same 401 so it does not reveal whether the account exists or what state it is in.
+    /// Users with MFA get an `mfa` challenge instead of tokens, to complete with `login_with_mfa`.
     pub async fn login_user(
         pool: &PgPool,
         request: LoginRequest,
+        user_agent: Option<String>,
+        client_ip: Option<String>,
     ) -> Result<LoginResponse, AppError> {
         // Validate required fields
         if request.email.trim().is_empty() {
@@ -174,54 +241,107 @@ impl UserService {
             ));
         }
 
-        // Find user by email
+        // Locked out emails and IPs are refused before the password is even checked
+        LoginProtectionService::ensure_not_locked(pool, &request.email, client_ip.as_deref()).await?;
+
+        // Find user by email and verify password; unknown emails cost the same time as wrong passwords
         let user = UserRepository::find_by_email(pool, &request.email).await?;
 
         let user = match user {
-            Some(user) => user,
-            None => {
-                return Err(AppError::ValidationError(
-                    "Invalid email or password".to_string(),
-                ));
+            Some(user) if verify_password(&request.password, &user.password)? => user,
+            user => {
+                if user.is_none() {
+                    verify_password_against_dummy(&request.password);
+                }
+                LoginProtectionService::record_failure(
+                    pool,
+                    &request.email,
+                    client_ip.as_deref(),
+                    user.map(|user| user.id),
+                )
+                .await?;
+                return Err(Self::invalid_credentials());
             }
         };
 
-        // Check if user is active
-        if user.status != "active" {
-            return Err(AppError::ValidationError(
-                format!("User account is {}", user.status),
+        // Only the account owner, having proven the password, learns that it awaits verification
+        if user.is_pending_verification() {
+            return Err(AppError::Forbidden(
+                "Email address has not been verified; use the link we emailed you".to_string(),
             ));
         }
 
-        // Verify password
-        let is_valid = verify_password(&request.password, &user.password)?;
+        // Inactive and suspended accounts look exactly like a wrong password
+        if !user.is_active() {
+            return Err(Self::invalid_credentials());
+        }
 
-        if !is_valid {
-            return Err(AppError::ValidationError(
-                "Invalid email or password".to_string(),
-            ));
+        // The password is proven, so a legacy or weaker hash can be replaced
+        PasswordService::upgrade_hash(pool, &user, &request.password).await;
+
+        // With a second factor to check, failures are only forgotten once it is
+        if let Some(challenge) = MfaService::login_challenge(pool, &user).await? {
+            return Ok(LoginResponse {
+                user: Self::to_response(user),
+                token: None,
+                refresh_token: None,
+                expires_in: None,
+                mfa: Some(challenge),
+            });
         }
 
-        // Generate JWT token
-        let token = generate_token(user.id, user.email.clone(), user.name.clone(), user.status.clone())?;
+        LoginProtectionService::reset_account(pool, &request.email).await?;
+        Self::finish_login(pool, user, user_agent).await
+    }
+
+    /// Complete a login that needed a second factor
+    pub async fn login_with_mfa(
+        pool: &PgPool,
+        request: MfaVerifyRequest,
+        user_agent: Option<String>,
+        client_ip: Option<String>,
+    ) -> Result<MfaLoginResponse, AppError> {
+        let (user, recovery_codes) = MfaService::verify_login(pool, request, client_ip.as_deref()).await?;
+
+        LoginProtectionService::reset_account(pool, &user.email).await?;
+        let login = Self::finish_login(pool, user, user_agent).await?;
+
+        Ok(MfaLoginResponse { login, recovery_codes })
+    }
+
+    /// Open a session for a fully authenticated user
+    async fn finish_login(pool: &PgPool, user: User, user_agent: Option<String>) -> Result<LoginResponse, AppError> {
+        // Open a session and issue its tokens
+        let tokens = SessionService::start_session(pool, &user, user_agent).await?;
 
         // Create login response
         let response = LoginResponse {
             user: Self::to_response(user),
-            token: Some(token),
+            token: Some(tokens.token),
+            refresh_token: Some(tokens.refresh_token),
+            expires_in: Some(tokens.expires_in),
+            mfa: None,
         };
 
         Ok(response)
     }
 
-    /// Update user password
+    /// Update the caller's own password
+    ///
+    /// A wrong current password counts as a failed login, so this cannot be used to get around the lockout.
     pub async fn update_password(
-        pool: &PgPool,
+        state: &AppState,
+        caller: &JwtUser,
         id: Uuid,
         request: UpdatePasswordRequest,
+        client_ip: Option<String>,
     ) -> Result<UserResponse, AppError> {
+        if caller.id != id {
+            return Err(AppError::Forbidden("You can only change your own password".to_string()));
+        }
+
         // Find user by ID
-        let existing_user = UserRepository::find_by_id(pool, id).await?;
+        let existing_user = UserRepository::find_by_id(&state.db, id).await?;
         let user = match existing_user {
             Some(user) => user,
             None => {
@@ -229,23 +349,32 @@ impl UserService {
             }
         };
 
-        // Verify current password
+        // Verify current password, refusing locked out accounts and IPs like a login would
+        LoginProtectionService::ensure_not_locked(&state.db, &user.email, client_ip.as_deref()).await?;
         let is_current_valid = verify_password(&request.current_password, &user.password)?;
         if !is_current_valid {
-            return Err(AppError::ValidationError(
-                "Current password is incorrect".to_string(),
-            ));
+            LoginProtectionService::record_failure(&state.db, &user.email, client_ip.as_deref(), Some(user.id)).await?;
+            return Err(Self::invalid_credentials());
         }
 
-        // Hash new password
-        let new_hashed_password = hash_password(&request.new_password)?;
+        // Check the new password against the policy and recent passwords, then hash it
+        let new_hashed_password =
+            PasswordService::hash_replacement(&state.db, id, "new_password", &request.new_password).await?;
 
         // Update password in database
-        let updated_user = UserRepository::update_password(pool, id, &new_hashed_password).await?;
+        let mut uow = state.unit_of_work().await?;
+        let updated_user = PasswordService::set_password(uow.conn(), id, &new_hashed_password).await?;
+        LoginProtectionService::reset_account(uow.conn(), &user.email).await?;
+        uow.commit().await?;
 
         Ok(Self::to_response(updated_user))
     }
 
+    /// The one error for unknown emails, wrong passwords and accounts that may not log in
+    fn invalid_credentials() -> AppError {
+        AppError::Unauthorized("Invalid email or password".to_string())
+    }
+
     /// Convert User model to response DTO
     fn to_response(user: User) -> UserResponse {
         UserResponse {
@@ -0,0 +1,26 @@
+// Environment variable helpers for numeric settings
+
+use std::{env, str::FromStr};
+
+/// A setting parsed from the environment, `default` when unset, or an error naming the invalid value
+pub fn setting_from_env<T: FromStr>(name: &str, default: T) -> Result<T, String> {
+    match env::var(name) {
+        Ok(value) => value.parse().map_err(|_| format!("Invalid {} '{}'", name, value)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// A positive whole-number setting, or `default` when unset or invalid (after a warning)
+pub fn positive_int_from_env(name: &str, default: i64) -> i64 {
+    match setting_from_env(name, default) {
+        Ok(value) if value > 0 => value,
+        Ok(value) => {
+            tracing::warn!("Invalid {} '{}', using default {}", name, value, default);
+            default
+        }
+        Err(message) => {
+            tracing::warn!("{}, using default {}", message, default);
+            default
+        }
+    }
+}
@@ -0,0 +1,406 @@
+// JWT signing keys - Startup-time key configuration, rotation and JWKS publication
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    crypto, decode, decode_header, encode, errors::ErrorKind, Algorithm, DecodingKey, EncodingKey, Header,
+    Validation,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use simple_asn1::{from_der, oid, ASN1Block};
+use std::{env, fs, path::Path, sync::OnceLock};
+
+const DEV_KEY_ID: &str = "dev";
+const DEV_SECRET: &str = "development-only-jwt-secret-never-use-in-production";
+const PUBLIC_KEY_SUFFIX: &str = ".pub.pem";
+const PRIVATE_KEY_SUFFIX: &str = ".key.pem";
+
+static JWT_KEYS: OnceLock<JwtKeySet> = OnceLock::new();
+
+/// A key tokens can be verified with, and signed with when its private half is configured
+pub struct JwtKey {
+    pub kid: String,
+    pub algorithm: Algorithm,
+    decoding_key: DecodingKey,
+    encoding_key: Option<EncodingKey>,
+    /// Public JWK; `None` for shared secrets, which are never published
+    jwk: Option<Value>,
+}
+
+impl JwtKey {
+    /// An HS256 shared secret; `signing` is false for secrets kept only to verify older tokens
+    pub fn hmac(kid: &str, secret: &str, signing: bool) -> Result<Self, String> {
+        if secret.is_empty() {
+            return Err(format!("JWT secret for key '{}' is empty", kid));
+        }
+
+        Ok(Self {
+            kid: kid.to_string(),
+            algorithm: Algorithm::HS256,
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            encoding_key: signing.then(|| EncodingKey::from_secret(secret.as_bytes())),
+            jwk: None,
+        })
+    }
+
+    /// An RS256 or EdDSA key from a SubjectPublicKeyInfo PEM and, for signing keys, a PKCS#8 private key PEM
+    ///
+    /// The algorithm follows from the public key type.
+    pub fn from_pem(kid: &str, public_pem: &[u8], private_pem: Option<&[u8]>) -> Result<Self, String> {
+        let invalid = |reason: String| format!("Invalid public key for JWT key '{}': {}", kid, reason);
+
+        let pem = pem::parse(public_pem).map_err(|e| invalid(e.to_string()))?;
+        if pem.tag() != "PUBLIC KEY" {
+            return Err(invalid(format!("expected a PUBLIC KEY block, found {}", pem.tag())));
+        }
+
+        let (algorithm, jwk) = public_jwk(kid, pem.contents()).map_err(invalid)?;
+        let decoding_key = match algorithm {
+            Algorithm::RS256 => DecodingKey::from_rsa_pem(public_pem),
+            _ => DecodingKey::from_ed_pem(public_pem),
+        }
+        .map_err(|e| invalid(e.to_string()))?;
+
+        let encoding_key = private_pem
+            .map(|private_pem| {
+                let key = match algorithm {
+                    Algorithm::RS256 => EncodingKey::from_rsa_pem(private_pem),
+                    _ => EncodingKey::from_ed_pem(private_pem),
+                }
+                .map_err(|e| format!("Invalid private key for JWT key '{}': {}", kid, e))?;
+
+                // Catch a private key paired with the wrong public key before any token is issued
+                let signature = crypto::sign(kid.as_bytes(), &key, algorithm).map_err(|e| e.to_string())?;
+                match crypto::verify(&signature, kid.as_bytes(), &decoding_key, algorithm) {
+                    Ok(true) => Ok(key),
+                    _ => Err(format!("Private key for JWT key '{}' does not match its public key", kid)),
+                }
+            })
+            .transpose()?;
+
+        Ok(Self {
+            kid: kid.to_string(),
+            algorithm,
+            decoding_key,
+            encoding_key,
+            jwk: Some(jwk),
+        })
+    }
+
+    pub fn can_sign(&self) -> bool {
+        self.encoding_key.is_some()
+    }
+}
+
+/// Every key accepted for verification, plus the one new tokens are signed with
+pub struct JwtKeySet {
+    keys: Vec<JwtKey>,
+    signing_kid: String,
+    /// Key for tokens issued before `kid` headers existed
+    legacy_kid: Option<String>,
+}
+
+impl JwtKeySet {
+    pub fn new(keys: Vec<JwtKey>, signing_kid: &str, legacy_kid: Option<&str>) -> Result<Self, String> {
+        for (index, key) in keys.iter().enumerate() {
+            if keys[..index].iter().any(|other| other.kid == key.kid) {
+                return Err(format!("Duplicate JWT key id '{}'", key.kid));
+            }
+        }
+
+        match keys.iter().find(|key| key.kid == signing_kid) {
+            Some(key) if key.can_sign() => {}
+            Some(_) => return Err(format!("JWT signing key '{}' has no private key or secret", signing_kid)),
+            None => return Err(format!("JWT signing key '{}' is not configured", signing_kid)),
+        }
+
+        Ok(Self {
+            keys,
+            signing_kid: signing_kid.to_string(),
+            legacy_kid: legacy_kid.map(str::to_string),
+        })
+    }
+
+    /// Load keys from the environment
+    ///
+    /// - `JWT_SECRET` / `JWT_KEY_ID`: HS256 secret and its key id (default `hs256`)
+    /// - `JWT_PREVIOUS_SECRETS`: comma-separated `kid:secret` pairs still accepted for verification
+    /// - `JWT_KEYS_DIR`: directory of `<kid>.pub.pem` keys, each signing-capable if `<kid>.key.pem` exists
+    /// - `JWT_SIGNING_KEY_ID`: key that signs new tokens (defaults to the `JWT_SECRET` key)
+    ///
+    /// Without any key, only `RUST_ENV=development` falls back to a built-in secret.
+    pub fn from_env() -> Result<Self, String> {
+        let mut keys = Vec::new();
+        let mut secret_kid = None;
+
+        if let Ok(secret) = env::var("JWT_SECRET") {
+            let kid = env::var("JWT_KEY_ID").unwrap_or_else(|_| "hs256".to_string());
+            keys.push(JwtKey::hmac(&kid, &secret, true)?);
+            secret_kid = Some(kid);
+        }
+
+        if let Ok(previous) = env::var("JWT_PREVIOUS_SECRETS") {
+            for entry in previous.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+                let (kid, secret) = entry
+                    .split_once(':')
+                    .ok_or_else(|| "JWT_PREVIOUS_SECRETS entries must look like kid:secret".to_string())?;
+                keys.push(JwtKey::hmac(kid.trim(), secret.trim(), false)?);
+            }
+        }
+
+        if let Ok(dir) = env::var("JWT_KEYS_DIR") {
+            keys.extend(load_key_dir(Path::new(&dir))?);
+        }
+
+        if keys.is_empty() {
+            if !is_development() {
+                return Err(
+                    "No JWT signing key configured: set JWT_SECRET or JWT_KEYS_DIR (the built-in secret is only used with RUST_ENV=development)"
+                        .to_string(),
+                );
+            }
+
+            tracing::warn!("No JWT key configured, using the development secret. Never run like this in production!");
+            keys.push(JwtKey::hmac(DEV_KEY_ID, DEV_SECRET, true)?);
+            secret_kid = Some(DEV_KEY_ID.to_string());
+        }
+
+        let signing_kid = env::var("JWT_SIGNING_KEY_ID")
+            .ok()
+            .or_else(|| secret_kid.clone())
+            .ok_or_else(|| "JWT_SIGNING_KEY_ID must name a key in JWT_KEYS_DIR".to_string())?;
+
+        let key_set = Self::new(keys, &signing_kid, secret_kid.as_deref())?;
+        tracing::info!(
+            "Loaded {} JWT verification keys, signing with '{}'",
+            key_set.keys.len(),
+            key_set.signing_kid
+        );
+        Ok(key_set)
+    }
+
+    /// Sign claims with the active signing key, stamping its `kid`
+    pub fn sign<T: Serialize>(&self, claims: &T) -> jsonwebtoken::errors::Result<String> {
+        let key = self
+            .keys
+            .iter()
+            .find(|key| key.kid == self.signing_kid)
+            .ok_or_else(|| jsonwebtoken::errors::Error::from(ErrorKind::InvalidKeyFormat))?;
+        let encoding_key = key
+            .encoding_key
+            .as_ref()
+            .ok_or_else(|| jsonwebtoken::errors::Error::from(ErrorKind::InvalidKeyFormat))?;
+
+        let mut header = Header::new(key.algorithm);
+        header.kid = Some(key.kid.clone());
+        encode(&header, claims, encoding_key)
+    }
+
+    /// Verify a token with the key named by its `kid`, pinning that key's algorithm
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> jsonwebtoken::errors::Result<T> {
+        let header = decode_header(token)?;
+        let kid = header.kid.as_deref().or(self.legacy_kid.as_deref());
+        let key = self
+            .keys
+            .iter()
+            .find(|key| Some(key.kid.as_str()) == kid)
+            .ok_or_else(|| jsonwebtoken::errors::Error::from(ErrorKind::InvalidToken))?;
+
+        decode::<T>(token, &key.decoding_key, &Validation::new(key.algorithm)).map(|data| data.claims)
+    }
+
+    /// JSON Web Key Set of the public keys; shared secrets are left out
+    pub fn jwks(&self) -> Value {
+        let keys: Vec<&Value> = self.keys.iter().filter_map(|key| key.jwk.as_ref()).collect();
+        json!({ "keys": keys })
+    }
+}
+
+/// Load and validate the key set from the environment; call once at startup
+pub fn init_jwt_keys() -> Result<&'static JwtKeySet, String> {
+    if let Some(key_set) = JWT_KEYS.get() {
+        return Ok(key_set);
+    }
+
+    let key_set = JwtKeySet::from_env()?;
+    Ok(JWT_KEYS.get_or_init(|| key_set))
+}
+
+/// The process-wide key set, loaded on first use if `init_jwt_keys` was not called
+pub fn jwt_key_set() -> &'static JwtKeySet {
+    JWT_KEYS.get_or_init(|| {
+        JwtKeySet::from_env().unwrap_or_else(|e| panic!("Invalid JWT key configuration: {}", e))
+    })
+}
+
+fn is_development() -> bool {
+    cfg!(test) || env::var("RUST_ENV").map(|value| value == "development").unwrap_or(false)
+}
+
+fn load_key_dir(dir: &Path) -> Result<Vec<JwtKey>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Cannot read JWT_KEYS_DIR {}: {}", dir.display(), e))?;
+
+    let mut kids: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.strip_suffix(PUBLIC_KEY_SUFFIX).map(str::to_string)
+        })
+        .collect();
+    kids.sort();
+
+    kids.iter()
+        .map(|kid| {
+            let read = |path: &Path| fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e));
+
+            let public_pem = read(&dir.join(format!("{}{}", kid, PUBLIC_KEY_SUFFIX)))?;
+            let private_path = dir.join(format!("{}{}", kid, PRIVATE_KEY_SUFFIX));
+            let private_pem = if private_path.exists() { Some(read(&private_path)?) } else { None };
+
+            JwtKey::from_pem(kid, &public_pem, private_pem.as_deref())
+        })
+        .collect()
+}
+
+/// Derive the algorithm and public JWK from a DER SubjectPublicKeyInfo
+fn public_jwk(kid: &str, der: &[u8]) -> Result<(Algorithm, Value), String> {
+    let blocks = from_der(der).map_err(|e| e.to_string())?;
+    let (algorithm_oid, key_bits) = match blocks.first() {
+        Some(ASN1Block::Sequence(_, items)) => match items.as_slice() {
+            [ASN1Block::Sequence(_, algorithm), ASN1Block::BitString(_, _, bits)] => match algorithm.first() {
+                Some(ASN1Block::ObjectIdentifier(_, oid)) => (oid, bits),
+                _ => return Err("missing key algorithm".to_string()),
+            },
+            _ => return Err("not a SubjectPublicKeyInfo".to_string()),
+        },
+        _ => return Err("not a SubjectPublicKeyInfo".to_string()),
+    };
+
+    if *algorithm_oid == oid!(1, 2, 840, 113549, 1, 1, 1) {
+        let (modulus, exponent) = match from_der(key_bits).map_err(|e| e.to_string())?.first() {
+            Some(ASN1Block::Sequence(_, parts)) => match parts.as_slice() {
+                [ASN1Block::Integer(_, n), ASN1Block::Integer(_, e)] => (n.to_bytes_be().1, e.to_bytes_be().1),
+                _ => return Err("malformed RSA public key".to_string()),
+            },
+            _ => return Err("malformed RSA public key".to_string()),
+        };
+
+        let jwk = json!({
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": kid,
+            "n": URL_SAFE_NO_PAD.encode(modulus),
+            "e": URL_SAFE_NO_PAD.encode(exponent),
+        });
+        Ok((Algorithm::RS256, jwk))
+    } else if *algorithm_oid == oid!(1, 3, 101, 112) {
+        if key_bits.len() != 32 {
+            return Err("malformed Ed25519 public key".to_string());
+        }
+
+        let jwk = json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "use": "sig",
+            "alg": "EdDSA",
+            "kid": kid,
+            "x": URL_SAFE_NO_PAD.encode(key_bits),
+        });
+        Ok((Algorithm::EdDSA, jwk))
+    } else {
+        Err("only RSA and Ed25519 keys are supported".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct TestClaims {
+        sub: String,
+        exp: i64,
+    }
+
+    fn claims() -> TestClaims {
+        TestClaims { sub: "user".to_string(), exp: chrono::Utc::now().timestamp() + 60 }
+    }
+
+    fn fixture_dir() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/jwt")
+    }
+
+    #[test]
+    fn test_rotated_secret_still_verifies() {
+        let old = JwtKeySet::new(vec![JwtKey::hmac("old", "old-secret", true).unwrap()], "old", Some("old")).unwrap();
+        let token = old.sign(&claims()).unwrap();
+        assert_eq!(decode_header(&token).unwrap().kid.as_deref(), Some("old"));
+
+        let rotated = JwtKeySet::new(
+            vec![
+                JwtKey::hmac("new", "new-secret", true).unwrap(),
+                JwtKey::hmac("old", "old-secret", false).unwrap(),
+            ],
+            "new",
+            Some("new"),
+        )
+        .unwrap();
+        assert_eq!(rotated.verify::<TestClaims>(&token).unwrap(), claims());
+        assert_eq!(decode_header(&rotated.sign(&claims()).unwrap()).unwrap().kid.as_deref(), Some("new"));
+
+        // Once the old key is dropped its tokens are rejected
+        let retired = JwtKeySet::new(vec![JwtKey::hmac("new", "new-secret", true).unwrap()], "new", None).unwrap();
+        assert!(retired.verify::<TestClaims>(&token).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_sets() {
+        let verify_only = || JwtKey::hmac("old", "old-secret", false).unwrap();
+        assert!(JwtKeySet::new(vec![verify_only()], "old", None).is_err());
+        assert!(JwtKeySet::new(vec![verify_only()], "missing", None).is_err());
+        assert!(JwtKeySet::new(vec![verify_only(), verify_only()], "old", None).is_err());
+        assert!(JwtKey::hmac("empty", "", true).is_err());
+    }
+
+    #[test]
+    fn test_asymmetric_keys_from_dir() {
+        let keys = load_key_dir(&fixture_dir()).unwrap();
+        let algorithms: Vec<(&str, Algorithm)> = keys.iter().map(|key| (key.kid.as_str(), key.algorithm)).collect();
+        assert_eq!(algorithms, vec![("ed-test", Algorithm::EdDSA), ("rsa-test", Algorithm::RS256)]);
+
+        for kid in ["ed-test", "rsa-test"] {
+            let key_set = JwtKeySet::new(load_key_dir(&fixture_dir()).unwrap(), kid, None).unwrap();
+            let token = key_set.sign(&claims()).unwrap();
+            assert_eq!(key_set.verify::<TestClaims>(&token).unwrap(), claims());
+        }
+
+        // Verification uses only the published public components
+        let key_set = JwtKeySet::new(load_key_dir(&fixture_dir()).unwrap(), "rsa-test", None).unwrap();
+        let token = key_set.sign(&claims()).unwrap();
+        let jwks = key_set.jwks();
+        let rsa = jwks["keys"].as_array().unwrap().iter().find(|jwk| jwk["kid"] == "rsa-test").unwrap();
+        let decoding_key =
+            DecodingKey::from_rsa_components(rsa["n"].as_str().unwrap(), rsa["e"].as_str().unwrap()).unwrap();
+        assert!(decode::<TestClaims>(&token, &decoding_key, &Validation::new(Algorithm::RS256)).is_ok());
+
+        let ed = jwks["keys"].as_array().unwrap().iter().find(|jwk| jwk["kid"] == "ed-test").unwrap();
+        assert_eq!(ed["kty"], "OKP");
+        assert!(DecodingKey::from_ed_components(ed["x"].as_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn test_mismatched_private_key_rejected() {
+        let dir = fixture_dir();
+        let public_pem = fs::read(dir.join("rsa-test.pub.pem")).unwrap();
+        let other_private_pem = fs::read(dir.join("ed-test.key.pem")).unwrap();
+        assert!(JwtKey::from_pem("rsa-test", &public_pem, Some(&other_private_pem)).is_err());
+    }
+
+    #[test]
+    fn test_secrets_are_not_published() {
+        let key_set = JwtKeySet::new(vec![JwtKey::hmac("hs", "secret", true).unwrap()], "hs", None).unwrap();
+        assert_eq!(key_set.jwks(), json!({ "keys": [] }));
+    }
+}
@@ -1,14 +1,14 @@
 // JWT utility functions for token generation and validation
 
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
-use std::env;
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::utils::{jwt_key_set, positive_int_from_env};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,        // Subject (user ID)
     pub email: String,      // User email
@@ -16,6 +16,9 @@ pub struct Claims {
     pub status: String,     // User status
     pub exp: i64,          // Expiration time
     pub iat: i64,          // Issued at
+    pub sid: String,       // Server-side session (user_sessions.id) the token belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>, // Active organization selected via "switch organization"
 }
 
 #[derive(Debug, Clone)]
@@ -24,13 +27,14 @@ pub struct JwtUser {
     pub email: String,
     pub name: String,
     pub status: String,
+    pub session_id: Uuid,
 }
 
 impl Claims {
-    /// Create new claims for a user
-    pub fn new(user_id: Uuid, email: String, name: String, status: String) -> Self {
+    /// Create new short-lived access token claims for a user session
+    pub fn new(user_id: Uuid, email: String, name: String, status: String, session_id: Uuid) -> Self {
         let now = Utc::now();
-        let exp = now + Duration::hours(24); // Token expires in 24 hours
+        let exp = now + access_token_ttl();
 
         Self {
             sub: user_id.to_string(),
@@ -39,13 +43,40 @@ impl Claims {
             status,
             exp: exp.timestamp(),
             iat: now.timestamp(),
+            sid: session_id.to_string(),
+            org_id: None,
         }
     }
 
+    /// Bind the claims to an active organization
+    pub fn with_organization(mut self, org_id: Uuid) -> Self {
+        self.org_id = Some(org_id.to_string());
+        self
+    }
+
+    /// Get the active organization carried by the token, if any
+    pub fn organization_id(&self) -> Result<Option<Uuid>, AppError> {
+        self.org_id
+            .as_deref()
+            .map(|org_id| {
+                Uuid::parse_str(org_id).map_err(|_| {
+                    AppError::Unauthorized("Invalid organization ID in token".to_string())
+                })
+            })
+            .transpose()
+    }
+
+    /// Get the session the token was issued for
+    pub fn session_id(&self) -> Result<Uuid, AppError> {
+        Uuid::parse_str(&self.sid).map_err(|_| {
+            AppError::Unauthorized("Invalid session ID in token".to_string())
+        })
+    }
+
     /// Convert claims to JwtUser
     pub fn to_user(&self) -> Result<JwtUser, AppError> {
         let id = Uuid::parse_str(&self.sub).map_err(|_| {
-            AppError::ValidationError("Invalid user ID in token".to_string())
+            AppError::Unauthorized("Invalid user ID in token".to_string())
         })?;
 
         Ok(JwtUser {
@@ -53,29 +84,86 @@ impl Claims {
             email: self.email.clone(),
             name: self.name.clone(),
             status: self.status.clone(),
+            session_id: self.session_id()?,
         })
     }
 }
 
-/// Get JWT secret from environment or use default
-fn get_jwt_secret() -> String {
-    env::var("JWT_SECRET").unwrap_or_else(|_| {
-        tracing::warn!("JWT_SECRET not set, using default secret. This is not secure for production!");
-        "your-secret-key-change-this-in-production".to_string()
-    })
+/// Lifetime of access tokens (`ACCESS_TOKEN_TTL_MINUTES`, default 15)
+pub fn access_token_ttl() -> Duration {
+    Duration::minutes(positive_int_from_env("ACCESS_TOKEN_TTL_MINUTES", 15))
+}
+
+/// Lifetime of refresh tokens, renewed on every rotation (`REFRESH_TOKEN_TTL_DAYS`, default 30)
+pub fn refresh_token_ttl() -> Duration {
+    Duration::days(positive_int_from_env("REFRESH_TOKEN_TTL_DAYS", 30))
+}
+
+/// Lifetime of organization invitations, renewed when one is resent (`INVITATION_TTL_DAYS`, default 7)
+pub fn invitation_ttl() -> Duration {
+    Duration::days(positive_int_from_env("INVITATION_TTL_DAYS", 7))
+}
+
+/// Lifetime of password reset links (`PASSWORD_RESET_TTL_MINUTES`, default 60)
+pub fn password_reset_ttl() -> Duration {
+    Duration::minutes(positive_int_from_env("PASSWORD_RESET_TTL_MINUTES", 60))
+}
+
+/// Lifetime of email verification links (`EMAIL_VERIFICATION_TTL_HOURS`, default 48)
+pub fn email_verification_ttl() -> Duration {
+    Duration::hours(positive_int_from_env("EMAIL_VERIFICATION_TTL_HOURS", 48))
+}
+
+/// Time allowed between the password and MFA steps of a login (`MFA_PENDING_TTL_MINUTES`, default 5)
+pub fn mfa_pending_ttl() -> Duration {
+    Duration::minutes(positive_int_from_env("MFA_PENDING_TTL_MINUTES", 5))
+}
+
+/// Generate an access token for a user session
+pub fn generate_token(
+    user_id: Uuid,
+    email: String,
+    name: String,
+    status: String,
+    session_id: Uuid,
+) -> Result<String, AppError> {
+    encode_claims(&Claims::new(user_id, email, name, status, session_id))
+}
+
+/// Generate an access token for a user session bound to an active organization
+pub fn generate_organization_token(
+    user_id: Uuid,
+    email: String,
+    name: String,
+    status: String,
+    session_id: Uuid,
+    org_id: Uuid,
+) -> Result<String, AppError> {
+    encode_claims(&Claims::new(user_id, email, name, status, session_id).with_organization(org_id))
 }
 
-/// Generate JWT token for a user
-pub fn generate_token(user_id: Uuid, email: String, name: String, status: String) -> Result<String, AppError> {
-    let claims = Claims::new(user_id, email, name, status);
-    let secret = get_jwt_secret();
-    
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(secret.as_ref()),
-    )
-    .map_err(|e| {
+/// Generate an opaque refresh token (256 random bits, hex encoded)
+pub fn generate_refresh_token() -> String {
+    generate_secret_token()
+}
+
+/// Hash a refresh token for storage; only the hash is persisted
+pub fn hash_refresh_token(refresh_token: &str) -> String {
+    hash_secret_token(refresh_token)
+}
+
+/// Generate an opaque token sent to a user out of band, e.g. in an invitation link
+pub fn generate_secret_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+/// Hash a secret token for storage, so a database leak does not expose usable tokens
+pub fn hash_secret_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn encode_claims(claims: &Claims) -> Result<String, AppError> {
+    jwt_key_set().sign(claims).map_err(|e| {
         tracing::error!("Failed to generate JWT token: {}", e);
         AppError::InternalServerError("Failed to generate authentication token".to_string())
     })
@@ -83,32 +171,23 @@ pub fn generate_token(user_id: Uuid, email: String, name: String, status: String
 
 /// Validate JWT token and extract claims
 pub fn validate_token(token: &str) -> Result<Claims, AppError> {
-    let secret = get_jwt_secret();
-    let validation = Validation::new(Algorithm::HS256);
-    
-    decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(secret.as_ref()),
-        &validation,
-    )
-    .map(|data| data.claims)
-    .map_err(|e| {
+    jwt_key_set().verify::<Claims>(token).map_err(|e| {
         tracing::debug!("JWT validation failed: {}", e);
-        AppError::ValidationError("Invalid or expired authentication token".to_string())
+        AppError::Unauthorized("Invalid or expired authentication token".to_string())
     })
 }
 
 /// Extract token from Authorization header
 pub fn extract_token_from_header(auth_header: &str) -> Result<&str, AppError> {
     if !auth_header.starts_with("Bearer ") {
-        return Err(AppError::ValidationError(
+        return Err(AppError::Unauthorized(
             "Authorization header must start with 'Bearer '".to_string(),
         ));
     }
 
     let token = &auth_header[7..]; // Remove "Bearer " prefix
     if token.is_empty() {
-        return Err(AppError::ValidationError(
+        return Err(AppError::Unauthorized(
             "Authorization token is empty".to_string(),
         ));
     }
@@ -127,8 +206,10 @@ mod tests {
         let name = "Test User".to_string();
         let status = "active".to_string();
 
+        let session_id = Uuid::new_v4();
+
         // Generate token
-        let token = generate_token(user_id, email.clone(), name.clone(), status.clone()).unwrap();
+        let token = generate_token(user_id, email.clone(), name.clone(), status.clone(), session_id).unwrap();
         assert!(!token.is_empty());
 
         // Validate token
@@ -142,6 +223,38 @@ mod tests {
         let jwt_user = claims.to_user().unwrap();
         assert_eq!(jwt_user.id, user_id);
         assert_eq!(jwt_user.email, email);
+        assert_eq!(jwt_user.session_id, session_id);
+    }
+
+    #[test]
+    fn test_organization_token() {
+        let user_id = Uuid::new_v4();
+        let org_id = Uuid::new_v4();
+        let session_id = Uuid::new_v4();
+
+        // Plain tokens carry no organization
+        let token = generate_token(user_id, "test@example.com".to_string(), "Test User".to_string(), "active".to_string(), session_id).unwrap();
+        let claims = validate_token(&token).unwrap();
+        assert_eq!(claims.organization_id().unwrap(), None);
+
+        // Organization tokens round-trip the active organization
+        let token = generate_organization_token(user_id, "test@example.com".to_string(), "Test User".to_string(), "active".to_string(), session_id, org_id).unwrap();
+        let claims = validate_token(&token).unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+        assert_eq!(claims.organization_id().unwrap(), Some(org_id));
+    }
+
+    #[test]
+    fn test_refresh_token_hashing() {
+        let refresh_token = generate_refresh_token();
+        assert_eq!(refresh_token.len(), 64);
+        assert_ne!(refresh_token, generate_refresh_token());
+
+        // Hashes are stable and never equal the token itself
+        let hash = hash_refresh_token(&refresh_token);
+        assert_eq!(hash, hash_refresh_token(&refresh_token));
+        assert_ne!(hash, refresh_token);
+        assert_ne!(hash, hash_refresh_token(&generate_refresh_token()));
     }
 
     #[test]
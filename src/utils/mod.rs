@@ -1,8 +1,16 @@
 // Utils module - Utility functions and helpers
 pub mod date_utils;
+pub mod env_utils;
+pub mod jwt_keys;
 pub mod jwt_utils;
+pub mod password_policy;
 pub mod password_utils;
+pub mod totp;
 
 pub use date_utils::*;
+pub use env_utils::*;
+pub use jwt_keys::*;
 pub use jwt_utils::*;
+pub use password_policy::*;
 pub use password_utils::*;
+pub use totp::*;
@@ -0,0 +1,172 @@
+// Password policy - Length limits and a blocklist of breached passwords, loaded at startup
+
+use std::{collections::HashSet, env, fs, sync::OnceLock};
+
+use crate::errors::AppError;
+use crate::utils::setting_from_env;
+
+static PASSWORD_POLICY: OnceLock<PasswordPolicy> = OnceLock::new();
+
+/// Rules every new password must follow
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    /// How many earlier passwords of a user may not be reused; 0 allows any
+    pub history_size: usize,
+    /// Lowercased passwords known from breaches
+    blocklist: HashSet<String>,
+}
+
+impl PasswordPolicy {
+    pub fn new(min_length: usize, max_length: usize, history_size: usize) -> Result<Self, String> {
+        if min_length == 0 || min_length > max_length {
+            return Err(format!(
+                "Invalid password length limits: minimum {} and maximum {}",
+                min_length, max_length
+            ));
+        }
+
+        Ok(Self {
+            min_length,
+            max_length,
+            history_size,
+            blocklist: HashSet::new(),
+        })
+    }
+
+    /// Add passwords to refuse, one per line; blank lines and lines starting with `#` are skipped
+    pub fn with_blocklist(mut self, contents: &str) -> Self {
+        self.blocklist.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_lowercase),
+        );
+        self
+    }
+
+    /// Load the policy from the environment
+    ///
+    /// - `PASSWORD_MIN_LENGTH` (default 8) and `PASSWORD_MAX_LENGTH` (default 128), counted in characters
+    /// - `PASSWORD_BLOCKLIST_FILE`: file of breached passwords, one per line (none by default)
+    /// - `PASSWORD_HISTORY_SIZE`: how many previous passwords may not be reused (default 5)
+    pub fn from_env() -> Result<Self, String> {
+        let policy = Self::new(
+            setting_from_env("PASSWORD_MIN_LENGTH", 8)?,
+            setting_from_env("PASSWORD_MAX_LENGTH", 128)?,
+            setting_from_env("PASSWORD_HISTORY_SIZE", 5)?,
+        )?;
+
+        match env::var("PASSWORD_BLOCKLIST_FILE") {
+            Ok(path) => {
+                let contents = fs::read_to_string(&path)
+                    .map_err(|e| format!("Cannot read PASSWORD_BLOCKLIST_FILE {}: {}", path, e))?;
+                Ok(policy.with_blocklist(&contents))
+            }
+            Err(_) => Ok(policy),
+        }
+    }
+
+    /// Check a new password's length and the blocklist, reporting problems against `field`
+    ///
+    /// Reuse of earlier passwords needs the database; see `PasswordService`.
+    pub fn check(&self, field: &str, password: &str) -> Result<(), AppError> {
+        let length = password.chars().count();
+
+        if length < self.min_length {
+            return Err(AppError::field(
+                field,
+                "too_short",
+                format!("Password must be at least {} characters long", self.min_length),
+            ));
+        }
+
+        if length > self.max_length {
+            return Err(AppError::field(
+                field,
+                "too_long",
+                format!("Password cannot exceed {} characters", self.max_length),
+            ));
+        }
+
+        if self.blocklist.contains(&password.to_lowercase()) {
+            return Err(AppError::field(
+                field,
+                "breached",
+                "This password has appeared in a data breach; choose a different one",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Load the password policy, failing on an invalid configuration; call once at startup
+pub fn init_password_policy() -> Result<&'static PasswordPolicy, String> {
+    if let Some(policy) = PASSWORD_POLICY.get() {
+        return Ok(policy);
+    }
+
+    let policy = PasswordPolicy::from_env()?;
+    Ok(PASSWORD_POLICY.get_or_init(|| policy))
+}
+
+/// The process-wide policy, loaded on first use if `init_password_policy` was not called
+pub fn password_policy() -> &'static PasswordPolicy {
+    PASSWORD_POLICY.get_or_init(|| {
+        PasswordPolicy::from_env().unwrap_or_else(|e| panic!("Invalid password policy configuration: {}", e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> PasswordPolicy {
+        PasswordPolicy::new(8, 128, 5)
+            .unwrap()
+            .with_blocklist("# breached passwords\n123456789\n\nPassword1\n")
+    }
+
+    fn rejection_code(result: Result<(), AppError>) -> String {
+        match result {
+            Err(AppError::FieldValidation(errors)) => errors["password"][0].code.clone(),
+            other => panic!("expected a field error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_password_validation() {
+        // Too short password
+        assert_eq!(rejection_code(policy().check("password", "1234567")), "too_short");
+
+        // Too long password (129 characters)
+        let long_password = "a".repeat(129);
+        assert_eq!(rejection_code(policy().check("password", &long_password)), "too_long");
+
+        // Valid passwords, including ones past bcrypt's 72 bytes
+        assert!(policy().check("password", "valid_password_123").is_ok());
+        assert!(policy().check("password", &"a".repeat(73)).is_ok());
+    }
+
+    #[test]
+    fn test_length_counts_characters() {
+        assert!(policy().check("password", "ééééééé").is_err());
+        assert!(policy().check("password", "éééééééé").is_ok());
+    }
+
+    #[test]
+    fn test_blocklist_ignores_case_and_comments() {
+        assert_eq!(rejection_code(policy().check("password", "123456789")), "breached");
+        assert_eq!(rejection_code(policy().check("password", "PASSWORD1")), "breached");
+        assert!(policy().check("password", "# breached passwords").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_limits() {
+        assert!(PasswordPolicy::new(0, 128, 5).is_err());
+        assert!(PasswordPolicy::new(20, 10, 5).is_err());
+    }
+}
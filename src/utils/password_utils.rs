@@ -1,36 +1,244 @@
 // Password utility functions for hashing and verification
 
-use bcrypt::{hash, verify, DEFAULT_COST};
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString},
+    Algorithm, Argon2, Params, Version,
+};
+use bcrypt::DEFAULT_COST;
+use rand::RngCore;
+use std::{env, sync::OnceLock};
 use crate::errors::AppError;
+use crate::utils::setting_from_env;
 
-/// Hash a plain text password using bcrypt
-pub fn hash_password(password: &str) -> Result<String, AppError> {
-    // Validate password strength
-    if password.len() < 6 {
-        return Err(AppError::ValidationError(
-            "Password must be at least 6 characters long".to_string(),
-        ));
+static PASSWORD_HASHING: OnceLock<PasswordHashing> = OnceLock::new();
+
+/// One password hashing scheme
+pub trait PasswordHasher: Send + Sync {
+    /// Name used in `PASSWORD_HASHER`
+    fn name(&self) -> &'static str;
+
+    fn hash(&self, password: &str) -> Result<String, AppError>;
+
+    fn verify(&self, password: &str, hashed_password: &str) -> Result<bool, AppError>;
+
+    /// Whether a stored hash was produced by this scheme
+    fn recognizes(&self, hashed_password: &str) -> bool;
+
+    /// Whether a hash of this scheme was produced with weaker settings than the current ones
+    fn is_outdated(&self, hashed_password: &str) -> bool;
+}
+
+/// Argon2id, the default
+pub struct Argon2idHasher {
+    params: Params,
+}
+
+impl Argon2idHasher {
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, String> {
+        let params = Params::new(memory_kib, iterations, parallelism, None)
+            .map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+        Ok(Self { params })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+impl PasswordHasher for Argon2idHasher {
+    fn name(&self) -> &'static str {
+        "argon2id"
+    }
+
+    fn hash(&self, password: &str) -> Result<String, AppError> {
+        let mut salt = [0u8; 16];
+        rand::rng().fill_bytes(&mut salt);
+        let salt = SaltString::encode_b64(&salt).map_err(|e| hashing_failed("hash", e))?;
+
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| hashing_failed("hash", e))
+    }
+
+    fn verify(&self, password: &str, hashed_password: &str) -> Result<bool, AppError> {
+        let hash = PasswordHash::new(hashed_password).map_err(|e| hashing_failed("verify", e))?;
+        Ok(self.argon2().verify_password(password.as_bytes(), &hash).is_ok())
+    }
+
+    fn recognizes(&self, hashed_password: &str) -> bool {
+        hashed_password.starts_with("$argon2id$")
+    }
+
+    fn is_outdated(&self, hashed_password: &str) -> bool {
+        let Ok(hash) = PasswordHash::new(hashed_password) else {
+            return true;
+        };
+        match Params::try_from(&hash) {
+            Ok(params) => {
+                params.m_cost() < self.params.m_cost()
+                    || params.t_cost() < self.params.t_cost()
+                    || params.p_cost() < self.params.p_cost()
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+/// bcrypt, which hashes created before Argon2id use
+pub struct BcryptHasher {
+    cost: u32,
+}
+
+impl BcryptHasher {
+    pub fn new(cost: u32) -> Result<Self, String> {
+        if !(4..=31).contains(&cost) {
+            return Err(format!("Invalid bcrypt cost {}: must be between 4 and 31", cost));
+        }
+        Ok(Self { cost })
+    }
+}
+
+impl PasswordHasher for BcryptHasher {
+    fn name(&self) -> &'static str {
+        "bcrypt"
+    }
+
+    fn hash(&self, password: &str) -> Result<String, AppError> {
+        // bcrypt ignores everything past 72 bytes, which would make longer passwords weaker than they look
+        if password.len() > 72 {
+            return Err(AppError::ValidationError(
+                "Password cannot exceed 72 bytes (bcrypt limitation)".to_string(),
+            ));
+        }
+
+        bcrypt::hash(password, self.cost).map_err(|e| hashing_failed("hash", e))
+    }
+
+    fn verify(&self, password: &str, hashed_password: &str) -> Result<bool, AppError> {
+        bcrypt::verify(password, hashed_password).map_err(|e| hashing_failed("verify", e))
+    }
+
+    fn recognizes(&self, hashed_password: &str) -> bool {
+        ["$2a$", "$2b$", "$2x$", "$2y$"].iter().any(|prefix| hashed_password.starts_with(prefix))
+    }
+
+    fn is_outdated(&self, hashed_password: &str) -> bool {
+        match hashed_password.parse::<bcrypt::HashParts>() {
+            Ok(parts) => parts.get_cost() < self.cost,
+            Err(_) => true,
+        }
+    }
+}
+
+/// The scheme new passwords are hashed with, plus every scheme stored hashes are still verified with
+pub struct PasswordHashing {
+    current: Box<dyn PasswordHasher>,
+    legacy: Vec<Box<dyn PasswordHasher>>,
+    /// Hash of a throwaway password, verified when there is no account to check against
+    dummy_hash: String,
+}
+
+impl PasswordHashing {
+    pub fn new(current: Box<dyn PasswordHasher>, legacy: Vec<Box<dyn PasswordHasher>>) -> Result<Self, String> {
+        let dummy_hash = current
+            .hash("dummy-password")
+            .map_err(|_| format!("Password hasher '{}' cannot hash", current.name()))?;
+        Ok(Self { current, legacy, dummy_hash })
+    }
+
+    /// Load the hashers from the environment
+    ///
+    /// - `PASSWORD_HASHER`: `argon2id` (default) or `bcrypt`
+    /// - `ARGON2_MEMORY_KIB` (19456), `ARGON2_ITERATIONS` (2), `ARGON2_PARALLELISM` (1)
+    /// - `BCRYPT_COST` (12)
+    ///
+    /// Hashes of the other scheme keep verifying and are replaced on the next login.
+    pub fn from_env() -> Result<Self, String> {
+        let argon2id = Argon2idHasher::new(
+            setting_from_env("ARGON2_MEMORY_KIB", Params::DEFAULT_M_COST)?,
+            setting_from_env("ARGON2_ITERATIONS", Params::DEFAULT_T_COST)?,
+            setting_from_env("ARGON2_PARALLELISM", Params::DEFAULT_P_COST)?,
+        )?;
+        let bcrypt = BcryptHasher::new(setting_from_env("BCRYPT_COST", DEFAULT_COST)?)?;
+
+        let (current, legacy): (Box<dyn PasswordHasher>, Box<dyn PasswordHasher>) =
+            match env::var("PASSWORD_HASHER").as_deref() {
+                Ok("argon2id") | Err(_) => (Box::new(argon2id), Box::new(bcrypt)),
+                Ok("bcrypt") => (Box::new(bcrypt), Box::new(argon2id)),
+                Ok(other) => return Err(format!("Unknown PASSWORD_HASHER '{}': use argon2id or bcrypt", other)),
+            };
+
+        Self::new(current, vec![legacy])
+    }
+
+    pub fn hash(&self, password: &str) -> Result<String, AppError> {
+        self.current.hash(password)
+    }
+
+    /// Verify with whichever scheme produced the stored hash
+    pub fn verify(&self, password: &str, hashed_password: &str) -> Result<bool, AppError> {
+        match self.hasher_for(hashed_password) {
+            Some(hasher) => hasher.verify(password, hashed_password),
+            None => {
+                tracing::error!("Stored password hash has an unknown format");
+                Err(AppError::InternalServerError("Failed to verify password".to_string()))
+            }
+        }
+    }
+
+    /// Whether a verified hash should be replaced by one from the current scheme and settings
+    pub fn needs_rehash(&self, hashed_password: &str) -> bool {
+        !self.current.recognizes(hashed_password) || self.current.is_outdated(hashed_password)
     }
 
-    if password.len() > 72 {
-        return Err(AppError::ValidationError(
-            "Password cannot exceed 72 characters (bcrypt limitation)".to_string(),
-        ));
+    fn hasher_for(&self, hashed_password: &str) -> Option<&dyn PasswordHasher> {
+        std::iter::once(&self.current)
+            .chain(&self.legacy)
+            .find(|hasher| hasher.recognizes(hashed_password))
+            .map(|hasher| hasher.as_ref())
+    }
+}
+
+/// Load the password hashers, failing on an invalid configuration; call once at startup
+pub fn init_password_hashing() -> Result<&'static PasswordHashing, String> {
+    if let Some(hashing) = PASSWORD_HASHING.get() {
+        return Ok(hashing);
     }
 
-    // Hash the password with default cost (12)
-    hash(password, DEFAULT_COST).map_err(|e| {
-        tracing::error!("Failed to hash password: {}", e);
-        AppError::InternalServerError("Failed to hash password".to_string())
+    let hashing = PasswordHashing::from_env()?;
+    Ok(PASSWORD_HASHING.get_or_init(|| hashing))
+}
+
+/// The process-wide hashers, loaded on first use if `init_password_hashing` was not called
+pub fn password_hashing() -> &'static PasswordHashing {
+    PASSWORD_HASHING.get_or_init(|| {
+        PasswordHashing::from_env().unwrap_or_else(|e| panic!("Invalid password hashing configuration: {}", e))
     })
 }
 
+/// Hash a plain text password with the configured scheme
+///
+/// Strength is checked by the password policy; see `password_policy`.
+pub fn hash_password(password: &str) -> Result<String, AppError> {
+    password_hashing().hash(password)
+}
+
 /// Verify a plain text password against a hashed password
 pub fn verify_password(password: &str, hashed_password: &str) -> Result<bool, AppError> {
-    verify(password, hashed_password).map_err(|e| {
-        tracing::error!("Failed to verify password: {}", e);
-        AppError::InternalServerError("Failed to verify password".to_string())
-    })
+    password_hashing().verify(password, hashed_password)
+}
+
+/// Whether a hash that just verified should be upgraded to the configured scheme
+pub fn password_needs_rehash(hashed_password: &str) -> bool {
+    password_hashing().needs_rehash(hashed_password)
+}
+
+/// Spend as long as `verify_password` would when there is no account to check against,
+/// so response times do not reveal which emails are registered
+pub fn verify_password_against_dummy(password: &str) {
+    let hashing = password_hashing();
+    let _ = hashing.verify(password, &hashing.dummy_hash);
 }
 
 /// Generate a secure random password (for testing or password reset)
@@ -40,7 +248,7 @@ pub fn generate_random_password(length: usize) -> String {
                             abcdefghijklmnopqrstuvwxyz\
                             0123456789\
                             !@#$%^&*";
-    
+
     let mut rng = rand::thread_rng();
     (0..length)
         .map(|_| {
@@ -50,40 +258,78 @@ pub fn generate_random_password(length: usize) -> String {
         .collect()
 }
 
+fn hashing_failed(operation: &str, error: impl std::fmt::Display) -> AppError {
+    tracing::error!("Failed to {} password: {}", operation, error);
+    AppError::InternalServerError(format!("Failed to {} password", operation))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // Cheap settings keep the tests fast
+    fn argon2id() -> Argon2idHasher {
+        Argon2idHasher::new(1024, 1, 1).unwrap()
+    }
+
+    fn hashing() -> PasswordHashing {
+        PasswordHashing::new(Box::new(argon2id()), vec![Box::new(BcryptHasher::new(4).unwrap())]).unwrap()
+    }
+
     #[test]
     fn test_password_hashing() {
         let password = "test_password_123";
         let hashed = hash_password(password).unwrap();
-        
+
         // Verify the password matches
         assert!(verify_password(password, &hashed).unwrap());
-        
+
         // Verify wrong password doesn't match
         assert!(!verify_password("wrong_password", &hashed).unwrap());
     }
 
     #[test]
-    fn test_password_validation() {
-        // Too short password
-        assert!(hash_password("12345").is_err());
-        
-        // Too long password (73 characters)
+    fn test_new_hashes_use_argon2id() {
+        let hashed = hashing().hash("test_password_123").unwrap();
+
+        assert!(hashed.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert!(!hashing().needs_rehash(&hashed));
+    }
+
+    #[test]
+    fn test_legacy_bcrypt_hashes_verify_and_need_rehash() {
+        let legacy = bcrypt::hash("test_password_123", 4).unwrap();
+
+        assert!(hashing().verify("test_password_123", &legacy).unwrap());
+        assert!(!hashing().verify("wrong_password", &legacy).unwrap());
+        assert!(hashing().needs_rehash(&legacy));
+    }
+
+    #[test]
+    fn test_weaker_argon2_settings_need_rehash() {
+        let weaker = Argon2idHasher::new(512, 1, 1).unwrap().hash("test_password_123").unwrap();
+
+        assert!(hashing().verify("test_password_123", &weaker).unwrap());
+        assert!(hashing().needs_rehash(&weaker));
+    }
+
+    #[test]
+    fn test_unknown_hash_format_is_an_error() {
+        assert!(hashing().verify("test_password_123", "plaintext").is_err());
+    }
+
+    #[test]
+    fn test_bcrypt_rejects_long_passwords() {
         let long_password = "a".repeat(73);
-        assert!(hash_password(&long_password).is_err());
-        
-        // Valid password
-        assert!(hash_password("valid_password_123").is_ok());
+        assert!(BcryptHasher::new(4).unwrap().hash(&long_password).is_err());
+        assert!(argon2id().hash(&long_password).is_ok());
     }
 
     #[test]
     fn test_random_password_generation() {
         let password = generate_random_password(12);
         assert_eq!(password.len(), 12);
-        
+
         // Test that generated passwords are different
         let password2 = generate_random_password(12);
         assert_ne!(password, password2);
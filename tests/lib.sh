@@ -0,0 +1,70 @@
+#!/bin/bash
+
+# Helpers shared by the test scripts, sourced after their configuration:
+#   source "$(dirname "$0")/lib.sh"
+
+BASE_URL="${BASE_URL:-http://127.0.0.1:8081}"
+
+# Send an unauthenticated request, printing the response body followed by the HTTP status
+public_request() {
+    curl -s -w "\n%{http_code}" -X "$1" "$BASE_URL$2" \
+      -H "Content-Type: application/json" \
+      ${3:+-d "$3"}
+}
+
+# Same as public_request, with a bearer token as the first argument
+auth_request() {
+    curl -s -w "\n%{http_code}" -X "$2" "$BASE_URL$3" \
+      -H "Authorization: Bearer $1" \
+      -H "Content-Type: application/json" \
+      ${4:+-d "$4"}
+}
+
+# Same as auth_request, operating on the organization under test ($ORG_ID)
+org_request() {
+    curl -s -w "\n%{http_code}" -X "$2" "$BASE_URL$3" \
+      -H "Authorization: Bearer $1" \
+      -H "X-Organization-Id: $ORG_ID" \
+      -H "Content-Type: application/json" \
+      ${4:+-d "$4"}
+}
+
+# Same as auth_request, as the admin ($TOKEN)
+admin_request() {
+    auth_request "$TOKEN" "$@"
+}
+
+# Log in with an email and password (password123 by default), printing the response body followed by the HTTP status
+login_request() {
+    public_request POST "/users/login" '{"email": "'$1'", "password": "'"${2:-password123}"'"}'
+}
+
+# Same as login_request, printing only the token
+login() {
+    login_request "$@" | grep -o '"token":"[^"]*"' | cut -d'"' -f4
+}
+
+# Same as login_request, printing only the HTTP status
+login_status() {
+    login_request "$@" | tail -1
+}
+
+# Stop the test unless a value matches the expected one
+expect_status() {
+    if [ "$1" = "$2" ]; then
+        echo "✅ $3"
+    else
+        echo "❌ $3: expected $2, got $1"
+        exit 1
+    fi
+}
+
+# Stop the test unless a response body contains a pattern
+expect_body() {
+    if echo "$1" | grep -q "$2"; then
+        echo "✅ $3"
+    else
+        echo "❌ $3: expected $2 in $1"
+        exit 1
+    fi
+}
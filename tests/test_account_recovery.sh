@@ -0,0 +1,100 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# Emailed tokens only leave the server through the outbox, so they are read back from it
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Password Reset and Email Verification..."
+
+# Token from the link in the latest email of a template sent to an address
+latest_email_token() {
+    psql "$DATABASE_URL" -At -c "SELECT payload::text FROM email_outbox WHERE recipient = '$1' AND template = '$2' ORDER BY created_at DESC LIMIT 1" \
+      | grep -o 'token=[0-9a-f]*' | cut -d= -f2
+}
+
+TIMESTAMP=$(date +%s)
+USER_EMAIL="recovery.$TIMESTAMP@example.com"
+
+# Step 1: Registration queues a verification email
+echo ""
+echo "📝 Step 1: Registering a user..."
+RESPONSE=$(public_request POST "/users" '{"name": "Recovery User", "email": "'$USER_EMAIL'", "password": "password123"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "User registered"
+USER_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+SESSION_TOKEN=$(echo "$RESPONSE" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+VERIFY_TOKEN=$(latest_email_token "$USER_EMAIL" "email_verification")
+if [ -n "$VERIFY_TOKEN" ]; then
+    echo "✅ Verification email queued"
+else
+    echo "❌ No verification email queued for $USER_EMAIL"
+    exit 1
+fi
+
+# Step 2: Verifying the email
+echo ""
+echo "📝 Step 2: Verifying the email address..."
+expect_status "$(public_request POST "/auth/verify-email" '{"token": "'$VERIFY_TOKEN'"}' | tail -1)" "200" "Email verified"
+expect_status "$(public_request POST "/auth/verify-email" '{"token": "'$VERIFY_TOKEN'"}' | tail -1)" "400" "Verification token is single-use"
+expect_status "$(public_request POST "/auth/verify-email" '{"token": "not-a-real-token"}' | tail -1)" "400" "Unknown verification token rejected"
+
+# Step 3: Requesting a password reset
+echo ""
+echo "📝 Step 3: Requesting a password reset..."
+UNKNOWN_RESPONSE=$(public_request POST "/auth/forgot-password" '{"email": "nobody.'$TIMESTAMP'@example.com"}')
+KNOWN_RESPONSE=$(public_request POST "/auth/forgot-password" '{"email": "'$USER_EMAIL'"}')
+expect_status "$(echo "$KNOWN_RESPONSE" | tail -1)" "202" "Reset requested"
+
+if [ "$(echo "$UNKNOWN_RESPONSE" | head -1)" = "$(echo "$KNOWN_RESPONSE" | head -1)" ]; then
+    echo "✅ Unknown emails get the same response"
+else
+    echo "❌ Responses reveal whether the email exists"
+    exit 1
+fi
+
+FIRST_RESET_TOKEN=$(latest_email_token "$USER_EMAIL" "password_reset")
+public_request POST "/auth/forgot-password" '{"email": "'$USER_EMAIL'"}' > /dev/null
+RESET_TOKEN=$(latest_email_token "$USER_EMAIL" "password_reset")
+expect_status "$(public_request POST "/auth/reset-password" '{"token": "'$FIRST_RESET_TOKEN'", "new_password": "newpassword456"}' | tail -1)" "400" "Superseded reset link rejected"
+
+# Step 4: Resetting the password
+echo ""
+echo "📝 Step 4: Resetting the password..."
+expect_status "$(public_request POST "/auth/reset-password" '{"token": "'$RESET_TOKEN'", "new_password": "short"}' | tail -1)" "400" "Weak password rejected"
+expect_status "$(public_request POST "/auth/reset-password" '{"token": "'$RESET_TOKEN'", "new_password": "newpassword456"}' | tail -1)" "200" "Password reset"
+expect_status "$(public_request POST "/auth/reset-password" '{"token": "'$RESET_TOKEN'", "new_password": "otherpassword789"}' | tail -1)" "400" "Reset token is single-use"
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $SESSION_TOKEN")
+expect_status "$STATUS" "401" "Existing sessions revoked"
+expect_status "$(login_status "$USER_EMAIL" "password123")" "401" "Old password no longer works"
+expect_status "$(login_status "$USER_EMAIL" "newpassword456")" "200" "New password works"
+
+# Step 5: Accounts pending verification cannot log in
+echo ""
+echo "📝 Step 5: Logging in before verifying..."
+PENDING_EMAIL="pending.$TIMESTAMP@example.com"
+PENDING_ID=$(public_request POST "/users" '{"name": "Pending User", "email": "'$PENDING_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+# Simulate REQUIRE_EMAIL_VERIFICATION=true for this account
+psql "$DATABASE_URL" -q -c "UPDATE users SET status = 'pending_verification' WHERE id = '$PENDING_ID'"
+
+expect_status "$(login_status "$PENDING_EMAIL" "password123")" "403" "Unverified account cannot log in"
+expect_status "$(login_status "$PENDING_EMAIL" "wrongpassword")" "401" "Wrong password still reported as such"
+
+PENDING_TOKEN=$(latest_email_token "$PENDING_EMAIL" "email_verification")
+expect_status "$(public_request POST "/auth/verify-email" '{"token": "'$PENDING_TOKEN'"}' | tail -1)" "200" "Email verified"
+expect_status "$(login_status "$PENDING_EMAIL" "password123")" "200" "Verified account can log in"
+
+# Step 6: Cleanup
+echo ""
+echo "📝 Step 6: Cleaning up..."
+ADMIN_TOKEN=$(login "test@example.com")
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$USER_ID" -H "Authorization: Bearer $ADMIN_TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$PENDING_ID" -H "Authorization: Bearer $ADMIN_TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Password Reset and Email Verification Test Complete!"
@@ -0,0 +1,135 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+echo "🧪 Testing Refresh Tokens and Session Revocation..."
+
+# Log in as a user and print "<access token> <refresh token>"
+login() {
+    local response
+    response=$(curl -s -X POST "$BASE_URL/users/login" \
+      -H "Content-Type: application/json" \
+      -d '{"email": "'$1'", "password": "password123"}')
+    echo "$(echo "$response" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)" \
+         "$(echo "$response" | grep -o '"refresh_token":"[^"]*"' | cut -d'"' -f4)"
+}
+
+# HTTP status of GET /users/me for an access token
+me_status() {
+    curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $1"
+}
+
+# Step 1: Login returns an access token and a refresh token
+echo ""
+echo "📝 Step 1: Logging in..."
+read -r TOKEN REFRESH_TOKEN <<< "$(login test@example.com)"
+
+if [ -n "$TOKEN" ] && [ -n "$REFRESH_TOKEN" ]; then
+    echo "✅ Received access and refresh tokens"
+else
+    echo "❌ Login did not return both tokens"
+    exit 1
+fi
+
+# Step 2: Refresh rotates the refresh token
+echo ""
+echo "📝 Step 2: Refreshing..."
+REFRESH_RESPONSE=$(curl -s -X POST "$BASE_URL/auth/refresh" \
+  -H "Content-Type: application/json" \
+  -d '{"refresh_token": "'$REFRESH_TOKEN'"}')
+
+NEW_TOKEN=$(echo "$REFRESH_RESPONSE" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+NEW_REFRESH_TOKEN=$(echo "$REFRESH_RESPONSE" | grep -o '"refresh_token":"[^"]*"' | cut -d'"' -f4)
+
+if [ -n "$NEW_TOKEN" ] && [ -n "$NEW_REFRESH_TOKEN" ] && [ "$NEW_REFRESH_TOKEN" != "$REFRESH_TOKEN" ] \
+    && [ "$(me_status "$NEW_TOKEN")" = "200" ]; then
+    echo "✅ Refresh issued a working access token and a new refresh token"
+else
+    echo "❌ Refresh failed: $REFRESH_RESPONSE"
+    exit 1
+fi
+
+# Step 3: Replaying the old refresh token revokes the session
+echo ""
+echo "📝 Step 3: Replaying a used refresh token..."
+REPLAY_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/auth/refresh" \
+  -H "Content-Type: application/json" \
+  -d '{"refresh_token": "'$REFRESH_TOKEN'"}')
+AFTER_REPLAY_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/auth/refresh" \
+  -H "Content-Type: application/json" \
+  -d '{"refresh_token": "'$NEW_REFRESH_TOKEN'"}')
+
+if [ "$REPLAY_STATUS" = "401" ] && [ "$AFTER_REPLAY_STATUS" = "401" ] && [ "$(me_status "$NEW_TOKEN")" = "401" ]; then
+    echo "✅ Reuse rejected and the session revoked"
+else
+    echo "❌ Expected 401/401/401, got $REPLAY_STATUS/$AFTER_REPLAY_STATUS/$(me_status "$NEW_TOKEN")"
+    exit 1
+fi
+
+# Step 4: Logout revokes only the current session
+echo ""
+echo "📝 Step 4: Logging out..."
+read -r TOKEN_A REFRESH_A <<< "$(login test@example.com)"
+read -r TOKEN_B REFRESH_B <<< "$(login test@example.com)"
+
+LOGOUT_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/auth/logout" \
+  -H "Authorization: Bearer $TOKEN_A")
+
+if [ "$LOGOUT_STATUS" = "204" ] && [ "$(me_status "$TOKEN_A")" = "401" ] && [ "$(me_status "$TOKEN_B")" = "200" ]; then
+    echo "✅ Logged out session rejected, other session still valid"
+else
+    echo "❌ Logout failed (status $LOGOUT_STATUS)"
+    exit 1
+fi
+
+# Step 5: Logout from all devices
+echo ""
+echo "📝 Step 5: Logging out of all devices..."
+LOGOUT_ALL_RESPONSE=$(curl -s -X POST "$BASE_URL/auth/logout-all" -H "Authorization: Bearer $TOKEN_B")
+REFRESH_B_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/auth/refresh" \
+  -H "Content-Type: application/json" \
+  -d '{"refresh_token": "'$REFRESH_B'"}')
+
+if echo "$LOGOUT_ALL_RESPONSE" | grep -q '"revoked_sessions":' && [ "$(me_status "$TOKEN_B")" = "401" ] \
+    && [ "$REFRESH_B_STATUS" = "401" ]; then
+    echo "✅ All sessions revoked"
+else
+    echo "❌ Logout-all failed: $LOGOUT_ALL_RESPONSE (refresh status $REFRESH_B_STATUS)"
+    exit 1
+fi
+
+# Step 6: Deactivated users lose their tokens immediately
+echo ""
+echo "📝 Step 6: Deactivating a user with a live token..."
+read -r ADMIN_TOKEN ADMIN_REFRESH <<< "$(login test@example.com)"
+TIMESTAMP=$(date +%s)
+USER_EMAIL="session.$TIMESTAMP@example.com"
+USER_RESPONSE=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Session User", "email": "'$USER_EMAIL'", "password": "password123"}')
+USER_ID=$(echo "$USER_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+read -r USER_TOKEN USER_REFRESH <<< "$(login "$USER_EMAIL")"
+
+# Admins can only deactivate members of their own organization
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $ADMIN_TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $ADMIN_TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+curl -s -o /dev/null -X POST "$BASE_URL/user-organizations" \
+  -H "Authorization: Bearer $ADMIN_TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"user_id": "'$USER_ID'", "org_id": "'$ORG_ID'", "role_name": "viewer"}'
+
+BEFORE_STATUS=$(me_status "$USER_TOKEN")
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$USER_ID" -H "Authorization: Bearer $ADMIN_TOKEN"
+AFTER_STATUS=$(me_status "$USER_TOKEN")
+
+if [ "$BEFORE_STATUS" = "200" ] && [ "$AFTER_STATUS" = "401" ]; then
+    echo "✅ Deactivated user's token rejected"
+else
+    echo "❌ Expected 200 then 401, got $BEFORE_STATUS then $AFTER_STATUS"
+    exit 1
+fi
+
+echo ""
+echo "🎉 Session Test Complete!"
@@ -0,0 +1,129 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+UNKNOWN_ORG_ID="00000000-0000-0000-0000-000000000000"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Authentication and Authorization Status Codes..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+# Step 2: Token problems are 401 with a WWW-Authenticate challenge
+echo ""
+echo "📝 Step 2: Sending missing, malformed and invalid credentials..."
+MISSING_HEADERS=$(curl -s -D - -o /dev/null -X GET "$BASE_URL/contacts/filter/fields")
+MISSING_STATUS=$(echo "$MISSING_HEADERS" | head -1 | awk '{print $2}')
+expect_status "$MISSING_STATUS" "401" "Missing token"
+
+if echo "$MISSING_HEADERS" | grep -qi "^www-authenticate: Bearer"; then
+    echo "✅ WWW-Authenticate challenge present"
+else
+    echo "❌ WWW-Authenticate header missing"
+    exit 1
+fi
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/contacts/filter/fields" -H "Authorization: Basic dGVzdDp0ZXN0")
+expect_status "$STATUS" "401" "Non-Bearer scheme"
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/contacts/filter/fields" -H "Authorization: Bearer not.a.token")
+expect_status "$STATUS" "401" "Invalid token"
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/users/me" -H "Authorization: Bearer not.a.token")
+expect_status "$STATUS" "401" "Invalid token on JWT-protected route"
+
+STATUS=$(login_status "test@example.com" "wrong-password")
+expect_status "$STATUS" "401" "Wrong password"
+
+# Step 3: Authenticated users without access get 403
+echo ""
+echo "📝 Step 3: Checking forbidden access..."
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/contacts/filter/fields" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "X-Organization-Id: $UNKNOWN_ORG_ID")
+expect_status "$STATUS" "403" "Organization without membership"
+
+TIMESTAMP=$(date +%s)
+ME_RESPONSE=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN")
+ADMIN_ID=$(echo "$ME_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+VIEWER_EMAIL="viewer.$TIMESTAMP@example.com"
+VIEWER_RESPONSE=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Viewer", "email": "'$VIEWER_EMAIL'", "password": "password123"}')
+VIEWER_ID=$(echo "$VIEWER_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+curl -s -o /dev/null -X POST "$BASE_URL/user-organizations" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"user_id": "'$VIEWER_ID'", "org_id": "'$ORG_ID'", "role_name": "viewer"}'
+
+VIEWER_TOKEN=$(login "$VIEWER_EMAIL")
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/contacts/filter/fields" \
+  -H "Authorization: Bearer $VIEWER_TOKEN" \
+  -H "X-Organization-Id: $ORG_ID")
+expect_status "$STATUS" "200" "Viewer reading contacts"
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $VIEWER_TOKEN" \
+  -H "X-Organization-Id: $ORG_ID" \
+  -H "Content-Type: application/json" \
+  -d '{"first_name": "Not", "last_name": "Allowed", "email": "not.allowed.'$TIMESTAMP'@example.com"}')
+expect_status "$STATUS" "403" "Viewer creating a contact"
+
+# Step 4: Duplicate emails are 409
+echo ""
+echo "📝 Step 4: Checking duplicate emails..."
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Viewer", "email": "'$VIEWER_EMAIL'", "password": "password123"}')
+expect_status "$STATUS" "409" "Duplicate user email"
+
+CONTACT_EMAIL="status.matrix.$TIMESTAMP@example.com"
+CONTACT_ID=$(curl -s -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"first_name": "Status", "last_name": "Matrix", "email": "'$CONTACT_EMAIL'"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+OTHER_ID=$(curl -s -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"first_name": "Status", "last_name": "Other", "email": "other.'$CONTACT_EMAIL'"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"first_name": "Status", "last_name": "Again", "email": "'$CONTACT_EMAIL'"}')
+expect_status "$STATUS" "409" "Duplicate contact email on create"
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X PATCH "$BASE_URL/contacts/$OTHER_ID" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "'$CONTACT_EMAIL'"}')
+expect_status "$STATUS" "409" "Duplicate contact email on patch"
+
+# Step 5: Cleanup
+echo ""
+echo "📝 Step 5: Cleaning up..."
+curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$CONTACT_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$OTHER_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$VIEWER_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Status Code Test Complete!"
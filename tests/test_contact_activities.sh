@@ -0,0 +1,153 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+echo "🧪 Testing Contact Activities API..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+LOGIN_RESPONSE=$(curl -s -X POST "$BASE_URL/users/login" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "test@example.com", "password": "password123"}')
+
+TOKEN=$(echo "$LOGIN_RESPONSE" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+# Step 2: Create a contact
+echo ""
+echo "📝 Step 2: Creating test contact..."
+TIMESTAMP=$(date +%s)
+
+CONTACT_RESPONSE=$(curl -s -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{
+    "first_name": "Activity",
+    "last_name": "Test",
+    "email": "activity.test.'$TIMESTAMP'@example.com"
+  }')
+
+CONTACT_ID=$(echo "$CONTACT_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ -z "$CONTACT_ID" ]; then
+    echo "❌ Failed to create contact: $CONTACT_RESPONSE"
+    exit 1
+fi
+
+echo "✅ Created contact $CONTACT_ID"
+
+# Step 3: Log a call and schedule a meeting and an overdue task
+echo ""
+echo "📝 Step 3: Creating activities..."
+CALL_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/contacts/$CONTACT_ID/activities" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"activity_type": "call", "subject": "Intro call", "duration_minutes": 15}')
+
+MEETING_RESPONSE=$(curl -s -X POST "$BASE_URL/contacts/$CONTACT_ID/activities" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"activity_type": "meeting", "subject": "Demo", "activity_date": "2099-01-01T10:00:00Z"}')
+MEETING_ID=$(echo "$MEETING_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+TASK_RESPONSE=$(curl -s -X POST "$BASE_URL/contacts/$CONTACT_ID/activities" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"activity_type": "task", "subject": "Send proposal", "activity_date": "2020-01-01T10:00:00Z", "status": "scheduled"}')
+TASK_ID=$(echo "$TASK_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ "$CALL_STATUS" = "201" ] && echo "$MEETING_RESPONSE" | grep -q '"status":"scheduled"' && echo "$TASK_RESPONSE" | grep -q '"is_overdue":true'; then
+    echo "✅ Activities created"
+else
+    echo "❌ Activity creation failed (call: $CALL_STATUS, meeting: $MEETING_RESPONSE, task: $TASK_RESPONSE)"
+    exit 1
+fi
+
+# Step 4: Invalid activity type is rejected
+echo ""
+echo "📝 Step 4: Validating activity input..."
+INVALID_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/contacts/$CONTACT_ID/activities" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"activity_type": "carrier_pigeon"}')
+
+if [ "$INVALID_STATUS" = "400" ]; then
+    echo "✅ Invalid activity type rejected"
+else
+    echo "❌ Expected 400, got $INVALID_STATUS"
+    exit 1
+fi
+
+# Step 5: Timeline is paginated
+echo ""
+echo "📝 Step 5: Reading the timeline..."
+TIMELINE_RESPONSE=$(curl -s -X GET "$BASE_URL/contacts/$CONTACT_ID/timeline?limit=2" \
+  -H "Authorization: Bearer $TOKEN")
+
+if echo "$TIMELINE_RESPONSE" | grep -q '"total_count":3' && echo "$TIMELINE_RESPONSE" | grep -q '"has_next":true'; then
+    echo "✅ Timeline returned 3 activities across pages"
+else
+    echo "❌ Unexpected timeline: $TIMELINE_RESPONSE"
+    exit 1
+fi
+
+# Step 6: Upcoming and overdue queues
+echo ""
+echo "📝 Step 6: Checking upcoming and overdue queues..."
+UPCOMING_RESPONSE=$(curl -s -X GET "$BASE_URL/activities/upcoming" -H "Authorization: Bearer $TOKEN")
+OVERDUE_RESPONSE=$(curl -s -X GET "$BASE_URL/activities/overdue" -H "Authorization: Bearer $TOKEN")
+
+if echo "$UPCOMING_RESPONSE" | grep -q "$MEETING_ID" && echo "$OVERDUE_RESPONSE" | grep -q "$TASK_ID"; then
+    echo "✅ Meeting is upcoming and task is overdue"
+else
+    echo "❌ Unexpected queues (upcoming: $UPCOMING_RESPONSE, overdue: $OVERDUE_RESPONSE)"
+    exit 1
+fi
+
+# Step 7: Complete the overdue task
+echo ""
+echo "📝 Step 7: Completing the task..."
+COMPLETE_RESPONSE=$(curl -s -X POST "$BASE_URL/activities/$TASK_ID/complete" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"duration_minutes": 30}')
+OVERDUE_RESPONSE=$(curl -s -X GET "$BASE_URL/activities/overdue" -H "Authorization: Bearer $TOKEN")
+
+if echo "$COMPLETE_RESPONSE" | grep -q '"status":"completed"' && ! echo "$OVERDUE_RESPONSE" | grep -q "$TASK_ID"; then
+    echo "✅ Task completed and left the overdue queue"
+else
+    echo "❌ Completion failed: $COMPLETE_RESPONSE"
+    exit 1
+fi
+
+# Step 8: Delete an activity
+echo ""
+echo "📝 Step 8: Deleting the meeting..."
+DELETE_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X DELETE "$BASE_URL/activities/$MEETING_ID" \
+  -H "Authorization: Bearer $TOKEN")
+GET_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/activities/$MEETING_ID" \
+  -H "Authorization: Bearer $TOKEN")
+
+if [ "$DELETE_STATUS" = "204" ] && [ "$GET_STATUS" = "404" ]; then
+    echo "✅ Activity deleted"
+else
+    echo "❌ Expected 204/404, got delete: $DELETE_STATUS, get: $GET_STATUS"
+    exit 1
+fi
+
+# Step 9: Cleanup
+echo ""
+echo "📝 Step 9: Cleaning up..."
+curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$CONTACT_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Contact Activities API Test Complete!"
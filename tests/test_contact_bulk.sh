@@ -0,0 +1,168 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# Large selections are seeded directly and run by the job worker against the same database
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Bulk Contact Operations..."
+
+# Submit a bulk request, printing the response body followed by the HTTP status
+bulk_request() {
+    auth_request "$1" POST "/contacts/bulk" "$2"
+}
+
+# Create a contact with the given token, printing its id
+create_contact() {
+    auth_request "$1" POST "/contacts" '{"first_name": "Bulk", "last_name": "Contact", "email": "bulk.'$TIMESTAMP'.'$2'@example.com"}' \
+      | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Step 2: Contacts, a tag and a custom field to work with
+echo ""
+echo "📝 Step 2: Creating contacts..."
+FIRST_ID=$(create_contact "$TOKEN" first)
+SECOND_ID=$(create_contact "$TOKEN" second)
+IDS='"'$FIRST_ID'", "'$SECOND_ID'"'
+
+TAG_ID=$(auth_request "$TOKEN" POST "/tags" '{"name": "Bulk Tag '$TIMESTAMP'"}' | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+FIELD_NAME="bulk_note_$TIMESTAMP"
+FIELD_ID=$(auth_request "$TOKEN" POST "/custom-fields" '{"module": "contact", "label": "Bulk Note", "field_name": "'$FIELD_NAME'", "field_type": "text"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ -n "$FIRST_ID" ] && [ -n "$SECOND_ID" ] && [ -n "$TAG_ID" ] && [ -n "$FIELD_ID" ]; then
+    echo "✅ Created contacts $FIRST_ID and $SECOND_ID"
+else
+    echo "❌ Failed to set up contacts, tag and custom field"
+    exit 1
+fi
+
+# Step 3: Rejected requests
+echo ""
+echo "📝 Step 3: Rejecting invalid requests..."
+expect_status "$(bulk_request "$TOKEN" '{"action": {"type": "delete"}}' | tail -1)" "400" "Selector required"
+expect_status "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "filter": {"logic": "and", "conditions": []}, "action": {"type": "delete"}}' | tail -1)" "400" "Only one selector allowed"
+expect_status "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "change_lead_status", "lead_status": "sleeping"}}' | tail -1)" "400" "Unknown lead status rejected"
+expect_status "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "update_fields", "fields": {}}}' | tail -1)" "400" "Empty field update rejected"
+expect_status "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "change_owner", "owner_id": "00000000-0000-0000-0000-000000000001"}}' | tail -1)" "400" "Owner outside the organization rejected"
+expect_status "$(bulk_request "$TOKEN" '{"filter": {"logic": "and", "conditions": []}, "action": {"type": "restore"}}' | tail -1)" "400" "Restore by filter rejected"
+
+# Step 4: Dry run
+echo ""
+echo "📝 Step 4: Dry run..."
+RESPONSE=$(bulk_request "$TOKEN" '{"ids": ['"$IDS"', "00000000-0000-0000-0000-000000000001"], "action": {"type": "change_lead_status", "lead_status": "qualified"}, "dry_run": true}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Dry run accepted"
+expect_body "$RESPONSE" '"affected":2,"matched":3,"queued":false' "Dry run counts only existing contacts"
+expect_body "$(auth_request "$TOKEN" GET "/contacts/$FIRST_ID")" '"lead_status":"new"' "Dry run changes nothing"
+
+# Step 5: Field, status, custom field, owner and tag actions
+echo ""
+echo "📝 Step 5: Updating contacts in bulk..."
+RESPONSE=$(bulk_request "$TOKEN" '{"ids": ['"$IDS"', "00000000-0000-0000-0000-000000000001"], "action": {"type": "update_fields", "fields": {"company": "Bulk Co", "city": "Utrecht"}}}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Fields updated"
+expect_body "$RESPONSE" '"failed":1' "Unknown contact reported as failed"
+expect_body "$RESPONSE" '"code":"not_found","contact_id":"00000000-0000-0000-0000-000000000001"' "Failure carries its reason"
+expect_body "$RESPONSE" '"succeeded":2' "Existing contacts updated"
+expect_body "$(auth_request "$TOKEN" GET "/contacts/$SECOND_ID")" '"company":"Bulk Co"' "Company set"
+
+expect_body "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "change_lead_status", "lead_status": "qualified"}}')" '"succeeded":2' "Lead status changed"
+expect_body "$(auth_request "$TOKEN" GET "/contacts/$FIRST_ID")" '"lead_status":"qualified"' "Lead status stored"
+
+expect_body "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "set_custom_fields", "custom_fields": {"'$FIELD_NAME'": "bulk value"}}}')" '"succeeded":2' "Custom field set"
+expect_body "$(auth_request "$TOKEN" GET "/contacts/$SECOND_ID")" '"'$FIELD_NAME'":"bulk value"' "Custom value stored"
+
+expect_body "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "change_owner", "owner_id": "'$ADMIN_ID'"}}')" '"succeeded":2' "Owner changed"
+
+expect_body "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "add_tags", "tag_ids": ["'$TAG_ID'"]}}')" '"succeeded":2' "Tag added"
+expect_body "$(auth_request "$TOKEN" GET "/contacts/$FIRST_ID/tags")" "$TAG_ID" "Tag assigned"
+
+RESPONSE=$(bulk_request "$TOKEN" '{"filter": {"logic": "and", "conditions": [{"type": "tags", "tags": ["Bulk Tag '$TIMESTAMP'"]}]}, "action": {"type": "remove_tags", "tag_ids": ["'$TAG_ID'"]}}')
+expect_body "$RESPONSE" '"succeeded":2,"total":2' "Tag removed from contacts selected by filter"
+expect_status "$(auth_request "$TOKEN" GET "/contacts/$FIRST_ID/tags" | grep -c "$TAG_ID")" "0" "Tag unassigned"
+
+# Step 6: Delete and restore
+echo ""
+echo "📝 Step 6: Deleting and restoring..."
+expect_body "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "delete"}}')" '"succeeded":2' "Contacts deleted"
+expect_status "$(auth_request "$TOKEN" GET "/contacts/$FIRST_ID" | tail -1)" "404" "Deleted contact hidden"
+expect_body "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "restore"}, "dry_run": true}')" '"affected":2' "Deleted contacts can be restored"
+expect_body "$(bulk_request "$TOKEN" '{"ids": ['"$IDS"'], "action": {"type": "restore"}}')" '"succeeded":2' "Contacts restored"
+expect_body "$(auth_request "$TOKEN" GET "/contacts/$FIRST_ID")" '"is_active":true' "Restored contact visible"
+expect_body "$(bulk_request "$TOKEN" '{"ids": ["'$FIRST_ID'"], "action": {"type": "restore"}}')" '"code":"conflict"' "Active contact cannot be restored"
+
+# Step 7: Per-contact permissions of a marketing user (contacts:bulk_update, contacts:update_own)
+echo ""
+echo "📝 Step 7: Checking permissions..."
+MARKETER_EMAIL="bulk.marketer.$TIMESTAMP@example.com"
+MARKETER_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Bulk Marketer", "email": "'$MARKETER_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+MEMBERSHIP_ID=$(auth_request "$TOKEN" POST "/user-organizations" '{"user_id": "'$MARKETER_ID'", "org_id": "'$ORG_ID'", "role_name": "marketing_user"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+MARKETER_TOKEN=$(login "$MARKETER_EMAIL")
+MARKETER_CONTACT_ID=$(create_contact "$MARKETER_TOKEN" marketer)
+
+RESPONSE=$(bulk_request "$MARKETER_TOKEN" '{"ids": ["'$FIRST_ID'", "'$MARKETER_CONTACT_ID'"], "action": {"type": "change_lead_status", "lead_status": "contacted"}}')
+expect_body "$RESPONSE" '"code":"forbidden","contact_id":"'$FIRST_ID'"' "Contact owned by someone else refused"
+expect_body "$RESPONSE" '"contact_id":"'$MARKETER_CONTACT_ID'","status":"succeeded"' "Own contact updated"
+expect_body "$(bulk_request "$MARKETER_TOKEN" '{"filter": {"logic": "and", "conditions": []}, "action": {"type": "change_lead_status", "lead_status": "contacted"}, "dry_run": true}')" '"affected":1,"matched":1' "Filter only selects own contacts"
+expect_status "$(bulk_request "$MARKETER_TOKEN" '{"ids": ["'$MARKETER_CONTACT_ID'"], "action": {"type": "delete"}}' | tail -1)" "403" "Delete needs contacts:delete"
+expect_status "$(bulk_request "$MARKETER_TOKEN" '{"ids": ["'$MARKETER_CONTACT_ID'"], "action": {"type": "change_owner", "owner_id": "'$ADMIN_ID'"}}' | tail -1)" "403" "Owner change needs contacts:assign_owner"
+
+# Step 8: Large selections are queued
+echo ""
+echo "📝 Step 8: Queueing a large selection..."
+psql "$DATABASE_URL" -q -c "INSERT INTO contacts (id, org_id, first_name, last_name, email, company, owner_id)
+  SELECT gen_random_uuid(), '$ORG_ID', 'Batch', 'Contact ' || n, 'batch.$TIMESTAMP.' || n || '@example.com', 'Batch $TIMESTAMP', '$ADMIN_ID'
+  FROM generate_series(1, 501) AS n"
+BATCH_FILTER='{"logic": "and", "conditions": [{"type": "condition", "field": "company", "operator": "equals", "value": "Batch '$TIMESTAMP'"}]}'
+
+expect_body "$(bulk_request "$TOKEN" '{"filter": '"$BATCH_FILTER"', "action": {"type": "change_lead_status", "lead_status": "proposal"}, "dry_run": true}')" '"matched":501,"queued":true' "Dry run reports the job fallback"
+
+RESPONSE=$(bulk_request "$TOKEN" '{"filter": '"$BATCH_FILTER"', "action": {"type": "change_lead_status", "lead_status": "proposal"}}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "202" "Large selection queued"
+JOB_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+expect_body "$(auth_request "$TOKEN" GET "/contacts/bulk/$JOB_ID")" '"status":"queued"' "Job waits for the worker"
+
+DATABASE_URL="$DATABASE_URL" cargo run -q --bin run_contact_bulk_jobs > /dev/null 2>&1
+
+RESPONSE=$(auth_request "$TOKEN" GET "/contacts/bulk/$JOB_ID")
+expect_body "$RESPONSE" '"status":"completed"' "Job completed"
+expect_body "$RESPONSE" '"succeeded":501' "Every selected contact changed"
+UNCHANGED=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM contacts WHERE company = 'Batch $TIMESTAMP' AND lead_status <> 'proposal'")
+expect_status "$UNCHANGED" "0" "Lead status stored by the worker"
+expect_status "$(auth_request "$MARKETER_TOKEN" GET "/contacts/bulk/00000000-0000-0000-0000-000000000001" | tail -1)" "404" "Unknown job"
+
+# Step 9: Cleanup
+echo ""
+echo "📝 Step 9: Cleaning up..."
+psql "$DATABASE_URL" -q -c "DELETE FROM contacts WHERE company = 'Batch $TIMESTAMP'"
+psql "$DATABASE_URL" -q -c "DELETE FROM contacts WHERE id IN ('$FIRST_ID', '$SECOND_ID', '$MARKETER_CONTACT_ID')"
+curl -s -o /dev/null -X DELETE "$BASE_URL/tags/$TAG_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/custom-fields/contact/$FIELD_ID?force=true" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/user-organizations/$MEMBERSHIP_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$MARKETER_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Bulk Contact Operations Test Complete!"
@@ -339,7 +339,7 @@ UNAUTHORIZED_RESPONSE_BODY=$(echo "$UNAUTHORIZED_FILTER_RESPONSE" | sed 's/HTTP_
 
 echo "Unauthorized Filter HTTP Status: $UNAUTHORIZED_HTTP_STATUS"
 
-if [ "$UNAUTHORIZED_HTTP_STATUS" = "400" ] || [ "$UNAUTHORIZED_HTTP_STATUS" = "401" ]; then
+if [ "$UNAUTHORIZED_HTTP_STATUS" = "401" ]; then
     echo "✅ Unauthorized access properly rejected"
 else
     echo "❌ Unauthorized access should return 400/401, got: $UNAUTHORIZED_HTTP_STATUS"
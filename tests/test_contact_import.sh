@@ -0,0 +1,154 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# Queued imports are run by the job worker against the same database
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+WORK_DIR=$(mktemp -d)
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Contact CSV Import..."
+
+# Upload a CSV file with extra form parts, printing the response body followed by the HTTP status
+import_request() {
+    local token=$1 file=$2
+    shift 2
+    curl -s -w "\n%{http_code}" -X POST "$BASE_URL/contacts/import" \
+      -H "Authorization: Bearer $token" \
+      -F "file=@$file;type=text/csv" "$@"
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+DOMAIN="import$TIMESTAMP.example.com"
+
+# Step 2: A custom field and a file mixing new, invalid and duplicate rows
+echo ""
+echo "📝 Step 2: Preparing the file..."
+FIELD_NAME="import_score_$TIMESTAMP"
+FIELD_ID=$(auth_request "$TOKEN" POST "/custom-fields" '{"module": "contact", "label": "Import Score", "field_name": "'$FIELD_NAME'", "field_type": "number"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+EXISTING_ID=$(auth_request "$TOKEN" POST "/contacts" '{"first_name": "Existing", "last_name": "Contact", "email": "existing@'$DOMAIN'", "company": "Old Co"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+cat > "$WORK_DIR/contacts.csv" <<EOF
+First Name,Last Name,E-mail Address,Company,Score,Internal Id
+Ada,Lovelace,ada@$DOMAIN,Engines Ltd,42,A1
+Grace,Hopper,grace@$DOMAIN,,7,A2
+Bad,Email,not-an-email,,,A3
+Bad,Score,score@$DOMAIN,,lots,A4
+Existing,Updated,existing@$DOMAIN,New Co,,A5
+EOF
+MAPPING='{"E-mail Address": "email", "Score": "'$FIELD_NAME'", "Internal Id": ""}'
+echo "✅ File ready"
+
+# Step 3: Rejected uploads
+echo ""
+echo "📝 Step 3: Rejecting invalid uploads..."
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv")
+expect_status "$(echo "$RESPONSE" | tail -1)" "400" "Email column must be mapped"
+expect_body "$RESPONSE" "No column is mapped to email" "Missing required field named"
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv" -F 'mapping={"E-mail Address": "email", "Score": "favourite_colour"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "400" "Unknown target field rejected"
+expect_body "$RESPONSE" "unknown_field" "Unknown field code"
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv" -F 'mapping={"Mail": "email"}')
+expect_body "$RESPONSE" "unknown_column" "Unknown column rejected"
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv" -F "mapping=$MAPPING" -F "duplicates=merge")
+expect_status "$(echo "$RESPONSE" | tail -1)" "400" "Unknown duplicate strategy rejected"
+printf 'first_name,last_name,email\n' > "$WORK_DIR/empty.csv"
+expect_status "$(import_request "$TOKEN" "$WORK_DIR/empty.csv" | tail -1)" "400" "File without rows rejected"
+
+# Step 4: Dry run
+echo ""
+echo "📝 Step 4: Dry run..."
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv" -F "mapping=$MAPPING" -F "duplicates=update" -F "dry_run=true")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Dry run answered"
+expect_body "$RESPONSE" '"created":2' "Dry run counts new contacts"
+expect_body "$RESPONSE" '"updated":1' "Dry run counts the duplicate as updated"
+expect_body "$RESPONSE" '"failed":2' "Dry run counts invalid rows"
+expect_body "$RESPONSE" '"ignored_columns":\["Internal Id"\]' "Ignored column reported"
+expect_body "$RESPONSE" '"First Name":"first_name"' "Header matched by name"
+expect_body "$RESPONSE" '"row":4' "Invalid email reported on its line"
+expect_body "$RESPONSE" "custom_fields.$FIELD_NAME" "Invalid custom value reported"
+CREATED=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM contacts WHERE email LIKE '%@$DOMAIN' AND email <> 'existing@$DOMAIN'")
+expect_status "$CREATED" "0" "Dry run wrote nothing"
+
+# Step 5: Duplicate strategies
+echo ""
+echo "📝 Step 5: Importing with each duplicate strategy..."
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv" -F "mapping=$MAPPING")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Import answered"
+expect_body "$RESPONSE" '"success":false' "Failed rows reported"
+expect_body "$RESPONSE" '"created":2' "New contacts created"
+expect_body "$RESPONSE" '"skipped":1' "Duplicate skipped by default"
+SCORE=$(psql "$DATABASE_URL" -At -c "SELECT v.value FROM contact_custom_values v JOIN contacts c ON c.id = v.contact_id WHERE c.email = 'ada@$DOMAIN' AND v.custom_field_id = '$FIELD_ID'")
+expect_status "$SCORE" "42" "Custom field value imported"
+
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv" -F "mapping=$MAPPING" -F "duplicates=create")
+expect_body "$RESPONSE" '"created":0' "Nothing new to create"
+expect_body "$RESPONSE" '"failed":5' "Duplicates fail with the create strategy"
+expect_body "$RESPONSE" '"code":"conflict"' "Duplicate reported as a conflict"
+
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/contacts.csv" -F "mapping=$MAPPING" -F "duplicates=update")
+expect_body "$RESPONSE" '"updated":3' "Duplicates updated"
+COMPANY=$(psql "$DATABASE_URL" -At -c "SELECT company || ' ' || last_name FROM contacts WHERE id = '$EXISTING_ID'")
+expect_status "$COMPANY" "New Co Updated" "Existing contact overwritten"
+COMPANY=$(psql "$DATABASE_URL" -At -c "SELECT company FROM contacts WHERE email = 'ada@$DOMAIN'")
+expect_status "$COMPANY" "Engines Ltd" "Values kept where the row has them"
+
+# Step 6: Large files are queued
+echo ""
+echo "📝 Step 6: Queueing a large file..."
+{
+    echo "first_name,last_name,email"
+    for i in $(seq 1 1100); do
+        echo "Batch,Row$i,batch$i@$DOMAIN"
+    done
+    echo "Batch,Broken,broken-email"
+} > "$WORK_DIR/large.csv"
+RESPONSE=$(import_request "$TOKEN" "$WORK_DIR/large.csv")
+expect_status "$(echo "$RESPONSE" | tail -1)" "202" "Large file queued"
+expect_body "$RESPONSE" '"total_rows":1101' "Rows counted up front"
+JOB_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+expect_body "$(auth_request "$TOKEN" GET "/contacts/import/$JOB_ID")" '"status":"queued"' "Job waits for the worker"
+
+DATABASE_URL="$DATABASE_URL" cargo run -q --bin run_contact_import_jobs > /dev/null 2>&1
+
+RESPONSE=$(auth_request "$TOKEN" GET "/contacts/import/$JOB_ID")
+expect_body "$RESPONSE" '"status":"completed"' "Job completed"
+expect_body "$RESPONSE" '"created_rows":1100' "Every valid row created"
+expect_body "$RESPONSE" '"failed_rows":1' "Invalid row counted"
+expect_body "$RESPONSE" '"processed_rows":1101' "Progress reached the end"
+
+RESPONSE=$(curl -s -i "$BASE_URL/contacts/import/$JOB_ID/errors" -H "Authorization: Bearer $TOKEN")
+expect_body "$RESPONSE" "text/csv" "Errors served as CSV"
+expect_body "$RESPONSE" "^row,first_name,last_name,email,error" "Error CSV keeps the original columns"
+expect_body "$RESPONSE" "^1102,Batch,Broken,broken-email,email: Invalid email format" "Failed row listed with its line and error"
+expect_status "$(auth_request "$TOKEN" GET "/contacts/import/00000000-0000-0000-0000-000000000001" | tail -1)" "404" "Unknown job"
+
+# Step 7: Cleanup
+echo ""
+echo "📝 Step 7: Cleaning up..."
+psql "$DATABASE_URL" -q -c "DELETE FROM contacts WHERE email LIKE '%@$DOMAIN'"
+psql "$DATABASE_URL" -q -c "DELETE FROM contact_import_jobs WHERE id = '$JOB_ID'"
+curl -s -o /dev/null -X DELETE "$BASE_URL/custom-fields/contact/$FIELD_ID?force=true" -H "Authorization: Bearer $TOKEN"
+rm -rf "$WORK_DIR"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Contact CSV Import Test Complete!"
@@ -0,0 +1,110 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Ownership-Scoped Contact Permissions..."
+
+# Send a request as the sales rep, printing only the HTTP status
+rep_request() {
+    curl -s -o /dev/null -w "%{http_code}" -X "$1" "$BASE_URL/contacts/$2" \
+      -H "Authorization: Bearer $REP_TOKEN" \
+      -H "X-Organization-Id: $ORG_ID" \
+      -H "Content-Type: application/json" \
+      ${3:+-d "$3"}
+}
+
+# Create a contact with the given token, printing its id
+create_contact() {
+    curl -s -X POST "$BASE_URL/contacts" \
+      -H "Authorization: Bearer $1" \
+      -H "X-Organization-Id: $ORG_ID" \
+      -H "Content-Type: application/json" \
+      -d '{"first_name": "Owned", "last_name": "Contact", "email": "owned.'$TIMESTAMP'.'$2'@example.com"}' \
+      | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+# Step 2: Create a sales rep (contacts:update_own, contacts:delete_own) in the admin's organization
+echo ""
+echo "📝 Step 2: Creating a sales rep..."
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+REP_EMAIL="owner.rep.$TIMESTAMP@example.com"
+REP_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Sales Rep", "email": "'$REP_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+MEMBERSHIP_ID=$(curl -s -X POST "$BASE_URL/user-organizations" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"user_id": "'$REP_ID'", "org_id": "'$ORG_ID'", "role_name": "sales_rep"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+REP_TOKEN=$(login "$REP_EMAIL")
+
+if [ -n "$MEMBERSHIP_ID" ] && [ -n "$REP_TOKEN" ]; then
+    echo "✅ Sales rep $REP_ID added to organization $ORG_ID"
+else
+    echo "❌ Failed to set up the sales rep"
+    exit 1
+fi
+
+# Step 3: One contact owned by the admin, one by the sales rep
+echo ""
+echo "📝 Step 3: Creating contacts..."
+ADMIN_CONTACT_ID=$(create_contact "$TOKEN" admin)
+REP_CONTACT_ID=$(create_contact "$REP_TOKEN" rep)
+
+if [ -n "$ADMIN_CONTACT_ID" ] && [ -n "$REP_CONTACT_ID" ]; then
+    echo "✅ Created $ADMIN_CONTACT_ID (admin) and $REP_CONTACT_ID (sales rep)"
+else
+    echo "❌ Failed to create contacts"
+    exit 1
+fi
+
+# Step 4: The sales rep can read every contact (contacts:read)
+echo ""
+echo "📝 Step 4: Reading contacts as the sales rep..."
+expect_status "$(rep_request GET "$ADMIN_CONTACT_ID")" "200" "Read another user's contact"
+
+# Step 5: Updates are limited to owned contacts
+echo ""
+echo "📝 Step 5: Updating contacts as the sales rep..."
+expect_status "$(rep_request PATCH "$REP_CONTACT_ID" '{"job_title": "Buyer"}')" "200" "Patched own contact"
+expect_status "$(rep_request PATCH "$ADMIN_CONTACT_ID" '{"job_title": "Buyer"}')" "403" "Patch of another user's contact rejected"
+expect_status "$(rep_request PUT "$ADMIN_CONTACT_ID" '{"first_name": "Taken", "last_name": "Over", "email": "taken.'$TIMESTAMP'@example.com"}')" "403" "Update of another user's contact rejected"
+
+# Step 6: Deletes are limited to owned contacts
+echo ""
+echo "📝 Step 6: Deleting contacts as the sales rep..."
+expect_status "$(rep_request DELETE "$ADMIN_CONTACT_ID")" "403" "Delete of another user's contact rejected"
+expect_status "$(rep_request DELETE "$REP_CONTACT_ID")" "204" "Deleted own contact"
+
+# Step 7: Cleanup
+echo ""
+echo "📝 Step 7: Cleaning up..."
+curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$ADMIN_CONTACT_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/user-organizations/$MEMBERSHIP_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$REP_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Contact Ownership Test Complete!"
@@ -0,0 +1,126 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+echo "🧪 Testing Contact Tags API..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+LOGIN_RESPONSE=$(curl -s -X POST "$BASE_URL/users/login" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "test@example.com", "password": "password123"}')
+
+TOKEN=$(echo "$LOGIN_RESPONSE" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+# Step 2: Create a contact and a tag
+echo ""
+echo "📝 Step 2: Creating test contact and tag..."
+TIMESTAMP=$(date +%s)
+
+CONTACT_RESPONSE=$(curl -s -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{
+    "first_name": "Tag",
+    "last_name": "Test",
+    "email": "tag.test.'$TIMESTAMP'@example.com"
+  }')
+
+CONTACT_ID=$(echo "$CONTACT_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+TAG_RESPONSE=$(curl -s -w "HTTP_STATUS:%{http_code}" -X POST "$BASE_URL/tags" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "tag-test-'$TIMESTAMP'", "color": "#00aa55"}')
+
+TAG_STATUS=$(echo "$TAG_RESPONSE" | grep -o "HTTP_STATUS:[0-9]*" | cut -d':' -f2)
+TAG_BODY=$(echo "$TAG_RESPONSE" | sed 's/HTTP_STATUS:[0-9]*$//')
+TAG_ID=$(echo "$TAG_BODY" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ -z "$CONTACT_ID" ] || [ "$TAG_STATUS" != "201" ]; then
+    echo "❌ Setup failed (contact: $CONTACT_ID, tag status: $TAG_STATUS)"
+    exit 1
+fi
+
+echo "✅ Created contact $CONTACT_ID and tag $TAG_ID"
+
+# Step 3: Duplicate tag name and invalid color are rejected
+echo ""
+echo "📝 Step 3: Validating tag input..."
+DUP_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/tags" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "tag-test-'$TIMESTAMP'"}')
+
+COLOR_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL/tags" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "tag-color-'$TIMESTAMP'", "color": "green"}')
+
+if [ "$DUP_STATUS" = "409" ] && [ "$COLOR_STATUS" = "400" ]; then
+    echo "✅ Duplicate name and invalid color rejected"
+else
+    echo "❌ Expected 409/400, got duplicate: $DUP_STATUS, color: $COLOR_STATUS"
+    exit 1
+fi
+
+# Step 4: Assign the tag to the contact
+echo ""
+echo "📝 Step 4: Assigning tag to contact..."
+ASSIGN_RESPONSE=$(curl -s -w "HTTP_STATUS:%{http_code}" -X POST "$BASE_URL/contacts/$CONTACT_ID/tags" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"tag_ids": ["'$TAG_ID'"]}')
+
+ASSIGN_STATUS=$(echo "$ASSIGN_RESPONSE" | grep -o "HTTP_STATUS:[0-9]*" | cut -d':' -f2)
+
+if [ "$ASSIGN_STATUS" = "200" ] && echo "$ASSIGN_RESPONSE" | grep -q "$TAG_ID"; then
+    echo "✅ Tag assigned"
+else
+    echo "❌ Tag assignment failed: $ASSIGN_STATUS"
+    exit 1
+fi
+
+# Step 5: Tag list reports the contact count
+echo ""
+echo "📝 Step 5: Checking tag counts..."
+LIST_RESPONSE=$(curl -s -X GET "$BASE_URL/tags" -H "Authorization: Bearer $TOKEN")
+
+if echo "$LIST_RESPONSE" | grep -o '{[^{}]*"id":"'$TAG_ID'"[^{}]*}' | grep -q '"contact_count":1'; then
+    echo "✅ Tag count is 1"
+else
+    echo "❌ Unexpected tag list: $LIST_RESPONSE"
+    exit 1
+fi
+
+# Step 6: Unassign the tag
+echo ""
+echo "📝 Step 6: Removing tag from contact..."
+UNASSIGN_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X DELETE "$BASE_URL/contacts/$CONTACT_ID/tags/$TAG_ID" \
+  -H "Authorization: Bearer $TOKEN")
+
+if [ "$UNASSIGN_STATUS" = "204" ]; then
+    echo "✅ Tag removed"
+else
+    echo "❌ Tag removal failed: $UNASSIGN_STATUS"
+    exit 1
+fi
+
+# Step 7: Cleanup
+echo ""
+echo "📝 Step 7: Cleaning up..."
+curl -s -o /dev/null -X DELETE "$BASE_URL/tags/$TAG_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$CONTACT_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Contact Tags API Test Complete!"
@@ -60,8 +60,8 @@ INVALID_EMAIL_RESPONSE_BODY=$(echo "$INVALID_EMAIL_RESPONSE" | sed 's/HTTP_STATU
 echo "Invalid Email HTTP Status: $INVALID_EMAIL_HTTP_STATUS"
 echo "Invalid Email Response: $INVALID_EMAIL_RESPONSE_BODY"
 
-if [ "$INVALID_EMAIL_HTTP_STATUS" = "400" ]; then
-    echo "✅ Invalid email format properly rejected"
+if [ "$INVALID_EMAIL_HTTP_STATUS" = "400" ] && echo "$INVALID_EMAIL_RESPONSE_BODY" | grep -q '"fields":{"email":\[{"code":"email"'; then
+    echo "✅ Invalid email format properly rejected with an email field error"
 else
     echo "❌ Invalid email format should return 400, got: $INVALID_EMAIL_HTTP_STATUS"
 fi
@@ -82,8 +82,9 @@ EMPTY_NAME_RESPONSE_BODY=$(echo "$EMPTY_NAME_RESPONSE" | sed 's/HTTP_STATUS:[0-9
 echo "Empty Name HTTP Status: $EMPTY_NAME_HTTP_STATUS"
 echo "Empty Name Response: $EMPTY_NAME_RESPONSE_BODY"
 
-if [ "$EMPTY_NAME_HTTP_STATUS" = "400" ]; then
-    echo "✅ Empty first name properly rejected"
+if [ "$EMPTY_NAME_HTTP_STATUS" = "400" ] && echo "$EMPTY_NAME_RESPONSE_BODY" | grep -q '"code":"validation_failed"' \
+    && echo "$EMPTY_NAME_RESPONSE_BODY" | grep -q '"first_name":\[{"code":"length"'; then
+    echo "✅ Empty first name properly rejected with a first_name field error"
 else
     echo "❌ Empty first name should return 400, got: $EMPTY_NAME_HTTP_STATUS"
 fi
@@ -0,0 +1,176 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+echo "🧪 Testing Custom Field Management API..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+LOGIN_RESPONSE=$(curl -s -X POST "$BASE_URL/users/login" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "test@example.com", "password": "password123"}')
+
+TOKEN=$(echo "$LOGIN_RESPONSE" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+# Step 2: Create a select field
+echo ""
+echo "📝 Step 2: Creating select field..."
+TIMESTAMP=$(date +%s)
+FIELD_NAME="tier_$TIMESTAMP"
+
+FIELD_RESPONSE=$(curl -s -w "HTTP_STATUS:%{http_code}" -X POST "$BASE_URL/custom-fields" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{
+    "module": "contact",
+    "label": "Tier",
+    "field_name": "'$FIELD_NAME'",
+    "field_type": "select",
+    "options": ["Gold", "Silver", "Bronze"]
+  }')
+
+FIELD_STATUS=$(echo "$FIELD_RESPONSE" | grep -o "HTTP_STATUS:[0-9]*" | cut -d':' -f2)
+FIELD_BODY=$(echo "$FIELD_RESPONSE" | sed 's/HTTP_STATUS:[0-9]*$//')
+FIELD_ID=$(echo "$FIELD_BODY" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ "$FIELD_STATUS" = "201" ] && [ -n "$FIELD_ID" ]; then
+    echo "✅ Created field $FIELD_ID"
+else
+    echo "❌ Field creation failed: $FIELD_BODY"
+    exit 1
+fi
+
+# Step 3: Store a value on a contact
+echo ""
+echo "📝 Step 3: Creating contact with a Silver tier..."
+CONTACT_RESPONSE=$(curl -s -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{
+    "first_name": "Custom",
+    "last_name": "Field",
+    "email": "custom.field.'$TIMESTAMP'@example.com",
+    "custom_fields": {"'$FIELD_NAME'": "Silver"}
+  }')
+
+CONTACT_ID=$(echo "$CONTACT_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if echo "$CONTACT_RESPONSE" | grep -q '"'$FIELD_NAME'":"Silver"'; then
+    echo "✅ Contact $CONTACT_ID stores Silver"
+else
+    echo "❌ Custom value not stored: $CONTACT_RESPONSE"
+    exit 1
+fi
+
+# Step 3b: Values outside the options are rejected per field
+echo ""
+echo "📝 Step 3b: Rejecting an invalid option..."
+INVALID_RESPONSE=$(curl -s -w "HTTP_STATUS:%{http_code}" -X POST "$BASE_URL/contacts" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{
+    "first_name": "Custom",
+    "last_name": "Invalid",
+    "email": "custom.invalid.'$TIMESTAMP'@example.com",
+    "custom_fields": {"'$FIELD_NAME'": "Diamond"}
+  }')
+
+INVALID_STATUS=$(echo "$INVALID_RESPONSE" | grep -o "HTTP_STATUS:[0-9]*" | cut -d':' -f2)
+
+if [ "$INVALID_STATUS" = "400" ] && echo "$INVALID_RESPONSE" | grep -q '"custom_fields.'$FIELD_NAME'":\[{"code":"invalid_option"'; then
+    echo "✅ Invalid option rejected with a field error"
+else
+    echo "❌ Expected 400 invalid_option, got: $INVALID_RESPONSE"
+    exit 1
+fi
+
+# Step 4: Removing an option in use requires a mapping
+echo ""
+echo "📝 Step 4: Replacing options..."
+UNMAPPED_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X PUT "$BASE_URL/custom-fields/contact/$FIELD_ID/options" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"options": ["Gold", "Platinum"]}')
+
+OPTIONS_RESPONSE=$(curl -s -X PUT "$BASE_URL/custom-fields/contact/$FIELD_ID/options" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"options": ["Gold", "Platinum"], "value_mapping": {"Silver": "Platinum"}}')
+
+CONTACT_AFTER=$(curl -s -X GET "$BASE_URL/contacts/$CONTACT_ID" -H "Authorization: Bearer $TOKEN")
+
+if [ "$UNMAPPED_STATUS" = "400" ] && echo "$OPTIONS_RESPONSE" | grep -q '"migrated_values":1' \
+    && echo "$CONTACT_AFTER" | grep -q '"'$FIELD_NAME'":"Platinum"'; then
+    echo "✅ Silver values migrated to Platinum"
+else
+    echo "❌ Option migration failed (unmapped: $UNMAPPED_STATUS, response: $OPTIONS_RESPONSE)"
+    exit 1
+fi
+
+# Step 5: Update and reorder
+echo ""
+echo "📝 Step 5: Updating and reordering..."
+UPDATE_RESPONSE=$(curl -s -X PUT "$BASE_URL/custom-fields/contact/$FIELD_ID" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"label": "Customer Tier", "help_text": "Account tier"}')
+
+REORDER_RESPONSE=$(curl -s -X PUT "$BASE_URL/custom-fields/contact/order" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"field_ids": ["'$FIELD_ID'"]}')
+
+if echo "$UPDATE_RESPONSE" | grep -q '"label":"Customer Tier"' \
+    && echo "$REORDER_RESPONSE" | grep -q '"display_order":1,"field_name":"'$FIELD_NAME'"'; then
+    echo "✅ Field updated and moved to position 1"
+else
+    echo "❌ Update/reorder failed (update: $UPDATE_RESPONSE, reorder: $REORDER_RESPONSE)"
+    exit 1
+fi
+
+# Step 6: Deactivate hides the field from the default listing
+echo ""
+echo "📝 Step 6: Deactivating field..."
+curl -s -o /dev/null -X POST "$BASE_URL/custom-fields/contact/$FIELD_ID/deactivate" -H "Authorization: Bearer $TOKEN"
+ACTIVE_LIST=$(curl -s -X GET "$BASE_URL/custom-fields/contact" -H "Authorization: Bearer $TOKEN")
+ALL_LIST=$(curl -s -X GET "$BASE_URL/custom-fields/contact?include_inactive=true" -H "Authorization: Bearer $TOKEN")
+
+if ! echo "$ACTIVE_LIST" | grep -q "$FIELD_ID" && echo "$ALL_LIST" | grep -q "$FIELD_ID"; then
+    echo "✅ Field deactivated"
+else
+    echo "❌ Deactivation failed"
+    exit 1
+fi
+
+# Step 7: Hard delete needs force while values exist
+echo ""
+echo "📝 Step 7: Deleting field..."
+SAFE_DELETE_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X DELETE "$BASE_URL/custom-fields/contact/$FIELD_ID" \
+  -H "Authorization: Bearer $TOKEN")
+FORCE_DELETE_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X DELETE "$BASE_URL/custom-fields/contact/$FIELD_ID?force=true" \
+  -H "Authorization: Bearer $TOKEN")
+
+if [ "$SAFE_DELETE_STATUS" = "400" ] && [ "$FORCE_DELETE_STATUS" = "204" ]; then
+    echo "✅ Delete refused without force and succeeded with it"
+else
+    echo "❌ Expected 400/204, got $SAFE_DELETE_STATUS/$FORCE_DELETE_STATUS"
+    exit 1
+fi
+
+# Step 8: Cleanup
+echo ""
+echo "📝 Step 8: Cleaning up..."
+curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$CONTACT_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Custom Field Management API Test Complete!"
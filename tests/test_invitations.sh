@@ -0,0 +1,197 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# Invitation tokens only leave the server by email, so they are read back from the outbox
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Organization Invitations..."
+
+# Token from the link in the latest invitation email sent to an address
+latest_invitation_token() {
+    psql "$DATABASE_URL" -At -c "SELECT payload->>'accept_url' FROM email_outbox WHERE recipient = '$1' ORDER BY created_at DESC LIMIT 1" \
+      | sed 's#.*/invitations/##'
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+INVITATIONS_URL="/organizations/$ORG_ID/invitations"
+
+# Step 2: Invite an email without an account
+echo ""
+echo "📝 Step 2: Inviting a new email..."
+INVITEE_EMAIL="invitee.$TIMESTAMP@example.com"
+RESPONSE=$(admin_request POST "$INVITATIONS_URL" '{"email": "'$INVITEE_EMAIL'", "role_name": "member"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Invitation created"
+INVITATION_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if echo "$RESPONSE" | grep -q '"token_hash"'; then
+    echo "❌ Invitation response leaks the token hash"
+    exit 1
+fi
+
+expect_status "$(admin_request POST "$INVITATIONS_URL" '{"email": "'$INVITEE_EMAIL'", "role_name": "member"}' | tail -1)" "409" "Duplicate pending invitation rejected"
+expect_status "$(admin_request POST "$INVITATIONS_URL" '{"email": "not-an-email", "role_name": "member"}' | tail -1)" "400" "Invalid email rejected"
+expect_status "$(admin_request POST "$INVITATIONS_URL" '{"email": "other.'$TIMESTAMP'@example.com", "role_name": "no_such_role"}' | tail -1)" "404" "Unknown role rejected"
+
+RESPONSE=$(admin_request GET "$INVITATIONS_URL?status=pending")
+if echo "$RESPONSE" | grep -q "\"email\":\"$INVITEE_EMAIL\""; then
+    echo "✅ Pending invitation listed"
+else
+    echo "❌ Invitation missing from list: $RESPONSE"
+    exit 1
+fi
+
+# Step 3: The invitation email is queued with a working link
+echo ""
+echo "📝 Step 3: Reading the invitation link..."
+FIRST_TOKEN=$(latest_invitation_token "$INVITEE_EMAIL")
+if [ -z "$FIRST_TOKEN" ]; then
+    echo "❌ No invitation email queued for $INVITEE_EMAIL"
+    exit 1
+fi
+echo "✅ Invitation email queued"
+
+RESPONSE=$(public_request GET "/invitations/$FIRST_TOKEN")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Invitation details loaded"
+if echo "$RESPONSE" | grep -q '"account_exists":false'; then
+    echo "✅ Invitee has no account yet"
+else
+    echo "❌ Unexpected invitation details: $RESPONSE"
+    exit 1
+fi
+
+expect_status "$(public_request GET "/invitations/not-a-real-token" | tail -1)" "404" "Unknown token rejected"
+
+# Step 4: Resending rotates the token
+echo ""
+echo "📝 Step 4: Resending the invitation..."
+expect_status "$(admin_request POST "$INVITATIONS_URL/$INVITATION_ID/resend" | tail -1)" "200" "Invitation resent"
+INVITATION_TOKEN=$(latest_invitation_token "$INVITEE_EMAIL")
+expect_status "$(public_request GET "/invitations/$FIRST_TOKEN" | tail -1)" "404" "Previous link no longer works"
+
+# Step 5: Accepting creates the account and the membership
+echo ""
+echo "📝 Step 5: Accepting the invitation..."
+expect_status "$(public_request POST "/invitations/$INVITATION_TOKEN/accept" '{}' | tail -1)" "400" "Name and password required for a new account"
+
+RESPONSE=$(public_request POST "/invitations/$INVITATION_TOKEN/accept" '{"name": "Invited User", "password": "password123"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Invitation accepted"
+INVITEE_TOKEN=$(echo "$RESPONSE" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+INVITEE_ID=$(echo "$RESPONSE" | grep -o '"user_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+MEMBERSHIP_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if echo "$RESPONSE" | grep -q '"account_created":true' && echo "$RESPONSE" | grep -q '"status":"active"'; then
+    echo "✅ Account created with an active membership"
+else
+    echo "❌ Unexpected accept response: $RESPONSE"
+    exit 1
+fi
+
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/me/permissions" \
+  -H "Authorization: Bearer $INVITEE_TOKEN" -H "X-Organization-Id: $ORG_ID")
+expect_status "$STATUS" "200" "New member can act in the organization"
+
+expect_status "$(public_request POST "/invitations/$INVITATION_TOKEN/accept" '{}' | tail -1)" "409" "Invitation cannot be accepted twice"
+expect_status "$(admin_request POST "$INVITATIONS_URL" '{"email": "'$INVITEE_EMAIL'", "role_name": "member"}' | tail -1)" "409" "Existing member cannot be invited"
+
+# Step 6: Declining
+echo ""
+echo "📝 Step 6: Declining an invitation..."
+DECLINE_EMAIL="decliner.$TIMESTAMP@example.com"
+admin_request POST "$INVITATIONS_URL" '{"email": "'$DECLINE_EMAIL'", "role_name": "viewer"}' > /dev/null
+DECLINE_TOKEN=$(latest_invitation_token "$DECLINE_EMAIL")
+expect_status "$(public_request POST "/invitations/$DECLINE_TOKEN/decline" | tail -1)" "204" "Invitation declined"
+expect_status "$(public_request POST "/invitations/$DECLINE_TOKEN/accept" '{"name": "Too Late", "password": "password123"}' | tail -1)" "409" "Declined invitation cannot be accepted"
+
+# Step 7: Revoking
+echo ""
+echo "📝 Step 7: Revoking an invitation..."
+REVOKE_EMAIL="revoked.$TIMESTAMP@example.com"
+REVOKE_ID=$(admin_request POST "$INVITATIONS_URL" '{"email": "'$REVOKE_EMAIL'", "role_name": "viewer"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+REVOKE_TOKEN=$(latest_invitation_token "$REVOKE_EMAIL")
+expect_status "$(admin_request DELETE "$INVITATIONS_URL/$REVOKE_ID" | tail -1)" "204" "Invitation revoked"
+expect_status "$(public_request GET "/invitations/$REVOKE_TOKEN" | tail -1)" "409" "Revoked link rejected"
+expect_status "$(admin_request POST "$INVITATIONS_URL/$REVOKE_ID/resend" | tail -1)" "409" "Revoked invitation cannot be resent"
+
+# Step 8: Only members with members:invite manage invitations
+echo ""
+echo "📝 Step 8: Listing invitations as the new member..."
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL$INVITATIONS_URL" -H "Authorization: Bearer $INVITEE_TOKEN")
+expect_status "$STATUS" "403" "Member without members:invite rejected"
+
+# Step 9: Inviters cannot hand out more than they hold
+echo ""
+echo "📝 Step 9: Inviting with roles beyond the inviter's permissions..."
+expect_status "$(admin_request POST "$INVITATIONS_URL" '{"email": "owner.'$TIMESTAMP'@example.com", "role_name": "owner"}' | tail -1)" "403" "Admin cannot invite an owner"
+
+RECRUITER_ROLE="recruiter_$TIMESTAMP"
+RECRUITER_ROLE_ID=$(admin_request POST "/organizations/$ORG_ID/roles" '{"name": "'$RECRUITER_ROLE'", "permissions": ["members:invite", "org:read", "contacts:read", "reports:read", "activities:read"]}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+RECRUITER_EMAIL="recruiter.$TIMESTAMP@example.com"
+RECRUITER_ID=$(public_request POST "/users" '{"name": "Recruiter", "email": "'$RECRUITER_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+RECRUITER_MEMBERSHIP_ID=$(admin_request POST "/user-organizations" '{"user_id": "'$RECRUITER_ID'", "org_id": "'$ORG_ID'", "role_name": "'$RECRUITER_ROLE'"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+RECRUITER_TOKEN=$(public_request POST "/users/login" '{"email": "'$RECRUITER_EMAIL'", "password": "password123"}' \
+  | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+recruiter_invite() {
+    curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL$INVITATIONS_URL" \
+      -H "Authorization: Bearer $RECRUITER_TOKEN" \
+      -H "Content-Type: application/json" \
+      -d '{"email": "'$1'", "role_name": "'$2'"}'
+}
+expect_status "$(recruiter_invite "escalate.$TIMESTAMP@example.com" "admin")" "403" "Role with more permissions rejected"
+expect_status "$(recruiter_invite "viewer.$TIMESTAMP@example.com" "viewer")" "201" "Role within the inviter's permissions accepted"
+
+# Step 10: The invitation and its email are written together
+echo ""
+echo "📝 Step 10: Inviting when the email cannot be queued..."
+UNQUEUED_EMAIL="unqueued.$TIMESTAMP@example.com"
+TRIGGER="outbox_conflict_$TIMESTAMP"
+psql "$DATABASE_URL" -q <<SQL
+CREATE FUNCTION $TRIGGER() RETURNS trigger AS \$\$
+BEGIN
+    IF NEW.recipient = '$UNQUEUED_EMAIL' THEN
+        RAISE unique_violation USING MESSAGE = 'simulated conflicting write';
+    END IF;
+    RETURN NEW;
+END
+\$\$ LANGUAGE plpgsql;
+CREATE TRIGGER $TRIGGER BEFORE INSERT ON email_outbox
+    FOR EACH ROW EXECUTE FUNCTION $TRIGGER();
+SQL
+expect_status "$(admin_request POST "$INVITATIONS_URL" '{"email": "'$UNQUEUED_EMAIL'", "role_name": "member"}' | tail -1)" "409" "Failed email write rejects the invitation"
+expect_status "$(psql "$DATABASE_URL" -At -c "SELECT count(*) FROM organization_invitations WHERE email = '$UNQUEUED_EMAIL'")" "0" "No invitation left without its email"
+psql "$DATABASE_URL" -q -c "DROP TRIGGER $TRIGGER ON email_outbox; DROP FUNCTION $TRIGGER()"
+
+# Step 11: Cleanup
+echo ""
+echo "📝 Step 11: Cleaning up..."
+admin_request DELETE "/user-organizations/$MEMBERSHIP_ID" > /dev/null
+admin_request DELETE "/user-organizations/$RECRUITER_MEMBERSHIP_ID" > /dev/null
+admin_request DELETE "/organizations/$ORG_ID/roles/$RECRUITER_ROLE_ID" > /dev/null
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$RECRUITER_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$INVITEE_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Organization Invitation Test Complete!"
@@ -0,0 +1,86 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+echo "🧪 Testing JWT Signing Keys and JWKS..."
+
+# Decode one base64url JWT segment
+decode_segment() {
+    local segment
+    segment=$(echo -n "$1" | tr '_-' '/+')
+    while [ $(( ${#segment} % 4 )) -ne 0 ]; do segment="$segment="; done
+    echo -n "$segment" | base64 -d 2>/dev/null
+}
+
+# Encode stdin as a base64url JWT segment
+encode_segment() {
+    base64 | tr -d '=\n' | tr '/+' '_-'
+}
+
+# Step 1: The JWKS endpoint is public and lists the published keys
+echo ""
+echo "📝 Step 1: Fetching /.well-known/jwks.json..."
+JWKS_RESPONSE=$(curl -s -w "\n%{http_code}" -X GET "$BASE_URL/.well-known/jwks.json")
+JWKS_STATUS=$(echo "$JWKS_RESPONSE" | tail -1)
+JWKS_BODY=$(echo "$JWKS_RESPONSE" | head -1)
+
+if [ "$JWKS_STATUS" = "200" ] && echo "$JWKS_BODY" | grep -q '"keys":\['; then
+    echo "✅ JWKS published ($(echo "$JWKS_BODY" | grep -o '"kid":"[^"]*"' | wc -l) public keys)"
+else
+    echo "❌ JWKS request failed ($JWKS_STATUS): $JWKS_BODY"
+    exit 1
+fi
+
+if echo "$JWKS_BODY" | grep -q '"k":'; then
+    echo "❌ JWKS leaks a shared secret"
+    exit 1
+fi
+
+# Step 2: Issued tokens carry a kid
+echo ""
+echo "📝 Step 2: Logging in..."
+TOKEN=$(curl -s -X POST "$BASE_URL/users/login" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "test@example.com", "password": "password123"}' | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+HEADER=$(decode_segment "$(echo "$TOKEN" | cut -d. -f1)")
+KID=$(echo "$HEADER" | grep -o '"kid":"[^"]*"' | cut -d'"' -f4)
+
+if [ -n "$KID" ]; then
+    echo "✅ Token signed with key '$KID' ($(echo "$HEADER" | grep -o '"alg":"[^"]*"' | cut -d'"' -f4))"
+else
+    echo "❌ Token header has no kid: $HEADER"
+    exit 1
+fi
+
+# Step 3: Tokens naming an unknown key or no algorithm are rejected
+echo ""
+echo "📝 Step 3: Sending tampered tokens..."
+PAYLOAD=$(echo "$TOKEN" | cut -d. -f2)
+SIGNATURE=$(echo "$TOKEN" | cut -d. -f3)
+
+UNKNOWN_KID_HEADER=$(echo -n "${HEADER/\"kid\":\"$KID\"/\"kid\":\"unknown-key\"}" | encode_segment)
+UNKNOWN_KID_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/users/me" \
+  -H "Authorization: Bearer $UNKNOWN_KID_HEADER.$PAYLOAD.$SIGNATURE")
+
+NONE_HEADER=$(echo -n '{"typ":"JWT","alg":"none","kid":"'$KID'"}' | encode_segment)
+NONE_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/users/me" \
+  -H "Authorization: Bearer $NONE_HEADER.$PAYLOAD.")
+
+ME_STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN")
+
+if [ "$UNKNOWN_KID_STATUS" = "401" ] && [ "$NONE_STATUS" = "401" ] && [ "$ME_STATUS" = "200" ]; then
+    echo "✅ Unknown kid and alg=none rejected, genuine token accepted"
+else
+    echo "❌ Expected 401/401/200, got $UNKNOWN_KID_STATUS/$NONE_STATUS/$ME_STATUS"
+    exit 1
+fi
+
+echo ""
+echo "🎉 JWKS Test Complete!"
@@ -0,0 +1,171 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# Lockouts and their audit entries are checked in the database directly
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Login Lockout..."
+
+# Earlier runs from this machine must not leave the IP close to its own limit
+reset_ip_throttle() {
+    psql "$DATABASE_URL" -q -c "DELETE FROM login_throttles WHERE scope = 'ip'"
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+reset_ip_throttle
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Step 2: Creating a member to lock out
+echo ""
+echo "📝 Step 2: Creating a member..."
+USER_EMAIL="lockout.$TIMESTAMP@example.com"
+USER_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Lockout User", "email": "'$USER_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+MEMBERSHIP_ID=$(admin_request POST "/user-organizations" '{"user_id": "'$USER_ID'", "org_id": "'$ORG_ID'", "role_name": "member"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ -n "$USER_ID" ] && [ -n "$MEMBERSHIP_ID" ]; then
+    echo "✅ Member created"
+else
+    echo "❌ Failed to create the member"
+    exit 1
+fi
+
+# Step 3: Every failure looks the same
+echo ""
+echo "📝 Step 3: Comparing failure responses..."
+SUSPENDED_EMAIL="suspended.$TIMESTAMP@example.com"
+SUSPENDED_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Suspended User", "email": "'$SUSPENDED_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+psql "$DATABASE_URL" -q -c "UPDATE users SET status = 'suspended' WHERE id = '$SUSPENDED_ID'"
+
+WRONG_PASSWORD=$(login_request "$USER_EMAIL" "wrongpassword")
+UNKNOWN_EMAIL=$(login_request "nobody.$TIMESTAMP@example.com" "wrongpassword")
+SUSPENDED=$(login_request "$SUSPENDED_EMAIL" "password123")
+expect_status "$(echo "$WRONG_PASSWORD" | tail -1)" "401" "Wrong password rejected"
+
+if [ "$WRONG_PASSWORD" = "$UNKNOWN_EMAIL" ] && [ "$WRONG_PASSWORD" = "$SUSPENDED" ]; then
+    echo "✅ Unknown emails and suspended accounts get the same response"
+else
+    echo "❌ Responses reveal account state: $WRONG_PASSWORD / $UNKNOWN_EMAIL / $SUSPENDED"
+    exit 1
+fi
+
+# Step 4: Repeated failures lock the account
+echo ""
+echo "📝 Step 4: Failing until the account locks..."
+# One failure was counted in step 3
+for ATTEMPT in 2 3 4 5; do
+    expect_status "$(login_status "$USER_EMAIL" "wrongpassword")" "401" "Failed attempt $ATTEMPT"
+done
+
+HEADERS=$(curl -s -D - -o /dev/null -X POST "$BASE_URL/users/login" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "'$USER_EMAIL'", "password": "password123"}')
+expect_status "$(echo "$HEADERS" | head -1 | awk '{print $2}')" "429" "Locked account refuses even the right password"
+
+if echo "$HEADERS" | grep -qi '^retry-after: [0-9]'; then
+    echo "✅ Retry-After header sent"
+else
+    echo "❌ Missing Retry-After header: $HEADERS"
+    exit 1
+fi
+
+LOCKED_AUDITS=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM audit_log WHERE action = 'account_locked' AND target_user_id = '$USER_ID'")
+expect_status "$LOCKED_AUDITS" "1" "Lockout recorded in the audit log"
+
+# Step 5: Unknown emails lock the same way
+echo ""
+echo "📝 Step 5: Failing with an unknown email..."
+for ATTEMPT in 2 3 4 5; do
+    login_status "nobody.$TIMESTAMP@example.com" "wrongpassword" > /dev/null
+done
+expect_status "$(login_status "nobody.$TIMESTAMP@example.com" "wrongpassword")" "429" "Unknown email locked like a real account"
+
+# Step 6: Only members with users:write unlock accounts
+echo ""
+echo "📝 Step 6: Unlocking the account..."
+UNLOCK_URL="/organizations/$ORG_ID/members/$USER_ID/unlock"
+# Reactivate the second user to act as an outsider
+psql "$DATABASE_URL" -q -c "UPDATE users SET status = 'active' WHERE id = '$SUSPENDED_ID'"
+OTHER_TOKEN=$(login "$SUSPENDED_EMAIL")
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X POST "$BASE_URL$UNLOCK_URL" -H "Authorization: Bearer $OTHER_TOKEN")
+expect_status "$STATUS" "403" "User outside the organization cannot unlock"
+
+expect_status "$(admin_request POST "/organizations/$ORG_ID/members/$SUSPENDED_ID/unlock" | tail -1)" "404" "Non-member cannot be unlocked"
+
+RESPONSE=$(admin_request POST "$UNLOCK_URL")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Account unlocked"
+
+if echo "$RESPONSE" | grep -q '"was_locked":true'; then
+    echo "✅ Response reports the lockout was lifted"
+else
+    echo "❌ Unexpected unlock response: $RESPONSE"
+    exit 1
+fi
+
+expect_status "$(login_status "$USER_EMAIL" "password123")" "200" "Unlocked account can log in"
+
+UNLOCKED_AUDITS=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM audit_log WHERE action = 'account_unlocked' AND target_user_id = '$USER_ID' AND actor_id = '$ADMIN_ID'")
+expect_status "$UNLOCKED_AUDITS" "1" "Unlock recorded in the audit log"
+
+# Step 7: A successful login clears earlier failures
+echo ""
+echo "📝 Step 7: Logging in between failures..."
+for ATTEMPT in 1 2 3 4; do
+    login_status "$USER_EMAIL" "wrongpassword" > /dev/null
+done
+login_status "$USER_EMAIL" "password123" > /dev/null
+expect_status "$(login_status "$USER_EMAIL" "wrongpassword")" "401" "Failure count restarted after a successful login"
+
+# Step 8: Wrong current passwords count as failed logins
+echo ""
+echo "📝 Step 8: Failing to change the password..."
+USER_TOKEN=$(login "$USER_EMAIL")
+PASSWORD_URL="/users/$USER_ID/password"
+RESPONSE=$(auth_request "$USER_TOKEN" PUT "$PASSWORD_URL" '{"current_password": "wrongpassword", "new_password": "newpassword456"}')
+if [ "$RESPONSE" = "$WRONG_PASSWORD" ]; then
+    echo "✅ Wrong current password gets the login failure response"
+else
+    echo "❌ Unexpected password change response: $RESPONSE"
+    exit 1
+fi
+for ATTEMPT in 2 3 4 5; do
+    expect_status "$(auth_request "$USER_TOKEN" PUT "$PASSWORD_URL" '{"current_password": "wrongpassword", "new_password": "newpassword456"}' | tail -1)" "401" "Failed change $ATTEMPT"
+done
+expect_status "$(auth_request "$USER_TOKEN" PUT "$PASSWORD_URL" '{"current_password": "password123", "new_password": "newpassword456"}' | tail -1)" "429" "Locked account cannot change its password"
+expect_status "$(login_status "$USER_EMAIL" "password123")" "429" "Failed changes lock logins too"
+
+# Step 9: Cleanup
+echo ""
+echo "📝 Step 9: Cleaning up..."
+admin_request DELETE "/user-organizations/$MEMBERSHIP_ID" > /dev/null
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$USER_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$SUSPENDED_ID" -H "Authorization: Bearer $TOKEN"
+psql "$DATABASE_URL" -q -c "DELETE FROM login_throttles WHERE subject IN ('nobody.$TIMESTAMP@example.com', '$USER_EMAIL')"
+reset_ip_throttle
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Login Lockout Test Complete!"
@@ -0,0 +1,215 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# Used to restore the organization's MFA policy however the test ends
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Two-Factor Authentication..."
+
+json_field() {
+    echo "$1" | grep -o "\"$2\":\"[^\"]*\"" | head -1 | cut -d'"' -f4
+}
+
+# Current TOTP code for a base32 secret, optionally some 30-second steps ahead
+totp_code() {
+    python3 - "$1" "${2:-0}" <<'EOF'
+import base64, hashlib, hmac, struct, sys, time
+secret = sys.argv[1] + "=" * (-len(sys.argv[1]) % 8)
+step = int(time.time()) // 30 + int(sys.argv[2])
+digest = hmac.new(base64.b32decode(secret), struct.pack(">Q", step), hashlib.sha1).digest()
+offset = digest[19] & 15
+print("%06d" % ((struct.unpack(">I", digest[offset:offset + 4])[0] & 0x7fffffff) % 1000000))
+EOF
+}
+
+register() {
+    public_request POST "/users" '{"name": "'"$1"'", "email": "'$2'", "password": "password123"}' \
+      | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+POLICY_URL="/organizations/$ORG_ID/mfa-policy"
+
+# The admin holds users:delete, so a left-over policy would lock other tests out
+trap 'psql "$DATABASE_URL" -q -c "UPDATE organization SET require_mfa = false WHERE id = '"'$ORG_ID'"'"' EXIT
+
+# Step 2: Enrolling a member
+echo ""
+echo "📝 Step 2: Enrolling in MFA..."
+USER_EMAIL="mfa.$TIMESTAMP@example.com"
+USER_ID=$(register "MFA User" "$USER_EMAIL")
+MEMBERSHIP_ID=$(auth_request "$TOKEN" POST "/user-organizations" '{"user_id": "'$USER_ID'", "org_id": "'$ORG_ID'", "role_name": "member"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+USER_TOKEN=$(login "$USER_EMAIL")
+
+RESPONSE=$(auth_request "$USER_TOKEN" GET "/auth/mfa")
+if echo "$RESPONSE" | grep -q '"enabled":false' && echo "$RESPONSE" | grep -q '"required":false'; then
+    echo "✅ MFA starts disabled"
+else
+    echo "❌ Unexpected MFA status: $RESPONSE"
+    exit 1
+fi
+
+RESPONSE=$(auth_request "$USER_TOKEN" POST "/auth/mfa/enroll")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Secret issued"
+SECRET=$(json_field "$RESPONSE" "secret")
+if echo "$RESPONSE" | grep -q '"provisioning_uri":"otpauth://totp/'; then
+    echo "✅ Provisioning URI returned"
+else
+    echo "❌ Missing provisioning URI: $RESPONSE"
+    exit 1
+fi
+
+expect_status "$(auth_request "$USER_TOKEN" POST "/auth/mfa/activate" '{"code": "000000"}' | tail -1)" "400" "Wrong code does not activate"
+
+RESPONSE=$(auth_request "$USER_TOKEN" POST "/auth/mfa/activate" '{"code": "'$(totp_code "$SECRET")'"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "MFA activated"
+RECOVERY_CODES=($(echo "$RESPONSE" | head -1 | grep -o '[0-9a-f]\{5\}-[0-9a-f]\{5\}'))
+expect_status "${#RECOVERY_CODES[@]}" "10" "Ten recovery codes issued"
+expect_status "$(auth_request "$USER_TOKEN" POST "/auth/mfa/enroll" | tail -1)" "409" "Enrolling again rejected"
+
+# Step 3: Logging in takes two steps
+echo ""
+echo "📝 Step 3: Logging in with a code..."
+RESPONSE=$(login_request "$USER_EMAIL")
+MFA_TOKEN=$(json_field "$RESPONSE" "mfa_token")
+if [ -n "$MFA_TOKEN" ] && echo "$RESPONSE" | grep -q '"token":null'; then
+    echo "✅ Password step returns an mfa_token instead of tokens"
+else
+    echo "❌ Unexpected login response: $RESPONSE"
+    exit 1
+fi
+
+expect_status "$(public_request POST "/auth/mfa/verify" '{"mfa_token": "'$MFA_TOKEN'", "code": "000000"}' | tail -1)" "401" "Wrong code rejected"
+
+# The current code was used to activate, so use the next one
+RESPONSE=$(public_request POST "/auth/mfa/verify" '{"mfa_token": "'$MFA_TOKEN'", "code": "'$(totp_code "$SECRET" 1)'"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Code accepted"
+MFA_SESSION=$(json_field "$RESPONSE" "token")
+expect_status "$(auth_request "$MFA_SESSION" GET "/users/me" | tail -1)" "200" "Session works"
+expect_status "$(public_request POST "/auth/mfa/verify" '{"mfa_token": "'$MFA_TOKEN'", "code": "'$(totp_code "$SECRET" 1)'"}' | tail -1)" "401" "mfa_token is single-use"
+
+# Step 4: Recovery codes
+echo ""
+echo "📝 Step 4: Logging in with a recovery code..."
+MFA_TOKEN=$(json_field "$(login_request "$USER_EMAIL")" "mfa_token")
+expect_status "$(public_request POST "/auth/mfa/verify" '{"mfa_token": "'$MFA_TOKEN'", "recovery_code": "'${RECOVERY_CODES[0]}'"}' | tail -1)" "200" "Recovery code accepted"
+
+MFA_TOKEN=$(json_field "$(login_request "$USER_EMAIL")" "mfa_token")
+expect_status "$(public_request POST "/auth/mfa/verify" '{"mfa_token": "'$MFA_TOKEN'", "recovery_code": "'${RECOVERY_CODES[0]}'"}' | tail -1)" "401" "Recovery code is single-use"
+
+if auth_request "$USER_TOKEN" GET "/auth/mfa" | grep -q '"recovery_codes_remaining":9'; then
+    echo "✅ Remaining recovery codes counted"
+else
+    echo "❌ Expected 9 remaining recovery codes"
+    exit 1
+fi
+
+# Step 5: The organization policy
+echo ""
+echo "📝 Step 5: Requiring MFA for privileged roles..."
+expect_status "$(auth_request "$USER_TOKEN" GET "$POLICY_URL" | tail -1)" "200" "Members can read the policy"
+expect_status "$(auth_request "$USER_TOKEN" PUT "$POLICY_URL" '{"require_mfa": true}' | tail -1)" "403" "Members cannot change the policy"
+
+RESPONSE=$(auth_request "$TOKEN" PUT "$POLICY_URL" '{"require_mfa": true}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Admin requires MFA"
+if echo "$RESPONSE" | grep -q '"applies_to_permissions":\["users:delete","org:delete"\]'; then
+    echo "✅ Policy lists the permissions it applies to"
+else
+    echo "❌ Unexpected policy: $RESPONSE"
+    exit 1
+fi
+
+# Step 6: A privileged member must enroll while logging in
+echo ""
+echo "📝 Step 6: Logging in as an admin without MFA..."
+ADMIN_EMAIL="mfa.admin.$TIMESTAMP@example.com"
+SECOND_ADMIN_ID=$(register "MFA Admin" "$ADMIN_EMAIL")
+SECOND_MEMBERSHIP_ID=$(auth_request "$TOKEN" POST "/user-organizations" '{"user_id": "'$SECOND_ADMIN_ID'", "org_id": "'$ORG_ID'", "role_name": "admin"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+RESPONSE=$(login_request "$ADMIN_EMAIL")
+MFA_TOKEN=$(json_field "$RESPONSE" "mfa_token")
+if echo "$RESPONSE" | grep -q '"enrollment_required":true'; then
+    echo "✅ Enrollment required before a session is issued"
+else
+    echo "❌ Unexpected login response: $RESPONSE"
+    exit 1
+fi
+
+expect_status "$(public_request POST "/auth/mfa/verify" '{"mfa_token": "'$MFA_TOKEN'", "code": "000000"}' | tail -1)" "400" "Verifying before setup rejected"
+
+ADMIN_SECRET=$(json_field "$(public_request POST "/auth/mfa/setup" '{"mfa_token": "'$MFA_TOKEN'"}')" "secret")
+RESPONSE=$(public_request POST "/auth/mfa/verify" '{"mfa_token": "'$MFA_TOKEN'", "code": "'$(totp_code "$ADMIN_SECRET")'"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Enrollment completed during login"
+ADMIN_SESSION=$(json_field "$RESPONSE" "token")
+if echo "$RESPONSE" | grep -q '"recovery_codes":\['; then
+    echo "✅ Recovery codes returned with the session"
+else
+    echo "❌ Missing recovery codes: $RESPONSE"
+    exit 1
+fi
+
+expect_status "$(auth_request "$ADMIN_SESSION" POST "/auth/mfa/disable" '{"code": "'$(totp_code "$ADMIN_SECRET" 1)'"}' | tail -1)" "409" "Required MFA cannot be turned off"
+
+# The owner role grants "*", which covers the privileged permissions
+OWNER_EMAIL="mfa.owner.$TIMESTAMP@example.com"
+OWNER_ID=$(register "MFA Owner" "$OWNER_EMAIL")
+expect_status "$(auth_request "$TOKEN" POST "/user-organizations" '{"user_id": "'$OWNER_ID'", "org_id": "'$ORG_ID'", "role_name": "owner"}' | tail -1)" "403" "Admins cannot make an owner"
+# The admin holds no "*", so the owner membership is set up in the database
+OWNER_MEMBERSHIP_ID=$(psql "$DATABASE_URL" -qAt -c "INSERT INTO user_organizations (user_id, org_id, role_id) SELECT '$OWNER_ID', '$ORG_ID', id FROM roles WHERE name = 'owner' AND org_id IS NULL RETURNING id")
+if login_request "$OWNER_EMAIL" | grep -q '"enrollment_required":true'; then
+    echo "✅ Wildcard role must enroll too"
+else
+    echo "❌ Owner logged in without MFA"
+    exit 1
+fi
+
+# Step 7: Turning MFA off
+echo ""
+echo "📝 Step 7: Turning MFA off..."
+expect_status "$(auth_request "$TOKEN" PUT "$POLICY_URL" '{"require_mfa": false}' | tail -1)" "200" "Policy lifted"
+expect_status "$(auth_request "$USER_TOKEN" POST "/auth/mfa/disable" '{"recovery_code": "'${RECOVERY_CODES[1]}'"}' | tail -1)" "204" "MFA turned off with a recovery code"
+
+RESPONSE=$(login_request "$USER_EMAIL")
+if [ -n "$(json_field "$RESPONSE" "token")" ]; then
+    echo "✅ Login is back to one step"
+else
+    echo "❌ Unexpected login response: $RESPONSE"
+    exit 1
+fi
+
+AUDITS=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM audit_log WHERE target_user_id = '$USER_ID' AND action IN ('mfa_enabled', 'mfa_disabled')")
+expect_status "$AUDITS" "2" "Enabling and disabling recorded in the audit log"
+
+# Step 8: Cleanup
+echo ""
+echo "📝 Step 8: Cleaning up..."
+auth_request "$TOKEN" DELETE "/user-organizations/$MEMBERSHIP_ID" > /dev/null
+auth_request "$TOKEN" DELETE "/user-organizations/$SECOND_MEMBERSHIP_ID" > /dev/null
+auth_request "$TOKEN" DELETE "/user-organizations/$OWNER_MEMBERSHIP_ID" > /dev/null
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$USER_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$SECOND_ADMIN_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$OWNER_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Two-Factor Authentication Test Complete!"
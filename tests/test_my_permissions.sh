@@ -0,0 +1,101 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+echo "🧪 Testing Permission Introspection..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(curl -s -X POST "$BASE_URL/users/login" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "test@example.com", "password": "password123"}' | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+# Step 2: An admin sees full contact permissions
+echo ""
+echo "📝 Step 2: Fetching the admin's permissions..."
+RESPONSE=$(curl -s -w "\n%{http_code}" -X GET "$BASE_URL/me/permissions" -H "Authorization: Bearer $TOKEN")
+STATUS=$(echo "$RESPONSE" | tail -1)
+BODY=$(echo "$RESPONSE" | head -n -1)
+
+if [ "$STATUS" = "200" ] && echo "$BODY" | grep -q '"name":"contacts:delete","resource":"contacts","description":"[^"]*","scope":"all"'; then
+    echo "✅ Admin holds contacts:delete for all contacts"
+else
+    echo "❌ Unexpected response ($STATUS): $BODY"
+    exit 1
+fi
+
+if echo "$BODY" | grep -q '"roles":\["admin"\]'; then
+    echo "✅ Roles listed"
+else
+    echo "❌ Admin role missing: $BODY"
+    exit 1
+fi
+
+# Step 3: A sales rep only holds the owned scope for updates
+echo ""
+echo "📝 Step 3: Fetching a sales rep's permissions..."
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+REP_EMAIL="perms.rep.$TIMESTAMP@example.com"
+REP_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Permission Rep", "email": "'$REP_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+MEMBERSHIP_ID=$(curl -s -X POST "$BASE_URL/user-organizations" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"user_id": "'$REP_ID'", "org_id": "'$ORG_ID'", "role_name": "sales_rep"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+REP_TOKEN=$(curl -s -X POST "$BASE_URL/users/login" \
+  -H "Content-Type: application/json" \
+  -d '{"email": "'$REP_EMAIL'", "password": "password123"}' | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+BODY=$(curl -s -X GET "$BASE_URL/me/permissions" -H "Authorization: Bearer $REP_TOKEN" -H "X-Organization-Id: $ORG_ID")
+
+if echo "$BODY" | grep -q '"name":"contacts:update","resource":"contacts","description":"[^"]*","scope":"own"'; then
+    echo "✅ Sales rep holds contacts:update for own contacts"
+else
+    echo "❌ Expected an owned contacts:update: $BODY"
+    exit 1
+fi
+
+if echo "$BODY" | grep -q '"name":"roles:manage"'; then
+    echo "❌ Sales rep should not hold roles:manage"
+    exit 1
+else
+    echo "✅ Permissions the rep lacks are omitted"
+fi
+
+# Step 4: Unauthenticated requests are rejected
+echo ""
+echo "📝 Step 4: Fetching permissions without a token..."
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL/me/permissions")
+
+if [ "$STATUS" = "401" ]; then
+    echo "✅ Unauthenticated request rejected"
+else
+    echo "❌ Expected 401, got $STATUS"
+    exit 1
+fi
+
+# Step 5: Cleanup
+echo ""
+echo "📝 Step 5: Cleaning up..."
+curl -s -o /dev/null -X DELETE "$BASE_URL/user-organizations/$MEMBERSHIP_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$REP_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Permission Introspection Test Complete!"
@@ -0,0 +1,97 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# A failing seed step is simulated with a temporary constraint on the same database
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Organization Creation..."
+
+# Drop the failure constraint even if a step exits early
+trap 'psql "$DATABASE_URL" -q -c "ALTER TABLE lead_statuses DROP CONSTRAINT IF EXISTS test_reject_closed_lost" 2>/dev/null' EXIT
+
+TIMESTAMP=$(date +%s)
+
+# Step 1: A new user creates an organization
+echo ""
+echo "📝 Step 1: Creating an organization as a new user..."
+CREATOR_EMAIL="creator.$TIMESTAMP@example.com"
+CREATOR_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Organization Creator", "email": "'$CREATOR_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+TOKEN=$(login "$CREATOR_EMAIL")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+RESPONSE=$(auth_request "$TOKEN" POST "/organizations" '{"name": "Created Org '$TIMESTAMP'", "country": "NL", "timezone": "Europe/Amsterdam"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Organization created"
+ORG_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Step 2: The creator is its admin
+echo ""
+echo "📝 Step 2: Checking the creator's membership..."
+expect_body "$(auth_request "$TOKEN" GET "/users/$CREATOR_ID/organizations")" "$ORG_ID" "Organization listed for its creator"
+
+ROLE=$(psql "$DATABASE_URL" -At -c "SELECT r.name FROM user_organizations uo JOIN roles r ON r.id = uo.role_id WHERE uo.user_id = '$CREATOR_ID' AND uo.org_id = '$ORG_ID' AND uo.status = 'active'")
+expect_status "$ROLE" "admin" "Creator is an active admin"
+
+expect_status "$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID" '{"name": "Created Org '$TIMESTAMP' Renamed"}' | tail -1)" "200" "Creator can manage the organization"
+expect_status "$(org_request "$TOKEN" POST "/contacts" '{"first_name": "First", "last_name": "Contact", "email": "first.contact.'$TIMESTAMP'@example.com"}' | tail -1)" "201" "Creator can add contacts"
+
+# Step 3: Default data is in place
+echo ""
+echo "📝 Step 3: Checking the default data..."
+RESPONSE=$(org_request "$TOKEN" GET "/custom-fields/contact")
+expect_body "$RESPONSE" '"field_name":"birthday"' "Default birthday field"
+expect_body "$RESPONSE" '"field_name":"preferred_contact_method"' "Default preferred contact method field"
+
+RESPONSE=$(org_request "$TOKEN" GET "/tags")
+expect_body "$RESPONSE" '"name":"Customer"' "Default Customer tag"
+expect_body "$RESPONSE" '"name":"VIP"' "Default VIP tag"
+
+RESPONSE=$(org_request "$TOKEN" GET "/lead-statuses")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Lead statuses listed"
+expect_body "$RESPONSE" '"name":"new"' "Default new status"
+expect_body "$RESPONSE" '"is_closed":true,"label":"Closed Lost","name":"closed_lost"' "Closed Lost status ends the pipeline"
+
+# Contacts take the organization's own statuses, not the defaults
+psql "$DATABASE_URL" -q -c "INSERT INTO lead_statuses (org_id, name, label, display_order) VALUES ('$ORG_ID', 'nurturing', 'Nurturing', 99)"
+psql "$DATABASE_URL" -q -c "DELETE FROM lead_statuses WHERE org_id = '$ORG_ID' AND name = 'proposal'"
+RESPONSE=$(org_request "$TOKEN" POST "/contacts" '{"first_name": "Status", "last_name": "Contact", "email": "status.contact.'$TIMESTAMP'@example.com", "lead_status": "nurturing"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Contact created with an organization status"
+STATUS_CONTACT_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+RESPONSE=$(org_request "$TOKEN" POST "/contacts" '{"first_name": "Status", "last_name": "Contact", "email": "removed.status.'$TIMESTAMP'@example.com", "lead_status": "proposal"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "400" "Removed status rejected on create"
+expect_body "$RESPONSE" "invalid_lead_status" "Invalid lead status code"
+expect_status "$(org_request "$TOKEN" PUT "/contacts/$STATUS_CONTACT_ID" '{"lead_status": "proposal"}' | tail -1)" "400" "Removed status rejected on update"
+expect_status "$(org_request "$TOKEN" PATCH "/contacts/$STATUS_CONTACT_ID" '{"lead_status": "qualified"}' | tail -1)" "200" "Remaining default status accepted"
+
+# Step 4: A failing step leaves nothing behind
+echo ""
+echo "📝 Step 4: Failing halfway through creation..."
+psql "$DATABASE_URL" -q -c "ALTER TABLE lead_statuses ADD CONSTRAINT test_reject_closed_lost CHECK (name <> 'closed_lost') NOT VALID"
+FAILED_NAME="Rolled Back Org $TIMESTAMP"
+expect_status "$(auth_request "$TOKEN" POST "/organizations" '{"name": "'"$FAILED_NAME"'"}' | tail -1)" "500" "Creation fails when seeding fails"
+psql "$DATABASE_URL" -q -c "ALTER TABLE lead_statuses DROP CONSTRAINT test_reject_closed_lost"
+
+REMAINING=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM organization WHERE name = '$FAILED_NAME'")
+expect_status "$REMAINING" "0" "No organization left behind"
+MEMBERSHIPS=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM user_organizations WHERE user_id = '$CREATOR_ID'")
+expect_status "$MEMBERSHIPS" "1" "No membership left behind"
+
+# Step 5: Cleanup
+echo ""
+echo "📝 Step 5: Cleaning up..."
+psql "$DATABASE_URL" -q -c "DELETE FROM organization WHERE id = '$ORG_ID'"
+# The creator belongs to no other organization, so they remove their own account
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$CREATOR_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Organization Creation Test Complete!"
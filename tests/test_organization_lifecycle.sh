@@ -0,0 +1,134 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# The grace period is shortened and the purge run against the same database
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Organization Lifecycle..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Step 2: Creating an organization to work on
+echo ""
+echo "📝 Step 2: Creating an organization..."
+expect_status "$(auth_request "$TOKEN" POST "/organizations" '{"name": "Bad Zone Org", "timezone": "Mars/Olympus"}' | tail -1)" "400" "Unknown timezone rejected on create"
+
+RESPONSE=$(auth_request "$TOKEN" POST "/organizations" '{"name": "Lifecycle Org '$TIMESTAMP'", "country": "de", "timezone": "Europe/Berlin"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Organization created"
+expect_body "$RESPONSE" '"country":"DE"' "Country code stored in upper case"
+ORG_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+MEMBER_EMAIL="lifecycle.$TIMESTAMP@example.com"
+MEMBER_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Lifecycle Member", "email": "'$MEMBER_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+org_request "$TOKEN" POST "/user-organizations" '{"user_id": "'$MEMBER_ID'", "org_id": "'$ORG_ID'", "role_name": "member"}' > /dev/null
+MEMBER_TOKEN=$(login "$MEMBER_EMAIL")
+
+# Step 3: Reading and updating the organization
+echo ""
+echo "📝 Step 3: Updating the organization..."
+expect_status "$(auth_request "$MEMBER_TOKEN" GET "/organizations/$ORG_ID" | tail -1)" "200" "Members can read the organization"
+expect_status "$(auth_request "$MEMBER_TOKEN" PUT "/organizations/$ORG_ID" '{"name": "Taken Over"}' | tail -1)" "403" "Members cannot update it"
+
+RESPONSE=$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID" '{"name": "Renamed Org", "country": "DE", "timezone": "Mars/Olympus"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "400" "Unknown timezone rejected"
+expect_body "$RESPONSE" '"code":"invalid_timezone"' "Rejection names the timezone"
+expect_body "$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID" '{"name": "Renamed Org", "country": "XX"}')" '"code":"invalid_country"' "Unknown country rejected"
+expect_body "$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID" '{"name": "   "}')" '"code":"required"' "Blank name rejected"
+
+RESPONSE=$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID" '{"name": "Renamed Org '$TIMESTAMP'", "country": "JP", "timezone": "Asia/Tokyo"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Organization updated"
+expect_body "$RESPONSE" '"timezone":"Asia/Tokyo"' "New timezone returned"
+expect_body "$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID" '{"name": "Renamed Org '$TIMESTAMP'"}')" '"country":null' "Omitted country cleared"
+
+# Step 4: Settings
+echo ""
+echo "📝 Step 4: Changing settings..."
+expect_body "$(auth_request "$MEMBER_TOKEN" GET "/organizations/$ORG_ID/settings")" '"currency":"USD"' "Defaults returned before anything is set"
+expect_status "$(auth_request "$MEMBER_TOKEN" PUT "/organizations/$ORG_ID/settings" '{"currency": "EUR"}' | tail -1)" "403" "Members cannot change settings"
+
+RESPONSE=$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID/settings" '{"currency": "EUR", "week_starts_on": "sunday"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Settings updated"
+expect_body "$RESPONSE" '"locale":"en"' "Omitted settings keep their defaults"
+expect_body "$(auth_request "$TOKEN" GET "/organizations/$ORG_ID/settings")" '"week_starts_on":"sunday"' "Settings stored"
+expect_body "$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID/settings" '{"currency": "euro"}')" '"code":"invalid_currency"' "Invalid currency rejected"
+expect_status "$(auth_request "$TOKEN" PUT "/organizations/$ORG_ID/settings" '{"theme": "dark"}' | tail -1)" "422" "Unknown setting rejected"
+
+# Step 5: Deleting hides the organization and its data
+echo ""
+echo "📝 Step 5: Deleting the organization..."
+CONTACT_ID=$(org_request "$TOKEN" POST "/contacts" '{"first_name": "Lifecycle", "last_name": "Contact", "email": "lifecycle.contact.'$TIMESTAMP'@example.com"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+expect_status "$(org_request "$TOKEN" GET "/contacts/$CONTACT_ID" | tail -1)" "200" "Contact created in the organization"
+
+expect_status "$(auth_request "$MEMBER_TOKEN" DELETE "/organizations/$ORG_ID" | tail -1)" "403" "Members cannot delete it"
+
+RESPONSE=$(auth_request "$TOKEN" DELETE "/organizations/$ORG_ID")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Organization deleted"
+expect_body "$RESPONSE" '"purge_after"' "Response says when it will be purged"
+
+expect_status "$(auth_request "$TOKEN" GET "/organizations/$ORG_ID" | tail -1)" "404" "Deleted organization not found"
+expect_status "$(org_request "$TOKEN" GET "/contacts/$CONTACT_ID" | tail -1)" "404" "Its contacts are out of reach"
+expect_status "$(auth_request "$TOKEN" DELETE "/organizations/$ORG_ID" | tail -1)" "404" "Deleting twice not possible"
+
+if curl -s -X GET "$BASE_URL/users/$MEMBER_ID/organizations" -H "Authorization: Bearer $TOKEN" | grep -q "$ORG_ID"; then
+    echo "❌ Deleted organization still listed for its members"
+    exit 1
+else
+    echo "✅ Deleted organization no longer listed for its members"
+fi
+
+ACTIVE=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM user_organizations WHERE org_id = '$ORG_ID' AND status = 'active'")
+expect_status "$ACTIVE" "0" "Memberships deactivated with the organization"
+
+# Step 6: Restoring within the grace period
+echo ""
+echo "📝 Step 6: Restoring the organization..."
+expect_status "$(auth_request "$MEMBER_TOKEN" POST "/organizations/$ORG_ID/restore" | tail -1)" "403" "Members cannot restore it"
+expect_status "$(auth_request "$TOKEN" POST "/organizations/$ORG_ID/restore" | tail -1)" "200" "Organization restored"
+expect_status "$(org_request "$TOKEN" GET "/contacts/$CONTACT_ID" | tail -1)" "200" "Its contacts are back"
+ACTIVE=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM user_organizations WHERE org_id = '$ORG_ID' AND status = 'active'")
+expect_status "$ACTIVE" "2" "Memberships active again"
+expect_status "$(auth_request "$TOKEN" POST "/organizations/$ORG_ID/restore" | tail -1)" "404" "Only deleted organizations can be restored"
+
+# Step 7: Purging after the grace period
+echo ""
+echo "📝 Step 7: Purging the organization..."
+auth_request "$TOKEN" DELETE "/organizations/$ORG_ID" > /dev/null
+psql "$DATABASE_URL" -q -c "UPDATE organization SET purge_after = NOW() - INTERVAL '1 minute' WHERE id = '$ORG_ID'"
+expect_status "$(auth_request "$TOKEN" POST "/organizations/$ORG_ID/restore" | tail -1)" "404" "Grace period over, no restore"
+
+DATABASE_URL="$DATABASE_URL" cargo run -q --bin purge_organizations > /dev/null 2>&1
+REMAINING=$(psql "$DATABASE_URL" -At -c "SELECT (SELECT COUNT(*) FROM organization WHERE id = '$ORG_ID') + (SELECT COUNT(*) FROM user_organizations WHERE org_id = '$ORG_ID') + (SELECT COUNT(*) FROM contacts WHERE org_id = '$ORG_ID')")
+expect_status "$REMAINING" "0" "Organization, memberships and contacts purged"
+
+PURGE_AUDITS=$(psql "$DATABASE_URL" -At -c "SELECT COUNT(*) FROM audit_log WHERE action = 'organization_purged' AND details->>'org_id' = '$ORG_ID'")
+expect_status "$PURGE_AUDITS" "1" "Purge recorded in the audit log"
+
+# Step 8: Cleanup
+echo ""
+echo "📝 Step 8: Cleaning up..."
+# The purged organization no longer links the member to the admin, so they remove their own account
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$MEMBER_ID" -H "Authorization: Bearer $MEMBER_TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Organization Lifecycle Test Complete!"
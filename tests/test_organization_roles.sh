@@ -0,0 +1,115 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Organization Role Management..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ROLES_URL="/organizations/$ORG_ID/roles"
+
+# Step 2: Listing includes the built-in roles
+echo ""
+echo "📝 Step 2: Listing roles..."
+RESPONSE=$(admin_request GET "$ROLES_URL")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Listed roles"
+ADMIN_ROLE_ID=$(echo "$RESPONSE" | tr '{' '\n' | grep '"name":"admin"' | grep -o '"id":"[^"]*"' | cut -d'"' -f4)
+echo "Built-in admin role: $ADMIN_ROLE_ID"
+
+# Step 3: Create a custom role
+echo ""
+echo "📝 Step 3: Creating a custom role..."
+ROLE_NAME="auditor_$TIMESTAMP"
+RESPONSE=$(admin_request POST "$ROLES_URL" '{"name": "'$ROLE_NAME'", "description": "Reads contacts", "permissions": ["contacts:read", "reports:read", "contacts:read"]}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Created custom role"
+ROLE_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+expect_status "$(admin_request POST "$ROLES_URL" '{"name": "'$ROLE_NAME'", "permissions": []}' | tail -1)" "409" "Duplicate role name rejected"
+expect_status "$(admin_request POST "$ROLES_URL" '{"name": "admin", "permissions": []}' | tail -1)" "409" "Built-in role name rejected"
+expect_status "$(admin_request POST "$ROLES_URL" '{"name": "typo_'$TIMESTAMP'", "permissions": ["contact:read"]}' | tail -1)" "400" "Unknown permission rejected"
+# The admin role holds roles:manage but not "*" or roles:*
+expect_status "$(admin_request POST "$ROLES_URL" '{"name": "escalate_'$TIMESTAMP'", "permissions": ["*"]}' | tail -1)" "403" "Wildcard beyond the caller's permissions rejected"
+expect_status "$(admin_request POST "$ROLES_URL" '{"name": "escalate_'$TIMESTAMP'", "permissions": ["contacts:read", "roles:*"]}' | tail -1)" "403" "Resource wildcard beyond the caller's permissions rejected"
+
+# Step 4: Update the custom role
+echo ""
+echo "📝 Step 4: Updating the custom role..."
+RESPONSE=$(admin_request PUT "$ROLES_URL/$ROLE_ID" '{"permissions": ["contacts:read", "contacts:update_own"]}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Updated custom role"
+if echo "$RESPONSE" | grep -q '"contacts:update_own"'; then
+    echo "✅ Permissions replaced"
+else
+    echo "❌ Permissions were not updated: $RESPONSE"
+    exit 1
+fi
+
+expect_status "$(admin_request PUT "$ROLES_URL/$ADMIN_ROLE_ID" '{"permissions": []}' | tail -1)" "403" "Built-in role is read-only"
+expect_status "$(admin_request PUT "$ROLES_URL/$ROLE_ID" '{"permissions": ["*"]}' | tail -1)" "403" "Update cannot grant more than the caller holds"
+
+# Step 5: A role in use cannot be deleted
+echo ""
+echo "📝 Step 5: Assigning the custom role..."
+MEMBER_EMAIL="role.member.$TIMESTAMP@example.com"
+MEMBER_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Role Member", "email": "'$MEMBER_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+MEMBERSHIP_ID=$(admin_request POST "/user-organizations" '{"user_id": "'$MEMBER_ID'", "org_id": "'$ORG_ID'", "role_name": "'$ROLE_NAME'"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ -n "$MEMBERSHIP_ID" ]; then
+    echo "✅ Custom role assigned"
+else
+    echo "❌ Failed to assign the custom role"
+    exit 1
+fi
+
+expect_status "$(admin_request DELETE "$ROLES_URL/$ROLE_ID" | tail -1)" "409" "Role in use cannot be deleted"
+
+# Step 6: The member cannot manage roles
+echo ""
+echo "📝 Step 6: Listing roles as the member..."
+MEMBER_TOKEN=$(login "$MEMBER_EMAIL")
+STATUS=$(curl -s -o /dev/null -w "%{http_code}" -X GET "$BASE_URL$ROLES_URL" -H "Authorization: Bearer $MEMBER_TOKEN")
+expect_status "$STATUS" "403" "Member without roles:read rejected"
+
+# Step 7: The last admin cannot be demoted or removed
+echo ""
+echo "📝 Step 7: Protecting the last admin..."
+ADMIN_COUNT=$(admin_request GET "/organizations/$ORG_ID/users?status=active" | grep -o '"name":"admin"' | wc -l)
+if [ "$ADMIN_COUNT" = "1" ]; then
+    ADMIN_MEMBERSHIP_ID=$(admin_request GET "/users/$ADMIN_ID/organizations?status=active" | tr '{' '\n' \
+      | grep '"org_id":"'$ORG_ID'"' | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+    expect_status "$(admin_request PUT "/user-organizations/$ADMIN_MEMBERSHIP_ID" '{"role_name": "member"}' | tail -1)" "409" "Last admin cannot be demoted"
+    expect_status "$(admin_request PUT "/user-organizations/$ADMIN_MEMBERSHIP_ID" '{"status": "inactive"}' | tail -1)" "409" "Last admin cannot be deactivated"
+else
+    echo "⚠️  Organization has $ADMIN_COUNT admins, skipping last-admin checks"
+fi
+
+# Step 8: Cleanup
+echo ""
+echo "📝 Step 8: Cleaning up..."
+admin_request DELETE "/user-organizations/$MEMBERSHIP_ID" > /dev/null
+expect_status "$(admin_request DELETE "$ROLES_URL/$ROLE_ID" | tail -1)" "204" "Unused custom role deleted"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$MEMBER_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Organization Role Test Complete!"
@@ -0,0 +1,111 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# Stored hashes and emailed reset tokens are read from the database directly
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+# bcrypt hash of "password123", as stored before Argon2id became the default
+LEGACY_HASH='$2a$10$bxhuqKcVCYDdxagwnT4WAuBLjOvbzD3KVwJKp4zO5cVQ8xkkVNe1.'
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Password Hashing and Policy..."
+
+expect_code() {
+    if echo "$1" | grep -q "\"code\":\"$2\""; then
+        echo "✅ $3"
+    else
+        echo "❌ $3: expected code $2 in $1"
+        exit 1
+    fi
+}
+
+register() {
+    public_request POST "/users" '{"name": "Policy User", "email": "'$1'", "password": "'$2'"}'
+}
+
+stored_hash() {
+    psql "$DATABASE_URL" -At -c "SELECT password FROM users WHERE id = '$1'"
+}
+
+latest_reset_token() {
+    psql "$DATABASE_URL" -At -c "SELECT payload::text FROM email_outbox WHERE recipient = '$1' AND template = 'password_reset' ORDER BY created_at DESC LIMIT 1" \
+      | grep -o 'token=[0-9a-f]*' | cut -d= -f2
+}
+
+TIMESTAMP=$(date +%s)
+USER_EMAIL="policy.$TIMESTAMP@example.com"
+
+# Step 1: Length limits on registration
+echo ""
+echo "📝 Step 1: Registering with passwords of different lengths..."
+RESPONSE=$(register "short.$TIMESTAMP@example.com" "short12")
+expect_status "$(echo "$RESPONSE" | tail -1)" "400" "Password under 8 characters rejected"
+expect_code "$RESPONSE" "too_short" "Rejection explains the password is too short"
+
+RESPONSE=$(register "long.$TIMESTAMP@example.com" "$(printf 'a%.0s' $(seq 1 129))")
+expect_code "$RESPONSE" "too_long" "Password over 128 characters rejected"
+
+# bcrypt could not tell these apart past 72 bytes
+RESPONSE=$(register "$USER_EMAIL" "$(printf 'b%.0s' $(seq 1 100))")
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "100-character password accepted"
+USER_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if stored_hash "$USER_ID" | grep -q '^\$argon2id\$'; then
+    echo "✅ New password stored as Argon2id"
+else
+    echo "❌ Unexpected hash: $(stored_hash "$USER_ID")"
+    exit 1
+fi
+
+expect_status "$(login_request "$USER_EMAIL" "$(printf 'b%.0s' $(seq 1 72))" | tail -1)" "401" "First 72 characters alone do not log in"
+
+# Step 2: Legacy bcrypt hashes are upgraded on login
+echo ""
+echo "📝 Step 2: Logging in with a bcrypt hash..."
+psql "$DATABASE_URL" -q -c "UPDATE users SET password = '$LEGACY_HASH' WHERE id = '$USER_ID'"
+RESPONSE=$(login_request "$USER_EMAIL" "password123")
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "bcrypt password still logs in"
+TOKEN=$(echo "$RESPONSE" | grep -o '"token":"[^"]*"' | cut -d'"' -f4)
+
+if stored_hash "$USER_ID" | grep -q '^\$argon2id\$'; then
+    echo "✅ Hash upgraded to Argon2id"
+else
+    echo "❌ Hash not upgraded: $(stored_hash "$USER_ID")"
+    exit 1
+fi
+
+expect_status "$(login_request "$USER_EMAIL" "password123" | tail -1)" "200" "Upgraded hash logs in"
+expect_status "$(login_request "$USER_EMAIL" "wrongpassword" | tail -1)" "401" "Upgraded hash rejects a wrong password"
+
+# Step 3: Recent passwords cannot be reused
+echo ""
+echo "📝 Step 3: Changing the password..."
+PASSWORD_URL="/users/$USER_ID/password"
+RESPONSE=$(auth_request "$TOKEN" PUT "$PASSWORD_URL" '{"current_password": "password123", "new_password": "'$(printf 'b%.0s' $(seq 1 100))'"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "400" "Password from registration cannot be reused"
+expect_code "$RESPONSE" "reused" "Rejection explains the password was used before"
+
+expect_code "$(auth_request "$TOKEN" PUT "$PASSWORD_URL" '{"current_password": "password123", "new_password": "short12"}')" "too_short" "Change checks the length"
+expect_status "$(auth_request "$TOKEN" PUT "$PASSWORD_URL" '{"current_password": "password123", "new_password": "newpassword456"}' | tail -1)" "200" "Password changed"
+expect_status "$(auth_request "$TOKEN" PUT "$PASSWORD_URL" '{"current_password": "newpassword456", "new_password": "newpassword456"}' | tail -1)" "400" "Current password cannot be kept"
+
+# Step 4: Resets follow the same rules
+echo ""
+echo "📝 Step 4: Resetting the password..."
+public_request POST "/auth/forgot-password" '{"email": "'$USER_EMAIL'"}' > /dev/null
+RESET_TOKEN=$(latest_reset_token "$USER_EMAIL")
+expect_status "$(public_request POST "/auth/reset-password" '{"token": "'$RESET_TOKEN'", "new_password": "newpassword456"}' | tail -1)" "400" "Reset to a recent password rejected"
+expect_status "$(public_request POST "/auth/reset-password" '{"token": "'$RESET_TOKEN'", "new_password": "freshpassword789"}' | tail -1)" "200" "Rejected reset left the link usable"
+expect_status "$(login_request "$USER_EMAIL" "freshpassword789" | tail -1)" "200" "Reset password logs in"
+
+# Step 5: Cleanup
+echo ""
+echo "📝 Step 5: Cleaning up..."
+ADMIN_TOKEN=$(login "test@example.com")
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$USER_ID" -H "Authorization: Bearer $ADMIN_TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Password Hashing and Policy Test Complete!"
@@ -0,0 +1,117 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Permission Caching and Invalidation..."
+
+# Create a contact as the member, printing the response body followed by the HTTP status
+create_contact() {
+    curl -s -w "\n%{http_code}" -X POST "$BASE_URL/contacts" \
+      -H "Authorization: Bearer $MEMBER_TOKEN" \
+      -H "X-Organization-Id: $ORG_ID" \
+      -H "Content-Type: application/json" \
+      -d '{"first_name": "Cache", "last_name": "Check", "email": "cache.'$TIMESTAMP'.'$1'@example.com"}'
+}
+
+# Update the member's role or status as the admin
+update_membership() {
+    curl -s -o /dev/null -X PUT "$BASE_URL/user-organizations/$MEMBERSHIP_ID" \
+      -H "Authorization: Bearer $TOKEN" \
+      -H "Content-Type: application/json" \
+      -d "$1"
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+# Step 2: Create a viewer in the admin's organization
+echo ""
+echo "📝 Step 2: Creating a viewer..."
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+MEMBER_EMAIL="cache.member.$TIMESTAMP@example.com"
+MEMBER_ID=$(curl -s -X POST "$BASE_URL/users" \
+  -H "Content-Type: application/json" \
+  -d '{"name": "Cache Member", "email": "'$MEMBER_EMAIL'", "password": "password123"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+MEMBERSHIP_ID=$(curl -s -X POST "$BASE_URL/user-organizations" \
+  -H "Authorization: Bearer $TOKEN" \
+  -H "Content-Type: application/json" \
+  -d '{"user_id": "'$MEMBER_ID'", "org_id": "'$ORG_ID'", "role_name": "viewer"}' \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+MEMBER_TOKEN=$(login "$MEMBER_EMAIL")
+
+if [ -n "$MEMBERSHIP_ID" ] && [ -n "$MEMBER_TOKEN" ]; then
+    echo "✅ Viewer $MEMBER_ID added to organization $ORG_ID"
+else
+    echo "❌ Failed to set up the viewer"
+    exit 1
+fi
+
+# Step 3: A viewer cannot create contacts (this also caches the viewer's permissions)
+echo ""
+echo "📝 Step 3: Creating a contact as a viewer..."
+STATUS=$(create_contact viewer | tail -1)
+
+if [ "$STATUS" = "403" ]; then
+    echo "✅ Viewer rejected"
+else
+    echo "❌ Expected 403, got $STATUS"
+    exit 1
+fi
+
+# Step 4: A role change applies immediately despite the cache
+echo ""
+echo "📝 Step 4: Promoting the member to admin..."
+update_membership '{"role_name": "admin"}'
+CREATE_RESPONSE=$(create_contact admin)
+STATUS=$(echo "$CREATE_RESPONSE" | tail -1)
+CONTACT_ID=$(echo "$CREATE_RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+if [ "$STATUS" = "201" ]; then
+    echo "✅ Promotion took effect immediately"
+else
+    echo "❌ Expected 201 after promotion, got $STATUS"
+    exit 1
+fi
+
+# Step 5: Deactivating the membership applies immediately too
+echo ""
+echo "📝 Step 5: Deactivating the membership..."
+update_membership '{"status": "inactive"}'
+STATUS=$(create_contact inactive | tail -1)
+
+if [ "$STATUS" = "403" ]; then
+    echo "✅ Deactivated member rejected"
+else
+    echo "❌ Expected 403 after deactivation, got $STATUS"
+    exit 1
+fi
+
+# Step 6: Cleanup
+echo ""
+echo "📝 Step 6: Cleaning up..."
+[ -n "$CONTACT_ID" ] && curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$CONTACT_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/user-organizations/$MEMBERSHIP_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$MEMBER_ID" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Permission Cache Test Complete!"
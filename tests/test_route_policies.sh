@@ -0,0 +1,91 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# The outsider's organization is removed from the database directly
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Account and Membership Route Policies..."
+
+register() {
+    public_request POST "/users" '{"name": "Policy User", "email": "'$1'", "password": "password123"}' \
+      | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4
+}
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+ADMIN_ID=$(curl -s -X GET "$BASE_URL/users/me" -H "Authorization: Bearer $TOKEN" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ORG_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"org_id":"[^"]*"' | head -1 | cut -d'"' -f4)
+ADMIN_MEMBERSHIP_ID=$(curl -s -X GET "$BASE_URL/users/$ADMIN_ID/organizations?status=active" -H "Authorization: Bearer $TOKEN" \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Step 2: A viewer in the admin's organization and an outsider with an organization of their own
+echo ""
+echo "📝 Step 2: Creating a viewer and an outsider..."
+VIEWER_EMAIL="policy.viewer.$TIMESTAMP@example.com"
+VIEWER_ID=$(register "$VIEWER_EMAIL")
+RESPONSE=$(auth_request "$TOKEN" POST "/user-organizations" '{"user_id": "'$VIEWER_ID'", "org_id": "'$ORG_ID'", "role_name": "viewer"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Viewer added by the admin"
+VIEWER_MEMBERSHIP_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+VIEWER_TOKEN=$(login "$VIEWER_EMAIL")
+
+OUTSIDER_EMAIL="policy.outsider.$TIMESTAMP@example.com"
+OUTSIDER_ID=$(register "$OUTSIDER_EMAIL")
+OUTSIDER_TOKEN=$(login "$OUTSIDER_EMAIL")
+RESPONSE=$(auth_request "$OUTSIDER_TOKEN" POST "/organizations" '{"name": "Outsider Org '$TIMESTAMP'"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Outsider created an organization"
+OUTSIDER_ORG_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+OUTSIDER_MEMBERSHIP_ID=$(curl -s -X GET "$BASE_URL/users/$OUTSIDER_ID/organizations" -H "Authorization: Bearer $OUTSIDER_TOKEN" \
+  | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Step 3: Memberships need users:write in the active organization
+echo ""
+echo "📝 Step 3: Managing memberships without users:write..."
+expect_status "$(auth_request "$OUTSIDER_TOKEN" POST "/user-organizations" '{"user_id": "'$OUTSIDER_ID'", "org_id": "'$ORG_ID'", "role_name": "admin"}' | tail -1)" "403" "Outsider cannot join another organization"
+expect_status "$(auth_request "$VIEWER_TOKEN" PUT "/user-organizations/$VIEWER_MEMBERSHIP_ID" '{"role_name": "admin"}' | tail -1)" "403" "Viewer cannot promote themselves"
+expect_status "$(auth_request "$VIEWER_TOKEN" DELETE "/user-organizations/$ADMIN_MEMBERSHIP_ID" | tail -1)" "403" "Viewer cannot remove the admin"
+
+# Step 4: Admins only manage memberships of their active organization, within their own permissions
+echo ""
+echo "📝 Step 4: Managing memberships as the admin..."
+expect_status "$(auth_request "$TOKEN" POST "/user-organizations" '{"user_id": "'$ADMIN_ID'", "org_id": "'$OUTSIDER_ORG_ID'", "role_name": "admin"}' | tail -1)" "403" "Admin cannot join an organization they do not manage"
+expect_status "$(auth_request "$TOKEN" PUT "/user-organizations/$OUTSIDER_MEMBERSHIP_ID" '{"status": "inactive"}' | tail -1)" "404" "Membership of another organization not found"
+expect_status "$(auth_request "$TOKEN" PUT "/user-organizations/$VIEWER_MEMBERSHIP_ID" '{"role_name": "owner"}' | tail -1)" "403" "Admin cannot hand out the owner role"
+expect_status "$(auth_request "$TOKEN" PUT "/user-organizations/$VIEWER_MEMBERSHIP_ID" '{"role_name": "member"}' | tail -1)" "200" "Admin changes a member's role"
+
+# Step 5: Accounts are managed by their user, or through an organization they belong to
+echo ""
+echo "📝 Step 5: Managing accounts..."
+expect_status "$(auth_request "$VIEWER_TOKEN" GET "/users/$VIEWER_ID" | tail -1)" "200" "Users read their own account"
+expect_status "$(auth_request "$VIEWER_TOKEN" PUT "/users/$ADMIN_ID" '{"status": "inactive"}' | tail -1)" "403" "Member cannot deactivate the admin"
+expect_status "$(auth_request "$VIEWER_TOKEN" DELETE "/users/$ADMIN_ID" | tail -1)" "403" "Member cannot delete the admin"
+expect_status "$(auth_request "$TOKEN" PUT "/users/$OUTSIDER_ID" '{"status": "inactive"}' | tail -1)" "404" "Admin cannot reach users outside the organization"
+expect_status "$(auth_request "$TOKEN" GET "/users/$OUTSIDER_ID/organizations" | tail -1)" "404" "Admin cannot list an outsider's organizations"
+expect_status "$(auth_request "$TOKEN" PUT "/users/$VIEWER_ID/password" '{"current_password": "password123", "new_password": "takenover123"}' | tail -1)" "403" "Admin cannot change a member's password"
+expect_status "$(login_status "$VIEWER_EMAIL")" "200" "Member's password unchanged"
+
+# Step 6: Cleanup
+echo ""
+echo "📝 Step 6: Cleaning up..."
+psql "$DATABASE_URL" -q -c "DELETE FROM organization WHERE id = '$OUTSIDER_ORG_ID'"
+auth_request "$TOKEN" DELETE "/user-organizations/$VIEWER_MEMBERSHIP_ID" > /dev/null
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$VIEWER_ID" -H "Authorization: Bearer $VIEWER_TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/users/$OUTSIDER_ID" -H "Authorization: Bearer $OUTSIDER_TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Route Policy Test Complete!"
@@ -0,0 +1,81 @@
+#!/bin/bash
+
+# Test configuration
+BASE_URL="http://127.0.0.1:8081"
+# The failing write is injected with a trigger created in the database directly
+DATABASE_URL="${DATABASE_URL:-postgres://postgres@localhost:5432/survey}"
+
+source "$(dirname "$0")/lib.sh"
+
+echo "🧪 Testing Transactional Contact Writes..."
+
+# Step 1: Authentication
+echo ""
+echo "📝 Step 1: Logging in..."
+TOKEN=$(login "test@example.com")
+
+if [ -z "$TOKEN" ]; then
+    echo "❌ Failed to get authentication token"
+    exit 1
+fi
+
+echo "✅ Authentication successful"
+
+TIMESTAMP=$(date +%s)
+FIELD_NAME="tx_note_$TIMESTAMP"
+CONTACT_EMAIL="transactional.$TIMESTAMP@example.com"
+
+RESPONSE=$(auth_request "$TOKEN" POST "/custom-fields" '{"module": "contact", "label": "Transaction Note", "field_name": "'$FIELD_NAME'", "field_type": "text"}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "Custom field created"
+FIELD_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Writing the value "conflict" fails with a unique violation, as if a concurrent write had won,
+# after the contact row itself was already written
+TRIGGER="tx_conflict_$TIMESTAMP"
+psql "$DATABASE_URL" -q <<SQL
+CREATE FUNCTION $TRIGGER() RETURNS trigger AS \$\$
+BEGIN
+    IF NEW.custom_field_id = '$FIELD_ID' AND NEW.value = 'conflict' THEN
+        RAISE unique_violation USING MESSAGE = 'simulated conflicting write';
+    END IF;
+    RETURN NEW;
+END
+\$\$ LANGUAGE plpgsql;
+CREATE TRIGGER $TRIGGER BEFORE INSERT OR UPDATE ON contact_custom_values
+    FOR EACH ROW EXECUTE FUNCTION $TRIGGER();
+SQL
+
+# Step 2: Creating a contact whose custom value write conflicts
+echo ""
+echo "📝 Step 2: Creating a contact with a conflicting custom value..."
+expect_status "$(auth_request "$TOKEN" POST "/contacts" '{"first_name": "Rolled", "last_name": "Back", "email": "'$CONTACT_EMAIL'", "custom_fields": {"'$FIELD_NAME'": "conflict"}}' | tail -1)" "409" "Creation fails with a conflict"
+
+# The email would conflict if the contact had been kept without its custom values
+RESPONSE=$(auth_request "$TOKEN" POST "/contacts" '{"first_name": "Kept", "last_name": "Contact", "email": "'$CONTACT_EMAIL'", "custom_fields": {"'$FIELD_NAME'": "first"}}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "201" "No contact left without its custom values"
+CONTACT_ID=$(echo "$RESPONSE" | grep -o '"id":"[^"]*"' | head -1 | cut -d'"' -f4)
+
+# Step 3: Updating a contact whose custom value write conflicts
+echo ""
+echo "📝 Step 3: Updating a contact with a conflicting custom value..."
+expect_status "$(auth_request "$TOKEN" PUT "/contacts/$CONTACT_ID" '{"first_name": "Renamed", "custom_fields": {"'$FIELD_NAME'": "conflict"}}' | tail -1)" "409" "Update fails with a conflict"
+expect_status "$(auth_request "$TOKEN" PATCH "/contacts/$CONTACT_ID" '{"first_name": "Patched", "custom_fields": {"'$FIELD_NAME'": "conflict"}}' | tail -1)" "409" "Patch fails with a conflict"
+
+RESPONSE=$(auth_request "$TOKEN" GET "/contacts/$CONTACT_ID")
+expect_body "$RESPONSE" '"first_name":"Kept"' "Contact fields unchanged"
+expect_body "$RESPONSE" '"'$FIELD_NAME'":"first"' "Custom value unchanged"
+
+RESPONSE=$(auth_request "$TOKEN" PATCH "/contacts/$CONTACT_ID" '{"first_name": "Patched", "custom_fields": {"'$FIELD_NAME'": "second"}}')
+expect_status "$(echo "$RESPONSE" | tail -1)" "200" "Patch succeeds once the value can be written"
+expect_body "$RESPONSE" '"'$FIELD_NAME'":"second"' "Custom value written with the contact"
+
+# Step 4: Cleanup
+echo ""
+echo "📝 Step 4: Cleaning up..."
+psql "$DATABASE_URL" -q -c "DROP TRIGGER $TRIGGER ON contact_custom_values" -c "DROP FUNCTION $TRIGGER()"
+curl -s -o /dev/null -X DELETE "$BASE_URL/contacts/$CONTACT_ID" -H "Authorization: Bearer $TOKEN"
+curl -s -o /dev/null -X DELETE "$BASE_URL/custom-fields/contact/$FIELD_ID?force=true" -H "Authorization: Bearer $TOKEN"
+echo "✅ Cleanup complete"
+
+echo ""
+echo "🎉 Transactional Contact Writes Test Complete!"